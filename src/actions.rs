@@ -1,32 +1,58 @@
+mod case;
+mod comment;
+mod dedupe;
 mod deletion;
+mod encoding;
+mod exec;
 #[cfg(feature = "german")]
 mod german;
+mod increment;
+mod locale;
 mod lower;
+mod mapping;
 mod normalization;
+#[cfg(feature = "symbols")]
+mod quotes;
+mod redact;
 /// Replacing inputs.
 pub mod replace;
 mod style;
+mod surround;
 #[cfg(feature = "symbols")]
 mod symbols;
 mod titlecase;
+mod trim;
 mod upper;
 
 use std::error::Error;
 use std::fmt;
 
+pub use case::{Camelcase, Kebabcase, Pascalcase, ScreamingSnakecase, Snakecase};
+pub use comment::{CommentOut, Uncomment};
+pub use dedupe::Dedupe;
 pub use deletion::Deletion;
+pub use encoding::{Base64Decode, Base64Encode, HexDecode, HexEncode, UrlDecode, UrlEncode};
+pub use exec::{Exec, ExecError};
 #[cfg(feature = "german")]
-pub use german::German;
+pub use german::{EszettInversion, German};
+pub use increment::Increment;
+pub use locale::Locale;
 pub use lower::Lower;
-pub use normalization::Normalization;
+pub use mapping::{Mapping, MappingError, MappingFormat};
+pub use normalization::{Normalization, NormalizationForm};
+#[cfg(feature = "symbols")]
+pub use quotes::{inversion::Quotes as QuotesInversion, QuoteStyle, Quotes};
+pub use redact::Redact;
 pub use replace::{Replacement, ReplacementError};
-pub use style::Style;
+pub use style::{Style, StyleError};
+pub use surround::Surround;
 #[cfg(feature = "symbols")]
-pub use symbols::{inversion::Symbols as SymbolsInversion, Symbols};
-pub use titlecase::Titlecase;
-pub use upper::Upper;
+pub use symbols::{inversion::Symbols as SymbolsInversion, SymbolCategory, Symbols};
+pub use titlecase::{Titlecase, TitlecaseStyle};
+pub use trim::{Trim, TrimEnd, TrimLines, TrimStart};
+pub use upper::{Upper, UppercaseEszett};
 
-use crate::scoping::scope::ScopeContext;
+use crate::scoping::scope::{Location, ScopeContext};
 
 /// An action in the processing pipeline.
 ///
@@ -57,13 +83,48 @@ pub trait Action: Send + Sync {
         let _ = context; // Mark variable as used
         Ok(self.act(input))
     }
+
+    /// Acts taking into account additional context, if any, as well as the
+    /// [`Location`] of the input.
+    ///
+    /// By default, the location is ignored and [`Action::act_with_context`] is called
+    /// if there is context, else [`Action::act`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Action::act_with_context`].
+    fn act_at(
+        &self,
+        input: &str,
+        context: Option<&ScopeContext<'_>>,
+        location: &Location<'_>,
+    ) -> Result<String, ActionError> {
+        let _ = location; // Mark variable as used
+        context.map_or_else(
+            || Ok(self.act(input)),
+            |context| self.act_with_context(input, context),
+        )
+    }
+
+    /// A short, human-readable name of this action, for reporting.
+    ///
+    /// By default, this is the name of the implementing type, without its module path
+    /// or generic parameters.
+    fn name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let name = name.split_once('<').map_or(name, |(name, _)| name);
+        name.rsplit_once("::").map_or(name, |(_, name)| name)
+    }
 }
 
 /// An error during application of an action.
 #[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ActionError {
     /// Produced if [`Replacement`] fails.
     ReplacementError(ReplacementError),
+    /// Produced if [`Exec`] fails.
+    ExecError(ExecError),
 }
 
 impl fmt::Display for ActionError {
@@ -72,6 +133,9 @@ impl fmt::Display for ActionError {
             Self::ReplacementError(re) => {
                 write!(f, "Action failed in replacement: {re}")
             }
+            Self::ExecError(ee) => {
+                write!(f, "Action failed in external command: {ee}")
+            }
         }
     }
 }
@@ -102,4 +166,17 @@ impl Action for Box<dyn Action> {
     ) -> Result<String, ActionError> {
         self.as_ref().act_with_context(input, context)
     }
+
+    fn act_at(
+        &self,
+        input: &str,
+        context: Option<&ScopeContext<'_>>,
+        location: &Location<'_>,
+    ) -> Result<String, ActionError> {
+        self.as_ref().act_at(input, context, location)
+    }
+
+    fn name(&self) -> &'static str {
+        self.as_ref().name()
+    }
 }
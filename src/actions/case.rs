@@ -0,0 +1,214 @@
+//! Case conversions for identifiers, such as `snake_case` and `camelCase`.
+//!
+//! All conversions first split their input into words, then join them back together
+//! in the respective style. Word boundaries are any non-alphanumeric characters (`_`,
+//! `-`, whitespace, ...), as well as changes in case (`fooBar`, `HTTPServer`).
+//! Leading and trailing non-alphanumeric characters are kept as-is, such that for
+//! example `__init__` or `_private` retain their meaning.
+
+use itertools::Itertools;
+
+use super::Action;
+
+/// Renders in `snake_case`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Snakecase {}
+
+impl Action for Snakecase {
+    fn act(&self, input: &str) -> String {
+        convert(input, "_", |word, _| word.to_lowercase())
+    }
+}
+
+/// Renders in `SCREAMING_SNAKE_CASE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScreamingSnakecase {}
+
+impl Action for ScreamingSnakecase {
+    fn act(&self, input: &str) -> String {
+        convert(input, "_", |word, _| word.to_uppercase())
+    }
+}
+
+/// Renders in `kebab-case`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Kebabcase {}
+
+impl Action for Kebabcase {
+    fn act(&self, input: &str) -> String {
+        convert(input, "-", |word, _| word.to_lowercase())
+    }
+}
+
+/// Renders in `camelCase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Camelcase {}
+
+impl Action for Camelcase {
+    fn act(&self, input: &str) -> String {
+        convert(input, "", |word, i| {
+            if i == 0 {
+                word.to_lowercase()
+            } else {
+                capitalize(word)
+            }
+        })
+    }
+}
+
+/// Renders in `PascalCase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pascalcase {}
+
+impl Action for Pascalcase {
+    fn act(&self, input: &str) -> String {
+        convert(input, "", |word, _| capitalize(word))
+    }
+}
+
+/// Splits `input` into words, renders each using `render` (which receives the word and
+/// its index) and joins them using `separator`.
+fn convert(input: &str, separator: &str, render: impl Fn(&str, usize) -> String) -> String {
+    let is_separator = |c: char| !c.is_alphanumeric();
+
+    let core = input.trim_matches(is_separator);
+    if core.is_empty() {
+        // Nothing to convert; also covers the empty string.
+        return input.to_owned();
+    }
+
+    let start = input.len() - input.trim_start_matches(is_separator).len();
+    let end = input.trim_end_matches(is_separator).len();
+
+    let mut result = String::with_capacity(input.len());
+    result.push_str(&input[..start]);
+    result.push_str(
+        &words(core)
+            .into_iter()
+            .enumerate()
+            .map(|(i, word)| render(word, i))
+            .join(separator),
+    );
+    result.push_str(&input[end..]);
+
+    result
+}
+
+/// Splits `input` into its words, at separators and case boundaries.
+fn words(input: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+
+    for part in input.split(|c: char| !c.is_alphanumeric()) {
+        let mut start = 0;
+        let chars = part.char_indices().collect_vec();
+
+        for (window_start, window) in chars.windows(2).enumerate() {
+            let [(_, prev), (i, curr)] = [window[0], window[1]];
+            let next = chars.get(window_start + 2).map(|(_, c)| *c);
+
+            // `fooBar`, `foo2Bar`
+            let lower_to_upper = !prev.is_uppercase() && curr.is_uppercase();
+            // `HTTPServer`: boundary is between `P` and `S`
+            let acronym_end =
+                prev.is_uppercase() && curr.is_uppercase() && next.is_some_and(char::is_lowercase);
+
+            if lower_to_upper || acronym_end {
+                words.push(&part[start..i]);
+                start = i;
+            }
+        }
+
+        words.push(&part[start..]);
+    }
+
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+/// Uppercases the first character, lowercases the rest.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    chars.next().map_or_else(String::new, |first| {
+        first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", &[])]
+    #[case("foo", &["foo"])]
+    #[case("foo_bar", &["foo", "bar"])]
+    #[case("foo__bar", &["foo", "bar"])]
+    #[case("foo-bar baz", &["foo", "bar", "baz"])]
+    #[case("fooBar", &["foo", "Bar"])]
+    #[case("FooBar", &["Foo", "Bar"])]
+    #[case("FOO_BAR", &["FOO", "BAR"])]
+    #[case("HTTPServer", &["HTTP", "Server"])]
+    #[case("getHTTPResponse", &["get", "HTTP", "Response"])]
+    #[case("utf8Decoder", &["utf8", "Decoder"])]
+    #[case("Version2", &["Version2"])]
+    #[case("überFlüssig", &["über", "Flüssig"])]
+    fn test_words(#[case] input: &str, #[case] expected: &[&str]) {
+        assert_eq!(words(input), expected);
+    }
+
+    #[rstest]
+    #[case("fooBar", "foo_bar")]
+    #[case("FooBar", "foo_bar")]
+    #[case("foo-bar", "foo_bar")]
+    #[case("FOO_BAR", "foo_bar")]
+    #[case("HTTPServer", "http_server")]
+    #[case("some words here", "some_words_here")]
+    #[case("_privateField", "_private_field")]
+    #[case("__init__", "__init__")]
+    #[case("__", "__")]
+    #[case("", "")]
+    fn test_snakecase(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Snakecase::default().act(input), expected);
+    }
+
+    #[rstest]
+    #[case("fooBar", "FOO_BAR")]
+    #[case("foo-bar", "FOO_BAR")]
+    #[case("maxRetryCount", "MAX_RETRY_COUNT")]
+    fn test_screaming_snakecase(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(ScreamingSnakecase::default().act(input), expected);
+    }
+
+    #[rstest]
+    #[case("fooBar", "foo-bar")]
+    #[case("FOO_BAR", "foo-bar")]
+    #[case("HTTPServer", "http-server")]
+    fn test_kebabcase(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Kebabcase::default().act(input), expected);
+    }
+
+    #[rstest]
+    #[case("foo_bar", "fooBar")]
+    #[case("FooBar", "fooBar")]
+    #[case("FOO_BAR", "fooBar")]
+    #[case("http_server", "httpServer")]
+    #[case("HTTPServer", "httpServer")]
+    #[case("_foo_bar", "_fooBar")]
+    fn test_camelcase(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Camelcase::default().act(input), expected);
+    }
+
+    #[rstest]
+    #[case("foo_bar", "FooBar")]
+    #[case("fooBar", "FooBar")]
+    #[case("foo-bar baz", "FooBarBaz")]
+    #[case("über_flüssig", "ÜberFlüssig")]
+    fn test_pascalcase(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Pascalcase::default().act(input), expected);
+    }
+}
@@ -0,0 +1,167 @@
+use super::Action;
+use crate::scoping::langs::CommentSyntax;
+
+/// Comments out input using a language's [`CommentSyntax`].
+///
+/// Input spanning a single line is wrapped in a block comment where available, as
+/// that is safe even if code follows on the same line. Otherwise, each line is
+/// prefixed with a line comment, after its indentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentOut {
+    syntax: CommentSyntax,
+}
+
+impl CommentOut {
+    /// Creates a new action commenting out using the given `syntax`.
+    #[must_use]
+    pub const fn new(syntax: CommentSyntax) -> Self {
+        Self { syntax }
+    }
+}
+
+impl Action for CommentOut {
+    fn act(&self, input: &str) -> String {
+        let (content, newline) = split_trailing_newline(input);
+
+        match (self.syntax.block, self.syntax.line) {
+            (Some((open, close)), _) if !content.contains('\n') && !content.is_empty() => {
+                format!("{open} {content} {close}{newline}")
+            }
+            (_, Some(prefix)) => map_lines(input, |indent, line| {
+                if line.trim().is_empty() {
+                    format!("{indent}{line}")
+                } else {
+                    format!("{indent}{prefix} {line}")
+                }
+            }),
+            (Some((open, close)), None) => format!("{open} {content} {close}{newline}"),
+            (None, None) => input.to_owned(),
+        }
+    }
+}
+
+/// Uncomments input using a language's [`CommentSyntax`], undoing [`CommentOut`].
+///
+/// Input wrapped in a block comment is unwrapped; otherwise, line comments are
+/// removed from the start of each line. Anything else is left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uncomment {
+    syntax: CommentSyntax,
+}
+
+impl Uncomment {
+    /// Creates a new action uncommenting using the given `syntax`.
+    #[must_use]
+    pub const fn new(syntax: CommentSyntax) -> Self {
+        Self { syntax }
+    }
+}
+
+impl Action for Uncomment {
+    fn act(&self, input: &str) -> String {
+        let (content, newline) = split_trailing_newline(input);
+
+        if let Some((open, close)) = self.syntax.block {
+            let trimmed = content.trim();
+            if let Some(inner) = trimmed
+                .strip_prefix(open)
+                .and_then(|rest| rest.strip_suffix(close))
+            {
+                let inner = inner.strip_prefix(' ').unwrap_or(inner);
+                let inner = inner.strip_suffix(' ').unwrap_or(inner);
+
+                let leading = &content[..content.len() - content.trim_start().len()];
+                let trailing = &content[content.trim_end().len()..];
+                return format!("{leading}{inner}{trailing}{newline}");
+            }
+        }
+
+        self.syntax.line.map_or_else(
+            || input.to_owned(),
+            |prefix| {
+                map_lines(input, |indent, line| {
+                    let line = line
+                        .strip_prefix(prefix)
+                        .map_or(line, |rest| rest.strip_prefix(' ').unwrap_or(rest));
+                    format!("{indent}{line}")
+                })
+            },
+        )
+    }
+}
+
+/// Splits off a single trailing line ending, if any.
+fn split_trailing_newline(input: &str) -> (&str, &str) {
+    let content = input
+        .strip_suffix("\r\n")
+        .or_else(|| input.strip_suffix('\n'))
+        .unwrap_or(input);
+
+    (content, &input[content.len()..])
+}
+
+/// Maps each line of `input`, given its indentation and its remainder (including
+/// any line ending), to a new line.
+fn map_lines(input: &str, f: impl Fn(&str, &str) -> String) -> String {
+    input
+        .split_inclusive('\n')
+        .map(|line| {
+            let rest = line.trim_start_matches([' ', '\t']);
+            f(&line[..line.len() - rest.len()], rest)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const C_LIKE: CommentSyntax = CommentSyntax {
+        line: Some("//"),
+        block: Some(("/*", "*/")),
+    };
+    const PYTHON_LIKE: CommentSyntax = CommentSyntax {
+        line: Some("#"),
+        block: None,
+    };
+
+    #[rstest]
+    #[case(C_LIKE, "foo(1)", "/* foo(1) */")]
+    #[case(C_LIKE, "foo(1)\n", "/* foo(1) */\n")]
+    #[case(C_LIKE, "foo(\n    1,\n)", "// foo(\n    // 1,\n// )")]
+    #[case(C_LIKE, "a\n\n  b\n", "// a\n\n  // b\n")]
+    #[case(PYTHON_LIKE, "foo(1)", "# foo(1)")]
+    #[case(PYTHON_LIKE, "    x = 1\n    y = 2\n", "    # x = 1\n    # y = 2\n")]
+    #[case(PYTHON_LIKE, "", "")]
+    fn test_comment_out(
+        #[case] syntax: CommentSyntax,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(CommentOut::new(syntax).act(input), expected);
+    }
+
+    #[rstest]
+    #[case(C_LIKE, "/* foo(1) */", "foo(1)")]
+    #[case(C_LIKE, "/* foo(1) */\n", "foo(1)\n")]
+    #[case(C_LIKE, "/*foo(1)*/", "foo(1)")]
+    #[case(C_LIKE, "// foo(\n    // 1,\n// )", "foo(\n    1,\n)")]
+    #[case(C_LIKE, "//a\n", "a\n")]
+    #[case(C_LIKE, "foo(1)", "foo(1)")]
+    #[case(PYTHON_LIKE, "    # x = 1\n    # y = 2\n", "    x = 1\n    y = 2\n")]
+    #[case(PYTHON_LIKE, "x = 1  # one", "x = 1  # one")]
+    fn test_uncomment(#[case] syntax: CommentSyntax, #[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Uncomment::new(syntax).act(input), expected);
+    }
+
+    #[rstest]
+    fn test_roundtrip(
+        #[values(C_LIKE, PYTHON_LIKE)] syntax: CommentSyntax,
+        #[values("foo(1)", "a\n  b\n", "x\n\ny")] input: &str,
+    ) {
+        let commented = CommentOut::new(syntax).act(input);
+        assert_eq!(Uncomment::new(syntax).act(&commented), input);
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashSet;
+
+use super::Action;
+
+/// Removes duplicate lines, keeping only the first occurrence of each.
+///
+/// Lines are compared without their line endings. The order of lines is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Dedupe {}
+
+impl Action for Dedupe {
+    fn act(&self, input: &str) -> String {
+        let mut seen = HashSet::new();
+
+        input
+            .split_inclusive('\n')
+            .filter(|line| seen.insert(line.trim_end_matches(['\r', '\n'])))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", "")]
+    #[case("a", "a")]
+    #[case("a\na\n", "a\n")]
+    #[case("a\nb\na\nc\nb\n", "a\nb\nc\n")]
+    #[case("a\r\na\n", "a\r\n")]
+    #[case("a\nb\na", "a\nb\n")] // Last line without line ending
+    #[case("a\n\n\nb\n", "a\n\nb\n")]
+    #[case(" a\na\n", " a\na\n")] // Whitespace is significant
+    fn test_dedupe(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Dedupe::default().act(input), expected);
+    }
+}
@@ -0,0 +1,260 @@
+//! Encoding and decoding actions, such as Base64.
+//!
+//! Decoding is lenient in the sense that it never fails: input which cannot be decoded
+//! (invalid encoding, or decoded bytes not being valid UTF-8) is returned unchanged.
+
+use std::fmt::Write as _;
+
+use log::trace;
+
+use super::Action;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_PADDING: u8 = b'=';
+
+/// Encodes as Base64 (standard alphabet, with padding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Base64Encode {}
+
+impl Action for Base64Encode {
+    fn act(&self, input: &str) -> String {
+        let bytes = input.as_bytes();
+        let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+        for chunk in bytes.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |acc, (i, b)| acc | u32::from(*b) << (16 - 8 * i));
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let index = (n >> (18 - 6 * i)) & 0b11_1111;
+                    result.push(BASE64_ALPHABET[index as usize].into());
+                } else {
+                    result.push(BASE64_PADDING.into());
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Decodes from Base64 (standard alphabet, padding optional).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Base64Decode {}
+
+impl Action for Base64Decode {
+    fn act(&self, input: &str) -> String {
+        let decode = || {
+            let symbols = input
+                .trim_end_matches(char::from(BASE64_PADDING))
+                .as_bytes();
+            if symbols.len() % 4 == 1 || input.len() - symbols.len() > 2 {
+                return None;
+            }
+
+            let mut bytes = Vec::with_capacity(symbols.len() / 4 * 3 + 2);
+            for chunk in symbols.chunks(4) {
+                let n = chunk.iter().enumerate().try_fold(0u32, |acc, (i, s)| {
+                    let value = BASE64_ALPHABET.iter().position(|a| a == s)?;
+                    #[allow(clippy::cast_possible_truncation)] // Alphabet has 64 entries
+                    Some(acc | (value as u32) << (18 - 6 * i))
+                })?;
+
+                // Each symbol carries 6 bits, so `k` symbols make up `k - 1` bytes.
+                for i in 0..chunk.len() - 1 {
+                    bytes.push((n >> (16 - 8 * i)).to_le_bytes()[0]);
+                }
+            }
+
+            String::from_utf8(bytes).ok()
+        };
+
+        decode().unwrap_or_else(|| {
+            trace!("Not decoding, invalid Base64 or not UTF-8: {input:?}");
+            input.to_owned()
+        })
+    }
+}
+
+/// Percent-encodes for use in URLs.
+///
+/// All bytes except unreserved characters (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_`, `~`;
+/// see [RFC 3986](https://datatracker.ietf.org/doc/html/rfc3986#section-2.3)) are
+/// encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UrlEncode {}
+
+impl Action for UrlEncode {
+    fn act(&self, input: &str) -> String {
+        let mut result = String::with_capacity(input.len());
+
+        for b in input.bytes() {
+            if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+                result.push(b.into());
+            } else {
+                write!(result, "%{b:02X}").expect("writing to `String` is infallible");
+            }
+        }
+
+        result
+    }
+}
+
+/// Decodes percent-encoded URL components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UrlDecode {}
+
+impl Action for UrlDecode {
+    fn act(&self, input: &str) -> String {
+        let decode = || {
+            let mut bytes = Vec::with_capacity(input.len());
+            let mut iter = input.bytes();
+
+            while let Some(b) = iter.next() {
+                if b == b'%' {
+                    let high = hex_value(iter.next()?)?;
+                    let low = hex_value(iter.next()?)?;
+                    bytes.push(high << 4 | low);
+                } else {
+                    bytes.push(b);
+                }
+            }
+
+            String::from_utf8(bytes).ok()
+        };
+
+        decode().unwrap_or_else(|| {
+            trace!("Not decoding, invalid percent-encoding or not UTF-8: {input:?}");
+            input.to_owned()
+        })
+    }
+}
+
+/// Encodes as lowercase hexadecimal, two digits per byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HexEncode {}
+
+impl Action for HexEncode {
+    fn act(&self, input: &str) -> String {
+        let mut result = String::with_capacity(input.len() * 2);
+
+        for b in input.bytes() {
+            write!(result, "{b:02x}").expect("writing to `String` is infallible");
+        }
+
+        result
+    }
+}
+
+/// Decodes from hexadecimal, two digits per byte (case-insensitive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HexDecode {}
+
+impl Action for HexDecode {
+    fn act(&self, input: &str) -> String {
+        let decode = || {
+            if input.len() % 2 != 0 {
+                return None;
+            }
+
+            let bytes = input
+                .as_bytes()
+                .chunks(2)
+                .map(|pair| Some(hex_value(pair[0])? << 4 | hex_value(pair[1])?))
+                .collect::<Option<Vec<_>>>()?;
+
+            String::from_utf8(bytes).ok()
+        };
+
+        decode().unwrap_or_else(|| {
+            trace!("Not decoding, invalid hex or not UTF-8: {input:?}");
+            input.to_owned()
+        })
+    }
+}
+
+/// The value of a single hexadecimal digit, if it is one.
+fn hex_value(digit: u8) -> Option<u8> {
+    char::from(digit).to_digit(16).map(|d| d.to_le_bytes()[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    // Test vectors from RFC 4648
+    #[case("", "")]
+    #[case("f", "Zg==")]
+    #[case("fo", "Zm8=")]
+    #[case("foo", "Zm9v")]
+    #[case("foob", "Zm9vYg==")]
+    #[case("fooba", "Zm9vYmE=")]
+    #[case("foobar", "Zm9vYmFy")]
+    //
+    #[case("Hello, World!", "SGVsbG8sIFdvcmxkIQ==")]
+    #[case("üñí©ødé", "w7zDscOtwqnDuGTDqQ==")]
+    #[case("?>?", "Pz4/")] // Hits `+` and `/`
+    fn test_base64_roundtrip(#[case] decoded: &str, #[case] encoded: &str) {
+        assert_eq!(Base64Encode::default().act(decoded), encoded);
+        assert_eq!(Base64Decode::default().act(encoded), decoded);
+    }
+
+    #[rstest]
+    #[case("Zm9vYg", "foob")] // Missing padding is fine
+    #[case("Zm9vYmE", "fooba")]
+    #[case("Zm9v!", "Zm9v!")] // Invalid symbol
+    #[case("Z", "Z")] // Impossible length
+    #[case("Zg===", "Zg===")] // Too much padding
+    #[case("/w==", "/w==")] // Not UTF-8
+    fn test_base64_decode(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Base64Decode::default().act(input), expected);
+    }
+
+    #[rstest]
+    #[case("", "")]
+    #[case("abc-._~XYZ019", "abc-._~XYZ019")]
+    #[case("a b", "a%20b")]
+    #[case("a+b=c&d", "a%2Bb%3Dc%26d")]
+    #[case("/path?q=1#x", "%2Fpath%3Fq%3D1%23x")]
+    #[case("ü", "%C3%BC")]
+    fn test_url_roundtrip(#[case] decoded: &str, #[case] encoded: &str) {
+        assert_eq!(UrlEncode::default().act(decoded), encoded);
+        assert_eq!(UrlDecode::default().act(encoded), decoded);
+    }
+
+    #[rstest]
+    #[case("%c3%bc", "ü")] // Lowercase is fine
+    #[case("a+b", "a+b")] // `+` is not special
+    #[case("100%", "100%")] // Incomplete
+    #[case("%zz", "%zz")] // Not hex
+    #[case("%FF", "%FF")] // Not UTF-8
+    fn test_url_decode(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(UrlDecode::default().act(input), expected);
+    }
+
+    #[rstest]
+    #[case("", "")]
+    #[case("a", "61")]
+    #[case("Hi!", "486921")]
+    #[case("ü", "c3bc")]
+    fn test_hex_roundtrip(#[case] decoded: &str, #[case] encoded: &str) {
+        assert_eq!(HexEncode::default().act(decoded), encoded);
+        assert_eq!(HexDecode::default().act(encoded), decoded);
+    }
+
+    #[rstest]
+    #[case("C3BC", "ü")]
+    #[case("abc", "abc")] // Odd length
+    #[case("zz", "zz")] // Not hex
+    #[case("ff", "ff")] // Not UTF-8
+    fn test_hex_decode(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(HexDecode::default().act(input), expected);
+    }
+}
@@ -0,0 +1,232 @@
+use std::error::Error;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::{fmt, thread};
+
+use log::{debug, error};
+
+use super::{Action, ActionError};
+use crate::scoping::scope::{Location, ScopeContext};
+
+/// Replaces input with the output of an external program.
+///
+/// The program is run once per input, receiving it on its standard input. Whatever it
+/// writes to its standard output becomes the result. If that output ends in a newline
+/// but the input did not, the newline is dropped, as many programs terminate their
+/// output that way.
+///
+/// Running the program can fail, which surfaces as an error via
+/// [`Action::act_with_context`]. The infallible [`Action::act`] instead logs the error
+/// and returns the input unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exec {
+    program: String,
+    args: Vec<String>,
+}
+
+impl Exec {
+    /// Creates a new action running `program` with the given `args`.
+    #[must_use]
+    pub const fn new(program: String, args: Vec<String>) -> Self {
+        Self { program, args }
+    }
+
+    /// Creates a new action from a command line such as `jq -c .`.
+    ///
+    /// The command line is split into words following POSIX shell quoting rules, but no
+    /// shell is involved. Returns [`None`] if the command line is empty or its quoting
+    /// is invalid.
+    #[must_use]
+    pub fn from_command_line(command: &str) -> Option<Self> {
+        let mut words = shlex::split(command)?.into_iter();
+        let program = words.next()?;
+
+        Some(Self::new(program, words.collect()))
+    }
+
+    fn run(&self, input: &str) -> Result<String, ExecError> {
+        debug!("Running {:?} with args {:?}", self.program, self.args);
+
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| ExecError::Io(e.to_string()))?;
+
+        let mut stdin = child.stdin.take().expect("stdin was configured as piped");
+        let output = thread::scope(|s| {
+            // Write concurrently: the child might block on a full stdout pipe before
+            // having read all of its input.
+            let writer = s.spawn(move || stdin.write_all(input.as_bytes()));
+            let output = child.wait_with_output();
+
+            match writer.join().expect("writing to stdin does not panic") {
+                // The program not reading all of its input is its own business.
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e),
+                _ => output,
+            }
+        })
+        .map_err(|e| ExecError::Io(e.to_string()))?;
+
+        if !output.status.success() {
+            return Err(ExecError::Failed {
+                code: output.status.code(),
+                stderr: String::from_utf8_lossy(&output.stderr)
+                    .trim_end()
+                    .to_owned(),
+            });
+        }
+
+        let mut stdout = String::from_utf8(output.stdout).map_err(|_| ExecError::InvalidUtf8)?;
+
+        if !input.ends_with('\n') && stdout.ends_with('\n') {
+            stdout.pop();
+
+            if !input.ends_with('\r') && stdout.ends_with('\r') {
+                stdout.pop();
+            }
+        }
+
+        Ok(stdout)
+    }
+}
+
+impl Action for Exec {
+    fn act(&self, input: &str) -> String {
+        self.run(input).unwrap_or_else(|e| {
+            error!("Leaving input unchanged: {e}");
+            input.to_owned()
+        })
+    }
+
+    fn act_with_context(
+        &self,
+        input: &str,
+        context: &ScopeContext<'_>,
+    ) -> Result<String, ActionError> {
+        self.act_at(input, Some(context), &Location::default())
+    }
+
+    fn act_at(
+        &self,
+        input: &str,
+        _context: Option<&ScopeContext<'_>>,
+        _location: &Location<'_>,
+    ) -> Result<String, ActionError> {
+        Ok(self.run(input)?)
+    }
+}
+
+/// An error while running an external program.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExecError {
+    /// The program could not be run or communicated with, e.g. because it does not
+    /// exist.
+    Io(String),
+    /// The program exited unsuccessfully.
+    Failed {
+        /// The exit code, if any (there is none if terminated by a signal).
+        code: Option<i32>,
+        /// What the program wrote to its standard error.
+        stderr: String,
+    },
+    /// The program's output is not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(msg) => write!(f, "Failed to run program: {msg}"),
+            Self::Failed { code, stderr } => {
+                match code {
+                    Some(code) => write!(f, "Program exited with code {code}")?,
+                    None => write!(f, "Program was terminated")?,
+                }
+
+                if stderr.is_empty() {
+                    Ok(())
+                } else {
+                    write!(f, ": {stderr}")
+                }
+            }
+            Self::InvalidUtf8 => write!(f, "Program output is not valid UTF-8"),
+        }
+    }
+}
+
+impl Error for ExecError {}
+
+impl From<ExecError> for ActionError {
+    fn from(value: ExecError) -> Self {
+        Self::ExecError(value)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("jq -c .", Some(("jq", &["-c", "."][..])))]
+    #[case("sed 's/a b/c/'", Some(("sed", &["s/a b/c/"][..])))]
+    #[case("  tr   a b ", Some(("tr", &["a", "b"][..])))]
+    #[case("cat", Some(("cat", &[][..])))]
+    #[case("", None)]
+    #[case("   ", None)]
+    #[case("sed 's/a", None)]
+    fn test_from_command_line(#[case] command: &str, #[case] expected: Option<(&str, &[&str])>) {
+        let expected = expected.map(|(program, args)| {
+            Exec::new(
+                program.to_owned(),
+                args.iter().map(ToString::to_string).collect(),
+            )
+        });
+
+        assert_eq!(Exec::from_command_line(command), expected);
+    }
+
+    #[rstest]
+    #[case("tr a-z A-Z", "hello", Ok("HELLO"))]
+    #[case("tr a-z A-Z", "", Ok(""))]
+    #[case("cat", "a\nb\n", Ok("a\nb\n"))]
+    // Trailing newline of output only dropped if input had none
+    #[case("echo hi", "", Ok("hi"))]
+    #[case("echo hi", "\n", Ok("hi\n"))]
+    #[case("printf 'a\r\n'", "", Ok("a"))]
+    #[case("printf '\\377'", "", Err(ExecError::InvalidUtf8))]
+    #[case(
+        "sh -c 'echo oops >&2; exit 3'",
+        "",
+        Err(ExecError::Failed { code: Some(3), stderr: "oops".to_owned() })
+    )]
+    fn test_exec(
+        #[case] command: &str,
+        #[case] input: &str,
+        #[case] expected: Result<&str, ExecError>,
+    ) {
+        let action = Exec::from_command_line(command).unwrap();
+
+        assert_eq!(action.run(input), expected.map(str::to_owned));
+    }
+
+    #[test]
+    fn test_exec_large_input() {
+        // Larger than typical pipe buffers, to provoke deadlocks.
+        let input = "x".repeat(1 << 20);
+
+        assert_eq!(Exec::from_command_line("cat").unwrap().act(&input), input);
+    }
+
+    #[test]
+    fn test_exec_missing_program() {
+        let action = Exec::from_command_line("srgn-no-such-program-surely").unwrap();
+
+        assert!(matches!(action.run("a"), Err(ExecError::Io(_))));
+        assert_eq!(action.act("a"), "a");
+    }
+}
@@ -1,4 +1,5 @@
 mod driver;
+mod inversion;
 mod machine;
 mod words;
 
@@ -8,4 +9,5 @@ const EXPECTABLE_AVERAGE_MATCHES_PER_WORD: u8 = 2;
 // Re-export symbols.
 #[allow(clippy::module_name_repetitions)]
 pub use driver::German;
+pub use inversion::Eszett as EszettInversion;
 use words::{LetterCasing, SpecialCharacter, Umlaut, Word};
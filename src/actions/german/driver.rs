@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::LazyLock;
 
 use cached::proc_macro::cached;
@@ -9,8 +11,10 @@ use log::{debug, trace};
 use unicode_titlecase::StrTitleCase;
 
 use crate::actions::german::machine::{StateMachine, Transition};
-use crate::actions::german::words::{Replace, Replacement, WordCasing};
-use crate::actions::Action;
+use crate::actions::german::words::{
+    LetterCasing, Replace, Replacement, SpecialCharacter, WordCasing,
+};
+use crate::actions::{Action, UppercaseEszett};
 
 /// German language action, responsible for Umlauts and Eszett.
 ///
@@ -208,10 +212,14 @@ use crate::actions::Action;
 /// ([`phf`](https://crates.io/crates/phf) and more), and benchmarks, see [this
 /// issue](https://github.com/alexpovel/srgn/issues/9) and [this
 /// thread](https://users.rust-lang.org/t/fast-string-lookup-in-a-single-str-containing-millions-of-unevenly-sized-substrings/98040).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct German {
     prefer_original: bool,
     naive: bool,
+    words: WordList,
+    exceptions: BTreeSet<String>,
+    swiss: bool,
+    uppercase_eszett: UppercaseEszett,
 }
 
 impl German {
@@ -298,9 +306,60 @@ impl German {
         Self {
             prefer_original,
             naive,
+            words: WordList::new(),
+            exceptions: BTreeSet::new(),
+            swiss: false,
+            uppercase_eszett: UppercaseEszett::Capital,
         }
     }
 
+    /// Consider the given `words` valid, in addition to the built-in word list.
+    ///
+    /// Useful for domain-specific vocabulary and proper nouns. Words have to be given
+    /// in their proper spelling, including Umlauts and Eszett, and casing: `Schrödinger`
+    /// allows replacing `Schroedinger`.
+    pub fn add_words(&mut self, words: impl IntoIterator<Item = String>) -> &mut Self {
+        self.words.extend(words);
+        self
+    }
+
+    /// Follow Swiss orthography, never producing Eszett (`ß`).
+    ///
+    /// Where a valid replacement would contain Eszett, `ss` is kept instead, but Umlauts
+    /// are still replaced: `Gruesse` becomes `Grüsse`. Existing Eszett is left alone,
+    /// see [`EszettInversion`](crate::actions::EszettInversion) for replacing it.
+    pub fn swiss(&mut self) -> &mut Self {
+        self.swiss = true;
+        self
+    }
+
+    /// Produce uppercase Eszett only if `eszett` is [`UppercaseEszett::Capital`], the
+    /// default. Otherwise, `SS` is kept as-is, as in `SCHLIESSEN`.
+    pub fn uppercase_eszett(&mut self, eszett: UppercaseEszett) -> &mut Self {
+        self.uppercase_eszett = eszett;
+        self
+    }
+
+    /// Whether `character` may be produced at all, as opposed to only being considered
+    /// for finding valid words.
+    fn produces(&self, character: SpecialCharacter) -> bool {
+        match character {
+            SpecialCharacter::Eszett(_) if self.swiss => false,
+            SpecialCharacter::Eszett(LetterCasing::Upper) => {
+                self.uppercase_eszett == UppercaseEszett::Capital
+            }
+            _ => true,
+        }
+    }
+
+    /// Never replace anything in the given `words`, like brand names or identifiers.
+    ///
+    /// Words have to match exactly, including casing.
+    pub fn add_exceptions(&mut self, words: impl IntoIterator<Item = String>) -> &mut Self {
+        self.exceptions.extend(words);
+        self
+    }
+
     /// Prefer the original word over any replacement.
     pub fn prefer_original(&mut self) -> &mut Self {
         self.prefer_original = true;
@@ -360,22 +419,26 @@ impl Action for German {
             match transition {
                 Transition::External => {
                     output.push(char);
-                    continue;
-                }
-                Transition::Entered | Transition::Internal => {
-                    continue;
                 }
+                Transition::Entered | Transition::Internal => {}
                 Transition::Exited => {
                     debug!("Exited machine: {:?}", machine);
 
                     let original = machine.current_word().content().to_owned();
-                    let word = find_valid_replacement(
-                        &original,
-                        machine.current_word().replacements(),
-                        self.prefer_original,
-                        self.naive,
-                    )
-                    .unwrap_or(original);
+                    let word = if self.exceptions.contains(&original) {
+                        debug!("Word is an exception, leaving as-is: {:?}", &original);
+                        original
+                    } else {
+                        find_valid_replacement(
+                            &original,
+                            machine.current_word().replacements(),
+                            self.prefer_original,
+                            self.naive,
+                            &|character| self.produces(character),
+                            &self.words,
+                        )
+                        .unwrap_or(original)
+                    };
 
                     debug!("Processed word, appending to output: {:?}", &word);
                     output.push_str(&word);
@@ -404,6 +467,8 @@ fn find_valid_replacement(
     replacements: &[Replacement],
     prefer_original: bool,
     naive: bool,
+    produces: &impl Fn(SpecialCharacter) -> bool,
+    words: &WordList,
 ) -> Option<String> {
     let replacement_combinations = {
         let mut res: Vec<Vec<_>> = replacements
@@ -452,14 +517,25 @@ fn find_valid_replacement(
 
     for replacements in replacement_combinations.into_iter().skip(n_skip) {
         let mut candidate = word.to_owned();
-        candidate.apply_replacements(replacements);
+        candidate.apply_replacements(replacements.iter().cloned());
         trace!(
             "Replaced candidate word, now is: '{}'. Starting validity check.",
             candidate
         );
 
-        if naive || is_valid(&candidate, &contained_in_global_word_list) {
+        if naive || is_valid(&candidate, words) {
             debug!("Candidate '{}' is valid, returning early", candidate);
+
+            if !replacements.iter().all(|r| produces(*r.content())) {
+                // Validity is judged by the word list, which spells e.g. Eszett, but
+                // that might not be wanted in the output.
+                word.clone_into(&mut candidate);
+                candidate.apply_replacements(
+                    replacements.into_iter().filter(|r| produces(*r.content())),
+                );
+                debug!("Reverted unwanted replacements: '{}'", candidate);
+            }
+
             return Some(candidate);
         }
 
@@ -486,13 +562,45 @@ fn contained_in_global_word_list(word: &str) -> bool {
     result
 }
 
+/// The list of words considered valid: the built-in one, plus any words added at
+/// runtime.
+#[derive(Debug, Clone)]
+struct WordList {
+    extra: BTreeSet<String>,
+    /// Identifies the extra words, as they are part of the cache key of [`is_valid`].
+    fingerprint: u64,
+}
+
+impl WordList {
+    const fn new() -> Self {
+        Self {
+            extra: BTreeSet::new(),
+            fingerprint: 0,
+        }
+    }
+
+    fn extend(&mut self, words: impl IntoIterator<Item = String>) {
+        self.extra.extend(words);
+
+        let mut hasher = DefaultHasher::new();
+        self.extra.hash(&mut hasher);
+        self.fingerprint = hasher.finish();
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        contained_in_global_word_list(word) || self.extra.contains(word)
+    }
+}
+
 // https://github.com/jaemk/cached/issues/135#issuecomment-1315911572
 #[cached(
-    ty = "SizedCache<String, bool>",
+    ty = "SizedCache<(String, u64), bool>",
     create = "{ SizedCache::with_size(256) }",
-    convert = r#"{ String::from(word) }"#
+    convert = r#"{ (String::from(word), words.fingerprint) }"#
 )]
-fn is_valid(word: &str, predicate: &impl Fn(&str) -> bool) -> bool {
+fn is_valid(word: &str, words: &WordList) -> bool {
+    let predicate = |word: &str| words.contains(word);
+
     trace!("Trying candidate '{}'", word);
 
     let casing = WordCasing::try_from(word);
@@ -508,7 +616,7 @@ fn is_valid(word: &str, predicate: &impl Fn(&str) -> bool) -> bool {
             // been split to `süß` and `es`, and *only these* are in the word list.
             // `süßes` is therefore a compound word, by our definition (it's not, it
             // just falls victim to an imperfect algorithm).
-            || decompound(word, predicate, DecompositionOptions::TRY_TITLECASE_SUFFIX).is_ok()
+            || decompound(word, &predicate, DecompositionOptions::TRY_TITLECASE_SUFFIX).is_ok()
         }
         Ok(WordCasing::AllUppercase) => {
             // Convert to something sensible before proceeding.
@@ -519,7 +627,7 @@ fn is_valid(word: &str, predicate: &impl Fn(&str) -> bool) -> bool {
                 "Titlecased word, but isn't categorized correctly."
             );
 
-            is_valid(&tc, predicate)
+            is_valid(&tc, words)
         }
         Ok(WordCasing::Mixed) => {
             // For MiXeD casing, the word's first character governs its further
@@ -533,9 +641,9 @@ fn is_valid(word: &str, predicate: &impl Fn(&str) -> bool) -> bool {
                         "Titlecased word, but isn't categorized correctly."
                     );
 
-                    is_valid(&tc, predicate)
+                    is_valid(&tc, words)
                 }
-                _ => is_valid(&word.to_lowercase(), predicate),
+                _ => is_valid(&word.to_lowercase(), words),
             }
         }
         Ok(WordCasing::Titlecase) => {
@@ -544,11 +652,11 @@ fn is_valid(word: &str, predicate: &impl Fn(&str) -> bool) -> bool {
             predicate(word)
                 // Adjectives and verbs might be titlecased at the beginning of
                 // sentences etc. (e.g. "Gut gemacht!" -> we need "gut").
-                || is_valid(&word.to_lowercase(), predicate)
+                || is_valid(&word.to_lowercase(), words)
                 // None of these worked: we might have a compound word. In the ordinary
                 // case, these only occur as titlecase, as they're nouns (e.g.
                 // "Hausüberfall").
-                || decompound(word, predicate, DecompositionOptions::TRY_TITLECASE_SUFFIX).is_ok()
+                || decompound(word, &predicate, DecompositionOptions::TRY_TITLECASE_SUFFIX).is_ok()
         }
         Err(_) => false, // Ran into some unexpected characters...
     }
@@ -585,7 +693,7 @@ mod tests {
 
     #[test]
     fn test_is_valid_on_empty_input() {
-        assert!(!is_valid("", &contained_in_global_word_list));
+        assert!(!is_valid("", &WordList::new()));
     }
 
     #[rstest]
@@ -636,7 +744,7 @@ mod tests {
     #[case("مرحبا", false)]
     #[case("你好", false)]
     fn test_is_valid(#[case] word: &str, #[case] expected: bool) {
-        assert_eq!(is_valid(word, &contained_in_global_word_list), expected);
+        assert_eq!(is_valid(word, &WordList::new()), expected);
     }
 
     #[rstest]
@@ -713,4 +821,78 @@ mod tests {
         let result = action.act(input);
         assert_eq!(result, expected);
     }
+
+    #[rstest]
+    #[case("Frau Schroekedaek", "Frau Schrökedäk")]
+    #[case("FRAU SCHROEKEDAEK", "FRAU SCHRÖKEDÄK")]
+    #[case("Schroekedaekbuero", "Schrökedäkbüro")] // Compound words
+    #[case("Abenteuer", "Abenteuer")]
+    fn test_added_words(#[case] input: &str, #[case] expected: &str) {
+        let mut action = German::default();
+        action.add_words(["Schrökedäk".to_owned()]);
+        assert_eq!(action.act(input), expected);
+
+        // Unaffected, despite caching.
+        let action = German::default();
+        assert_eq!(action.act(input), input);
+    }
+
+    #[rstest]
+    #[case("Gruesse", "Grüsse")]
+    #[case("GRUESSE", "GRÜSSE")]
+    #[case("Strasse", "Strasse")]
+    #[case("Busse", "Busse")]
+    #[case("Aepfel und Baeume", "Äpfel und Bäume")]
+    #[case("Straße", "Straße")] // Existing Eszett is left alone
+    fn test_swiss(#[case] input: &str, #[case] expected: &str) {
+        let mut action = German::default();
+        action.swiss();
+        assert_eq!(action.act(input), expected);
+
+        // Swiss orthography has no Eszett of any casing.
+        action.uppercase_eszett(UppercaseEszett::Capital);
+        assert_eq!(action.act(input), expected);
+    }
+
+    #[rstest]
+    #[case(UppercaseEszett::Capital, "SCHLIESSEN", "SCHLIEẞEN")]
+    #[case(UppercaseEszett::Ss, "SCHLIESSEN", "SCHLIESSEN")]
+    #[case(UppercaseEszett::Keep, "SCHLIESSEN", "SCHLIESSEN")]
+    #[case(UppercaseEszett::Ss, "GRUESSE", "GRÜSSE")]
+    #[case(UppercaseEszett::Ss, "Gruesse", "Grüße")]
+    #[case(UppercaseEszett::Ss, "WiR sChLiesSEn ab", "WiR sChLießEn ab")]
+    fn test_uppercase_eszett(
+        #[case] eszett: UppercaseEszett,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let mut action = German::default();
+        action.uppercase_eszett(eszett);
+        assert_eq!(action.act(input), expected);
+    }
+
+    #[rstest]
+    #[case("Guessa", "Güssa")]
+    #[case("GUESSA", "GÜSSA")]
+    fn test_swiss_naive(#[case] input: &str, #[case] expected: &str) {
+        let mut action = German::default();
+        action.swiss().naive();
+        assert_eq!(action.act(input), expected);
+    }
+
+    #[rstest]
+    #[case("Die Muesli AG", "Die Muesli AG")]
+    #[case("Muesli schmeckt gut", "Muesli schmeckt gut")]
+    #[case("MUESLI", "MÜSLI")] // Casing has to match
+    #[case("Muesliriegel", "Müsliriegel")] // Whole words only
+    #[case("Gruesse", "Grüße")]
+    fn test_exceptions(#[case] input: &str, #[case] expected: &str) {
+        let mut action = German::default();
+        action.add_exceptions(["Muesli".to_owned()]);
+        assert_eq!(action.act(input), expected);
+
+        // Also in naive mode.
+        action.naive();
+        assert_eq!(action.act("Muesli"), "Muesli");
+    }
 }
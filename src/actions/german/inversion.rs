@@ -0,0 +1,53 @@
+use crate::actions::Action;
+
+/// Replaces Eszett (`ß`, `ẞ`) with its alternative spelling `ss`, as in Swiss
+/// orthography.
+///
+/// Eszett surrounded by uppercase letters, like in `STRAßE`, becomes `SS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Eszett {}
+
+impl Action for Eszett {
+    fn act(&self, input: &str) -> String {
+        let mut output = String::with_capacity(input.len());
+        let mut chars = input.chars().peekable();
+        let mut previous = None;
+
+        while let Some(c) = chars.next() {
+            match c {
+                'ẞ' => output.push_str("SS"),
+                'ß' if previous.is_some_and(char::is_uppercase)
+                    && !chars.peek().is_some_and(|next| next.is_lowercase()) =>
+                {
+                    output.push_str("SS");
+                }
+                'ß' => output.push_str("ss"),
+                _ => output.push(c),
+            }
+
+            previous = Some(c);
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", "")]
+    #[case("Straße", "Strasse")]
+    #[case("STRAẞE", "STRASSE")]
+    #[case("STRAßE", "STRASSE")]
+    #[case("GROß", "GROSS")]
+    #[case("Grüße aus Zürich", "Grüsse aus Zürich")]
+    #[case("ßuper", "ssuper")]
+    #[case("Maß", "Mass")]
+    fn test_eszett_inversion(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Eszett::default().act(input), expected);
+    }
+}
@@ -0,0 +1,90 @@
+use log::trace;
+
+use super::Action;
+
+/// Adds a fixed delta to integers.
+///
+/// Input which is not an integer, or for which the result would overflow, is returned
+/// unchanged. Leading zeros (`007`) and an explicit plus sign (`+1`) are preserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Increment {
+    delta: i64,
+}
+
+impl Increment {
+    /// Creates a new action adding `delta` (which may be negative) to integers.
+    #[must_use]
+    pub const fn new(delta: i64) -> Self {
+        Self { delta }
+    }
+}
+
+impl Action for Increment {
+    fn act(&self, input: &str) -> String {
+        let Some(result) = input
+            .parse::<i64>()
+            .ok()
+            .and_then(|n| n.checked_add(self.delta))
+        else {
+            trace!("Not incrementing, not an integer or overflow: {input:?}");
+            return input.to_owned();
+        };
+
+        let digits = input.trim_start_matches(['+', '-']);
+        let zero_padded = digits.len() > 1 && digits.starts_with('0');
+
+        let sign = if result < 0 {
+            "-"
+        } else if input.starts_with('+') {
+            "+"
+        } else {
+            ""
+        };
+
+        let magnitude = result.unsigned_abs();
+        if zero_padded {
+            format!("{sign}{magnitude:0width$}", width = digits.len())
+        } else {
+            format!("{sign}{magnitude}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(1, "0", "1")]
+    #[case(1, "41", "42")]
+    #[case(-1, "42", "41")]
+    #[case(-5, "3", "-2")]
+    #[case(5, "-3", "2")]
+    #[case(10, "99", "109")]
+    #[case(0, "7", "7")]
+    //
+    // Formatting is preserved
+    #[case(1, "007", "008")]
+    #[case(1, "099", "100")]
+    #[case(1, "999", "1000")]
+    #[case(-1, "010", "009")]
+    #[case(-20, "010", "-010")]
+    #[case(1, "+1", "+2")]
+    #[case(-3, "+1", "-2")]
+    //
+    // Non-integers are left alone
+    #[case(1, "", "")]
+    #[case(1, "a", "a")]
+    #[case(1, "1.5", "1.5")]
+    #[case(1, " 1", " 1")]
+    #[case(1, "1_000", "1_000")]
+    //
+    // Overflow leaves input alone
+    #[case(1, "9223372036854775807", "9223372036854775807")]
+    #[case(-1, "-9223372036854775808", "-9223372036854775808")]
+    fn test_increment(#[case] delta: i64, #[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Increment::new(delta).act(input), expected);
+    }
+}
@@ -0,0 +1,111 @@
+/// A locale, for language-specific casing rules.
+///
+/// Casing mostly works the same regardless of language, but some languages tailor
+/// Unicode's default rules. Turkish, for example, has both a dotted and a dotless i,
+/// such that uppercasing `i` gives `İ`, not `I`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// No tailoring, using Unicode's default casing.
+    #[default]
+    Root,
+    /// Turkish, with dotted `İ`/`i` and dotless `I`/`ı`.
+    Turkish,
+    /// Azerbaijani, which is cased like Turkish.
+    Azerbaijani,
+}
+
+impl Locale {
+    /// Uppercases `input` according to this locale.
+    #[must_use]
+    pub fn to_uppercase(self, input: &str) -> String {
+        match self {
+            Self::Root => input.to_uppercase(),
+            // Dotless `ı` already uppercases to `I` by default.
+            Self::Turkish | Self::Azerbaijani => input.replace('i', "İ").to_uppercase(),
+        }
+    }
+
+    /// Lowercases `input` according to this locale.
+    #[must_use]
+    pub fn to_lowercase(self, input: &str) -> String {
+        match self {
+            Self::Root => input.to_lowercase(),
+            // By default, `İ` lowercases to `i` followed by a combining dot above.
+            Self::Turkish | Self::Azerbaijani => input
+                .replace('İ', "i")
+                .replace("I\u{307}", "i")
+                .replace('I', "ı")
+                .to_lowercase(),
+        }
+    }
+
+    /// Corrects the casing of characters of `original` which were cased without
+    /// regard to this locale, resulting in `cased`. Useful if casing is left to
+    /// code unaware of locales.
+    ///
+    /// The two are expected to correspond character by character; if they do not,
+    /// `cased` is returned as-is.
+    #[must_use]
+    pub fn tailor(self, original: &str, cased: String) -> String {
+        match self {
+            Self::Root => cased,
+            Self::Turkish | Self::Azerbaijani => {
+                if original.chars().count() != cased.chars().count() {
+                    return cased;
+                }
+
+                original
+                    .chars()
+                    .zip(cased.chars())
+                    .map(|pair| match pair {
+                        ('i', 'I') => 'İ',
+                        ('I', 'i') => 'ı',
+                        (_, c) => c,
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Locale::Root, "istanbul", "ISTANBUL")]
+    #[case(Locale::Turkish, "istanbul", "İSTANBUL")]
+    #[case(Locale::Turkish, "ılık", "ILIK")]
+    #[case(Locale::Azerbaijani, "istanbul", "İSTANBUL")]
+    #[case(Locale::Turkish, "straße", "STRASSE")]
+    fn test_to_uppercase(#[case] locale: Locale, #[case] input: &str, #[case] expected: &str) {
+        assert_eq!(locale.to_uppercase(input), expected);
+    }
+
+    #[rstest]
+    #[case(Locale::Root, "ISTANBUL", "istanbul")]
+    #[case(Locale::Root, "İ", "i\u{307}")]
+    #[case(Locale::Turkish, "ISTANBUL", "ıstanbul")]
+    #[case(Locale::Turkish, "İSTANBUL", "istanbul")]
+    #[case(Locale::Turkish, "I\u{307}", "i")]
+    #[case(Locale::Turkish, "ΟΔΟΣ", "οδος")]
+    fn test_to_lowercase(#[case] locale: Locale, #[case] input: &str, #[case] expected: &str) {
+        assert_eq!(locale.to_lowercase(input), expected);
+    }
+
+    #[rstest]
+    #[case(Locale::Root, "iI", "Ii", "Ii")]
+    #[case(Locale::Turkish, "iI", "Ii", "İı")]
+    #[case(Locale::Turkish, "ißi", "ISSI", "ISSI")] // Length mismatch
+    #[case(Locale::Turkish, "ab", "Ab", "Ab")]
+    fn test_tailor(
+        #[case] locale: Locale,
+        #[case] original: &str,
+        #[case] cased: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(locale.tailor(original, cased.to_owned()), expected);
+    }
+}
@@ -1,15 +1,25 @@
 use log::info;
 
-use super::Action;
+use super::{Action, Locale};
 
-/// Renders in lowercase.
+/// Renders in lowercase, according to the [`Locale`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Lower {}
+pub struct Lower {
+    locale: Locale,
+}
+
+impl Lower {
+    /// Create a new [`Lower`], lowercasing according to `locale`.
+    #[must_use]
+    pub const fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+}
 
 impl Action for Lower {
     fn act(&self, input: &str) -> String {
         info!("Lowercasing: '{}'", input);
-        input.to_lowercase()
+        self.locale.to_lowercase(input)
     }
 }
 
@@ -49,6 +59,6 @@ mod tests {
     // Emojis
     #[case("👋\0", "👋\0")]
     fn substitute(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(Lower {}.act(input), expected);
+        assert_eq!(Lower::default().act(input), expected);
     }
 }
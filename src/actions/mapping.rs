@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use super::Action;
+use crate::RegexPattern;
+
+/// Replaces input according to a mapping of keys to values, for example for bulk
+/// renames of identifiers.
+///
+/// Input is looked up among the keys, which are either literal strings or regular
+/// expressions; see [`Mapping::literal`] and [`Mapping::regex`]. Input without a
+/// matching key is returned unchanged. This action is meant to be used with a scope
+/// consisting of all keys, such that each scoped part has a value.
+///
+/// ## Examples
+///
+/// ```rust
+/// use srgn::actions::{Action, Mapping};
+///
+/// let mapping = Mapping::literal(vec![
+///     ("colour".to_owned(), "color".to_owned()),
+///     ("grey".to_owned(), "gray".to_owned()),
+/// ]);
+///
+/// assert_eq!(mapping.act("grey"), "gray");
+/// assert_eq!(mapping.act("blue"), "blue");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mapping(Keys);
+
+#[derive(Debug, Clone)]
+enum Keys {
+    Literal(HashMap<String, String>),
+    Regex(Vec<(RegexPattern, String)>),
+}
+
+impl Mapping {
+    /// Creates a new mapping with literal string keys. Of duplicate keys, the first one
+    /// wins.
+    #[must_use]
+    pub fn literal(pairs: Vec<(String, String)>) -> Self {
+        let mut map = HashMap::with_capacity(pairs.len());
+        for (key, value) in pairs {
+            map.entry(key).or_insert(value);
+        }
+
+        Self(Keys::Literal(map))
+    }
+
+    /// Creates a new mapping with regular expression keys. Input is mapped to the value
+    /// of the first key matching it *entirely*.
+    ///
+    /// # Errors
+    ///
+    /// If any key is not a valid regular expression.
+    pub fn regex(pairs: Vec<(String, String)>) -> Result<Self, Box<fancy_regex::Error>> {
+        pairs
+            .into_iter()
+            .map(|(key, value)| {
+                RegexPattern::new(&format!("^(?:{key})$"))
+                    .map(|key| (key, value))
+                    .map_err(Box::new)
+            })
+            .collect::<Result<_, _>>()
+            .map(|keys| Self(Keys::Regex(keys)))
+    }
+}
+
+impl Action for Mapping {
+    fn act(&self, input: &str) -> String {
+        let value = match &self.0 {
+            Keys::Literal(map) => map.get(input),
+            Keys::Regex(keys) => keys
+                .iter()
+                .find(|(key, _)| key.is_match(input).unwrap_or_default())
+                .map(|(_, value)| value),
+        };
+
+        value.map_or_else(|| input.to_owned(), Clone::clone)
+    }
+}
+
+/// A file format for [`Mapping`] pairs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingFormat {
+    /// Comma-separated values, one `key,value` record per line, without a header.
+    /// Fields may be quoted with `"`, in which a literal `"` is written as `""`.
+    Csv,
+    /// Tab-separated values, one `key<TAB>value` record per line, without a header.
+    /// There is no quoting.
+    Tsv,
+    /// A JSON object with string values, such as `{"key": "value"}`.
+    Json,
+}
+
+impl MappingFormat {
+    /// Parses `contents` into key-value pairs, in order. Empty lines in CSV and TSV are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// If `contents` are not valid in this format, see [`MappingError`].
+    pub fn parse(self, contents: &str) -> Result<Vec<(String, String)>, MappingError> {
+        match self {
+            Self::Csv => parse_records(contents, b',', true),
+            Self::Tsv => parse_records(contents, b'\t', false),
+            Self::Json => serde_json::from_str::<serde_json::Map<_, _>>(contents)?
+                .into_iter()
+                .map(|(key, value)| Ok((key, serde_json::from_value(value)?)))
+                .collect(),
+        }
+    }
+}
+
+/// An error when parsing [`Mapping`] pairs.
+#[derive(Debug)]
+pub enum MappingError {
+    /// A record does not consist of exactly two fields.
+    InvalidRecord {
+        /// The (1-based) line number of the record.
+        line: u64,
+        /// The number of fields found.
+        fields: usize,
+    },
+    /// The CSV or TSV is invalid.
+    Csv(csv::Error),
+    /// The JSON is invalid or not an object of strings.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord { line, fields } => write!(
+                f,
+                "Line {line}: expected 2 fields (key and value), found {fields}"
+            ),
+            Self::Csv(e) => write!(f, "Invalid CSV: {e}"),
+            Self::Json(e) => write!(f, "Invalid JSON: {e}"),
+        }
+    }
+}
+
+impl Error for MappingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidRecord { .. } => None,
+            Self::Csv(e) => Some(e),
+            Self::Json(e) => Some(e),
+        }
+    }
+}
+
+impl From<csv::Error> for MappingError {
+    fn from(err: csv::Error) -> Self {
+        Self::Csv(err)
+    }
+}
+
+impl From<serde_json::Error> for MappingError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Parses `contents` into records of exactly two fields each, without a header.
+fn parse_records(
+    contents: &str,
+    delimiter: u8,
+    quoting: bool,
+) -> Result<Vec<(String, String)>, MappingError> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .quoting(quoting)
+        .from_reader(contents.as_bytes())
+        .into_records()
+        .map(|record| {
+            let record = record?;
+            match (record.len(), record.get(0), record.get(1)) {
+                (2, Some(key), Some(value)) => Ok((key.to_owned(), value.to_owned())),
+                (fields, ..) => Err(MappingError::InvalidRecord {
+                    line: record.position().map_or(0, csv::Position::line),
+                    fields,
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+            .collect()
+    }
+
+    #[rstest]
+    #[case(MappingFormat::Csv, "", vec![])]
+    #[case(MappingFormat::Csv, "a,b\n\nc,d", vec![("a", "b"), ("c", "d")])]
+    #[case(MappingFormat::Csv, "a,b\r\nc,d\r\n", vec![("a", "b"), ("c", "d")])]
+    #[case(MappingFormat::Csv, "a,\n", vec![("a", "")])]
+    #[case(
+        MappingFormat::Csv,
+        "\"a,b\",\"say \"\"hi\"\"\"\n\"multi\nline\",x\n",
+        vec![("a,b", "say \"hi\""), ("multi\nline", "x")]
+    )]
+    #[case(MappingFormat::Tsv, "a\tb\n\nc,d\t\"e\"\n", vec![("a", "b"), ("c,d", "\"e\"")])]
+    #[case(MappingFormat::Json, "{}", vec![])]
+    #[case(
+        MappingFormat::Json,
+        " { \"a\" : \"b\",\n\"c\":\"d\" } \n",
+        vec![("a", "b"), ("c", "d")]
+    )]
+    #[case(
+        MappingFormat::Json,
+        r#"{"z": "1", "a": "2"}"#,
+        vec![("z", "1"), ("a", "2")]
+    )]
+    #[case(
+        MappingFormat::Json,
+        r#"{"\"\\\/\n\t": "ü😀"}"#,
+        vec![("\"\\/\n\t", "ü😀")]
+    )]
+    fn test_parse(
+        #[case] format: MappingFormat,
+        #[case] contents: &str,
+        #[case] expected: Vec<(&str, &str)>,
+    ) {
+        assert_eq!(format.parse(contents).unwrap(), pairs(&expected));
+    }
+
+    #[rstest]
+    #[case(
+        MappingFormat::Csv,
+        "a,b\nc\n",
+        "Line 2: expected 2 fields (key and value), found 1"
+    )]
+    #[case(
+        MappingFormat::Csv,
+        "a,b,c",
+        "Line 1: expected 2 fields (key and value), found 3"
+    )]
+    // Unterminated quotes run to the end of input
+    #[case(
+        MappingFormat::Csv,
+        "a,b\n\"c,d\n",
+        "Line 2: expected 2 fields (key and value), found 1"
+    )]
+    #[case(
+        MappingFormat::Tsv,
+        "a\tb\tc\n",
+        "Line 1: expected 2 fields (key and value), found 3"
+    )]
+    #[case(
+        MappingFormat::Json,
+        r#"{"a": 1}"#,
+        "Invalid JSON: invalid type: integer `1`"
+    )]
+    #[case(MappingFormat::Json, r#"{"a": "b",}"#, "Invalid JSON: trailing comma")]
+    #[case(
+        MappingFormat::Json,
+        r#"{"a": "b"} x"#,
+        "Invalid JSON: trailing characters"
+    )]
+    #[case(
+        MappingFormat::Json,
+        r#"["a"]"#,
+        "Invalid JSON: invalid type: sequence"
+    )]
+    #[case(
+        MappingFormat::Json,
+        r#"{"a": "b"#,
+        "Invalid JSON: EOF while parsing a string"
+    )]
+    fn test_parse_invalid(
+        #[case] format: MappingFormat,
+        #[case] contents: &str,
+        #[case] expected: &str,
+    ) {
+        let err = format.parse(contents).unwrap_err().to_string();
+
+        assert!(err.starts_with(expected), "{err}");
+    }
+
+    #[rstest]
+    #[case("colour", "color")]
+    #[case("grey", "gray")]
+    #[case("gre", "gre")]
+    #[case("greyish", "greyish")]
+    fn test_literal(#[case] input: &str, #[case] expected: &str) {
+        let mapping = Mapping::literal(pairs(&[
+            ("colour", "color"),
+            ("grey", "gray"),
+            ("grey", "ignored"),
+        ]));
+
+        assert_eq!(mapping.act(input), expected);
+    }
+
+    #[rstest]
+    #[case("colour", "color")]
+    #[case("color", "color")]
+    #[case("gray", "grey")]
+    #[case("grayish", "grayish")] // Keys must match entirely
+    #[case("graycolor", "graycolor")]
+    fn test_regex(#[case] input: &str, #[case] expected: &str) {
+        let mapping = Mapping::regex(pairs(&[
+            ("colou?r", "color"),
+            ("gr[ae]y|silver", "grey"),
+            ("gray", "ignored"),
+        ]))
+        .unwrap();
+
+        assert_eq!(mapping.act(input), expected);
+    }
+
+    #[test]
+    fn test_regex_invalid() {
+        assert!(Mapping::regex(pairs(&[("(", "x")])).is_err());
+    }
+}
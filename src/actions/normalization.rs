@@ -5,12 +5,83 @@ use super::Action;
 
 /// Performs Unicode normalization.
 ///
-/// Uses NFD (Normalization Form D), canonical decomposition.
+/// By default, uses NFD (Normalization Form D), canonical decomposition, and throws
+/// away marks. This de-accents input, turning `é` into `e`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    form: NormalizationForm,
+    strip_marks: bool,
+}
+
+/// A Unicode [normalization form](https://unicode.org/reports/tr15/).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Normalization {}
+pub enum NormalizationForm {
+    /// Canonical decomposition, followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    #[default]
+    Nfd,
+    /// Compatibility decomposition, followed by canonical composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl Normalization {
+    /// Creates a new action normalizing to the given `form`, and throwing away marks
+    /// afterwards if `strip_marks` is set.
+    ///
+    /// Marks only remain separate from the characters they apply to in the
+    /// decomposing forms; use those for stripping them.
+    #[must_use]
+    pub const fn new(form: NormalizationForm, strip_marks: bool) -> Self {
+        Self { form, strip_marks }
+    }
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self::new(NormalizationForm::default(), true)
+    }
+}
 
 impl Action for Normalization {
     fn act(&self, input: &str) -> String {
-        input.nfd().filter(|c| !c.is_mark()).collect()
+        let normalized: Box<dyn Iterator<Item = char>> = match self.form {
+            NormalizationForm::Nfc => Box::new(input.nfc()),
+            NormalizationForm::Nfd => Box::new(input.nfd()),
+            NormalizationForm::Nfkc => Box::new(input.nfkc()),
+            NormalizationForm::Nfkd => Box::new(input.nfkd()),
+        };
+
+        if self.strip_marks {
+            normalized.filter(|c| !c.is_mark()).collect()
+        } else {
+            normalized.collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(NormalizationForm::Nfd, true, "Café", "Cafe")]
+    #[case(NormalizationForm::Nfd, false, "Café", "Cafe\u{301}")]
+    #[case(NormalizationForm::Nfc, false, "Cafe\u{301}", "Café")]
+    #[case(NormalizationForm::Nfc, true, "Cafe\u{301}", "Café")] // Composed: no marks left
+    #[case(NormalizationForm::Nfkc, false, "ﬁ ①", "fi 1")]
+    #[case(NormalizationForm::Nfkd, true, "ﬁ é", "fi e")]
+    #[case(NormalizationForm::Nfd, false, "ﬁ", "ﬁ")] // Canonical only
+    fn test_normalization(
+        #[case] form: NormalizationForm,
+        #[case] strip_marks: bool,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(Normalization::new(form, strip_marks).act(input), expected);
     }
 }
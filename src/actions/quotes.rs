@@ -0,0 +1,131 @@
+use crate::actions::Action;
+
+pub mod inversion;
+
+/// Replace straight quotes (`"`, `'`) with typographic ones (`“”`, `‘’`, ...),
+/// according to a [`QuoteStyle`], and three dots (`...`) with an ellipsis (`…`).
+///
+/// Whether a quote opens or closes is guessed from the character before it: at the
+/// start, after whitespace or after opening brackets, it opens; otherwise, it closes.
+/// A single quote between two letters (as in `don't`) is an apostrophe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Quotes {
+    style: QuoteStyle,
+}
+
+/// The typographic conventions for quotes of a language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    /// “Double” and ‘single’ quotes.
+    #[default]
+    English,
+    /// „Double“ and ‚single‘ quotes.
+    German,
+    /// «Double» and ‹single› quotes (guillemets).
+    French,
+}
+
+impl QuoteStyle {
+    /// Opening and closing double quotes.
+    const fn double(self) -> (char, char) {
+        match self {
+            Self::English => ('“', '”'),
+            Self::German => ('„', '“'),
+            Self::French => ('«', '»'),
+        }
+    }
+
+    /// Opening and closing single quotes.
+    const fn single(self) -> (char, char) {
+        match self {
+            Self::English => ('‘', '’'),
+            Self::German => ('‚', '‘'),
+            Self::French => ('‹', '›'),
+        }
+    }
+}
+
+/// The apostrophe, the same across all [`QuoteStyle`]s.
+const APOSTROPHE: char = '’';
+
+/// The ellipsis, replacing three dots.
+const ELLIPSIS: char = '…';
+
+impl Quotes {
+    /// Creates a new action using quotes of the given `style`.
+    #[must_use]
+    pub const fn new(style: QuoteStyle) -> Self {
+        Self { style }
+    }
+}
+
+impl Action for Quotes {
+    fn act(&self, input: &str) -> String {
+        let chars: Vec<char> = input.chars().collect();
+        let mut out = String::with_capacity(input.len());
+
+        let mut i = 0;
+        while i < chars.len() {
+            let prev = i.checked_sub(1).map(|j| chars[j]);
+            let next = chars.get(i + 1).copied();
+            let opens = prev.map_or(true, |c| c.is_whitespace() || "([{-–—".contains(c));
+
+            match chars[i] {
+                '"' => {
+                    let (open, close) = self.style.double();
+                    out.push(if opens { open } else { close });
+                }
+                '\'' => {
+                    let (open, close) = self.style.single();
+                    let between_letters = prev.is_some_and(char::is_alphanumeric)
+                        && next.is_some_and(char::is_alphanumeric);
+
+                    out.push(if between_letters {
+                        APOSTROPHE
+                    } else if opens {
+                        open
+                    } else {
+                        close
+                    });
+                }
+                '.' if chars[i..].starts_with(&['.', '.', '.']) => {
+                    out.push(ELLIPSIS);
+                    i += 2;
+                }
+                c => out.push(c),
+            }
+
+            i += 1;
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(QuoteStyle::English, "", "")]
+    #[case(QuoteStyle::English, r#""Hello""#, "“Hello”")]
+    #[case(
+        QuoteStyle::English,
+        r#"She said "hi" and left."#,
+        "She said “hi” and left."
+    )]
+    #[case(QuoteStyle::English, "It's 'fine'", "It’s ‘fine’")]
+    #[case(QuoteStyle::English, r#"("quoted")"#, "(“quoted”)")]
+    #[case(QuoteStyle::English, "Wait...", "Wait…")]
+    #[case(QuoteStyle::English, "....", "….")]
+    #[case(QuoteStyle::English, "the students' books", "the students’ books")]
+    #[case(QuoteStyle::German, r#"Er sagte "hallo"."#, "Er sagte „hallo“.")]
+    #[case(QuoteStyle::German, "'einfach' geht's", "‚einfach‘ geht’s")]
+    #[case(QuoteStyle::French, r#"Il a dit "bonjour""#, "Il a dit «bonjour»")]
+    #[case(QuoteStyle::French, "'oui'", "‹oui›")]
+    fn test_quotes(#[case] style: QuoteStyle, #[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Quotes::new(style).act(input), expected);
+    }
+}
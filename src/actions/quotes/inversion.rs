@@ -0,0 +1,51 @@
+use crate::actions::Action;
+
+/// Inverts all quotes and ellipses inserted by [`super::Quotes`], of any
+/// [`QuoteStyle`][`super::QuoteStyle`].
+///
+/// As straight quotes do not distinguish between opening and closing, this is only
+/// the inverse for input that contained no typographic quotes in the first place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Quotes {}
+
+impl Action for Quotes {
+    fn act(&self, input: &str) -> String {
+        let mut out = String::with_capacity(input.len());
+
+        for c in input.chars() {
+            match c {
+                '“' | '”' | '„' | '«' | '»' => out.push('"'),
+                '‘' | '’' | '‚' | '‹' | '›' => out.push('\''),
+                '…' => out.push_str("..."),
+                c => out.push(c),
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::super::{QuoteStyle, Quotes as Forward};
+    use super::*;
+
+    #[rstest]
+    #[case("“Hello”", r#""Hello""#)]
+    #[case("„hallo“ geht’s…", r#""hallo" geht's..."#)]
+    #[case("«bonjour» ‹oui›", r#""bonjour" 'oui'"#)]
+    fn test_inversion(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(Quotes::default().act(input), expected);
+    }
+
+    #[rstest]
+    fn test_roundtrip(
+        #[values(QuoteStyle::English, QuoteStyle::German, QuoteStyle::French)] style: QuoteStyle,
+        #[values(r#"He said "it's 'fine'"..."#, "plain", r#"("a") ['b']"#)] input: &str,
+    ) {
+        let quoted = Forward::new(style).act(input);
+        assert_eq!(Quotes::default().act(&quoted), input);
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt::Write as _;
+
+use sha2::{Digest, Sha256};
+
+use super::Action;
+
+/// Redacts input, for example to anonymize secrets in logs before sharing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Redact {
+    /// Replaces each character with the given one, keeping the length (in characters).
+    Mask {
+        /// The character to mask with.
+        with: char,
+    },
+    /// Replaces input with its SHA-256 hash, in lowercase hexadecimal.
+    ///
+    /// Equal inputs produce equal outputs, so redacted values can still be correlated.
+    Hash,
+    /// Keeps only the first characters, replacing the rest with a single `…`.
+    ///
+    /// Input with at most that many characters is returned unchanged.
+    Truncate {
+        /// The number of characters to keep.
+        keep: usize,
+    },
+}
+
+impl Default for Redact {
+    fn default() -> Self {
+        Self::Mask { with: '*' }
+    }
+}
+
+impl Action for Redact {
+    fn act(&self, input: &str) -> String {
+        match *self {
+            Self::Mask { with } => input.chars().map(|_| with).collect(),
+            Self::Hash => Sha256::digest(input.as_bytes()).iter().fold(
+                String::with_capacity(64),
+                |mut result, b| {
+                    write!(result, "{b:02x}").expect("writing to `String` is infallible");
+                    result
+                },
+            ),
+            Self::Truncate { keep } => match input.char_indices().nth(keep) {
+                Some((i, _)) => format!("{}…", &input[..i]),
+                None => input.to_owned(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Redact::default(), "", "")]
+    #[case(Redact::default(), "hunter2", "*******")]
+    #[case(Redact::Mask { with: 'x' }, "123-45-6789", "xxxxxxxxxxx")]
+    #[case(Redact::Mask { with: '█' }, "üñí", "███")]
+    //
+    #[case(
+        Redact::Hash,
+        "",
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    )]
+    #[case(
+        Redact::Hash,
+        "abc",
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    )]
+    //
+    #[case(Redact::Truncate { keep: 3 }, "sk-abcdef", "sk-…")]
+    #[case(Redact::Truncate { keep: 3 }, "sk-", "sk-")]
+    #[case(Redact::Truncate { keep: 3 }, "ab", "ab")]
+    #[case(Redact::Truncate { keep: 0 }, "abc", "…")]
+    #[case(Redact::Truncate { keep: 1 }, "üñí", "ü…")]
+    fn test_redact(#[case] action: Redact, #[case] input: &str, #[case] expected: &str) {
+        assert_eq!(action.act(input), expected);
+    }
+}
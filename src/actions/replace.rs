@@ -1,12 +1,13 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 
 use log::{debug, info};
 use unescape::unescape;
-use variables::{inject_variables, VariableExpressionError};
+use variables::{inject_variables, Builtins, VariableExpressionError};
 
 use super::{Action, ActionError};
-use crate::scoping::scope::ScopeContext;
+use crate::scoping::scope::{Location, ScopeContext};
 
 /// Items for dealing with variables in replacement values.
 pub mod variables;
@@ -131,15 +132,34 @@ impl Action for Replacement {
 
     fn act_with_context(
         &self,
-        _input: &str,
+        input: &str,
         context: &ScopeContext<'_>,
     ) -> Result<String, ActionError> {
+        self.act_at(input, Some(context), &Location::default())
+    }
+
+    fn act_at(
+        &self,
+        input: &str,
+        context: Option<&ScopeContext<'_>>,
+        location: &Location<'_>,
+    ) -> Result<String, ActionError> {
+        let builtins = Builtins {
+            input,
+            location: *location,
+        };
+
         match context {
-            ScopeContext::CaptureGroups(cgs) => {
+            Some(ScopeContext::CaptureGroups(cgs)) => {
                 debug!("Available capture group variables: {cgs:?}");
 
-                Ok(inject_variables(self.0.as_str(), cgs)?)
+                Ok(inject_variables(self.0.as_str(), cgs, &builtins)?)
             }
+            None => Ok(inject_variables(
+                self.0.as_str(),
+                &HashMap::new(),
+                &builtins,
+            )?),
         }
     }
 }
@@ -1,21 +1,114 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::error::Error;
+use std::ffi::OsStr;
 use std::fmt;
+use std::path::Path;
 
+use chrono::format::{Item, StrftimeItems};
+use chrono::Local;
 use log::trace;
+use uuid::Uuid;
 
 use crate::scoping::regex::CaptureGroup;
+use crate::scoping::scope::Location;
 
 type Variables<'a> = HashMap<CaptureGroup, &'a str>;
 
+/// Variables available without being defined as capture groups.
+///
+/// Capture groups of the same name take precedence.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Builtins<'a> {
+    /// The input about to be replaced.
+    pub(super) input: &'a str,
+    /// Where the input is located.
+    pub(super) location: Location<'a>,
+}
+
+impl Builtins<'_> {
+    /// Format for `date` if none is given.
+    const DEFAULT_DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+    /// Resolves the variable of the given `name`, with an optional `arg`ument (as in
+    /// `${date:%Y}`).
+    fn resolve(&self, name: &str, arg: Option<&str>) -> Result<String, VariableExpressionError> {
+        let invalid_argument = || VariableExpressionError::InvalidArgument {
+            variable: name.to_owned(),
+            argument: arg.unwrap_or_default().to_owned(),
+        };
+
+        let file = || {
+            self.location
+                .file
+                .ok_or_else(|| VariableExpressionError::UndefinedVariable(name.to_owned()))
+        };
+        // Missing components (like the extension of `Makefile`) resolve to nothing.
+        let component = |c: Option<&OsStr>| c.unwrap_or_default().to_string_lossy().into_owned();
+
+        let value = match (name, arg) {
+            ("match", None) => self.input.to_owned(),
+            ("match_upper", None) => self.input.to_uppercase(),
+            ("match_lower", None) => self.input.to_lowercase(),
+            ("line", None) => self.location.line.to_string(),
+            ("file", None) => file()?.display().to_string(),
+            ("file_name", None) => component(file()?.file_name()),
+            ("file_stem", None) => component(file()?.file_stem()),
+            ("file_ext", None) => component(file()?.extension()),
+            ("file_dir", None) => component(file()?.parent().map(Path::as_os_str)),
+            ("uuid", None) => Uuid::new_v4().to_string(),
+            ("date", arg) => {
+                let format = arg.unwrap_or(Self::DEFAULT_DATE_FORMAT);
+                let items = StrftimeItems::new(format).collect::<Vec<_>>();
+
+                // Formatting would panic later on.
+                if items.iter().any(|item| matches!(item, Item::Error)) {
+                    return Err(invalid_argument());
+                }
+
+                Local::now()
+                    .format_with_items(items.into_iter())
+                    .to_string()
+            }
+            (
+                "match" | "match_upper" | "match_lower" | "line" | "file" | "file_name"
+                | "file_stem" | "file_ext" | "file_dir" | "uuid",
+                Some(_),
+            ) => return Err(invalid_argument()),
+            _ => return Err(VariableExpressionError::UndefinedVariable(name.to_owned())),
+        };
+
+        Ok(value)
+    }
+}
+
+/// Looks up a named variable, first in the capture groups, then in the builtins.
+fn resolve_named<'a>(
+    name: &str,
+    arg: Option<&str>,
+    variables: &Variables<'a>,
+    builtins: &Builtins<'_>,
+) -> Result<Cow<'a, str>, VariableExpressionError> {
+    variables
+        .get(&CaptureGroup::Named(name.to_owned()))
+        .copied()
+        .map_or_else(
+            || builtins.resolve(name, arg).map(Cow::Owned),
+            |value| Ok(Cow::Borrowed(value)),
+        )
+}
+
 /// In an input like `Hello $var World`, inject all variables.
 ///
 /// Variables are treated as they occur in regular expressions: they can be [named or
-/// numbered](https://docs.rs/regex/latest/regex/struct.Captures.html).
+/// numbered](https://docs.rs/regex/latest/regex/struct.Captures.html). Named
+/// variables not found among the `variables` are looked up in the `builtins`. Braced
+/// named variables can take an argument, as in `${date:%Y}`.
 #[allow(clippy::too_many_lines)] // :(
 pub(super) fn inject_variables(
     input: &str,
     variables: &Variables<'_>,
+    builtins: &Builtins<'_>,
 ) -> Result<String, VariableExpressionError> {
     let mut state = State::default();
     let mut out = String::with_capacity(input.len());
@@ -49,10 +142,12 @@ pub(super) fn inject_variables(
             }
             (State::Start, 'a'..='z' | 'A'..='Z' | '_') => State::BuildingNamedVar {
                 name: String::from(c),
+                arg: None,
                 braced: false,
             },
             (State::BracedStart, 'a'..='z' | 'A'..='Z' | '_') => State::BuildingNamedVar {
                 name: String::from(c),
+                arg: None,
                 braced: true,
             },
             (State::Start, '0'..='9') => State::BuildingNumberedVar {
@@ -71,15 +166,51 @@ pub(super) fn inject_variables(
 
             // Building up
             (
-                State::BuildingNamedVar { mut name, braced },
+                State::BuildingNamedVar {
+                    mut name,
+                    arg: None,
+                    braced,
+                },
                 'a'..='z' | 'A'..='Z' | '_' | '0'..='9',
             ) => State::BuildingNamedVar {
                 name: {
                     name.push(c);
                     name
                 },
+                arg: None,
                 braced,
             },
+            (
+                State::BuildingNamedVar {
+                    name,
+                    arg: None,
+                    braced: true,
+                },
+                ':',
+            ) => {
+                to_remove += 1;
+
+                State::BuildingNamedVar {
+                    name,
+                    arg: Some(String::new()),
+                    braced: true,
+                }
+            }
+            (
+                State::BuildingNamedVar {
+                    name,
+                    arg: Some(mut arg),
+                    braced: true,
+                },
+                _,
+            ) if c != '}' => State::BuildingNamedVar {
+                name,
+                arg: Some({
+                    arg.push(c);
+                    arg
+                }),
+                braced: true,
+            },
             (
                 State::BuildingNumberedVar {
                     num: magnitude,
@@ -95,10 +226,17 @@ pub(super) fn inject_variables(
             },
 
             // Building stops
-            (State::BuildingNamedVar { name, braced: true }, '}') => {
+            (
+                State::BuildingNamedVar {
+                    name,
+                    arg,
+                    braced: true,
+                },
+                '}',
+            ) => {
                 to_remove += 1;
 
-                State::FinishNamedVar(name)
+                State::FinishNamedVar { name, arg }
             }
             (State::BuildingNumberedVar { num, braced: true }, '}') => {
                 to_remove += 1;
@@ -118,19 +256,18 @@ pub(super) fn inject_variables(
                 _,
             ) => return Err(VariableExpressionError::MismatchedBraces(num.to_string())),
 
-            (State::FinishNamedVar(name) | State::BuildingNamedVar { name, .. }, _) => {
-                trace!("Finishing up named variable '{name}'");
-                match variables.get(&CaptureGroup::Named(name.clone())) {
-                    Some(repl) => {
-                        let tail = out
-                            .pop()
-                            .expect("chars are pushed unconditionally, one is present");
-                        out.truncate(out.len() - (to_remove + name.len()));
-                        out.push_str(repl);
-                        out.push(tail);
-                    }
-                    None => return Err(VariableExpressionError::UndefinedVariable(name)),
-                };
+            (
+                State::FinishNamedVar { name, arg } | State::BuildingNamedVar { name, arg, .. },
+                _,
+            ) => {
+                trace!("Finishing up named variable '{name}' (argument: {arg:?})");
+                let repl = resolve_named(&name, arg.as_deref(), variables, builtins)?;
+                let tail = out
+                    .pop()
+                    .expect("chars are pushed unconditionally, one is present");
+                out.truncate(out.len() - (to_remove + name.len() + arg.map_or(0, |a| a.len())));
+                out.push_str(&repl);
+                out.push(tail);
 
                 match c {
                     '$' => {
@@ -154,7 +291,7 @@ pub(super) fn inject_variables(
                     None => {
                         return Err(VariableExpressionError::UndefinedVariable(num.to_string()))
                     }
-                };
+                }
 
                 match c {
                     '$' => {
@@ -177,23 +314,22 @@ pub(super) fn inject_variables(
     let last = out.chars().last();
     state = match (&state, last) {
         (
-            State::FinishNamedVar(name)
+            State::FinishNamedVar { name, arg }
             | State::BuildingNamedVar {
                 name,
+                arg,
                 braced: false,
             },
             _,
         ) => {
-            trace!("Finishing up named variable '{name}'");
-            match variables.get(&CaptureGroup::Named(name.clone())) {
-                Some(repl) => {
-                    out.truncate(out.len() - (to_remove + name.len()));
-                    out.push_str(repl);
+            trace!("Finishing up named variable '{name}' (argument: {arg:?})");
+            let repl = resolve_named(name, arg.as_deref(), variables, builtins)?;
+            out.truncate(
+                out.len() - (to_remove + name.len() + arg.as_ref().map_or(0, String::len)),
+            );
+            out.push_str(&repl);
 
-                    state
-                }
-                None => return Err(VariableExpressionError::UndefinedVariable(name.clone())),
-            }
+            state
         }
         (State::FinishNumberedVar(num) | State::BuildingNumberedVar { num, braced: false }, _) => {
             trace!("Finishing up numbered variable '{num}'");
@@ -250,12 +386,17 @@ enum State {
     Start,
     /// The detected, potential variable additionally starts with an opening brace.
     BracedStart,
-    /// A named variable is detected and is being built up.
-    BuildingNamedVar { name: String, braced: bool },
+    /// A named variable is detected and is being built up, potentially including an
+    /// argument.
+    BuildingNamedVar {
+        name: String,
+        arg: Option<String>,
+        braced: bool,
+    },
     /// A numbered variable is detected and is being built up.
     BuildingNumberedVar { num: usize, braced: bool },
     /// Processing of a named variable is done, finish it up.
-    FinishNamedVar(String),
+    FinishNamedVar { name: String, arg: Option<String> },
     /// Processing of a numbered variable is done, finish it up.
     FinishNumberedVar(usize),
 }
@@ -267,6 +408,13 @@ pub enum VariableExpressionError {
     MismatchedBraces(String),
     /// A requested variable was not passed.
     UndefinedVariable(String),
+    /// A variable does not accept the argument it was given.
+    InvalidArgument {
+        /// The variable's name.
+        variable: String,
+        /// The offending argument.
+        argument: String,
+    },
 }
 
 impl fmt::Display for VariableExpressionError {
@@ -278,6 +426,12 @@ impl fmt::Display for VariableExpressionError {
             Self::UndefinedVariable(var) => {
                 write!(f, "Undefined variable, unable to substitute: '{var}'")
             }
+            Self::InvalidArgument { variable, argument } => {
+                write!(
+                    f,
+                    "Invalid argument for variable '{variable}': '{argument}'"
+                )
+            }
         }
     }
 }
@@ -286,10 +440,23 @@ impl Error for VariableExpressionError {}
 
 #[cfg(test)]
 mod test {
+    use std::path::Path;
+
     use rstest::*;
 
     use super::*;
 
+    #[fixture]
+    fn builtins() -> Builtins<'static> {
+        Builtins {
+            input: "Input",
+            location: Location {
+                file: Some(Path::new("some/file.txt")),
+                line: 42,
+            },
+        }
+    }
+
     #[fixture]
     fn variables() -> Variables<'static> {
         Variables::from([
@@ -365,13 +532,111 @@ mod test {
         #[case] expression: &str,
         #[case] expected: Result<&str, VariableExpressionError>,
         variables: Variables<'_>,
+        builtins: Builtins<'_>,
     ) {
-        let result = inject_variables(expression, &variables);
+        let result = inject_variables(expression, &variables, &builtins);
         let expected = expected.map(str::to_owned);
 
         assert_eq!(result, expected);
     }
 
+    #[rstest]
+    #[case("$match", Ok("Input"))]
+    #[case("${match_upper}!", Ok("INPUT!"))]
+    #[case("$match_lower", Ok("input"))]
+    #[case("$file:$line", Ok("some/file.txt:42"))]
+    #[case("$file_name", Ok("file.txt"))]
+    #[case("$file_stem.$file_ext", Ok("file.txt"))]
+    #[case("${file_dir}/", Ok("some/"))]
+    #[case("${date:}", Ok(""))]
+    #[case("${date:literal}", Ok("literal"))]
+    #[case("${date:a:b}", Ok("a:b"))]
+    #[case("${date:%%}$match", Ok("%Input"))]
+    #[case("${date:ü}", Ok("ü"))]
+    //
+    // Capture groups take precedence
+    #[case("$var1", Ok("captured"))]
+    //
+    // Arguments
+    #[case(
+        "${date:%Q}",
+        Err(VariableExpressionError::InvalidArgument {
+            variable: "date".to_owned(),
+            argument: "%Q".to_owned()
+        })
+    )]
+    #[case(
+        "${match:x}",
+        Err(VariableExpressionError::InvalidArgument {
+            variable: "match".to_owned(),
+            argument: "x".to_owned()
+        })
+    )]
+    #[case("${nope:x}", Err(VariableExpressionError::UndefinedVariable("nope".to_owned())))]
+    #[case("${date:%Y", Err(VariableExpressionError::MismatchedBraces("date".to_owned())))]
+    fn test_inject_builtin_variables(
+        #[case] expression: &str,
+        #[case] expected: Result<&str, VariableExpressionError>,
+        builtins: Builtins<'_>,
+    ) {
+        let variables = Variables::from([(CaptureGroup::Named("var1".to_owned()), "captured")]);
+
+        let result = inject_variables(expression, &variables, &builtins);
+        let expected = expected.map(str::to_owned);
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    fn test_inject_dynamic_builtin_variables(builtins: Builtins<'_>) {
+        let uuid = inject_variables("$uuid", &Variables::new(), &builtins).unwrap();
+        assert!(Uuid::parse_str(&uuid).is_ok());
+        assert_ne!(
+            uuid,
+            inject_variables("$uuid", &Variables::new(), &builtins).unwrap()
+        );
+
+        let date = inject_variables("$date", &Variables::new(), &builtins).unwrap();
+        assert_eq!(date, Local::now().format("%Y-%m-%d").to_string());
+    }
+
+    #[rstest]
+    fn test_inject_file_without_file(
+        #[values("file", "file_name", "file_stem", "file_ext", "file_dir")] name: &str,
+    ) {
+        let builtins = Builtins {
+            input: "",
+            location: Location::default(),
+        };
+
+        assert_eq!(
+            inject_variables(&format!("${name}"), &Variables::new(), &builtins),
+            Err(VariableExpressionError::UndefinedVariable(name.to_owned()))
+        );
+    }
+
+    #[rstest]
+    #[case("Makefile", "$file_stem|$file_ext|$file_dir", "Makefile||")]
+    #[case("a/b.tar.gz", "$file_stem|$file_ext|$file_dir", "b.tar|gz|a")]
+    fn test_inject_file_components(
+        #[case] file: &str,
+        #[case] expression: &str,
+        #[case] expected: &str,
+    ) {
+        let builtins = Builtins {
+            input: "",
+            location: Location {
+                file: Some(Path::new(file)),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(
+            inject_variables(expression, &Variables::new(), &builtins),
+            Ok(expected.to_owned())
+        );
+    }
+
     #[rstest]
     #[case(0, 1)]
     #[case(1, 1)]
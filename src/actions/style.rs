@@ -1,3 +1,7 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
 pub use colored::{Color, ColoredString, Colorize, Styles};
 
 use super::Action;
@@ -35,6 +39,58 @@ impl Style {
     }
 }
 
+/// Parses a comma-separated specification, like `red,bold` or `black,on-yellow`.
+///
+/// A color name (like `red` or `bright-blue`) sets the foreground color, one prefixed
+/// by `on-` the background color. Other items are styles: `bold`, `dimmed`, `italic`,
+/// `underline`, `blink`, `reversed`, `hidden` and `strikethrough`. `none` is the empty
+/// specification, styling nothing.
+impl FromStr for Style {
+    type Err = StyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let color = |name: &str| Color::from_str(&name.replace(['-', '_'], " ")).ok();
+        let mut style = Self::default();
+
+        for item in s.split(',').map(str::trim) {
+            match item.to_lowercase().as_str() {
+                "none" => {}
+                "bold" => style.styles.push(Styles::Bold),
+                "dimmed" => style.styles.push(Styles::Dimmed),
+                "italic" => style.styles.push(Styles::Italic),
+                "underline" => style.styles.push(Styles::Underline),
+                "blink" => style.styles.push(Styles::Blink),
+                "reversed" => style.styles.push(Styles::Reversed),
+                "hidden" => style.styles.push(Styles::Hidden),
+                "strikethrough" => style.styles.push(Styles::Strikethrough),
+                other => match other.strip_prefix("on-") {
+                    Some(bg) => {
+                        style.bg = Some(color(bg).ok_or_else(|| StyleError(item.to_owned()))?);
+                    }
+                    None => {
+                        style.fg = Some(color(other).ok_or_else(|| StyleError(item.to_owned()))?);
+                    }
+                },
+            }
+        }
+
+        Ok(style)
+    }
+}
+
+/// An error that can occur when parsing a [`Style`]: an item is neither a known color
+/// nor style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleError(pub String);
+
+impl fmt::Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown color or style: '{}'", self.0)
+    }
+}
+
+impl Error for StyleError {}
+
 impl Action for Style {
     fn act(&self, input: &str) -> String {
         const NEWLINE: char = '\n';
@@ -95,3 +151,28 @@ impl Action for Style {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("red", Ok(Style { fg: Some(Color::Red), bg: None, styles: vec![] }))]
+    #[case("none", Ok(Style::default()))]
+    #[case(
+        "Bright-Blue, on-black,bold,underline",
+        Ok(Style {
+            fg: Some(Color::BrightBlue),
+            bg: Some(Color::Black),
+            styles: vec![Styles::Bold, Styles::Underline],
+        })
+    )]
+    #[case("on-bright_white", Ok(Style { fg: None, bg: Some(Color::BrightWhite), styles: vec![] }))]
+    #[case("red,blinking", Err(StyleError("blinking".into())))]
+    #[case("on-nothing", Err(StyleError("on-nothing".into())))]
+    fn test_style_from_str(#[case] input: &str, #[case] expected: Result<Style, StyleError>) {
+        assert_eq!(input.parse(), expected);
+    }
+}
@@ -0,0 +1,78 @@
+use super::Action;
+
+/// Wraps input in a prefix and a suffix.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Surround {
+    prefix: String,
+    suffix: String,
+}
+
+impl Surround {
+    /// The placeholder separating prefix and suffix in [`Surround::from_template`].
+    pub const PLACEHOLDER: &'static str = "...";
+
+    /// Creates a new action wrapping input in the given `prefix` and `suffix`.
+    #[must_use]
+    pub const fn new(prefix: String, suffix: String) -> Self {
+        Self { prefix, suffix }
+    }
+
+    /// Creates a new action from a template such as `<...>`, where the (first)
+    /// [`Surround::PLACEHOLDER`] stands for the input.
+    ///
+    /// Returns [`None`] if the template does not contain the placeholder.
+    #[must_use]
+    pub fn from_template(template: &str) -> Option<Self> {
+        template
+            .split_once(Self::PLACEHOLDER)
+            .map(|(prefix, suffix)| Self::new(prefix.to_owned(), suffix.to_owned()))
+    }
+}
+
+impl Action for Surround {
+    fn act(&self, input: &str) -> String {
+        let mut result = String::with_capacity(self.prefix.len() + input.len() + self.suffix.len());
+
+        result.push_str(&self.prefix);
+        result.push_str(input);
+        result.push_str(&self.suffix);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", "", "a", "a")]
+    #[case("<", ">", "a", "<a>")]
+    #[case("\"", "", "a", "\"a")]
+    #[case("", "!", "a", "a!")]
+    #[case("«", "»", "", "«»")]
+    fn test_surround(
+        #[case] prefix: &str,
+        #[case] suffix: &str,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let action = Surround::new(prefix.to_owned(), suffix.to_owned());
+        assert_eq!(action.act(input), expected);
+    }
+
+    #[rstest]
+    #[case("<...>", Some(("<", ">")))]
+    #[case("...", Some(("", "")))]
+    #[case("'...", Some(("'", "")))]
+    #[case("(...)...", Some(("(", ")...")))] // Only first placeholder counts
+    #[case("<>", None)]
+    #[case("..", None)]
+    fn test_from_template(#[case] template: &str, #[case] expected: Option<(&str, &str)>) {
+        let expected =
+            expected.map(|(prefix, suffix)| Surround::new(prefix.to_owned(), suffix.to_owned()));
+        assert_eq!(Surround::from_template(template), expected);
+    }
+}
@@ -14,8 +14,100 @@ pub mod inversion;
 ///
 /// This action is greedy, i.e. it will try to replace as many symbols as possible,
 /// replacing left-to-right as greedily as possible.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Symbols {}
+///
+/// Only symbols of the enabled [categories](SymbolCategory) are replaced. Some are
+/// prone to false positives, and are not enabled by [default](Symbols::default).
+/// Custom symbols can be added on top, taking precedence over built-in ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    categories: Vec<SymbolCategory>,
+    custom: Vec<(Vec<char>, String)>,
+}
+
+/// A category of symbols to replace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolCategory {
+    /// `--` and `---` to `–` and `—`. Enabled by default.
+    Dashes,
+    /// Arrows, like `->` to `→` and `=>` to `⇒`. Enabled by default.
+    Arrows,
+    /// Comparisons: `!=`, `<=` and `>=` to `≠`, `≤` and `≥`. Enabled by default.
+    Math,
+    /// The fractions `1/2`, `1/4` and `3/4` to `½`, `¼` and `¾`.
+    Fractions,
+    /// `x` between numbers to `×`, as in `3x4` or `3 x 4`, but not `0x4`.
+    Multiplication,
+    /// `+-` to `±`.
+    PlusMinus,
+    /// `(c)`, `(tm)` and `(r)` to `©`, `™` and `®`.
+    Legal,
+}
+
+impl SymbolCategory {
+    /// The categories enabled by default.
+    pub const DEFAULT: [Self; 3] = [Self::Dashes, Self::Arrows, Self::Math];
+}
+
+impl Symbols {
+    /// Create a new [`Symbols`], replacing symbols of exactly the given `categories`.
+    #[must_use]
+    pub const fn new(categories: Vec<SymbolCategory>) -> Self {
+        Self {
+            categories,
+            custom: Vec::new(),
+        }
+    }
+
+    /// Additionally replace each of the given `(from, to)` pairs of custom symbols.
+    ///
+    /// Custom symbols take precedence over built-in ones. Where several custom symbols
+    /// match, the longest one wins. Empty `from` symbols are ignored.
+    #[must_use]
+    pub fn with_custom(mut self, custom: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.custom.extend(
+            custom
+                .into_iter()
+                .filter(|(from, _)| !from.is_empty())
+                .map(|(from, to)| (from.chars().collect(), to)),
+        );
+        // Stable, so earlier entries win among equally long ones.
+        self.custom
+            .sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+
+        self
+    }
+
+    fn enabled(&self, category: SymbolCategory) -> bool {
+        self.categories.contains(&category)
+    }
+
+    /// Replace the entire `stack` with the given `symbol`, if its category is enabled.
+    /// Otherwise, the stack is restored to the symbol's ASCII representation, which
+    /// it might have partially lost to an earlier, shorter symbol (like `--` before
+    /// `-->`).
+    fn replace(&self, stack: &mut Vec<char>, symbol: Symbol) {
+        stack.clear();
+
+        if self.enabled(symbol.category()) {
+            stack.push(symbol.into());
+        } else {
+            stack.extend(symbol.ascii().chars());
+        }
+    }
+
+    /// The custom symbol matching at the start of `deque`, if any.
+    fn custom_at(&self, deque: &VecDeque<char>) -> Option<&(Vec<char>, String)> {
+        self.custom.iter().find(|(from, _)| {
+            deque.len() >= from.len() && deque.iter().zip(from).all(|(a, b)| a == b)
+        })
+    }
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Self::new(SymbolCategory::DEFAULT.to_vec())
+    }
+}
 
 macro_rules! fetch_next {
     ($it:expr, $stack:expr, $buf:expr $(, $label:tt)?) => {
@@ -45,53 +137,59 @@ impl Action for Symbols {
     /// coroutine so it can be yielded again.
     ///
     /// All in all, ugly and verbose, would not recommend, but a worthwhile experiment.
-    #[allow(clippy::cognitive_complexity)] // Yep, it's terrible alright
+    #[allow(clippy::cognitive_complexity, clippy::too_many_lines)] // Yep, it's terrible alright
     fn act(&self, input: &str) -> String {
         let mut deque = input.chars().collect::<VecDeque<_>>();
         let mut out = String::new();
 
         'outer: loop {
+            if let Some((from, to)) = self.custom_at(&deque) {
+                deque.drain(..from.len());
+                out.push_str(to);
+                continue;
+            }
+
             let mut stack = Vec::new();
 
             match fetch_next!(deque, stack, out) {
                 '-' => match fetch_next!(deque, stack, out) {
                     '-' => {
                         // Be greedy, could be last character
-                        replace(&mut stack, Symbol::EnDash);
+                        self.replace(&mut stack, Symbol::EnDash);
 
                         match fetch_next!(deque, stack, out) {
-                            '-' => replace(&mut stack, Symbol::EmDash),
-                            '>' => replace(&mut stack, Symbol::LongRightArrow),
+                            '-' => self.replace(&mut stack, Symbol::EmDash),
+                            '>' => self.replace(&mut stack, Symbol::LongRightArrow),
                             _ => undo_overfetching(&mut deque, &mut stack),
                         }
                     }
-                    '>' => replace(&mut stack, Symbol::ShortRightArrow),
+                    '>' => self.replace(&mut stack, Symbol::ShortRightArrow),
                     _ => undo_overfetching(&mut deque, &mut stack),
                 },
                 '<' => match fetch_next!(deque, stack, out) {
                     '-' => {
                         // Be greedy, could be last character
-                        replace(&mut stack, Symbol::ShortLeftArrow);
+                        self.replace(&mut stack, Symbol::ShortLeftArrow);
 
                         match fetch_next!(deque, stack, out) {
-                            '-' => replace(&mut stack, Symbol::LongLeftArrow),
-                            '>' => replace(&mut stack, Symbol::LeftRightArrow),
+                            '-' => self.replace(&mut stack, Symbol::LongLeftArrow),
+                            '>' => self.replace(&mut stack, Symbol::LeftRightArrow),
                             _ => undo_overfetching(&mut deque, &mut stack),
                         }
                     }
-                    '=' => replace(&mut stack, Symbol::LessThanOrEqual),
+                    '=' => self.replace(&mut stack, Symbol::LessThanOrEqual),
                     _ => undo_overfetching(&mut deque, &mut stack),
                 },
                 '>' => match fetch_next!(deque, stack, out) {
-                    '=' => replace(&mut stack, Symbol::GreaterThanOrEqual),
+                    '=' => self.replace(&mut stack, Symbol::GreaterThanOrEqual),
                     _ => undo_overfetching(&mut deque, &mut stack),
                 },
                 '!' => match fetch_next!(deque, stack, out) {
-                    '=' => replace(&mut stack, Symbol::NotEqual),
+                    '=' => self.replace(&mut stack, Symbol::NotEqual),
                     _ => undo_overfetching(&mut deque, &mut stack),
                 },
                 '=' => match fetch_next!(deque, stack, out) {
-                    '>' => replace(&mut stack, Symbol::RightDoubleArrow),
+                    '>' => self.replace(&mut stack, Symbol::RightDoubleArrow),
                     _ => undo_overfetching(&mut deque, &mut stack),
                 },
                 // "Your scientists were so preoccupied with whether or not they could,
@@ -130,6 +228,66 @@ impl Action for Symbols {
                     },
                     _ => undo_overfetching(&mut deque, &mut stack),
                 },
+                '+' if self.enabled(SymbolCategory::PlusMinus) => {
+                    match fetch_next!(deque, stack, out) {
+                        '-' => self.replace(&mut stack, Symbol::PlusMinus),
+                        _ => undo_overfetching(&mut deque, &mut stack),
+                    }
+                }
+                '(' if self.enabled(SymbolCategory::Legal) => {
+                    match fetch_next!(deque, stack, out) {
+                        'c' | 'C' => match fetch_next!(deque, stack, out) {
+                            ')' => self.replace(&mut stack, Symbol::Copyright),
+                            _ => undo_overfetching(&mut deque, &mut stack),
+                        },
+                        'r' | 'R' => match fetch_next!(deque, stack, out) {
+                            ')' => self.replace(&mut stack, Symbol::Registered),
+                            _ => undo_overfetching(&mut deque, &mut stack),
+                        },
+                        't' | 'T' => match fetch_next!(deque, stack, out) {
+                            'm' | 'M' => match fetch_next!(deque, stack, out) {
+                                ')' => self.replace(&mut stack, Symbol::Trademark),
+                                _ => undo_overfetching(&mut deque, &mut stack),
+                            },
+                            _ => undo_overfetching(&mut deque, &mut stack),
+                        },
+                        _ => undo_overfetching(&mut deque, &mut stack),
+                    }
+                }
+                c @ ('1' | '3')
+                    if self.enabled(SymbolCategory::Fractions)
+                        && !out
+                            .ends_with(|p: char| p.is_alphanumeric() || matches!(p, '/' | '.')) =>
+                {
+                    match fetch_next!(deque, stack, out) {
+                        '/' => {
+                            let fraction = match (c, fetch_next!(deque, stack, out)) {
+                                ('1', '2') => Some(Symbol::OneHalf),
+                                ('1', '4') => Some(Symbol::OneQuarter),
+                                ('3', '4') => Some(Symbol::ThreeQuarters),
+                                _ => None,
+                            };
+
+                            match fraction {
+                                // Do not tear apart `1/25` or dates like `1/2/2024`
+                                Some(symbol)
+                                    if !deque
+                                        .front()
+                                        .is_some_and(|n| n.is_ascii_digit() || *n == '/') =>
+                                {
+                                    self.replace(&mut stack, symbol);
+                                }
+                                _ => undo_overfetching(&mut deque, &mut stack),
+                            }
+                        }
+                        _ => undo_overfetching(&mut deque, &mut stack),
+                    }
+                }
+                'x' if self.enabled(SymbolCategory::Multiplication)
+                    && is_between_numbers(&out, &deque) =>
+                {
+                    self.replace(&mut stack, Symbol::Multiplication);
+                }
                 _ => {}
             }
 
@@ -157,6 +315,65 @@ enum Symbol {
     NotEqual,
     LessThanOrEqual,
     GreaterThanOrEqual,
+    PlusMinus,
+    Multiplication,
+    // Fractions
+    OneHalf,
+    OneQuarter,
+    ThreeQuarters,
+    // Legal
+    Copyright,
+    Registered,
+    Trademark,
+}
+
+impl Symbol {
+    /// The ASCII representation this symbol is replaced from.
+    ///
+    /// These are implicitly encoded in the coroutine of [`Symbols::act`] as well, so
+    /// the two can get out of sync. There is a property test in place to catch this.
+    const fn ascii(self) -> &'static str {
+        match self {
+            Self::EmDash => "---",
+            Self::EnDash => "--",
+            Self::ShortRightArrow => "->",
+            Self::ShortLeftArrow => "<-",
+            Self::LongRightArrow => "-->",
+            Self::LongLeftArrow => "<--",
+            Self::LeftRightArrow => "<->",
+            Self::RightDoubleArrow => "=>",
+            Self::NotEqual => "!=",
+            Self::LessThanOrEqual => "<=",
+            Self::GreaterThanOrEqual => ">=",
+            Self::PlusMinus => "+-",
+            Self::Multiplication => "x",
+            Self::OneHalf => "1/2",
+            Self::OneQuarter => "1/4",
+            Self::ThreeQuarters => "3/4",
+            Self::Copyright => "(c)",
+            Self::Registered => "(r)",
+            Self::Trademark => "(tm)",
+        }
+    }
+
+    const fn category(self) -> SymbolCategory {
+        match self {
+            Self::EmDash | Self::EnDash => SymbolCategory::Dashes,
+            Self::ShortRightArrow
+            | Self::ShortLeftArrow
+            | Self::LongRightArrow
+            | Self::LongLeftArrow
+            | Self::LeftRightArrow
+            | Self::RightDoubleArrow => SymbolCategory::Arrows,
+            Self::NotEqual | Self::LessThanOrEqual | Self::GreaterThanOrEqual => {
+                SymbolCategory::Math
+            }
+            Self::PlusMinus => SymbolCategory::PlusMinus,
+            Self::Multiplication => SymbolCategory::Multiplication,
+            Self::OneHalf | Self::OneQuarter | Self::ThreeQuarters => SymbolCategory::Fractions,
+            Self::Copyright | Self::Registered | Self::Trademark => SymbolCategory::Legal,
+        }
+    }
 }
 
 impl From<Symbol> for char {
@@ -175,6 +392,16 @@ impl From<Symbol> for char {
             Symbol::NotEqual => '≠',
             Symbol::LessThanOrEqual => '≤',
             Symbol::GreaterThanOrEqual => '≥',
+            Symbol::PlusMinus => '±',
+            Symbol::Multiplication => '×',
+            //
+            Symbol::OneHalf => '½',
+            Symbol::OneQuarter => '¼',
+            Symbol::ThreeQuarters => '¾',
+            //
+            Symbol::Copyright => '©',
+            Symbol::Registered => '®',
+            Symbol::Trademark => '™',
         }
     }
 }
@@ -198,6 +425,16 @@ impl TryFrom<char> for Symbol {
             '≠' => Ok(Self::NotEqual),
             '≤' => Ok(Self::LessThanOrEqual),
             '≥' => Ok(Self::GreaterThanOrEqual),
+            '±' => Ok(Self::PlusMinus),
+            '×' => Ok(Self::Multiplication),
+            // Fractions
+            '½' => Ok(Self::OneHalf),
+            '¼' => Ok(Self::OneQuarter),
+            '¾' => Ok(Self::ThreeQuarters),
+            // Legal
+            '©' => Ok(Self::Copyright),
+            '®' => Ok(Self::Registered),
+            '™' => Ok(Self::Trademark),
             _ => Err(()),
         }
     }
@@ -217,10 +454,25 @@ fn undo_overfetching<T>(deque: &mut VecDeque<T>, stack: &mut Vec<T>) {
     );
 }
 
-/// Replace the entire `stack` with the given `symbol`.
-fn replace(stack: &mut Vec<char>, symbol: Symbol) {
-    stack.clear();
-    stack.push(symbol.into());
+/// Whether an `x` between what was already `processed` and what `remains` stands
+/// between two numbers, as in `3x4` or `3 x 4`. Hexadecimal literals like `0x4` and
+/// identifiers like `mat3x4` do not count.
+fn is_between_numbers(processed: &str, remains: &VecDeque<char>) -> bool {
+    let (processed, spaced) = processed
+        .strip_suffix(' ')
+        .map_or((processed, false), |p| (p, true));
+    let prefix = processed.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = &processed[prefix.len()..];
+
+    let mut remains = remains.iter();
+    if spaced && remains.next() != Some(&' ') {
+        return false;
+    }
+
+    !number.is_empty()
+        && (spaced || number != "0")
+        && !prefix.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        && remains.next().is_some_and(char::is_ascii_digit)
 }
 
 #[cfg(test)]
@@ -448,6 +700,140 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[rstest]
+    #[case(SymbolCategory::PlusMinus, "+-", "±")]
+    #[case(SymbolCategory::PlusMinus, "5 +- 0.1", "5 ± 0.1")]
+    #[case(SymbolCategory::PlusMinus, "+->", "±>")]
+    #[case(SymbolCategory::PlusMinus, "a + b", "a + b")]
+    //
+    #[case(SymbolCategory::Legal, "(c) 2024", "© 2024")]
+    #[case(SymbolCategory::Legal, "(C)", "©")]
+    #[case(SymbolCategory::Legal, "Foo(tm)", "Foo™")]
+    #[case(SymbolCategory::Legal, "Foo(TM)", "Foo™")]
+    #[case(SymbolCategory::Legal, "(r)", "®")]
+    #[case(SymbolCategory::Legal, "(t)", "(t)")]
+    #[case(SymbolCategory::Legal, "(tm", "(tm")]
+    #[case(SymbolCategory::Legal, "(c->", "(c→")]
+    #[case(SymbolCategory::Legal, "((c)", "(©")]
+    //
+    #[case(SymbolCategory::Fractions, "1/2", "½")]
+    #[case(SymbolCategory::Fractions, "1/4 and 3/4", "¼ and ¾")]
+    #[case(SymbolCategory::Fractions, "(1/2)", "(½)")]
+    #[case(SymbolCategory::Fractions, "1/2.", "½.")]
+    #[case(SymbolCategory::Fractions, "1/3", "1/3")]
+    #[case(SymbolCategory::Fractions, "3/2", "3/2")]
+    #[case(SymbolCategory::Fractions, "11/2", "11/2")]
+    #[case(SymbolCategory::Fractions, "1/25", "1/25")]
+    #[case(SymbolCategory::Fractions, "1/2/2024", "1/2/2024")]
+    #[case(SymbolCategory::Fractions, "1/1/2", "1/1/2")]
+    #[case(SymbolCategory::Fractions, "0.1/2", "0.1/2")]
+    #[case(SymbolCategory::Fractions, "v1/2", "v1/2")]
+    #[case(SymbolCategory::Fractions, "1/", "1/")]
+    //
+    #[case(SymbolCategory::Multiplication, "3x4", "3×4")]
+    #[case(SymbolCategory::Multiplication, "1920x1080", "1920×1080")]
+    #[case(SymbolCategory::Multiplication, "3 x 4", "3 × 4")]
+    #[case(SymbolCategory::Multiplication, "3 x4", "3 x4")]
+    #[case(SymbolCategory::Multiplication, "3x 4", "3x 4")]
+    #[case(SymbolCategory::Multiplication, "0x4", "0x4")]
+    #[case(SymbolCategory::Multiplication, "0 x 4", "0 × 4")]
+    #[case(SymbolCategory::Multiplication, "10x4", "10×4")]
+    #[case(SymbolCategory::Multiplication, "mat3x4", "mat3x4")]
+    #[case(SymbolCategory::Multiplication, "3x", "3x")]
+    #[case(SymbolCategory::Multiplication, "x4", "x4")]
+    #[case(SymbolCategory::Multiplication, "box", "box")]
+    fn test_symbol_substitution_categories(
+        #[case] category: SymbolCategory,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let mut categories = SymbolCategory::DEFAULT.to_vec();
+        categories.push(category);
+        let action = Symbols::new(categories);
+        let result = action.act(input);
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("+-")]
+    #[case("(c) (tm) (r)")]
+    #[case("1/2")]
+    #[case("3x4")]
+    fn test_symbol_substitution_categories_disabled_by_default(#[case] input: &str) {
+        let action = Symbols::default();
+        let result = action.act(input);
+
+        assert_eq!(result, input);
+    }
+
+    #[rstest]
+    #[case(SymbolCategory::Dashes, "a -- b --- c", "a -- b --- c")]
+    #[case(SymbolCategory::Dashes, "-->", "⟶")]
+    #[case(
+        SymbolCategory::Arrows,
+        "a -> b --> c <-> d => e",
+        "a -> b --> c <-> d => e"
+    )]
+    #[case(SymbolCategory::Arrows, "a -- b", "a – b")]
+    #[case(SymbolCategory::Arrows, "<--", "<--")]
+    #[case(SymbolCategory::Math, "a != b <= c >= d", "a != b <= c >= d")]
+    #[case(SymbolCategory::Math, "<=>", "<=>")]
+    fn test_symbol_substitution_disabled_categories(
+        #[case] disabled: SymbolCategory,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let categories = SymbolCategory::DEFAULT
+            .into_iter()
+            .filter(|category| *category != disabled)
+            .collect();
+        let action = Symbols::new(categories);
+        let result = action.act(input);
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(&[("<=>", "⇔")], "a <=> b", "a ⇔ b")]
+    #[case(&[("<=>", "⇔")], "a <= b", "a ≤ b")]
+    #[case(&[("->", "⇾")], "a -> b --> c", "a ⇾ b ⟶ c")]
+    #[case(&[("(+)", "⊕"), ("(+", "?")], "(+) (+", "⊕ ?")]
+    #[case(&[("deg", "°")], "30deg", "30°")]
+    #[case(&[("", "!")], "ab", "ab")]
+    #[case(&[("a", "b"), ("a", "c")], "a", "b")]
+    fn test_symbol_substitution_custom(
+        #[case] custom: &[(&str, &str)],
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let action = Symbols::default().with_custom(
+            custom
+                .iter()
+                .map(|(from, to)| ((*from).to_owned(), (*to).to_owned())),
+        );
+        let result = action.act(input);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_symbol_from_ascii() {
+        let action = Symbols::new(vec![
+            SymbolCategory::Dashes,
+            SymbolCategory::Arrows,
+            SymbolCategory::Math,
+            SymbolCategory::Fractions,
+            SymbolCategory::PlusMinus,
+            SymbolCategory::Legal,
+        ]);
+
+        // Requires surrounding numbers, covered separately.
+        for symbol in all::<Symbol>().filter(|s| *s != Symbol::Multiplication) {
+            assert_eq!(action.act(symbol.ascii()), char::from(symbol).to_string());
+        }
+    }
+
     #[test]
     fn test_symbol_to_char_and_back_is_bijective() {
         let symbols: Vec<_> = all::<Symbol>().collect();
@@ -1,39 +1,65 @@
-use super::Symbol;
+use super::{Symbol, SymbolCategory};
 use crate::actions::Action;
 
-/// Inverts all symbols inserted by [`super::Symbols`].
+/// Inverts symbols of the enabled [categories](SymbolCategory) inserted by
+/// [`super::Symbols`].
 ///
-/// This is guaranteed to be the inverse of [`super::Symbols`], as the replacements and
-/// originals form a [bijection](https://en.wikipedia.org/wiki/Bijection).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Symbols {}
+/// This is guaranteed to be the inverse of [`super::Symbols`] with the same categories,
+/// as the replacements and originals form a
+/// [bijection](https://en.wikipedia.org/wiki/Bijection). Custom symbols are not
+/// inverted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    categories: Vec<SymbolCategory>,
+}
+
+impl Symbols {
+    /// Create a new [`Symbols`] inversion, inverting symbols of exactly the given
+    /// `categories`.
+    #[must_use]
+    pub const fn new(categories: Vec<SymbolCategory>) -> Self {
+        Self { categories }
+    }
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Self::new(SymbolCategory::DEFAULT.to_vec())
+    }
+}
 
 impl Action for Symbols {
     fn act(&self, input: &str) -> String {
         input
             .chars()
             .map(|c| match Symbol::try_from(c) {
-                Ok(s) => match s {
-                    // This is *horrible* as in the current implementation, we cannot
-                    // access these symbols. They are implicitly encoded in the
-                    // `substitute` method of `Symbols`. As such, this inversion
-                    // can get out of sync with the original. There is a property test
-                    // in place to catch this.
-                    Symbol::EmDash => "---",
-                    Symbol::EnDash => "--",
-                    Symbol::ShortRightArrow => "->",
-                    Symbol::ShortLeftArrow => "<-",
-                    Symbol::LongRightArrow => "-->",
-                    Symbol::LongLeftArrow => "<--",
-                    Symbol::LeftRightArrow => "<->",
-                    Symbol::RightDoubleArrow => "=>",
-                    Symbol::NotEqual => "!=",
-                    Symbol::LessThanOrEqual => "<=",
-                    Symbol::GreaterThanOrEqual => ">=",
-                }
-                .into(),
-                Err(()) => c.to_string(),
+                Ok(s) if self.categories.contains(&s.category()) => s.ascii().into(),
+                _ => c.to_string(),
             })
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(&[], "a – b → c ≠ d", "a – b → c ≠ d")]
+    #[case(&SymbolCategory::DEFAULT, "a – b → c ≠ d ©", "a -- b -> c != d ©")]
+    #[case(&[SymbolCategory::Arrows], "a – b ⟶ c ≠ d", "a – b --> c ≠ d")]
+    #[case(&[SymbolCategory::Dashes, SymbolCategory::Math], "a — b ⇒ c ≤ d", "a --- b ⇒ c <= d")]
+    #[case(&[SymbolCategory::Legal, SymbolCategory::Fractions], "© ½ ™", "(c) 1/2 (tm)")]
+    #[case(&[SymbolCategory::Multiplication, SymbolCategory::PlusMinus], "3×4 ± 1", "3x4 +- 1")]
+    fn test_symbols_inversion_categories(
+        #[case] categories: &[SymbolCategory],
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let action = Symbols::new(categories.to_vec());
+
+        assert_eq!(action.act(input), expected);
+    }
+}
@@ -1,14 +1,259 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
 use titlecase::titlecase;
 
-use super::Action;
+use super::{Action, Locale};
+
+/// Renders in titlecase, according to the [`Locale`] and a [`TitlecaseStyle`].
+///
+/// Beyond the style, the words kept lowercase can be replaced entirely, and some
+/// words can be forced into a fixed spelling, like `iOS`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Titlecase {
+    locale: Locale,
+    style: TitlecaseStyle,
+    stop_words: Option<BTreeSet<String>>,
+    /// Forced spellings, keyed by their lowercase version.
+    forced: BTreeMap<String, String>,
+}
 
-/// Renders in titlecase.
+/// A style guide for titlecasing, deciding which words are kept lowercase.
+///
+/// The first and last words, and those starting a subsentence (after a colon etc.),
+/// are capitalized regardless.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Titlecase {}
+pub enum TitlecaseStyle {
+    /// The style of John Gruber, keeping short articles, conjunctions and
+    /// prepositions lowercase, like `a`, `and`, `of`, `via`.
+    #[default]
+    Gruber,
+    /// The style of the Associated Press, keeping articles, coordinating
+    /// conjunctions and prepositions of up to three letters lowercase. `With` is
+    /// capitalized, `for` is not.
+    Ap,
+    /// The style of the Chicago Manual of Style, keeping articles, coordinating
+    /// conjunctions and all prepositions lowercase, regardless of their length.
+    Chicago,
+}
+
+impl TitlecaseStyle {
+    /// The words kept lowercase in this style.
+    #[must_use]
+    pub const fn stop_words(self) -> &'static [&'static str] {
+        match self {
+            Self::Gruber => GRUBER_STOP_WORDS,
+            Self::Ap => AP_STOP_WORDS,
+            Self::Chicago => CHICAGO_STOP_WORDS,
+        }
+    }
+}
+
+/// What the `titlecase` crate keeps lowercase.
+const GRUBER_STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "en", "for", "if", "in", "of", "on", "or", "the",
+    "to", "v", "via", "vs",
+];
+
+const AP_STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "if", "in", "nor", "of", "off", "on", "or",
+    "out", "per", "so", "the", "to", "up", "via", "vs", "yet",
+];
+
+const CHICAGO_STOP_WORDS: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "across",
+    "after",
+    "against",
+    "along",
+    "among",
+    "an",
+    "and",
+    "around",
+    "as",
+    "at",
+    "before",
+    "behind",
+    "below",
+    "beneath",
+    "beside",
+    "between",
+    "beyond",
+    "but",
+    "by",
+    "despite",
+    "down",
+    "during",
+    "except",
+    "for",
+    "from",
+    "in",
+    "inside",
+    "into",
+    "like",
+    "near",
+    "nor",
+    "of",
+    "off",
+    "on",
+    "onto",
+    "or",
+    "out",
+    "outside",
+    "over",
+    "past",
+    "per",
+    "since",
+    "the",
+    "through",
+    "throughout",
+    "to",
+    "toward",
+    "towards",
+    "under",
+    "underneath",
+    "until",
+    "up",
+    "upon",
+    "via",
+    "vs",
+    "with",
+    "within",
+    "without",
+];
+
+impl Titlecase {
+    /// Create a new [`Titlecase`], casing according to `locale`.
+    #[must_use]
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            ..Default::default()
+        }
+    }
+
+    /// Keep words lowercase according to `style`.
+    pub fn style(&mut self, style: TitlecaseStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Keep exactly the given `words` lowercase, instead of those of the
+    /// [`TitlecaseStyle`]. Words match regardless of their casing.
+    pub fn stop_words(&mut self, words: impl IntoIterator<Item = String>) -> &mut Self {
+        self.stop_words = Some(words.into_iter().map(|w| w.to_lowercase()).collect());
+        self
+    }
+
+    /// Always spell the given `words` exactly as given, like `iOS` or `macOS`,
+    /// wherever they occur in any casing. This takes precedence over all other rules.
+    pub fn force_case(&mut self, words: impl IntoIterator<Item = String>) -> &mut Self {
+        self.forced
+            .extend(words.into_iter().map(|w| (w.to_lowercase(), w)));
+        self
+    }
+
+    fn is_stop_word(&self, lowercase: &str) -> bool {
+        self.stop_words.as_ref().map_or_else(
+            || self.style.stop_words().contains(&lowercase),
+            |words| words.contains(lowercase),
+        )
+    }
+
+    /// Adjusts the output of the `titlecase` crate, which always follows
+    /// [`TitlecaseStyle::Gruber`], to the configured rules.
+    fn restyle(&self, cased: &str) -> String {
+        let words = words(cased);
+        let mut result = String::with_capacity(cased.len());
+        let mut last_end = 0;
+
+        for (i, range) in words.iter().enumerate() {
+            let word = &cased[range.clone()];
+            let lowercase = word.to_lowercase();
+            let starts_subsentence = cased[..range.start]
+                .trim_end()
+                .trim_end_matches(|c: char| !c.is_alphanumeric() && !is_terminator(c))
+                .ends_with(is_terminator);
+            let ends_subsentence = cased[range.end..]
+                .chars()
+                .take_while(|c| !c.is_alphanumeric() && !c.is_whitespace())
+                .any(is_terminator);
+            let is_boundary =
+                i == 0 || i == words.len() - 1 || starts_subsentence || ends_subsentence;
+
+            result.push_str(&cased[last_end..range.start]);
+            if let Some(forced) = self.forced.get(&lowercase) {
+                result.push_str(forced);
+            } else if is_boundary {
+                result.push_str(word);
+            } else if self.is_stop_word(&lowercase) {
+                result.push_str(&lowercase);
+            } else if word == lowercase && GRUBER_STOP_WORDS.contains(&word) {
+                // Was kept lowercase by the crate, but shouldn't have been.
+                let mut chars = word.chars();
+                result.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+                result.push_str(chars.as_str());
+            } else {
+                result.push_str(word);
+            }
+            last_end = range.end;
+        }
+
+        result.push_str(&cased[last_end..]);
+        result
+    }
+}
+
+/// Whether `c` ends a (sub)sentence, around which words are capitalized.
+const fn is_terminator(c: char) -> bool {
+    matches!(c, ':' | '.' | ';' | '?' | '!')
+}
+
+/// Byte ranges of all plain words in `input`, in order.
+///
+/// Chunks of non-whitespace are trimmed of surrounding punctuation. If anything
+/// other than letters, digits and apostrophes remains, the chunk is skipped: it is
+/// probably a path, URL, hyphenated compound or similar, which is best left alone.
+fn words(input: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut offset = 0;
+
+    for chunk in input.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += chunk.len();
+
+        let trimmed = chunk.trim_start_matches(|c: char| !c.is_alphanumeric());
+        let start = start + chunk.len() - trimmed.len();
+        let word = trimmed.trim_end_matches(|c: char| !c.is_alphanumeric());
+
+        if !word.is_empty()
+            && word
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '\'' | '’'))
+        {
+            words.push(start..start + word.len());
+        }
+    }
+
+    words
+}
 
 impl Action for Titlecase {
     fn act(&self, input: &str) -> String {
-        titlecase(input)
+        let cased = titlecase(input);
+        let cased = if self.style == TitlecaseStyle::Gruber
+            && self.stop_words.is_none()
+            && self.forced.is_empty()
+        {
+            cased
+        } else {
+            self.restyle(&cased)
+        };
+
+        // The crate is unaware of locales.
+        self.locale.tailor(input, cased)
     }
 }
 
@@ -30,4 +275,91 @@ mod tests {
         let result = Titlecase::default().act(input);
         assert_eq!(result, expected);
     }
+
+    #[rstest]
+    #[case(
+        TitlecaseStyle::Gruber,
+        "a guide for walking with dogs",
+        "A Guide for Walking With Dogs"
+    )]
+    #[case(
+        TitlecaseStyle::Ap,
+        "a guide for walking with dogs",
+        "A Guide for Walking With Dogs"
+    )]
+    #[case(
+        TitlecaseStyle::Chicago,
+        "a guide for walking with dogs",
+        "A Guide for Walking with Dogs"
+    )]
+    #[case(TitlecaseStyle::Ap, "what it is about", "What It Is About")]
+    #[case(
+        TitlecaseStyle::Ap,
+        "the road to nowhere via dover",
+        "The Road to Nowhere via Dover"
+    )]
+    #[case(
+        TitlecaseStyle::Chicago,
+        "up and down: a story of ups",
+        "Up and Down: A Story of Ups"
+    )]
+    #[case(
+        TitlecaseStyle::Chicago,
+        "running through the woods",
+        "Running through the Woods"
+    )]
+    #[case(
+        TitlecaseStyle::Chicago,
+        "see example.com/about for more",
+        "See example.com/about for More"
+    )]
+    #[case(TitlecaseStyle::Ap, "cats en masse", "Cats En Masse")]
+    fn test_titlecasing_style(
+        #[case] style: TitlecaseStyle,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let result = Titlecase::default().style(style).act(input);
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(&["with"], "a walk with a dog", "A Walk with A Dog")]
+    #[case(&[], "a walk with a dog", "A Walk With A Dog")]
+    #[case(&["A"], "this is a dog", "This Is a Dog")]
+    fn test_titlecasing_stop_words(
+        #[case] words: &[&str],
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let result = Titlecase::default()
+            .stop_words(words.iter().map(ToString::to_string))
+            .act(input);
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("updating ios and macos", "Updating iOS and macOS")]
+    #[case("IOS IS GREAT", "iOS Is Great")]
+    #[case("ios: the story", "iOS: The Story")]
+    #[case("iosevka font", "Iosevka Font")]
+    fn test_titlecasing_force_case(#[case] input: &str, #[case] expected: &str) {
+        let result = Titlecase::default()
+            .force_case(["iOS".to_owned(), "macOS".to_owned()])
+            .act(input);
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(Locale::Root, "istanbul is big", "Istanbul Is Big")]
+    #[case(Locale::Turkish, "istanbul is big", "İstanbul İs Big")]
+    #[case(Locale::Turkish, "ılık", "Ilık")]
+    fn test_titlecasing_locale(
+        #[case] locale: Locale,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        let result = Titlecase::new(locale).act(input);
+        assert_eq!(result, expected);
+    }
 }
@@ -0,0 +1,88 @@
+use super::Action;
+
+/// Removes leading and trailing whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Trim {}
+
+impl Action for Trim {
+    fn act(&self, input: &str) -> String {
+        input.trim().to_owned()
+    }
+}
+
+/// Removes leading whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TrimStart {}
+
+impl Action for TrimStart {
+    fn act(&self, input: &str) -> String {
+        input.trim_start().to_owned()
+    }
+}
+
+/// Removes trailing whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TrimEnd {}
+
+impl Action for TrimEnd {
+    fn act(&self, input: &str) -> String {
+        input.trim_end().to_owned()
+    }
+}
+
+/// Removes leading and trailing whitespace from each line, keeping line endings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TrimLines {}
+
+impl Action for TrimLines {
+    fn act(&self, input: &str) -> String {
+        let mut result = String::with_capacity(input.len());
+
+        for line in input.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\r', '\n']);
+            let ending = &line[content.len()..];
+
+            result.push_str(content.trim());
+            result.push_str(ending);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("", "", "", "")]
+    #[case("a", "a", "a", "a")]
+    #[case("  a  ", "a", "a  ", "  a")]
+    #[case("\t a b \n", "a b", "a b \n", "\t a b")]
+    #[case(" \u{a0}a\u{2003}", "a", "a\u{2003}", " \u{a0}a")] // Unicode whitespace
+    #[case(" \n ", "", "", "")]
+    fn test_trim(
+        #[case] input: &str,
+        #[case] expected_trim: &str,
+        #[case] expected_trim_start: &str,
+        #[case] expected_trim_end: &str,
+    ) {
+        assert_eq!(Trim::default().act(input), expected_trim);
+        assert_eq!(TrimStart::default().act(input), expected_trim_start);
+        assert_eq!(TrimEnd::default().act(input), expected_trim_end);
+    }
+
+    #[rstest]
+    #[case("", "")]
+    #[case("  a  ", "a")]
+    #[case("  a  \n", "a\n")]
+    #[case("  a  \n  b", "a\nb")]
+    #[case("  a  \r\n\tb\t\r\n", "a\r\nb\r\n")]
+    #[case("\n\n", "\n\n")]
+    #[case("  \n  \n", "\n\n")]
+    fn test_trim_lines(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(TrimLines::default().act(input), expected);
+    }
+}
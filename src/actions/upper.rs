@@ -1,12 +1,48 @@
-use super::Action;
+use itertools::Itertools;
+
+use super::{Action, Locale};
 
 /// Renders in uppercase.
+///
+/// How Eszett (`ß`) is uppercased is controlled by [`UppercaseEszett`], other
+/// language-specific rules by the [`Locale`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Upper {
+    eszett: UppercaseEszett,
+    locale: Locale,
+}
+
+/// How to uppercase Eszett (`ß`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Upper {}
+pub enum UppercaseEszett {
+    /// Use the capital Eszett, `ẞ`, official since 2017.
+    #[default]
+    Capital,
+    /// Use `SS`, as in orthography before 2017.
+    Ss,
+    /// Leave `ß` alone.
+    Keep,
+}
+
+impl Upper {
+    /// Create a new [`Upper`], uppercasing Eszett according to `eszett` and
+    /// everything else according to `locale`.
+    #[must_use]
+    pub const fn new(eszett: UppercaseEszett, locale: Locale) -> Self {
+        Self { eszett, locale }
+    }
+}
 
 impl Action for Upper {
     fn act(&self, input: &str) -> String {
-        input.replace('ß', "ẞ").to_uppercase()
+        let upper = |s: &str| self.locale.to_uppercase(s);
+
+        match self.eszett {
+            UppercaseEszett::Capital => upper(&input.replace('ß', "ẞ")),
+            // This is what Unicode does by default.
+            UppercaseEszett::Ss => upper(input),
+            UppercaseEszett::Keep => input.split('ß').map(upper).join("ß"),
+        }
     }
 }
 
@@ -49,4 +85,28 @@ mod tests {
         let result = Upper::default().act(input);
         assert_eq!(result, expected);
     }
+
+    #[rstest]
+    #[case(UppercaseEszett::Capital, "Straße", "STRAẞE")]
+    #[case(UppercaseEszett::Ss, "Straße", "STRASSE")]
+    #[case(UppercaseEszett::Ss, "STRAẞE", "STRAẞE")]
+    #[case(UppercaseEszett::Keep, "Straße", "STRAßE")]
+    #[case(UppercaseEszett::Keep, "ßaß", "ßAß")]
+    #[case(UppercaseEszett::Keep, "", "")]
+    fn test_uppercase_eszett(
+        #[case] eszett: UppercaseEszett,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(Upper::new(eszett, Locale::Root).act(input), expected);
+    }
+
+    #[rstest]
+    #[case(Locale::Root, "istanbul", "ISTANBUL")]
+    #[case(Locale::Turkish, "istanbul", "İSTANBUL")]
+    #[case(Locale::Turkish, "ayrılık", "AYRILIK")]
+    fn test_locale(#[case] locale: Locale, #[case] input: &str, #[case] expected: &str) {
+        let result = Upper::new(UppercaseEszett::default(), locale).act(input);
+        assert_eq!(result, expected);
+    }
 }
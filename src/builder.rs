@@ -0,0 +1,269 @@
+use std::fmt;
+use std::path::Path;
+
+use log::debug;
+
+use crate::actions::{Action, ActionError};
+use crate::scoping::langs::LanguageScoper;
+use crate::scoping::view::ScopedViewBuilder;
+use crate::scoping::Scoper;
+
+/// A high-level entrypoint, bundling scopers and actions, ready to be
+/// [run][`SrgnBuilder::run`] on any number of inputs.
+///
+/// This mirrors what the binary does: language scopers are applied first, then
+/// general scopers narrow the view down further, then actions are applied to whatever
+/// remains in scope.
+///
+/// ## Examples
+///
+/// ```rust
+/// use srgn::actions::Upper;
+/// use srgn::scoping::langs::python::{CompiledQuery, PreparedQuery};
+/// use srgn::scoping::regex::Regex;
+/// use srgn::{RegexPattern, SrgnBuilder};
+///
+/// let mut builder = SrgnBuilder::new();
+/// builder
+///     .lang(CompiledQuery::from(PreparedQuery::Comments))
+///     .scope(Regex::new(RegexPattern::new(r"todo").unwrap()))
+///     .action(Upper::default());
+///
+/// let input = "todo = 1  # todo: rename\n";
+/// assert_eq!(builder.run(input).unwrap(), "todo = 1  # TODO: rename\n");
+/// ```
+#[derive(Default)]
+pub struct SrgnBuilder {
+    language_scopers: Vec<Box<dyn LanguageScoper>>,
+    scopers: Vec<Box<dyn Scoper>>,
+    invert: bool,
+    squeeze: bool,
+    actions: Vec<Box<dyn Action>>,
+}
+
+impl SrgnBuilder {
+    /// Create a new, empty builder.
+    ///
+    /// Without any scopers, all input is in scope. Without any actions, input is
+    /// returned unchanged.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a language scoper. Multiple language scopers intersect: only what all of them
+    /// scope is in scope.
+    ///
+    /// Language scopers always run before general [scopers][`Self::scope`], as they
+    /// need to see the entire input to parse it.
+    pub fn lang(&mut self, scoper: impl LanguageScoper + 'static) -> &mut Self {
+        self.language_scopers.push(Box::new(scoper));
+
+        self
+    }
+
+    /// Add a general scoper, such as a [`Regex`][`crate::scoping::regex::Regex`]. Each
+    /// scoper narrows down the scope of previous ones further.
+    pub fn scope(&mut self, scoper: impl Scoper + 'static) -> &mut Self {
+        self.scopers.push(Box::new(scoper));
+
+        self
+    }
+
+    /// Invert the final scope, such that previously out-of-scope parts are in scope
+    /// and vice versa (see [`ScopedViewBuilder::invert`]).
+    pub fn invert(&mut self) -> &mut Self {
+        self.invert = true;
+
+        self
+    }
+
+    /// Squeeze consecutive in-scope parts into one, before applying actions (see
+    /// [`ScopedView::squeeze`][`crate::scoping::view::ScopedView::squeeze`]).
+    pub fn squeeze(&mut self) -> &mut Self {
+        self.squeeze = true;
+
+        self
+    }
+
+    /// Add an action. Actions are applied in the order they are added.
+    pub fn action(&mut self, action: impl Action + 'static) -> &mut Self {
+        self.actions.push(Box::new(action));
+
+        self
+    }
+
+    /// Check whether anything in `input` is in scope.
+    #[must_use]
+    pub fn has_any_in_scope(&self, input: &str) -> bool {
+        self.build_view(input).build().has_any_in_scope()
+    }
+
+    /// Run scopers and actions on the given `input`, returning the result.
+    ///
+    /// # Errors
+    ///
+    /// If any action fails, see [`ActionError`].
+    pub fn run(&self, input: &str) -> Result<String, ActionError> {
+        self.run_impl(input, None)
+    }
+
+    /// Like [`Self::run`], but with `input` stemming from the given `file`, which is
+    /// made available to actions (for example, the `${file}` variable of
+    /// [`Replacement`][`crate::actions::Replacement`]).
+    ///
+    /// # Errors
+    ///
+    /// If any action fails, see [`ActionError`].
+    pub fn run_on_file(&self, input: &str, file: &Path) -> Result<String, ActionError> {
+        self.run_impl(input, Some(file))
+    }
+
+    fn run_impl(&self, input: &str, file: Option<&Path>) -> Result<String, ActionError> {
+        let mut view = self.build_view(input).build();
+
+        if let Some(file) = file {
+            view.set_file(file.to_owned());
+        }
+
+        if self.squeeze {
+            view.squeeze();
+        }
+
+        for action in &self.actions {
+            view.map_with_context(action)?;
+        }
+
+        Ok(view.to_string())
+    }
+
+    fn build_view<'viewee>(&self, input: &'viewee str) -> ScopedViewBuilder<'viewee> {
+        debug!("Building view.");
+        let mut builder = ScopedViewBuilder::new(input);
+
+        for scoper in &self.language_scopers {
+            builder.explode(scoper);
+        }
+
+        for scoper in &self.scopers {
+            builder.explode(scoper);
+        }
+
+        if self.invert {
+            builder.invert();
+        }
+
+        builder
+    }
+}
+
+impl fmt::Debug for SrgnBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SrgnBuilder")
+            .field("language_scopers", &self.language_scopers.len())
+            .field("scopers", &self.scopers.len())
+            .field("invert", &self.invert)
+            .field("squeeze", &self.squeeze)
+            .field("actions", &self.actions.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::actions::{Deletion, Lower, Replacement, Upper};
+    use crate::scoping::regex::Regex;
+    use crate::RegexPattern;
+
+    fn regex(pattern: &str) -> Regex {
+        Regex::new(RegexPattern::new(pattern).unwrap())
+    }
+
+    #[test]
+    fn test_empty_builder_is_identity() {
+        assert_eq!(SrgnBuilder::new().run("Hello").unwrap(), "Hello");
+    }
+
+    #[test]
+    fn test_actions_in_order() {
+        let mut builder = SrgnBuilder::new();
+        builder
+            .scope(regex(r"\w+"))
+            .action(Replacement::try_from("[$0]".to_owned()).unwrap())
+            .action(Upper::default());
+
+        assert_eq!(builder.run("a, b").unwrap(), "[A], [B]");
+    }
+
+    #[test]
+    fn test_scopers_narrow_down() {
+        let mut builder = SrgnBuilder::new();
+        builder
+            .scope(regex(r"\w+"))
+            .scope(regex(r"[a-c]"))
+            .action(Upper::default());
+
+        assert_eq!(builder.run("abcdef, xyz").unwrap(), "ABCdef, xyz");
+    }
+
+    #[rstest]
+    #[case(false, "HELLO world")]
+    #[case(true, "hello WORLD")]
+    fn test_invert(#[case] invert: bool, #[case] expected: &str) {
+        let mut builder = SrgnBuilder::new();
+        builder.scope(regex(r"hello")).action(Upper::default());
+        if invert {
+            builder.invert();
+        }
+
+        assert_eq!(builder.run("hello world").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_squeeze() {
+        let mut builder = SrgnBuilder::new();
+        builder.scope(regex(r"\n")).squeeze();
+
+        assert_eq!(builder.run("a\n\n\nb").unwrap(), "a\nb");
+    }
+
+    #[test]
+    fn test_reusable() {
+        let mut builder = SrgnBuilder::new();
+        builder.scope(regex(r"\d")).action(Deletion::default());
+
+        assert_eq!(builder.run("a1").unwrap(), "a");
+        assert_eq!(builder.run("2b").unwrap(), "b");
+        assert!(builder.has_any_in_scope("3"));
+        assert!(!builder.has_any_in_scope("c"));
+    }
+
+    #[test]
+    fn test_run_on_file() {
+        let mut builder = SrgnBuilder::new();
+        builder
+            .scope(regex(r"here"))
+            .action(Replacement::try_from("${file}".to_owned()).unwrap());
+
+        assert_eq!(
+            builder
+                .run_on_file("from here", Path::new("a.txt"))
+                .unwrap(),
+            "from a.txt"
+        );
+    }
+
+    #[test]
+    fn test_failing_action() {
+        let mut builder = SrgnBuilder::new();
+        builder
+            .scope(regex(r"x"))
+            .action(Lower::default())
+            .action(Replacement::try_from("$nope".to_owned()).unwrap());
+
+        assert!(builder.run("x").is_err());
+    }
+}
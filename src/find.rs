@@ -2,6 +2,10 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+mod gitattributes;
+
+pub use gitattributes::{GitAttributes, GITATTRIBUTES_FILENAME};
+
 /// A trait to facilitate finding corresponding, in one sense or another, files.
 ///
 /// For example, a type responsible for Python source code files may implement this to
@@ -25,6 +29,15 @@ pub trait Find {
         None
     }
 
+    /// Names of the corresponding language as known to [GitHub
+    /// Linguist](https://github.com/github-linguist/linguist), in any case.
+    ///
+    /// Used to match `linguist-language` overrides in `.gitattributes`, see
+    /// [`GitAttributes`].
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     /// Paths matching the criteria will not be considered valid.
     ///
     /// Can be used to override based on arbitrary characteristics of the [`Path`].
@@ -42,53 +55,46 @@ pub trait Find {
         match (path.extension(), self.interpreters()) {
             (Some(ext), _) => ext
                 .to_str()
-                .map_or(false, |ext| self.extensions().contains(&ext)),
-            (None, Some(interpreters)) => File::open(path).map_or(false, |mut fh| {
-                find_interpreter(&mut fh).map_or(false, |interpreter| {
-                    interpreters.contains(&interpreter.as_str())
-                })
+                .is_some_and(|ext| self.extensions().contains(&ext)),
+            (None, Some(interpreters)) => File::open(path).is_ok_and(|mut fh| {
+                find_interpreter(&mut fh)
+                    .is_some_and(|interpreter| interpreters.contains(&interpreter.as_str()))
             }),
             _ => false,
         }
     }
 }
 
-pub(crate) fn find_interpreter(source: &mut impl Read) -> Option<String> {
-    let mut interpreter = String::new();
-    let mut seen_space = false;
-    let mut buf = [0u8; 32];
-    let mut n_char = 0;
-
-    'read: while let Ok(n) = source.read(&mut buf) {
-        let done = n == 0;
-        let maximum_exceeded = n_char >= 128;
-        if done || maximum_exceeded {
-            break;
-        }
-
-        for c in buf.into_iter().take(n) {
-            n_char += 1;
+/// Maximum length of a shebang line to consider; longer ones are not worth reading.
+const MAX_SHEBANG_LEN: usize = 128;
 
-            match (n_char - 1, c as char) {
-                // Correct shebang start
-                (0, '#') | (1, '!') => continue,
-
-                (0 | 1, _) | (_, '\n' | '\r') => break 'read,
-
-                // At each path element, reset
-                (_, '/') => interpreter.clear(),
-
-                // Take whatever comes first after a space, if any, as the interpreter.
-                (_, ' ') if seen_space => break 'read,
-                (_, ' ') => {
-                    seen_space = true;
-                    interpreter.clear();
-                }
-
-                (_, c) => interpreter.push(c),
-            }
-        }
-    }
+pub(crate) fn find_interpreter(source: &mut impl Read) -> Option<String> {
+    // Only the first line is of interest: read no more than it can span, in one go.
+    let mut head = Vec::with_capacity(MAX_SHEBANG_LEN);
+    source
+        .take(MAX_SHEBANG_LEN as u64)
+        .read_to_end(&mut head)
+        .ok()?;
+
+    let line = head.strip_prefix(b"#!")?;
+    let line = match line.iter().position(|b| matches!(b, b'\n' | b'\r')) {
+        Some(end) => &line[..end],
+        // Not terminated within the limit, so likely not a shebang at all.
+        None if head.len() == MAX_SHEBANG_LEN => return None,
+        None => line,
+    };
+    let line = std::str::from_utf8(line).ok()?;
+
+    let mut words = line.split_whitespace();
+    let basename = |word: &str| word.rsplit('/').next().unwrap_or_default().to_owned();
+    let program = basename(words.next()?);
+
+    let interpreter = if program == "env" {
+        // Options to `env` such as `-S` come before the actual interpreter.
+        basename(words.find(|word| !word.starts_with('-'))?)
+    } else {
+        program
+    };
 
     (!interpreter.is_empty()).then_some(interpreter)
 }
@@ -134,6 +140,11 @@ mod test {
     #[case("#!/usr/bin/env perl -w\n", Some(String::from("perl")))]
     #[case("#!/usr/bin/env perl -w\r\n", Some(String::from("perl")))]
     //
+    #[case("#!/bin/bash -e\n", Some(String::from("bash")))]
+    #[case("#!/usr/bin/env -S deno run --allow-all\n", Some(String::from("deno")))]
+    #[case("#! /usr/bin/env node\n", Some(String::from("node")))]
+    #[case("#!/usr/bin/env\n", None)]
+    //
     #[case("#!/some/very/long/path/which/is/not/expected/in/real/life/but/should/still/work/because/why/not/bin/bash", Some(String::from("bash")))]
     //
     #[case("#!/some/very/long/path/which/is/not/expected/in/real/life/and/will/not/work/because/there/is/a/certain/limit/to/the/nonsense/we/accept/in/this/function/bin/nope", None)]
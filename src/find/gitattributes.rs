@@ -0,0 +1,274 @@
+use std::path::{Component, Path};
+use std::{fs, io};
+
+use glob::{MatchOptions, Pattern};
+use log::{debug, trace};
+
+use super::Find;
+
+/// Name of the file attributes are read from.
+pub const GITATTRIBUTES_FILENAME: &str = ".gitattributes";
+
+/// The state of an attribute for a path, as in `gitattributes(5)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    /// `attr`
+    Set,
+    /// `-attr`
+    Unset,
+    /// `attr=value`
+    Value(String),
+    /// `!attr`
+    Unspecified,
+}
+
+impl State {
+    /// Whether this is a truthy state, like `attr` or `attr=true`.
+    fn is_true(&self) -> bool {
+        match self {
+            Self::Set => true,
+            Self::Value(value) => value == "true",
+            Self::Unset | Self::Unspecified => false,
+        }
+    }
+}
+
+/// A single line of a `.gitattributes` file.
+#[derive(Debug)]
+struct Rule {
+    pattern: Pattern,
+    /// Patterns without a slash match the file name at any depth, others the entire
+    /// path.
+    basename_only: bool,
+    attributes: Vec<(String, State)>,
+}
+
+impl Rule {
+    fn matches(&self, path: &str) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+
+        if self.basename_only {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            self.pattern.matches_with(name, options)
+        } else {
+            self.pattern.matches_with(path, options)
+        }
+    }
+}
+
+/// Attributes of files as found in a `.gitattributes` file, as far as relevant for
+/// language detection.
+///
+/// These are the [GitHub Linguist
+/// overrides](https://github.com/github-linguist/linguist/blob/main/docs/overrides.md):
+/// `linguist-language` forces the language of matching files, while
+/// `linguist-generated` and `linguist-vendored` mark files not to be touched.
+#[derive(Debug, Default)]
+pub struct GitAttributes {
+    rules: Vec<Rule>,
+}
+
+impl GitAttributes {
+    /// Parses `contents` in `.gitattributes` format.
+    ///
+    /// Lines which cannot be understood (macros, invalid patterns, ...) are skipped.
+    #[must_use]
+    pub fn parse(contents: &str) -> Self {
+        let rules = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let pattern = fields.next()?;
+
+                // Macros and negative patterns are not supported (by git either, for
+                // the latter), and patterns with a trailing slash only match
+                // directories, never files.
+                if pattern.starts_with("[attr]")
+                    || pattern.starts_with('!')
+                    || pattern.ends_with('/')
+                {
+                    trace!("Skipping unsupported gitattributes pattern: {pattern}");
+                    return None;
+                }
+
+                let basename_only = !pattern.contains('/');
+                let pattern = Pattern::new(pattern.trim_start_matches('/'))
+                    .inspect_err(|e| debug!("Skipping invalid gitattributes pattern: {e}"))
+                    .ok()?;
+
+                let attributes = fields
+                    .map(|field| {
+                        let (name, state) = match (
+                            field.strip_prefix('-'),
+                            field.strip_prefix('!'),
+                            field.split_once('='),
+                        ) {
+                            (Some(name), _, _) => (name, State::Unset),
+                            (_, Some(name), _) => (name, State::Unspecified),
+                            (_, _, Some((name, value))) => (name, State::Value(value.to_owned())),
+                            _ => (field, State::Set),
+                        };
+
+                        (name.to_owned(), state)
+                    })
+                    .collect();
+
+                Some(Rule {
+                    pattern,
+                    basename_only,
+                    attributes,
+                })
+            })
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Reads the `.gitattributes` file in `dir`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the file exists but cannot be read.
+    pub fn from_dir(dir: &Path) -> io::Result<Self> {
+        match fs::read_to_string(dir.join(GITATTRIBUTES_FILENAME)) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The state of attribute `name` for `path`: as with git, the last matching line
+    /// wins.
+    fn get(&self, path: &Path, name: &str) -> Option<&State> {
+        // Patterns are written with forward slashes, relative to the directory
+        let path = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(part) => part.to_str(),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        self.rules
+            .iter()
+            .rev()
+            .filter(|rule| rule.matches(&path))
+            .find_map(|rule| {
+                rule.attributes
+                    .iter()
+                    .rev()
+                    .find(|(attribute, _)| attribute == name)
+            })
+            .map(|(_, state)| state)
+            .filter(|state| **state != State::Unspecified)
+    }
+
+    /// The language `path` is forced to via `linguist-language`, if any.
+    #[must_use]
+    pub fn language(&self, path: &Path) -> Option<&str> {
+        match self.get(path, "linguist-language")? {
+            State::Value(language) => Some(language),
+            _ => None,
+        }
+    }
+
+    /// Whether `path` is marked as generated or vendored, and should therefore be
+    /// left alone.
+    #[must_use]
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        ["linguist-generated", "linguist-vendored"]
+            .iter()
+            .any(|name| self.get(path, name).is_some_and(State::is_true))
+    }
+
+    /// Like [`Find::is_valid_path`], but honoring these attributes first: excluded
+    /// paths are never valid, and paths with a forced language are valid exactly if
+    /// `find` is for that language.
+    pub fn is_valid_path(&self, find: &(impl Find + ?Sized), path: &Path) -> bool {
+        if self.is_excluded(path) {
+            return false;
+        }
+
+        self.language(path).map_or_else(
+            || find.is_valid_path(path),
+            |language| {
+                find.linguist_names()
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(language))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const ATTRIBUTES: &str = r"
+# Comment
+*.gen.rs linguist-generated
+/vendor/** linguist-vendored
+vendor/ours/** -linguist-vendored
+*.inc linguist-language=C
+scripts/build linguist-language=Python
+[attr]binary -diff -merge -text
+*.pyi linguist-generated=true
+keep.gen.rs !linguist-generated
+";
+
+    struct Python;
+
+    impl Find for Python {
+        fn extensions(&self) -> &'static [&'static str] {
+            &["py"]
+        }
+
+        fn linguist_names(&self) -> &'static [&'static str] {
+            &["Python"]
+        }
+    }
+
+    #[rstest]
+    #[case("src/main.rs", false)]
+    #[case("src/lib.gen.rs", true)]
+    #[case("lib.gen.rs", true)]
+    #[case("src/keep.gen.rs", false)]
+    #[case("vendor/dep/lib.py", true)]
+    #[case("./vendor/dep/lib.py", true)]
+    #[case("src/vendor/dep/lib.py", false)]
+    #[case("vendor/ours/lib.py", false)]
+    #[case("stubs/types.pyi", true)]
+    fn test_is_excluded(#[case] path: &str, #[case] expected: bool) {
+        let attributes = GitAttributes::parse(ATTRIBUTES);
+        assert_eq!(attributes.is_excluded(Path::new(path)), expected);
+    }
+
+    #[rstest]
+    #[case("src/defs.inc", Some("C"))]
+    #[case("scripts/build", Some("Python"))]
+    #[case("other/scripts/build", None)]
+    #[case("src/main.rs", None)]
+    fn test_language(#[case] path: &str, #[case] expected: Option<&str>) {
+        let attributes = GitAttributes::parse(ATTRIBUTES);
+        assert_eq!(attributes.language(Path::new(path)), expected);
+    }
+
+    #[rstest]
+    #[case("main.py", true)]
+    #[case("main.rs", false)]
+    #[case("scripts/build", true)]
+    #[case("defs.inc", false)]
+    #[case("vendor/dep/lib.py", false)]
+    fn test_is_valid_path(#[case] path: &str, #[case] expected: bool) {
+        let attributes = GitAttributes::parse(ATTRIBUTES);
+        assert_eq!(attributes.is_valid_path(&Python, Path::new(path)), expected);
+    }
+}
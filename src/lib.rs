@@ -14,6 +14,30 @@
 //!
 //! A couple end-to-end examples specific to library usage are shown.
 //!
+//! ## All in one
+//!
+//! For the common case of scoping, then applying actions, there is [`SrgnBuilder`]. It
+//! does what the binary does, minus file handling:
+//!
+//! ```rust
+//! use srgn::actions::{Replacement, Upper};
+//! use srgn::scoping::regex::Regex;
+//! use srgn::{RegexPattern, SrgnBuilder};
+//!
+//! let mut builder = SrgnBuilder::new();
+//! builder
+//!     .scope(Regex::new(RegexPattern::new(r"\b[a-z]{3}\b").unwrap()))
+//!     .action(Replacement::try_from("<$0>".to_owned()).unwrap())
+//!     .action(Upper::default());
+//!
+//! assert_eq!(builder.run("the cat sat, mostly").unwrap(), "<THE> <CAT> <SAT>, mostly");
+//! ```
+//!
+//! To run it on entire directory trees, rewriting files in place, see
+//! [`walker::FileProcessor`].
+//!
+//! The following sections show the building blocks underneath, for finer control.
+//!
 //! ## Building a scoped view
 //!
 //! The starting point is always some [`str`] input. Over it, a [`ScopedView`] is built.
@@ -182,16 +206,21 @@ use crate::{
 
 /// Main components around [`Action`]s.
 pub mod actions;
+/// A high-level entrypoint bundling scopers and actions.
+pub mod builder;
 /// Utilities around finding files.
 pub mod find;
 /// Components to work with collections of [`Range`]s.
 pub mod ranges;
 /// Main components around [`ScopedView`].
 pub mod scoping;
+/// Walking directory trees, processing all files found.
+pub mod walker;
 
 /// Pattern signalling global scope, aka matching entire inputs.
 pub const GLOBAL_SCOPE: &str = r".*";
 
+pub use builder::SrgnBuilder;
 /// The type of regular expression used throughout the crate. Abstracts away the
 /// underlying implementation.
 pub use fancy_regex::Regex as RegexPattern;
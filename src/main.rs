@@ -3,59 +3,196 @@
 //! It mainly draws from `srgn`, the library, for actual implementations. This file then
 //! deals with CLI argument handling, I/O, threading, and more.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::error::Error;
+use std::ffi::OsString;
+use std::fmt::Write as _;
 use std::fs::{self, File};
-use std::io::{self, stdout, Read, Write};
+use std::io::{self, stdout, BufRead, IsTerminal, Read, Write};
+use std::num::NonZero;
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
 use std::sync::{Arc, Mutex};
-use std::{env, fmt};
+use std::time::{Duration, Instant};
+use std::{env, fmt, thread};
 
-use anyhow::{Context, Result};
-use colored::Colorize;
-use ignore::{WalkBuilder, WalkState};
+use anyhow::{bail, Context, Result};
+use clap::builder::PossibleValue;
 use itertools::Itertools;
 use log::{debug, error, info, trace, warn, LevelFilter};
-use pathdiff::diff_paths;
-#[cfg(feature = "german")]
-use srgn::actions::German;
+use serde::{Deserialize, Serialize};
 use srgn::actions::{
-    Action, ActionError, Deletion, Lower, Normalization, Replacement, Style, Titlecase, Upper,
+    Action, ActionError, Base64Decode, Base64Encode, Camelcase, CommentOut, Dedupe, Deletion, Exec,
+    HexDecode, HexEncode, Increment, Kebabcase, Lower, Mapping, MappingFormat, Normalization,
+    NormalizationForm, Pascalcase, Redact, Replacement, ScreamingSnakecase, Snakecase, Style,
+    Surround, Trim, TrimEnd, TrimLines, TrimStart, Uncomment, Upper, UrlDecode, UrlEncode,
 };
+#[cfg(feature = "german")]
+use srgn::actions::{EszettInversion, German};
 #[cfg(feature = "symbols")]
-use srgn::actions::{Symbols, SymbolsInversion};
+use srgn::actions::{
+    QuoteStyle, Quotes, QuotesInversion, SymbolCategory, Symbols, SymbolsInversion,
+};
+use srgn::find::GitAttributes;
 use srgn::iterext::ParallelZipExt;
-use srgn::scoping::langs::LanguageScoper;
-use srgn::scoping::literal::{Literal, LiteralError};
-use srgn::scoping::regex::{Regex, RegexError};
-use srgn::scoping::view::ScopedViewBuilder;
+use srgn::scoping::langs::{dump_tree, CommentSyntax, LanguageScoper};
+use srgn::scoping::lines::Lines;
+use srgn::scoping::literal::{Literal, Literals};
+use srgn::scoping::occurrences::Occurrences;
+use srgn::scoping::regex::{CaptureGroup, Regex};
+use srgn::scoping::scope::Scope::{In, Out};
+use srgn::scoping::scope::{Location, RWScope};
+use srgn::scoping::view::{ScopedView, ScopedViewBuilder};
 use srgn::scoping::Scoper;
+use srgn::walker::{rename_file, Encoding, FileProcessor, SourceFile, WalkError};
 use tree_sitter::QueryError as TSQueryError;
+use unescape::unescape;
 
 // We have `LanguageScoper: Scoper`, but we cannot upcast
 // (https://github.com/rust-lang/rust/issues/65991), so hack around the limitation
 // by providing both.
 type ScoperList = Vec<Box<dyn LanguageScoper>>;
 
-#[allow(clippy::too_many_lines)] // Only slightly above.
-#[allow(clippy::cognitive_complexity)]
-fn main() -> Result<()> {
+/// Language scopers whose results are taken out of scope, as requested via
+/// `--not-inside`.
+#[derive(Default)]
+struct ExcludedScopers(ScoperList);
+
+impl fmt::Debug for ExcludedScopers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ExcludedScopers(<{} scoper(s)>)", self.0.len())
+    }
+}
+
+/// Language scopers as requested via `--auto`, one list per language offering all
+/// requested queries. Which list applies is decided per file.
+#[derive(Default, Clone)]
+struct AutoScopers(Arc<Vec<ScoperList>>);
+
+impl AutoScopers {
+    /// Whether no languages are in play, i.e. `--auto` was not given.
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The scopers of the first language `path` is valid for, if any.
+    fn for_path(
+        &self,
+        path: &Path,
+        gitattributes: &GitAttributes,
+    ) -> Option<&[Box<dyn LanguageScoper>]> {
+        self.0
+            .iter()
+            .find(|scopers| {
+                scopers
+                    .first()
+                    .is_some_and(|s| gitattributes.is_valid_path(s.as_ref(), path))
+            })
+            .map(Vec::as_slice)
+    }
+}
+
+impl fmt::Debug for AutoScopers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AutoScopers(<{} language(s)>)", self.0.len())
+    }
+}
+
+fn main() -> Result<ExitCode> {
     let args = cli::Args::init();
+    let grep_exit_codes = args.options.grep_exit_codes;
+
+    match run(args) {
+        Ok(()) => Ok(ExitCode::SUCCESS),
+        Err(e) if grep_exit_codes => {
+            if matches!(
+                e.downcast_ref::<ProgramError>(),
+                Some(
+                    ProgramError::NothingProcessed
+                        | ProgramError::ApplicationError(ApplicationError::NoneInScope)
+                )
+            ) {
+                // Not an error, just nothing found; stay quiet like grep.
+                Ok(ExitCode::from(1))
+            } else {
+                eprintln!("Error: {e:?}");
+                Ok(ExitCode::from(2))
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// A log message, as written with `--log-format json`.
+#[derive(Serialize)]
+struct LogRecord<'a> {
+    timestamp: String,
+    level: &'static str,
+    target: &'a str,
+    message: String,
+    file: Option<&'a str>,
+    line: Option<u32>,
+    /// Microseconds since the logger was set up.
+    elapsed: u128,
+}
+
+/// Sets up logging, at the verbosity and in the format requested by `options`.
+fn init_logger(options: &cli::GlobalOptions) {
+    let level_filter = level_filter_from_env_and_verbosity(options.additional_verbosity);
+    let mut logger = env_logger::Builder::new();
+    logger.filter_level(level_filter);
+    match options.log_format {
+        cli::LogFormat::Text => {
+            logger.format_timestamp_micros(); // High precision is nice for benchmarks
+        }
+        cli::LogFormat::Json => {
+            let start = Instant::now();
+            logger.format(move |buf, record| {
+                let record = LogRecord {
+                    timestamp: buf.timestamp_micros().to_string(),
+                    level: record.level().as_str(),
+                    target: record.target(),
+                    message: record.args().to_string(),
+                    file: record.file(),
+                    line: record.line(),
+                    elapsed: start.elapsed().as_micros(),
+                };
+
+                serde_json::to_writer(&mut *buf, &record)?;
+                writeln!(buf)
+            });
+        }
+    }
+    logger.init();
+}
 
-    let level_filter = level_filter_from_env_and_verbosity(args.options.additional_verbosity);
-    env_logger::Builder::new()
-        .filter_level(level_filter)
-        .format_timestamp_micros() // High precision is nice for benchmarks
-        .init();
+/// Runs the program as requested by `args`.
+#[allow(clippy::too_many_lines)]
+fn run(args: cli::Args) -> Result<()> {
+    init_logger(&args.options);
 
     info!("Launching app with args: {:?}", args);
 
     let cli::Args {
         scope,
         shell,
+        preset: _,
+        expand_preset,
+        list_queries,
+        explain_query,
+        dump_ast,
+        byte_ranges,
         composable_actions,
         standalone_actions,
         mut options,
         languages_scopes,
+        titlecase_options,
+        #[cfg(feature = "symbols")]
+        symbols_options,
         #[cfg(feature = "german")]
         german_options,
     } = args;
@@ -68,41 +205,240 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let standalone_action = standalone_actions.into();
+    if let Some(name) = expand_preset {
+        let presets = load_presets()?;
+        let args = get_preset(&presets, &name)?;
+        let invocation = shlex::try_join(
+            std::iter::once(env!("CARGO_PKG_NAME")).chain(args.iter().map(String::as_str)),
+        )
+        .with_context(|| format!("Failed quoting arguments of preset '{name}'"))?;
+        println!("{invocation}");
+
+        return Ok(());
+    }
+
+    if let Some(format) = list_queries {
+        let languages = cli::LanguageScopes::prepared_queries();
+        match write_prepared_queries(&languages, format, stdout().lock()) {
+            // Like when piped into `head`; not an issue.
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+            result => result?,
+        }
+
+        return Ok(());
+    }
+
+    if explain_query {
+        let Some(sources) = languages_scopes.query_sources()? else {
+            bail!("Queries can only be explained for language scopes");
+        };
+
+        let explanation = sources
+            .iter()
+            .map(|(origin, source)| format!("; {origin}\n{}\n", source.trim_end()))
+            .join("\n");
+        print!("{explanation}");
+
+        return Ok(());
+    }
+
+    if dump_ast {
+        let Some(lang) = languages_scopes.lang() else {
+            bail!("Syntax trees can only be dumped for language scopes");
+        };
+
+        let mut source = String::new();
+        io::stdin().lock().read_to_string(&mut source)?;
+        print!("{}", dump_tree(&lang, &source, byte_ranges));
+
+        return Ok(());
+    }
+
+    let view_action = if composable_actions.squeeze {
+        ViewAction::Squeeze
+    } else if standalone_actions.delete_lines {
+        ViewAction::DeleteLines
+    } else if composable_actions.dedupe == Some(cli::DedupeMode::Matches) {
+        ViewAction::DedupeMatches
+    } else {
+        ViewAction::None
+    };
+
+    match options.color {
+        // `colored` already honors `NO_COLOR` and whether stdout is a terminal
+        cli::ColorMode::Auto => {}
+        cli::ColorMode::Always => colored::control::set_override(true),
+        cli::ColorMode::Never => colored::control::set_override(false),
+    }
+    if let Ok(spec) = env::var("SRGN_COLORS") {
+        options
+            .fill_colors_from(&spec)
+            .context("Invalid value of SRGN_COLORS")?;
+    }
 
     debug!("Assembling scopers.");
-    let general_scoper = get_general_scoper(&options, scope)?;
+    if !options.literals.is_empty() {
+        options.literal_string = true;
+    }
+
+    let replace_map = composable_actions
+        .replace_map
+        .as_deref()
+        .map(read_replace_map)
+        .transpose()?;
+    let map_keys = replace_map.iter().flatten().map(|(key, _)| key.clone());
+
+    // Names what is searched for in reports
+    let rule_id = std::iter::once(languages_scopes.query_labels().join("+"))
+        .chain(scope.clone().filter(|scope| scope != srgn::GLOBAL_SCOPE))
+        .filter(|part| !part.is_empty())
+        .join(":");
+
+    let general_scopers = std::iter::once(get_main_scoper(&options, scope, map_keys))
+        .chain(
+            options
+                .scope_and
+                .iter()
+                .map(|scope| get_general_scoper(&options, scope.clone(), None)),
+        )
+        .collect::<Result<Vec<_>>>()?;
     // Will be sent across threads and might (the borrow checker is convinced at least)
     // outlive the main one. Scoped threads would work here, `ignore` uses them
     // internally even, but we have no access here.
 
-    let language_scopers = languages_scopes
-        .compile_query_sources_to_scopes()?
-        .map(Arc::new);
+    if let Some(auto_scopers) = languages_scopes.compile_auto_scopes()? {
+        options.auto_scopers = auto_scopers;
+    }
+    let (language_scopers, excluded_scopers) = languages_scopes
+        .compile_query_sources_to_scopes(&options.capture, &options.not_inside)?
+        .map_or((None, Vec::new()), |(scopers, excluded)| {
+            (Some(Arc::new(scopers)), excluded)
+        });
+    if language_scopers.is_none() && !options.capture.is_empty() {
+        bail!("Captures can only be filtered for language scopes");
+    }
+    if language_scopers.is_none() && !options.not_inside.is_empty() {
+        bail!("Scopes can only be excluded via query for language scopes");
+    }
+    options.excluded_scopers = ExcludedScopers(excluded_scopers);
     debug!("Done assembling scopers.");
 
     let mut actions = {
         debug!("Assembling actions.");
-        let mut actions = assemble_common_actions(&composable_actions, standalone_action)?;
+        let comment_syntax = language_scopers
+            .as_ref()
+            .and_then(|scopers| scopers.first())
+            .map(|scoper| scoper.comment_syntax());
+        let mut actions = assemble_common_actions(
+            &composable_actions,
+            &titlecase_options,
+            &standalone_actions,
+            options.invert,
+            comment_syntax,
+        )?;
+
+        if let Some(pairs) = replace_map {
+            // Keys need to line up exactly with what the main scoper scopes
+            let mapping = if options.literal_string {
+                Mapping::literal(
+                    pairs
+                        .into_iter()
+                        .map(|(key, value)| (unescape(&key).unwrap_or(key), value))
+                        .collect(),
+                )
+            } else {
+                Mapping::regex(
+                    pairs
+                        .into_iter()
+                        .map(|(key, value)| (regex_flags(&options) + &key, value))
+                        .collect(),
+                )
+                .context("Failed building replacement mapping")?
+            };
+            actions.insert(0, Box::new(mapping));
+            debug!("Loaded action: Mapping");
+        }
 
         #[cfg(feature = "symbols")]
         if composable_actions.symbols {
+            let categories = [
+                (!symbols_options.symbols_no_dashes, SymbolCategory::Dashes),
+                (!symbols_options.symbols_no_arrows, SymbolCategory::Arrows),
+                (!symbols_options.symbols_no_math, SymbolCategory::Math),
+                (symbols_options.symbols_fractions, SymbolCategory::Fractions),
+                (
+                    symbols_options.symbols_multiplication,
+                    SymbolCategory::Multiplication,
+                ),
+                (
+                    symbols_options.symbols_plus_minus,
+                    SymbolCategory::PlusMinus,
+                ),
+                (symbols_options.symbols_legal, SymbolCategory::Legal),
+            ]
+            .into_iter()
+            .filter_map(|(enabled, category)| enabled.then_some(category))
+            .collect();
+
             if options.invert {
-                actions.push(Box::<SymbolsInversion>::default());
+                actions.push(Box::new(SymbolsInversion::new(categories)));
                 debug!("Loaded action: SymbolsInversion");
             } else {
-                actions.push(Box::<Symbols>::default());
+                let custom = match symbols_options.symbols_map {
+                    Some(ref path) => {
+                        let contents = fs::read_to_string(path).with_context(|| {
+                            format!("Failed reading symbols map '{}'", path.display())
+                        })?;
+                        parse_symbols_map(&contents).with_context(|| {
+                            format!("Failed parsing symbols map '{}'", path.display())
+                        })?
+                    }
+                    None => Vec::new(),
+                };
+
+                actions.push(Box::new(Symbols::new(categories).with_custom(custom)));
                 debug!("Loaded action: Symbols");
             }
         }
 
+        #[cfg(feature = "symbols")]
+        if let Some(style) = composable_actions.quotes {
+            if options.invert {
+                actions.push(Box::<QuotesInversion>::default());
+                debug!("Loaded action: QuotesInversion");
+            } else {
+                actions.push(Box::new(Quotes::new(match style {
+                    cli::QuoteStyle::English => QuoteStyle::English,
+                    cli::QuoteStyle::German => QuoteStyle::German,
+                    cli::QuoteStyle::French => QuoteStyle::French,
+                })));
+                debug!("Loaded action: Quotes");
+            }
+        }
+
         #[cfg(feature = "german")]
-        if composable_actions.german {
-            actions.push(Box::new(German::new(
+        if composable_actions.german && options.invert && german_options.german_swiss {
+            actions.push(Box::<EszettInversion>::default());
+            debug!("Loaded action: EszettInversion");
+        } else if composable_actions.german {
+            let mut german = German::new(
                 // Smell? Bug if bools swapped.
                 german_options.german_prefer_original,
                 german_options.german_naive,
-            )));
+            );
+
+            if let Some(ref path) = german_options.german_words {
+                german.add_words(read_word_list(path)?);
+            }
+            if let Some(ref path) = german_options.german_exceptions {
+                german.add_exceptions(read_word_list(path)?);
+            }
+            if german_options.german_swiss {
+                german.swiss();
+            }
+            german.uppercase_eszett(composable_actions.uppercase_eszett.to_action());
+
+            actions.push(Box::new(german));
             debug!("Loaded action: German");
         }
 
@@ -110,59 +446,45 @@ fn main() -> Result<()> {
         actions
     };
 
-    let is_readable_stdin = grep_cli::is_readable_stdin();
-    info!("Detected stdin as readable: {is_readable_stdin}.");
-
-    // See where we're reading from
-    let input = match (
-        options.stdin_override_to.unwrap_or(is_readable_stdin),
-        options.glob.clone(),
-        &language_scopers,
-    ) {
-        // stdin considered viable: always use it.
-        (true, None, _)
-        // Nothing explicitly available: this should open an interactive stdin prompt.
-        | (false, None, None) => Input::Stdin,
-        (true, Some(..), _) => {
-            // Usage error... warn loudly, the user is likely interested.
-            error!("Detected stdin, and request for files: will use stdin and ignore files.");
-            Input::Stdin
-        }
+    if !options.no_gitattributes {
+        options.gitattributes = Arc::new(
+            GitAttributes::from_dir(&env::current_dir()?)
+                .context("Failed reading .gitattributes")?,
+        );
+    }
 
-        // When a pattern is specified, it takes precedence.
-        (false, Some(pattern), _) => Input::WalkOn(Box::new(move |path| {
-            let res = pattern.matches_path(path);
-            trace!("Path '{}' matches: {}.", path.display(), res);
-            res
-        })),
+    let validator = path_validator(&options, language_scopers.as_ref());
 
-        // If pattern wasn't manually overridden, consult the language scoper itself, if
-        // any.
-        (false, None, Some(language_scopers)) => {
-            let language_scopers = Arc::clone(language_scopers);
-            Input::WalkOn(Box::new(move |path| {
-                // TODO: perform this work only once (it's super fast but in the hot
-                // path).
-                let res = language_scopers
-                    .iter()
-                    .map(|s| s.is_valid_path(path))
-                    .all_equal_value()
-                    .expect("all language scopers to agree on path validity");
+    options.file_renames = options
+        .rename_files
+        .iter()
+        .tuples()
+        .map(|(pattern, replacement)| {
+            let pattern = fancy_regex::Regex::new(pattern)
+                .with_context(|| format!("Invalid pattern to rename files by: '{pattern}'"))?;
+            Ok((pattern, replacement.clone()))
+        })
+        .collect::<Result<_>>()?;
 
-                trace!(
-                    "Language scoper considers path '{}' valid: {}",
-                    path.display(),
-                    res
-                );
-                res
-            }))
-        },
-    };
+    let input = select_input(&mut options, validator)?;
 
     // Only have this kick in if a language scoper is in play; otherwise, we'd just be a
     // poor imitation of ripgrep itself. Plus, this retains the `tr`-like behavior,
-    // setting it apart from other utilities.
-    let search_mode = actions.is_empty() && language_scopers.is_some() || options.dry_run;
+    // setting it apart from other utilities. Unless explicitly requested, that is.
+    let search_mode = actions.is_empty()
+        && matches!(view_action, ViewAction::None)
+        && (language_scopers.is_some() || !options.auto_scopers.is_empty())
+        && !options.print
+        && !options.check
+        || options.highlight
+        || options.fail_any_report
+        || options.dry_run
+        || options.count
+        || options.count_matches
+        || options.format == cli::OutputFormat::Sarif
+        // Decorated output makes no sense for writing back to files.
+        || (options.only_matching || options.column || options.byte_offset)
+            && !matches!(input, Input::Stdin);
 
     if search_mode {
         info!("Will use search mode."); // Modelled after ripgrep!
@@ -170,16 +492,31 @@ fn main() -> Result<()> {
         let style = if options.dry_run {
             Style::green_bold() // "Would change to this", like git diff
         } else {
-            Style::red_bold() // "Found!", like ripgrep
+            options.match_style() // "Found!", like ripgrep
         };
         actions.push(Box::new(style));
 
-        options.only_matching = true;
+        options.matching_lines = true;
+        options.line_numbers = true;
+        // Thresholds decide on failure by themselves
+        if options.fail_if_more_than.is_none()
+            && options.fail_if_fewer_than.is_none()
+            && !options.fail_any_report
+        {
+            options.fail_none = true;
+        }
+    }
+
+    if options.column {
         options.line_numbers = true;
-        options.fail_none = true;
     }
 
-    if actions.is_empty() && !search_mode {
+    if actions.is_empty()
+        && matches!(view_action, ViewAction::None)
+        && !search_mode
+        && !options.print
+        && !(options.only_matching || options.column || options.byte_offset)
+    {
         // Also kind of an error users will likely want to know about.
         error!(
             "No actions specified, and not in search mode. Will return input unchanged, if any."
@@ -197,48 +534,366 @@ fn main() -> Result<()> {
     let pipeline: Vec<&[Box<dyn Action>]> = pipeline.iter().map(Vec::as_slice).collect();
     let language_scopers = language_scopers.unwrap_or_default();
 
+    if !options.auto_scopers.is_empty() && matches!(input, Input::Stdin) {
+        bail!("Languages can only be detected automatically for files, not stdin");
+    }
+
+    if options.format == cli::OutputFormat::Patch && matches!(input, Input::Stdin) {
+        bail!("Patches can only be written for files, not stdin");
+    }
+
+    if options.check && matches!(input, Input::Stdin) {
+        bail!("Only files can be checked, not stdin");
+    }
+
+    if let Some(iterations) = options.self_bench {
+        if !matches!(input, Input::Stdin) {
+            bail!("Benchmarking requires input on stdin");
+        }
+
+        info!("Will benchmark {iterations} iteration(s) on stdin.");
+        self_bench(
+            &options,
+            view_action,
+            &general_scopers,
+            &language_scopers,
+            &pipeline,
+            iterations,
+        )?;
+
+        return Ok(());
+    }
+
+    // Whether anything was in scope is also tracked by the stats
+    let stats = (options.stats
+        || options.grep_exit_codes
+        || options.progress
+        || options.check
+        || options.fail_if_more_than.is_some()
+        || options.fail_if_fewer_than.is_some()
+        || options.fail_any_report
+        || options.format == cli::OutputFormat::Sarif)
+        .then(|| {
+            Arc::new(Stats::new(if options.stats {
+                pipeline.last().expect("pipeline always has an entry")
+            } else {
+                // Per-action counting is expensive, only do it if reported
+                &[]
+            }))
+        });
+
+    // Progress only makes sense for multiple files
+    let show_progress = options.progress && !matches!(input, Input::Stdin);
+    let done = AtomicBool::new(false);
+
     // Now write out
-    match (input, options.sorted) {
-        (Input::Stdin, _ /* no effect */) => {
-            info!("Will read from stdin and write to stdout, applying actions.");
-            handle_actions_on_stdin(
-                &options,
-                standalone_action,
-                &general_scoper,
-                &language_scopers,
-                &pipeline,
-            )?;
+    let result = thread::scope(|s| {
+        let reporter = stats
+            .as_deref()
+            .filter(|_| show_progress)
+            .map(|stats| s.spawn(|| report_progress(stats, &done)));
+
+        // Errors must not escape before the reporter is stopped, else the scope hangs
+        let result = (|| -> Result<_> {
+            Ok(match (input, options.sorted) {
+                (Input::Stdin, _ /* no effect */) if options.stream => {
+                    if !language_scopers.is_empty()
+                        || search_mode
+                        || options.matching_lines
+                        || options.only_matching
+                        || options.byte_offset
+                        || options.max_count.is_some()
+                        || !options.occurrence.is_empty()
+                        || options.line_numbers
+                        || !options.lines.is_empty()
+                    {
+                        warn!(
+                            "Streaming requires the full input in this mode, will read it in full."
+                        );
+                        handle_actions_on_stdin(
+                            &options,
+                            view_action,
+                            &general_scopers,
+                            &language_scopers,
+                            &pipeline,
+                            stats.as_deref(),
+                        )
+                    } else {
+                        info!("Will stream from stdin to stdout, applying actions line by line.");
+                        handle_actions_on_stdin_streaming(
+                            &options,
+                            view_action,
+                            &general_scopers,
+                            &pipeline,
+                            stats.as_deref(),
+                        )
+                    }
+                }
+                (Input::Stdin, _ /* no effect */) => {
+                    info!("Will read from stdin and write to stdout, applying actions.");
+                    handle_actions_on_stdin(
+                        &options,
+                        view_action,
+                        &general_scopers,
+                        &language_scopers,
+                        &pipeline,
+                        stats.as_deref(),
+                    )
+                }
+                (Input::WalkOn(validator), sorted) => {
+                    let root = env::current_dir()?;
+                    info!(
+                        "Will walk file tree using {:?} thread(s), applying actions, starting from: {:?}",
+                        n_threads(&options),
+                        root.canonicalize()
+                    );
+                    let processor = file_processor(
+                        &options,
+                        &root,
+                        None,
+                        validator,
+                        stats.clone(),
+                        search_mode,
+                    );
+                    handle_actions_on_many_files(
+                        &options,
+                        view_action,
+                        &processor,
+                        &general_scopers,
+                        &language_scopers,
+                        &pipeline,
+                        stats.as_deref(),
+                        search_mode,
+                        sorted,
+                    )
+                }
+                (Input::FilesFrom(source, validator), _ /* no effect */) => {
+                    info!(
+                        "Will read list of files from {:?}, applying actions.",
+                        source
+                    );
+                    let paths = read_file_list(&source, options.null_data)?;
+                    if let Some(stats) = &stats {
+                        stats.files_total.store(paths.len(), Ordering::Relaxed);
+                    }
+                    let processor = file_processor(
+                        &options,
+                        &env::current_dir()?,
+                        Some(paths),
+                        validator,
+                        stats.clone(),
+                        search_mode,
+                    );
+                    handle_actions_on_many_files(
+                        &options,
+                        view_action,
+                        &processor,
+                        &general_scopers,
+                        &language_scopers,
+                        &pipeline,
+                        stats.as_deref(),
+                        search_mode,
+                        false,
+                    )
+                }
+                (Input::GitDiff(paths, validator), _ /* no effect */) => {
+                    info!(
+                        "Will work on {} file(s) changed according to git.",
+                        paths.len()
+                    );
+                    if let Some(stats) = &stats {
+                        stats.files_total.store(paths.len(), Ordering::Relaxed);
+                    }
+                    let processor = file_processor(
+                        &options,
+                        &env::current_dir()?,
+                        Some(paths),
+                        validator,
+                        stats.clone(),
+                        search_mode,
+                    );
+                    handle_actions_on_many_files(
+                        &options,
+                        view_action,
+                        &processor,
+                        &general_scopers,
+                        &language_scopers,
+                        &pipeline,
+                        stats.as_deref(),
+                        search_mode,
+                        false,
+                    )
+                }
+            })
+        })();
+
+        done.store(true, Ordering::Relaxed);
+        if let Some(reporter) = reporter {
+            reporter.join().expect("progress reporter does not panic")?;
         }
-        (Input::WalkOn(validator), false) => {
-            info!("Will walk file tree, applying actions.");
-            handle_actions_on_many_files_threaded(
-                &options,
-                standalone_action,
-                &validator,
-                &general_scoper,
-                &language_scopers,
-                &pipeline,
-                search_mode,
-                options.threads.map_or_else(
-                    || std::thread::available_parallelism().map_or(1, std::num::NonZero::get),
-                    std::num::NonZero::get,
-                ),
-            )?;
+
+        result
+    })?;
+
+    conclude(&options, stats.as_deref(), &rule_id, result)
+}
+
+/// Which files are valid to work on, if working on files at all: those matching the
+/// glob patterns, else those valid for the language scopers, minus any excluded.
+fn path_validator(
+    options: &cli::GlobalOptions,
+    language_scopers: Option<&Arc<ScoperList>>,
+) -> Option<Validator> {
+    let validator: Option<Validator> = match (options.glob.clone(), language_scopers) {
+        // When a pattern is specified, it takes precedence.
+        (patterns, _) if !patterns.is_empty() => Some(Box::new(move |path| {
+            let res = patterns.iter().any(|pattern| pattern.matches_path(path));
+            trace!("Path '{}' matches: {}.", path.display(), res);
+            res
+        })),
+
+        // Languages are detected per file, so any language will do.
+        (_, None) if !options.auto_scopers.is_empty() => {
+            let auto_scopers = options.auto_scopers.clone();
+            let gitattributes = Arc::clone(&options.gitattributes);
+            Some(Box::new(move |path| {
+                let res = auto_scopers.for_path(path, &gitattributes).is_some();
+                trace!(
+                    "Path '{}' valid for any auto-detected language: {}",
+                    path.display(),
+                    res
+                );
+                res
+            }))
         }
-        (Input::WalkOn(validator), true) => {
-            info!("Will walk file tree, applying actions.");
-            handle_actions_on_many_files_sorted(
-                &options,
-                standalone_action,
-                &validator,
-                &general_scoper,
-                &language_scopers,
-                &pipeline,
-                search_mode,
-            )?;
+
+        // If pattern wasn't manually overridden, consult the language scoper itself, if
+        // any.
+        (_, Some(language_scopers)) => {
+            let language_scopers = Arc::clone(language_scopers);
+            let gitattributes = Arc::clone(&options.gitattributes);
+            Some(Box::new(move |path| {
+                // TODO: perform this work only once (it's super fast but in the hot
+                // path).
+                let res = language_scopers
+                    .iter()
+                    .map(|s| gitattributes.is_valid_path(s.as_ref(), path))
+                    .all_equal_value()
+                    .expect("all language scopers to agree on path validity");
+
+                trace!(
+                    "Language scoper considers path '{}' valid: {}",
+                    path.display(),
+                    res
+                );
+                res
+            }))
+        }
+        (_, None) => None,
+    };
+
+    match validator {
+        Some(validator) if !options.exclude.is_empty() => {
+            let excludes = options.exclude.clone();
+            Some::<Validator>(Box::new(move |path| {
+                if excludes.iter().any(|pattern| pattern.matches_path(path)) {
+                    trace!("Path '{}' is excluded.", path.display());
+                    return false;
+                }
+
+                validator(path)
+            }))
+        }
+        validator => validator,
+    }
+}
+
+/// Where to read from: files listed explicitly or by git, stdin, or a walk of the
+/// current directory for files `validator` accepts.
+fn select_input(options: &mut cli::GlobalOptions, validator: Option<Validator>) -> Result<Input> {
+    let is_readable_stdin = grep_cli::is_readable_stdin();
+    info!("Detected stdin as readable: {is_readable_stdin}.");
+
+    let input = if let Some(source) = options.files_from.clone() {
+        // An explicit list of files: whatever else is going on, work on those.
+        Input::FilesFrom(source, validator.unwrap_or_else(|| Box::new(|_| true)))
+    } else if let Some(base) = options.git_diff.clone() {
+        // Same, but the list is coming from git.
+        if options.git_diff_lines {
+            options.git_changed_lines = Some(git_changed_lines(&base)?);
+        }
+        Input::GitDiff(
+            git_changed_files(&base)?,
+            validator.unwrap_or_else(|| Box::new(|_| true)),
+        )
+    } else {
+        match (
+            options.stdin_override_to.unwrap_or(is_readable_stdin),
+            options.glob.is_empty(),
+            validator,
+        ) {
+            // stdin considered viable: always use it.
+            (true, true, _)
+            // Nothing explicitly available: this should open an interactive stdin prompt.
+            | (false, _, None) => Input::Stdin,
+            (true, false, _) => {
+                // Usage error... warn loudly, the user is likely interested.
+                error!("Detected stdin, and request for files: will use stdin and ignore files.");
+                Input::Stdin
+            }
+            (false, _, Some(validator)) => Input::WalkOn(validator),
         }
     };
 
+    Ok(input)
+}
+
+/// Writes the final reports, and turns `result` plus any failure conditions (checks,
+/// thresholds, grep-like exit codes) into the program's result.
+fn conclude(
+    options: &cli::GlobalOptions,
+    stats: Option<&Stats>,
+    rule_id: &str,
+    result: std::result::Result<(), ProgramError>,
+) -> Result<()> {
+    if let Some(stats) = stats.filter(|_| options.format == cli::OutputFormat::Sarif) {
+        let mut findings = std::mem::take(&mut *stats.findings.lock().unwrap());
+        // Threads finish in any order
+        findings.sort();
+        write_sarif(&findings, rule_id, stdout().lock())?;
+    }
+
+    if let Some(stats) = stats.filter(|_| options.stats) {
+        stats.report(io::stderr().lock())?;
+    }
+    result?;
+
+    if let Some(stats) = stats.filter(|_| options.check) {
+        let n = stats.files_changed.load(Ordering::Relaxed);
+        if n > 0 {
+            return Err(ProgramError::WouldChange(n).into());
+        }
+    }
+
+    if let Some(stats) = stats {
+        let n = stats.matches.load(Ordering::Relaxed);
+        if let Some(max) = options.fail_if_more_than.filter(|&max| n > max) {
+            return Err(ProgramError::TooManyMatches(n, max).into());
+        }
+        if let Some(min) = options.fail_if_fewer_than.filter(|&min| n < min) {
+            return Err(ProgramError::TooFewMatches(n, min).into());
+        }
+        if options.fail_any_report && n > 0 {
+            return Err(ProgramError::SomethingProcessed.into());
+        }
+    }
+
+    if options.grep_exit_codes
+        && stats.is_some_and(|stats| stats.matches.load(Ordering::Relaxed) == 0)
+    {
+        return Err(ProgramError::NothingProcessed.into());
+    }
+
     info!("Done, exiting");
     Ok(())
 }
@@ -255,320 +910,901 @@ enum Input {
     /// indicates valid filesystem entries. This is similar to globbing, but more
     /// flexible.
     WalkOn(Validator),
+    /// Work on the files listed in the given file (`-` for stdin), in order, and only
+    /// if valid according to the contained validator.
+    FilesFrom(PathBuf, Validator),
+    /// Work on the given files changed according to git, in order, and only if valid
+    /// according to the contained validator.
+    GitDiff(Vec<PathBuf>, Validator),
 }
 
-/// A standalone action to perform on the results of applying a scope.
+/// An action on a view as a whole, instead of on its individual scopes. Runs before
+/// any [`Action`].
 #[derive(Clone, Copy, Debug)]
-enum StandaloneAction {
-    /// Delete anything in scope.
-    ///
-    /// Cannot be used with any other action: there is no point in deleting and
-    /// performing any other processing. Sibling actions would either receive empty
-    /// input or have their work wiped.
-    Delete,
+enum ViewAction {
     /// Squeeze consecutive occurrences of scope into one.
     Squeeze,
-    /// No stand alone action is set.
+    /// Delete all lines with anything in scope.
+    DeleteLines,
+    /// Remove all repeated occurrences of scope.
+    DedupeMatches,
+    /// No view action is set.
     None,
 }
 
+impl ViewAction {
+    fn apply(self, view: &mut ScopedView<'_>) {
+        match self {
+            Self::Squeeze => {
+                view.squeeze();
+            }
+            Self::DeleteLines => {
+                view.delete_lines();
+            }
+            Self::DedupeMatches => {
+                view.dedupe();
+            }
+            Self::None => {}
+        }
+    }
+}
+
 /// A "pipeline" in that there's not just a single sequence (== slice) of actions, but
 /// instead multiple. These can be used in parallel (on the same or different views),
 /// and the different results then used for advanced use cases. For example, diffing
 /// different results against one another.
 type Pipeline<'a> = &'a [&'a [Box<dyn Action>]];
 
-/// Main entrypoint for simple `stdin` -> `stdout` processing.
-#[allow(clippy::borrowed_box)] // Used throughout, not much of a pain
-fn handle_actions_on_stdin(
-    global_options: &cli::GlobalOptions,
-    standalone_action: StandaloneAction,
-    general_scoper: &Box<dyn Scoper>,
-    language_scopers: &[Box<dyn LanguageScoper>],
+/// Output of a processed file, keyed by its path, on its way to the writer thread.
+type FileOutput = (PathBuf, Vec<u8>);
+
+/// Files to be renamed (from, to) once all files are processed, such that the walk
+/// does not come across renamed files again.
+type PendingRenames = Mutex<Vec<(PathBuf, PathBuf)>>;
+
+/// Counters for the summary printed with `--stats`.
+///
+/// Shared across all threads of the walker, hence atomic.
+#[derive(Debug)]
+struct Stats {
+    start: Instant,
+    /// Number of files to be processed, if known upfront (`0` otherwise).
+    files_total: AtomicUsize,
+    files_discovered: AtomicUsize,
+    files_scanned: AtomicUsize,
+    files_changed: AtomicUsize,
+    bytes_scanned: AtomicU64,
+    matches: AtomicUsize,
+    /// Per action, in order of application, the number of matches it changed.
+    changed_by_action: Vec<(&'static str, AtomicUsize)>,
+    /// Everything in scope, for `--format sarif`.
+    findings: Mutex<Vec<Finding>>,
+}
+
+impl Stats {
+    fn new(actions: &[Box<dyn Action>]) -> Self {
+        Self {
+            start: Instant::now(),
+            files_total: AtomicUsize::default(),
+            files_discovered: AtomicUsize::default(),
+            files_scanned: AtomicUsize::default(),
+            files_changed: AtomicUsize::default(),
+            bytes_scanned: AtomicU64::default(),
+            matches: AtomicUsize::default(),
+            changed_by_action: actions
+                .iter()
+                .map(|action| (action.name(), AtomicUsize::default()))
+                .collect(),
+            findings: Mutex::default(),
+        }
+    }
+
+    /// Records a scanned file (or `stdin`) of `size` bytes.
+    fn add_scanned(&self, size: u64) {
+        self.files_scanned.fetch_add(1, Ordering::Relaxed);
+        self.bytes_scanned.fetch_add(size, Ordering::Relaxed);
+    }
+
+    /// Writes a human-readable summary of all counters.
+    fn report(&self, mut to: impl Write) -> io::Result<()> {
+        let elapsed = self.start.elapsed();
+        let bytes = self.bytes_scanned.load(Ordering::Relaxed);
+
+        writeln!(
+            to,
+            "Files scanned: {}",
+            self.files_scanned.load(Ordering::Relaxed)
+        )?;
+        writeln!(
+            to,
+            "Files changed: {}",
+            self.files_changed.load(Ordering::Relaxed)
+        )?;
+        writeln!(to, "Matches: {}", self.matches.load(Ordering::Relaxed))?;
+        for (name, n) in &self.changed_by_action {
+            writeln!(to, "  changed by {name}: {}", n.load(Ordering::Relaxed))?;
+        }
+        writeln!(to, "Bytes scanned: {bytes}")?;
+        writeln!(to, "Elapsed: {:.3}s", elapsed.as_secs_f64())?;
+
+        #[allow(clippy::cast_precision_loss)] // Approximations are fine for reporting
+        let throughput = bytes as f64 / elapsed.as_secs_f64() / 1024.0 / 1024.0;
+        writeln!(to, "Throughput: {throughput:.2} MiB/s")
+    }
+
+    /// Writes a single-line snapshot of progress so far, without line terminator.
+    fn progress(&self, mut to: impl Write) -> io::Result<()> {
+        let elapsed = self.start.elapsed();
+        let total = self.files_total.load(Ordering::Relaxed);
+        let discovered = self.files_discovered.load(Ordering::Relaxed);
+
+        write!(
+            to,
+            "Files: {discovered} discovered, {} processed",
+            self.files_scanned.load(Ordering::Relaxed)
+        )?;
+        if total > 0 {
+            write!(to, " (of {total})")?;
+        }
+        write!(
+            to,
+            ", {} changed | Matches: {} | Elapsed: {:.1}s",
+            self.files_changed.load(Ordering::Relaxed),
+            self.matches.load(Ordering::Relaxed),
+            elapsed.as_secs_f64()
+        )?;
+
+        if total > 0 && discovered > 0 {
+            #[allow(clippy::cast_precision_loss)] // Approximations are fine for reporting
+            let eta =
+                elapsed.as_secs_f64() / discovered as f64 * total.saturating_sub(discovered) as f64;
+            write!(to, " | ETA: {eta:.1}s")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single item in scope, as reported by `--format sarif`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Finding {
+    file: Option<PathBuf>,
+    /// 1-based line and column (in characters) of the first character.
+    start: (usize, usize),
+    /// 1-based line and column (in characters) just past the last character.
+    end: (usize, usize),
+    text: String,
+}
+
+/// Everything in scope in `view`, located in the input as of `location`.
+fn findings(view: &ScopedView<'_>, location: Location<'_>) -> Vec<Finding> {
+    let (mut line, mut column) = (location.line, 1);
+    let mut findings = Vec::new();
+
+    for scope in &view.scopes().0 {
+        let s = <&str>::from(scope);
+        let start = (line, column);
+        for c in s.chars() {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+
+        if matches!(scope, RWScope(In(..))) && !s.is_empty() {
+            findings.push(Finding {
+                file: location.file.map(Path::to_owned),
+                start,
+                end: (line, column),
+                text: s.to_owned(),
+            });
+        }
+    }
+
+    findings
+}
+
+/// A [SARIF](https://sarifweb.azurewebsites.net/) 2.1.0 log, holding a single run.
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    version: &'static str,
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    runs: [SarifRun<'a>; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun<'a> {
+    tool: SarifTool<'a>,
+    column_kind: &'static str,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct SarifTool<'a> {
+    driver: SarifDriver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver<'a> {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: [SarifRule<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct SarifRule<'a> {
+    id: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    rule_id: &'a str,
+    level: &'static str,
+    message: SarifText<String>,
+    locations: [SarifLocation<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct SarifText<T> {
+    text: T,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation<'a> {
+    physical_location: SarifPhysicalLocation<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation<'a> {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion<'a>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion<'a> {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    snippet: SarifText<&'a str>,
+}
+
+/// Writes `findings` as a [SARIF](https://sarifweb.azurewebsites.net/) 2.1.0 log, all
+/// results belonging to the single rule `rule_id`.
+fn write_sarif(findings: &[Finding], rule_id: &str, mut to: impl Write) -> io::Result<()> {
+    let rule_id = if rule_id.is_empty() { "srgn" } else { rule_id };
+
+    let results = findings
+        .iter()
+        .map(|finding| SarifResult {
+            rule_id,
+            level: "note",
+            message: SarifText {
+                text: format!("In scope: {}", finding.text),
+            },
+            locations: [SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: finding.file.as_deref().map_or_else(
+                            || String::from("stdin"),
+                            // URIs use forward slashes regardless of platform
+                            |file| {
+                                file.components()
+                                    .map(|c| c.as_os_str().to_string_lossy())
+                                    .join("/")
+                            },
+                        ),
+                    },
+                    region: SarifRegion {
+                        start_line: finding.start.0,
+                        start_column: finding.start.1,
+                        end_line: finding.end.0,
+                        end_column: finding.end.1,
+                        snippet: SarifText {
+                            text: &finding.text,
+                        },
+                    },
+                },
+            }],
+        })
+        .collect();
+
+    let log = SarifLog {
+        version: "2.1.0",
+        schema: "https://json.schemastore.org/sarif-2.1.0.json",
+        runs: [SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "srgn",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: env!("CARGO_PKG_REPOSITORY"),
+                    rules: [SarifRule { id: rule_id }],
+                },
+            },
+            column_kind: "unicodeCodePoints",
+            results,
+        }],
+    };
+
+    serde_json::to_writer(&mut to, &log)?;
+    writeln!(to)
+}
+
+/// Periodically reports progress to stderr, until `done` is set.
+///
+/// On a terminal, a single line is redrawn in place. Otherwise, lines are printed
+/// less frequently, one after another, as is suitable for log files.
+fn report_progress(stats: &Stats, done: &AtomicBool) -> io::Result<()> {
+    let is_terminal = io::stderr().is_terminal();
+    let interval = if is_terminal {
+        Duration::from_millis(100)
+    } else {
+        Duration::from_secs(1)
+    };
+    let tick = Duration::from_millis(20);
+
+    let draw = || -> io::Result<()> {
+        let mut stderr = io::stderr().lock();
+        if is_terminal {
+            // Return to line start and clear it
+            write!(stderr, "\r\x1b[2K")?;
+            stats.progress(&mut stderr)?;
+        } else {
+            stats.progress(&mut stderr)?;
+            writeln!(stderr)?;
+        }
+        stderr.flush()
+    };
+
+    let mut last = Instant::now();
+    while !done.load(Ordering::Relaxed) {
+        if last.elapsed() >= interval {
+            draw()?;
+            last = Instant::now();
+        }
+        thread::sleep(tick);
+    }
+
+    // Final state, always shown
+    draw()?;
+    if is_terminal {
+        writeln!(io::stderr())?;
+    }
+
+    Ok(())
+}
+
+/// Main entrypoint for simple `stdin` -> `stdout` processing.
+fn handle_actions_on_stdin(
+    global_options: &cli::GlobalOptions,
+    view_action: ViewAction,
+    general_scopers: &[Box<dyn Scoper>],
+    language_scopers: &[Box<dyn LanguageScoper>],
     pipeline: Pipeline<'_>,
+    stats: Option<&Stats>,
 ) -> Result<(), ProgramError> {
     info!("Will use stdin to stdout.");
-    let mut source = String::new();
-    io::stdin().lock().read_to_string(&mut source)?;
+    let source = if global_options.binary_mode == cli::BinaryMode::Lossy {
+        let mut bytes = Vec::new();
+        io::stdin().lock().read_to_end(&mut bytes)?;
+        Encoding::Utf8.decode_lossy(&bytes).into_owned()
+    } else {
+        let mut source = String::new();
+        io::stdin().lock().read_to_string(&mut source)?;
+        source
+    };
     let mut destination = String::with_capacity(source.len());
 
-    apply(
+    let changed = apply(
         global_options,
-        standalone_action,
+        view_action,
         &source,
+        Location::default(),
         &mut destination,
-        general_scoper,
+        general_scopers,
         language_scopers,
         pipeline,
+        stats,
     )?;
 
+    if let Some(stats) = stats {
+        stats.add_scanned(source.len() as u64);
+        if changed {
+            stats.files_changed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
     stdout().lock().write_all(destination.as_bytes())?;
 
     Ok(())
 }
 
-/// Main entrypoint for processing using strictly sequential, *single-threaded*
-/// processing.
+/// Main entrypoint for `stdin` -> `stdout` processing, line by line.
 ///
-/// If it's good enough for [ripgrep], it's good enough for us :-). Main benefit it full
-/// control of output for testing anyway.
+/// Each line is processed in isolation, so memory usage is bounded by the longest line
+/// instead of the entire input.
+fn handle_actions_on_stdin_streaming(
+    global_options: &cli::GlobalOptions,
+    view_action: ViewAction,
+    general_scopers: &[Box<dyn Scoper>],
+    pipeline: Pipeline<'_>,
+    stats: Option<&Stats>,
+) -> Result<(), ProgramError> {
+    info!("Will stream stdin to stdout.");
+    let mut stdin = io::stdin().lock();
+    let mut stdout = stdout().lock();
+
+    let mut line = Vec::new();
+    let mut destination = String::new();
+    let mut any_in_scope = false;
+    let mut any_changed = false;
+    let mut n_bytes = 0;
+    let mut location = Location::default();
+
+    while stdin.read_until(b'\n', &mut line)? > 0 {
+        let source = decode(global_options, &line)?;
+        destination.clear();
+
+        match apply(
+            global_options,
+            view_action,
+            &source,
+            location,
+            &mut destination,
+            general_scopers,
+            &[],
+            pipeline,
+            stats,
+        ) {
+            Ok(changed) => {
+                // Only tells us anything if failing on none in scope, see below.
+                any_in_scope = true;
+                any_changed |= changed;
+                stdout.write_all(destination.as_bytes())?;
+            }
+            Err(ApplicationError::NoneInScope) => {
+                // Nothing in scope for this line, so it passes through unchanged. Only
+                // the input *as a whole* having nothing in scope is an error.
+                stdout.write_all(source.as_bytes())?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        n_bytes += line.len() as u64;
+        line.clear();
+        location.line += 1;
+    }
+
+    if let Some(stats) = stats {
+        stats.add_scanned(n_bytes);
+        if any_changed {
+            stats.files_changed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    if global_options.fail_none && !any_in_scope {
+        return Err(ApplicationError::NoneInScope.into());
+    }
+
+    Ok(())
+}
+
+/// Benchmarks processing of `stdin`, running each stage `iterations` times, and
+/// reports the time taken per stage on `stdout`.
 ///
-/// [ripgrep]:
-///     https://github.com/BurntSushi/ripgrep/blob/71d71d2d98964653cdfcfa315802f518664759d7/GUIDE.md#L1016-L1017
-#[allow(clippy::borrowed_box)] // Used throughout, not much of a pain
-fn handle_actions_on_many_files_sorted(
+/// Helps find out whether a slow run is due to language (tree-sitter) scoping, general
+/// (regex) scoping, or actions. The first two are also measured in isolation, each on
+/// the entire input.
+fn self_bench(
     global_options: &cli::GlobalOptions,
-    standalone_action: StandaloneAction,
-    validator: &Validator,
-    general_scoper: &Box<dyn Scoper>,
+    view_action: ViewAction,
+    general_scopers: &[Box<dyn Scoper>],
     language_scopers: &[Box<dyn LanguageScoper>],
     pipeline: Pipeline<'_>,
-    search_mode: bool,
+    iterations: NonZero<u32>,
 ) -> Result<(), ProgramError> {
-    let root = env::current_dir()?;
-    info!(
-        "Will walk file tree sequentially, in sorted order, starting from: {:?}",
-        root.canonicalize()
-    );
+    let start = Instant::now();
+    let mut source = String::new();
+    io::stdin().lock().read_to_string(&mut source)?;
+    let reading = start.elapsed();
 
-    let mut n_files_processed: usize = 0;
-    let mut n_files_seen: usize = 0;
-    for entry in WalkBuilder::new(&root)
-        .hidden(!global_options.hidden)
-        .git_ignore(!global_options.gitignored)
-        .sort_by_file_path(Ord::cmp)
-        .build()
-    {
-        match entry {
-            Ok(entry) => {
-                let path = entry.path();
-                let res = process_path(
-                    global_options,
-                    standalone_action,
-                    path,
-                    &root,
-                    validator,
-                    general_scoper,
-                    language_scopers,
-                    pipeline,
-                    search_mode,
-                );
+    let mut language_scoping = Duration::ZERO;
+    let mut general_scoping = Duration::ZERO;
+    let mut scoping = Duration::ZERO;
+    let mut acting = Duration::ZERO;
+    let mut rendering = Duration::ZERO;
+    let mut destination = String::with_capacity(source.len());
 
-                n_files_seen += match res {
-                    Err(PathProcessingError::NotAFile | PathProcessingError::InvalidFile) => 0,
-                    _ => 1,
-                };
+    for _ in 0..iterations.get() {
+        let start = Instant::now();
+        build_view(global_options, &source, None, &[], language_scopers);
+        language_scoping += start.elapsed();
 
-                n_files_processed += match res {
-                    Ok(()) => 1,
+        let start = Instant::now();
+        build_view(global_options, &source, None, general_scopers, &[]);
+        general_scoping += start.elapsed();
 
-                    // Soft errors with reasonable handling available:
-                    Err(PathProcessingError::NotAFile | PathProcessingError::InvalidFile) => 0,
-                    Err(PathProcessingError::ApplicationError(ApplicationError::SomeInScope))
-                        if global_options.fail_any =>
-                    {
-                        // Early-out
-                        info!("Match at {}, exiting early", path.display());
-                        return Err(ProgramError::SomethingProcessed);
-                    }
-                    #[allow(clippy::match_same_arms)]
-                    Err(PathProcessingError::ApplicationError(
-                        ApplicationError::NoneInScope | ApplicationError::SomeInScope,
-                    )) => 0,
-                    Err(PathProcessingError::IoError(e, _))
-                        if e.kind() == io::ErrorKind::BrokenPipe && search_mode =>
-                    {
-                        trace!("Detected broken pipe, stopping search.");
-                        break;
-                    }
-                    Err(PathProcessingError::IoError(e, _))
-                        // `InvalidData` does NOT equal "invalid utf-8", but that's how
-                        // it's _effectively_ used in the "read to string" type of
-                        // functions we use throughout.
-                        // https://github.com/rust-lang/rust/blob/096277e989d6de11c3077472fc05778e261e7b8e/library/std/src/io/error.rs#L78-L79
-                        if e.kind() == io::ErrorKind::InvalidData =>
-                    {
-                        warn!("File contains unreadable data (binary? invalid utf-8?), skipped: {}", path.display());
-                        0
-                    }
+        let start = Instant::now();
+        let mut view = build_view(
+            global_options,
+            &source,
+            None,
+            general_scopers,
+            language_scopers,
+        );
+        scoping += start.elapsed();
 
-                    // Hard errors we should do something about:
-                    Err(
-                        e @ (PathProcessingError::ApplicationError(ApplicationError::ActionError(
-                            ..,
-                        ))
-                        | PathProcessingError::IoError(..)),
-                    ) => {
-                        if search_mode {
-                            error!("Error walking at {}: {}", path.display(), e);
-                            0
-                        } else {
-                            error!("Aborting walk at {} due to: {}", path.display(), e);
-                            return Err(e.into());
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                if search_mode {
-                    error!("Error walking: {}", e);
-                } else {
-                    error!("Aborting walk due to: {}", e);
-                    return Err(e.into());
-                }
-            }
+        view_action.apply(&mut view);
+        let mut views = vec![view; pipeline.len()];
+
+        let start = Instant::now();
+        for (actions, view) in pipeline.iter().zip_eq(&mut views) {
+            map_actions(view, actions, None).map_err(ApplicationError::from)?;
         }
+        acting += start.elapsed();
+
+        let start = Instant::now();
+        destination.clear();
+        for view in &views {
+            write!(destination, "{view}").expect("writing to `String` is infallible");
+        }
+        rendering += start.elapsed();
     }
 
-    info!("Saw {} items", n_files_seen);
-    info!("Processed {} files", n_files_processed);
+    let mut stdout = stdout().lock();
+    writeln!(stdout, "Iterations: {iterations}")?;
+    writeln!(stdout, "Input: {} bytes", source.len())?;
+    writeln!(stdout, "Reading input (once): {reading:.3?}")?;
+    for (stage, total) in [
+        ("Language scoping (alone)", language_scoping),
+        ("General scoping (alone)", general_scoping),
+        ("Scoping", scoping),
+        ("Actions", acting),
+        ("Rendering", rendering),
+    ] {
+        writeln!(
+            stdout,
+            "{stage}: {total:.3?} total, {:.3?} per iteration",
+            total / iterations.get()
+        )?;
+    }
 
-    if n_files_seen == 0 && global_options.fail_no_files {
-        Err(ProgramError::NoFilesFound)
-    } else if n_files_processed == 0 && global_options.fail_none {
-        Err(ProgramError::NothingProcessed)
-    } else {
-        Ok(())
+    Ok(())
+}
+
+/// Creates the processor for files below `root` (or the given `paths` therein),
+/// configured as requested (hidden files, ignore files, encoding, writing, ...).
+///
+/// Only files `validator` accepts are processed.
+fn file_processor(
+    global_options: &cli::GlobalOptions,
+    root: &Path,
+    paths: Option<Vec<PathBuf>>,
+    validator: Validator,
+    stats: Option<Arc<Stats>>,
+    search_mode: bool,
+) -> FileProcessor {
+    // Mirrors the output branches in `process_path`: everything else writes back.
+    let in_place = !(search_mode
+        || global_options.check
+        || global_options.format == cli::OutputFormat::Patch
+        || global_options.print);
+
+    let mut processor = FileProcessor::new(root);
+    processor
+        .hidden(global_options.hidden)
+        .gitignored(global_options.gitignored)
+        .no_ignore(global_options.no_ignore)
+        .no_ignore_vcs(global_options.no_ignore_vcs)
+        .follow_links(global_options.follow)
+        .max_depth(global_options.max_depth)
+        .binary(global_options.binary)
+        .encoding(global_options.encoding.encoding())
+        .lossy(global_options.binary_mode == cli::BinaryMode::Lossy)
+        .mmap(global_options.mmap.policy())
+        .in_place(in_place)
+        .force_write(global_options.force_write)
+        .preserve_times(global_options.preserve_times)
+        .write_through_symlinks(global_options.symlinks == cli::SymlinkPolicy::Target)
+        .threads(n_threads(global_options));
+
+    if let Some(paths) = paths {
+        processor.paths(paths);
+    }
+    for pattern in &global_options.prune {
+        processor.prune(pattern.clone());
+    }
+    for path in &global_options.ignore_file {
+        processor.ignore_file(path);
+    }
+    if let Some(max) = global_options.max_filesize {
+        processor.max_filesize(max);
     }
+    if let Some(suffix) = &global_options.backup {
+        processor.backup(suffix);
+    }
+    if let Some(output_dir) = &global_options.output_dir {
+        processor.output_dir(output_dir);
+    }
+
+    let root = root.to_owned();
+    let skip_symlinks = global_options.symlinks == cli::SymlinkPolicy::Skip;
+    let auto_scopers = global_options.auto_scopers.clone();
+    let gitattributes = Arc::clone(&global_options.gitattributes);
+    processor.filter(move |path| {
+        if let Some(stats) = &stats {
+            stats.files_discovered.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if skip_symlinks && root.join(path).is_symlink() {
+            trace!("Skipping path (symlink): {:?}", path);
+            return false;
+        }
+
+        if !validator(path) {
+            trace!("Skipping path (invalid): {:?}", path);
+            return false;
+        }
+
+        if !auto_scopers.is_empty() && auto_scopers.for_path(path, &gitattributes).is_none() {
+            trace!("Skipping path (no language detected): {:?}", path);
+            return false;
+        }
+
+        true
+    });
+
+    processor
 }
 
-/// Main entrypoint for processing using at least 1 thread.
-#[allow(clippy::borrowed_box)] // Used throughout, not much of a pain
+/// The number of threads to process files on.
+fn n_threads(global_options: &cli::GlobalOptions) -> NonZero<usize> {
+    global_options
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().unwrap_or(NonZero::<usize>::MIN))
+}
+
+/// Main entrypoint for processing files, as walked (or listed) by `processor`.
+///
+/// Output is handed to a dedicated writer thread. If `sorted` output is requested, it
+/// is buffered and emitted in sorted order once all files are processed.
 #[allow(clippy::too_many_lines)]
 #[allow(clippy::too_many_arguments)]
-fn handle_actions_on_many_files_threaded(
+fn handle_actions_on_many_files(
     global_options: &cli::GlobalOptions,
-    standalone_action: StandaloneAction,
-    validator: &Validator,
-    general_scoper: &Box<dyn Scoper>,
+    view_action: ViewAction,
+    processor: &FileProcessor,
+    general_scopers: &[Box<dyn Scoper>],
     language_scopers: &[Box<dyn LanguageScoper>],
     pipeline: Pipeline<'_>,
+    stats: Option<&Stats>,
     search_mode: bool,
-    n_threads: usize,
+    sorted: bool,
 ) -> Result<(), ProgramError> {
-    let root = env::current_dir()?;
-    info!(
-        "Will walk file tree using {:?} thread(s), processing in arbitrary order, starting from: {:?}",
-        n_threads,
-        root.canonicalize()
-    );
-
-    let n_files_processed = Arc::new(Mutex::new(0usize));
-    let n_files_seen = Arc::new(Mutex::new(0usize));
-    let err: Arc<Mutex<Option<ProgramError>>> = Arc::new(Mutex::new(None));
-
-    WalkBuilder::new(&root)
-        .threads(
-            // https://github.com/BurntSushi/ripgrep/issues/2854
-            n_threads,
-        )
-        .hidden(!global_options.hidden)
-        .git_ignore(!global_options.gitignored)
-        .build_parallel()
-        .run(|| {
-            Box::new(|entry| match entry {
-                Ok(entry) => {
-                    let path = entry.path();
+    let n_files_processed = AtomicUsize::new(0);
+    let n_files_seen = AtomicUsize::new(0);
+    let n_files_failed = AtomicUsize::new(0);
+    let err: Mutex<Option<ProgramError>> = Mutex::new(None);
+    let renames = PendingRenames::default();
+
+    // Workers hand their output to a single writer, instead of contending for stdout.
+    // Bounded, so workers wait for output to be written instead of piling it up.
+    let (output, received) = mpsc::sync_channel::<FileOutput>(n_threads(global_options).get() * 4);
+
+    let (walked, written) = thread::scope(|scope| {
+        let writer = scope.spawn(move || write_output(received, sorted));
+
+        let walked = processor.walk(|file| {
+            let (path, res) = match file {
+                Ok(file) => {
                     let res = process_path(
                         global_options,
-                        standalone_action,
-                        path,
-                        &root,
-                        validator,
-                        general_scoper,
+                        view_action,
+                        &file,
+                        processor,
+                        general_scopers,
                         language_scopers,
                         pipeline,
+                        stats,
                         search_mode,
+                        &output,
+                        &renames,
                     );
+                    (file.path().to_owned(), res)
+                }
+                Err(WalkError::Io { path, error }) => {
+                    (path, Err(PathProcessingError::IoError(error, None)))
+                }
+                Err(WalkError::Action { path, error }) => (
+                    path,
+                    Err(PathProcessingError::ApplicationError(error.into())),
+                ),
+                Err(WalkError::Walk(e)) => {
+                    return if search_mode || global_options.keep_going {
+                        error!("Error walking: {}", e);
+                        n_files_failed.fetch_add(1, Ordering::Relaxed);
+                        ControlFlow::Continue(())
+                    } else {
+                        error!("Aborting walk due to: {}", e);
+                        *err.lock().unwrap() = Some(e.into());
+                        ControlFlow::Break(())
+                    };
+                }
+            };
 
-                    match res {
-                        Err(PathProcessingError::NotAFile | PathProcessingError::InvalidFile) => (),
-                        _ => *n_files_seen.lock().unwrap() += 1,
-                    }
-
-                    match res {
-                        Ok(()) => {
-                            *n_files_processed.lock().unwrap() += 1;
-                            WalkState::Continue
-                        }
+            if !matches!(res, Err(PathProcessingError::InvalidFile)) {
+                n_files_seen.fetch_add(1, Ordering::Relaxed);
+            }
 
-                        // Soft errors with reasonable handling available:
-                        Err(PathProcessingError::NotAFile | PathProcessingError::InvalidFile) => {
-                            WalkState::Continue
-                        }
-                        Err(
-                            e
-                            @ PathProcessingError::ApplicationError(ApplicationError::SomeInScope),
-                        ) if global_options.fail_any => {
-                            // Early-out
-                            info!("Match at {}, exiting early", path.display());
-                            *err.lock().unwrap() = Some(e.into());
-                            WalkState::Quit
-                        }
-                        Err(PathProcessingError::ApplicationError(
-                            ApplicationError::NoneInScope | ApplicationError::SomeInScope,
-                        )) => WalkState::Continue,
-                        Err(PathProcessingError::IoError(e, _))
-                            if e.kind() == io::ErrorKind::BrokenPipe && search_mode =>
-                        {
-                            trace!("Detected broken pipe, stopping search.");
-                            WalkState::Quit
-                        }
-                        Err(PathProcessingError::IoError(e, _))
-                            // `InvalidData` does NOT equal "invalid utf-8", but that's
-                            // how it's _effectively_ used in the "read to string" type
-                            // of functions we use throughout.
-                            // https://github.com/rust-lang/rust/blob/096277e989d6de11c3077472fc05778e261e7b8e/library/std/src/io/error.rs#L78-L79
-                            if e.kind() == io::ErrorKind::InvalidData =>
-                        {
-                            warn!("File contains unreadable data (binary? invalid utf-8?), skipped: {}", path.display());
-                            WalkState::Continue
-                        }
+            match res {
+                Ok(()) => {
+                    n_files_processed.fetch_add(1, Ordering::Relaxed);
+                    ControlFlow::Continue(())
+                }
 
-                        // Hard errors we should do something about:
-                        Err(
-                            e @ (PathProcessingError::ApplicationError(..)
-                            | PathProcessingError::IoError(..)),
-                        ) => {
-                            error!("Error walking at {} due to: {}", path.display(), e);
-
-                            if search_mode {
-                                WalkState::Continue
-                            } else {
-                                // Chances are something bad and/or unintended happened;
-                                // bail out to limit any potential damage.
-                                error!("Aborting walk for safety");
-                                *err.lock().unwrap() = Some(e.into());
-                                WalkState::Quit
-                            }
-                        }
-                    }
+                // Soft errors with reasonable handling available:
+                Err(PathProcessingError::ApplicationError(ApplicationError::SomeInScope))
+                    if global_options.fail_any =>
+                {
+                    // Early-out
+                    info!("Match at {}, exiting early", path.display());
+                    *err.lock().unwrap() = Some(ProgramError::SomethingProcessed);
+                    ControlFlow::Break(())
                 }
-                Err(e) => {
-                    if search_mode {
-                        error!("Error walking: {}", e);
-                        WalkState::Continue
+                Err(
+                    PathProcessingError::InvalidFile
+                    | PathProcessingError::ApplicationError(
+                        ApplicationError::NoneInScope | ApplicationError::SomeInScope,
+                    ),
+                ) => ControlFlow::Continue(()),
+                Err(PathProcessingError::IoError(e, _))
+                    if e.kind() == io::ErrorKind::BrokenPipe && search_mode =>
+                {
+                    trace!("Detected broken pipe, stopping search.");
+                    ControlFlow::Break(())
+                }
+                Err(PathProcessingError::IoError(e, _))
+                    // `InvalidData` does NOT equal "invalid utf-8", but that's how it's
+                    // _effectively_ used in the "read to string" type of functions we
+                    // use throughout.
+                    // https://github.com/rust-lang/rust/blob/096277e989d6de11c3077472fc05778e261e7b8e/library/std/src/io/error.rs#L78-L79
+                    if e.kind() == io::ErrorKind::InvalidData
+                        && global_options.binary_mode == cli::BinaryMode::Skip =>
+                {
+                    warn!("File contains unreadable data (binary? invalid utf-8?), skipped: {}", path.display());
+                    ControlFlow::Continue(())
+                }
+
+                // Hard errors we should do something about:
+                Err(
+                    e @ (PathProcessingError::ApplicationError(ApplicationError::ActionError(..))
+                    | PathProcessingError::IoError(..)),
+                ) => {
+                    if search_mode || global_options.keep_going {
+                        error!("Error walking at {}: {}", path.display(), e);
+                        n_files_failed.fetch_add(1, Ordering::Relaxed);
+                        ControlFlow::Continue(())
                     } else {
-                        error!("Aborting walk due to: {}", e);
+                        // Chances are something bad and/or unintended happened; bail
+                        // out to limit any potential damage.
+                        error!("Aborting walk at {} due to: {}", path.display(), e);
                         *err.lock().unwrap() = Some(e.into());
-                        WalkState::Quit
+                        ControlFlow::Break(())
                     }
                 }
-            })
+            }
         });
 
-    let error = err.lock().unwrap().take();
-    if let Some(e) = error {
+        // Hang up, such that the writer finishes
+        drop(output);
+        (
+            walked,
+            writer.join().expect("writer thread should not panic"),
+        )
+    });
+
+    walked?;
+    match written {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe && search_mode => {
+            trace!("Detected broken pipe, stopping output.");
+        }
+        res => res?,
+    }
+
+    if let Some(e) = err.into_inner().unwrap() {
         return Err(e);
     }
 
-    let n_files_seen = *n_files_seen.lock().unwrap();
+    rename_files(global_options, renames)?;
+
+    finish_walk(
+        global_options,
+        n_files_seen.into_inner(),
+        n_files_processed.into_inner(),
+        n_files_failed.into_inner(),
+    )
+}
+
+/// Performs (or, when dry running, only reports) all `renames` collected during a walk.
+fn rename_files(
+    global_options: &cli::GlobalOptions,
+    renames: PendingRenames,
+) -> Result<(), ProgramError> {
+    let mut renames = renames.into_inner().unwrap();
+    renames.sort();
+
+    let mut stdout = stdout().lock();
+    for (from, to) in renames {
+        if global_options.dry_run {
+            info!("Would rename {:?} to {:?}", from, to);
+        } else {
+            rename_file(&from, &to)?;
+            debug!("Renamed {:?} to {:?}", from, to);
+        }
+
+        let style = global_options.path_style();
+        writeln!(
+            stdout,
+            "{} -> {}",
+            style.act(&from.display().to_string()),
+            style.act(&to.display().to_string())
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The path `path` is renamed to by `renames`, applied in order to its file name, if
+/// that changes anything.
+fn renamed_path(renames: &[(fancy_regex::Regex, String)], path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let new_name = renames
+        .iter()
+        .fold(name.to_owned(), |name, (pattern, replacement)| {
+            pattern
+                .replace_all(&name, replacement.as_str())
+                .into_owned()
+        });
+
+    (new_name != name && !new_name.is_empty()).then(|| path.with_file_name(new_name))
+}
+
+/// Reports on a finished walk, and checks its outcome against what was requested.
+fn finish_walk(
+    global_options: &cli::GlobalOptions,
+    n_files_seen: usize,
+    n_files_processed: usize,
+    n_files_failed: usize,
+) -> Result<(), ProgramError> {
     info!("Saw {} items", n_files_seen);
-    let n_files_processed = *n_files_processed.lock().unwrap();
     info!("Processed {} files", n_files_processed);
 
+    if global_options.keep_going {
+        let n_files_skipped = n_files_seen.saturating_sub(n_files_processed + n_files_failed);
+        eprintln!(
+            "{n_files_processed} file(s) ok, {n_files_skipped} skipped, {n_files_failed} failed"
+        );
+    }
+
     if n_files_seen == 0 && global_options.fail_no_files {
         Err(ProgramError::NoFilesFound)
+    } else if n_files_failed > 0 && global_options.keep_going {
+        Err(ProgramError::FilesFailed(n_files_failed))
     } else if n_files_processed == 0 && global_options.fail_none {
         Err(ProgramError::NothingProcessed)
     } else {
@@ -576,105 +1812,476 @@ fn handle_actions_on_many_files_threaded(
     }
 }
 
+/// Writes output of processed files to stdout as it comes in, or sorted by path once
+/// all of it is in.
+fn write_output(received: Receiver<FileOutput>, sorted: bool) -> io::Result<()> {
+    let mut stdout = stdout().lock();
+
+    if sorted {
+        let mut outputs = received.into_iter().collect_vec();
+        outputs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (_, output) in outputs {
+            stdout.write_all(&output)?;
+        }
+    } else {
+        for (_, output) in received {
+            stdout.write_all(&output)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a list of paths from `source` (`-` for stdin), one per line or, if
+/// `null_separated`, separated by NUL bytes. Empty entries are skipped.
+fn read_file_list(source: &Path, null_separated: bool) -> io::Result<Vec<PathBuf>> {
+    let mut contents = Vec::new();
+    if source == Path::new("-") {
+        io::stdin().lock().read_to_end(&mut contents)?;
+    } else {
+        File::open(source)?.read_to_end(&mut contents)?;
+    }
+
+    let contents =
+        String::from_utf8(contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(parse_file_list(&contents, null_separated))
+}
+
+fn parse_file_list(contents: &str, null_separated: bool) -> Vec<PathBuf> {
+    let entries = if null_separated {
+        contents.split('\0').collect_vec()
+    } else {
+        contents.lines().collect_vec()
+    };
+
+    entries
+        .into_iter()
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let path = PathBuf::from(entry);
+            if !path.is_file() {
+                warn!("Listed path is not a file, skipping: {}", path.display());
+            }
+            path
+        })
+        .collect()
+}
+
+/// Runs `git` with the given `args` in the current directory, returning its output.
+fn git(args: &[&str]) -> Result<String> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        );
+    }
+
+    String::from_utf8(output.stdout).context("git output is not valid UTF-8")
+}
+
+/// Files (relative to the current directory) changed relative to the `base` ref,
+/// including uncommitted changes. Deleted files are left out.
+fn git_changed_files(base: &str) -> Result<Vec<PathBuf>> {
+    let output = git(&[
+        "diff",
+        "--name-only",
+        "-z",
+        "--relative",
+        "--no-renames",
+        "--diff-filter=d",
+        base,
+        "--",
+    ])?;
+
+    Ok(parse_file_list(&output, true))
+}
+
+/// Lines (in the current version of files) added or modified relative to the `base`
+/// ref, per file (relative to the current directory).
+fn git_changed_lines(base: &str) -> Result<HashMap<PathBuf, Lines>> {
+    let output = git(&[
+        "diff",
+        "--unified=0",
+        "--relative",
+        "--no-renames",
+        "--no-color",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        base,
+        "--",
+    ])?;
+
+    Ok(parse_git_diff_hunks(&output))
+}
+
+/// Parses the target line ranges of hunks of a unified diff without context.
+fn parse_git_diff_hunks(diff: &str) -> HashMap<PathBuf, Lines> {
+    let mut changed: HashMap<PathBuf, Vec<_>> = HashMap::new();
+    let mut current = None;
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            current = path.strip_prefix("b/").map(PathBuf::from);
+            if let Some(path) = &current {
+                changed.entry(path.clone()).or_default();
+            }
+        } else if let (Some(path), Some(header)) = (&current, line.strip_prefix("@@ ")) {
+            // Like `@@ -3,2 +4,5 @@`; the count is optional and defaults to 1.
+            let Some(target) = header.split(' ').find_map(|range| range.strip_prefix('+')) else {
+                continue;
+            };
+            let (start, count) = target.split_once(',').unwrap_or((target, "1"));
+            if let (Ok(start), Ok(count)) = (start.parse::<usize>(), count.parse::<usize>()) {
+                if count > 0 {
+                    changed
+                        .get_mut(path)
+                        .expect("inserted on file header")
+                        .push(start..=start + count - 1);
+                }
+            }
+        }
+    }
+
+    changed
+        .into_iter()
+        .map(|(path, ranges)| (path, Lines::new(ranges)))
+        .collect()
+}
+
+/// Decodes `bytes` read from stdin as UTF-8, replacing invalid sequences if running
+/// lossily. Files are decoded by [`SourceFile::contents`] instead.
+fn decode<'a>(global_options: &cli::GlobalOptions, bytes: &'a [u8]) -> io::Result<Cow<'a, str>> {
+    if global_options.binary_mode == cli::BinaryMode::Lossy {
+        return Ok(Encoding::Utf8.decode_lossy(bytes));
+    }
+
+    Encoding::Utf8.decode(bytes).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("input is not valid {:?}", Encoding::Utf8),
+        )
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
-#[allow(clippy::borrowed_box)] // Used throughout, not much of a pain
+#[allow(clippy::too_many_lines)] // Only slightly above.
 fn process_path(
     global_options: &cli::GlobalOptions,
-    standalone_action: StandaloneAction,
-    path: &Path,
-    root: &Path,
-    validator: &Validator,
-    general_scoper: &Box<dyn Scoper>,
+    view_action: ViewAction,
+    file: &SourceFile,
+    processor: &FileProcessor,
+    general_scopers: &[Box<dyn Scoper>],
     language_scopers: &[Box<dyn LanguageScoper>],
     pipeline: Pipeline<'_>,
+    stats: Option<&Stats>,
     search_mode: bool,
+    output: &SyncSender<FileOutput>,
+    pending_renames: &PendingRenames,
 ) -> std::result::Result<(), PathProcessingError> {
-    if !path.is_file() {
-        trace!("Skipping path (not a file): {:?}", path);
-        return Err(PathProcessingError::NotAFile);
-    }
+    let path = file.relative_path();
 
-    let path = diff_paths(path, root).expect("started walk at root, so relative to root works");
-
-    if !validator(&path) {
-        trace!("Skipping path (invalid): {:?}", path);
+    let language_scopers = if global_options.auto_scopers.is_empty() {
+        language_scopers
+    } else if let Some(scopers) = global_options
+        .auto_scopers
+        .for_path(path, &global_options.gitattributes)
+    {
+        scopers
+    } else {
+        trace!("Skipping path (no language detected): {:?}", path);
         return Err(PathProcessingError::InvalidFile);
-    }
+    };
 
     debug!("Processing path: {:?}", path);
 
-    let (new_contents, filesize, changed) = {
-        let mut file = File::open(&path)?;
+    // Only renamed if actually writing, or reporting on what would be written
+    let renamed = renamed_path(&global_options.file_renames, path)
+        .filter(|_| !search_mode || global_options.dry_run);
 
-        let filesize = file.metadata().map_or(0, |m| m.len());
-        let mut source =
-            String::with_capacity(filesize.try_into().unwrap_or(/* no perf gains for you */ 0));
-        file.read_to_string(&mut source)?;
+    let (new_contents, changed) = {
+        let source = file.contents()?;
+        if let Some(stats) = stats {
+            stats.add_scanned(file.size());
+        }
 
         let mut destination = String::with_capacity(source.len());
 
         let changed = apply(
             global_options,
-            standalone_action,
+            view_action,
             &source,
+            Location {
+                file: Some(path),
+                line: 1,
+            },
             &mut destination,
-            general_scoper,
+            general_scopers,
             language_scopers,
             pipeline,
-        )?;
+            stats,
+        );
+
+        // A patch takes the place of the new contents
+        let destination = match changed {
+            Ok(changed) if global_options.format == cli::OutputFormat::Patch => {
+                if changed {
+                    unified_diff(path, &source, &destination)
+                } else {
+                    String::new()
+                }
+            }
+            _ => destination,
+        };
+
+        if let (Some(renamed), Ok(_) | Err(ApplicationError::NoneInScope)) = (&renamed, &changed) {
+            // Renaming is independent of anything being in scope
+            pending_renames
+                .lock()
+                .unwrap()
+                .push((path.to_owned(), renamed.clone()));
+        }
 
-        (destination, filesize, changed)
+        (destination, changed?)
     };
 
-    // Hold the lock so results aren't intertwined
-    let mut stdout = stdout().lock();
+    if let Some(stats) = stats.filter(|_| (changed || renamed.is_some()) && !search_mode) {
+        stats.files_changed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Buffered for the writer thread, so results aren't intertwined
+    let mut buffer = Vec::new();
+    let stdout: &mut dyn Write = &mut buffer;
+
+    let name = global_options.path_style().act(&path.display().to_string());
+    let (name_then_line, name_then_newline) = if global_options.null {
+        ("\0", "\0")
+    } else {
+        (":", "\n")
+    };
 
     if search_mode {
-        if !new_contents.is_empty() {
-            writeln!(
+        if global_options.no_filename {
+            write!(stdout, "{new_contents}")?;
+        } else if global_options.count || global_options.count_matches {
+            write!(stdout, "{name}{name_then_line}{new_contents}")?;
+        } else if global_options.no_heading {
+            for line in new_contents.split_inclusive('\n') {
+                write!(stdout, "{name}{name_then_line}{line}")?;
+            }
+        } else if !new_contents.is_empty() {
+            writeln!(stdout, "{name}{name_then_newline}{new_contents}")?;
+        }
+    } else if global_options.check {
+        if changed {
+            writeln!(stdout, "{name}")?;
+        }
+    } else if global_options.format == cli::OutputFormat::Patch {
+        write!(stdout, "{new_contents}")?;
+    } else if global_options.print {
+        if changed || !global_options.changed_only {
+            print_contents(
+                global_options,
+                &name,
+                name_then_newline,
+                &new_contents,
                 stdout,
-                "{}\n{}",
-                path.display().to_string().magenta(),
-                &new_contents
             )?;
         }
     } else {
-        if filesize > 0 && new_contents.is_empty() {
-            error!(
-                    "Failsafe triggered: file {} is nonempty ({} bytes), but new contents are empty. Will not wipe file.",
-                    path.display(),
-                    filesize
-                );
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "attempt to wipe non-empty file (failsafe guard)",
-            )
-            .into());
-        }
+        write_back(
+            global_options,
+            processor,
+            file,
+            &new_contents,
+            changed,
+            stdout,
+        )?;
 
-        if changed {
-            debug!("Got new file contents, writing to file: {:?}", path);
-            assert!(
-                !global_options.dry_run,
-                // Dry run leverages search mode, so should never get here. Assert for
-                // extra safety.
-                "Dry running, but attempted to write file!"
-            );
-            fs::write(&path, new_contents.as_bytes())?;
+        debug!("Done processing file: {:?}", path);
+    }
+
+    if !buffer.is_empty() {
+        output
+            .send((path.to_owned(), buffer))
+            // The writer only hangs up on failure, most likely a closed pipe
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+    }
+
+    Ok(())
+}
+
+/// A unified diff from `old` to `new` contents of `path`, in the format of `git diff`
+/// (paths prefixed with `a/` and `b/`), as used for `--format patch`.
+fn unified_diff(path: &Path, old: &str, new: &str) -> String {
+    // Patches use forward slashes regardless of platform
+    let path = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .join("/");
+
+    similar::TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
+/// Prints `new_contents` of the file of `name` for `--print`, headed by the name if
+/// requested.
+fn print_contents(
+    global_options: &cli::GlobalOptions,
+    name: &str,
+    name_then_newline: &str,
+    new_contents: &str,
+    stdout: &mut dyn Write,
+) -> io::Result<()> {
+    if global_options.with_filename {
+        write!(stdout, "{name}{name_then_newline}")?;
+    }
+    write!(stdout, "{new_contents}")?;
+    if global_options.with_filename && !new_contents.ends_with('\n') {
+        // Keep the next heading on a line of its own
+        writeln!(stdout)?;
+    }
 
-            // Confirm after successful processing.
+    Ok(())
+}
+
+/// Writes `new_contents` of `file` back via `processor`, in place or mirrored into the
+/// output directory, and confirms on `stdout`.
+fn write_back(
+    global_options: &cli::GlobalOptions,
+    processor: &FileProcessor,
+    file: &SourceFile,
+    new_contents: &str,
+    changed: bool,
+    stdout: &mut dyn Write,
+) -> Result<(), PathProcessingError> {
+    let path = file.relative_path();
+
+    if let Some(output_dir) = &global_options.output_dir {
+        // Mirror all files, not only changed ones, for a complete output tree
+        processor.write_back(file, new_contents)?;
+
+        if changed || !global_options.changed_only {
+            // As given, not as resolved against the root
+            writeln!(stdout, "{}", output_dir.join(path).display())?;
+        }
+    } else if changed || global_options.force_write {
+        debug!("Writing file contents (changed: {changed}): {:?}", path);
+        assert!(
+            !global_options.dry_run,
+            // Dry run leverages search mode, so should never get here. Assert for
+            // extra safety.
+            "Dry running, but attempted to write file!"
+        );
+        processor.write_back(file, new_contents)?;
+
+        // Confirm after successful processing.
+        if changed || !global_options.changed_only {
             writeln!(stdout, "{}", path.display())?;
-        } else {
-            debug!(
-                "Skipping writing file anew (nothing changed): {}",
-                path.display()
-            );
         }
+    } else {
+        debug!(
+            "Skipping writing file anew (nothing changed): {}",
+            path.display()
+        );
+    }
 
-        debug!("Done processing file: {:?}", path);
+    Ok(())
+}
+
+/// Builds the view on `source`, applying all scopers as requested.
+fn build_view<'viewee>(
+    global_options: &cli::GlobalOptions,
+    source: &'viewee str,
+    file: Option<&Path>,
+    general_scopers: &[Box<dyn Scoper>],
+    language_scopers: &[Box<dyn LanguageScoper>],
+) -> ScopedView<'viewee> {
+    debug!("Building view.");
+    let mut builder = ScopedViewBuilder::new(source);
+
+    // General scopers intersect, so if any cannot possibly match, nothing will end up
+    // in scope. Language scoping (parsing!) is expensive, so skip it then; the view
+    // comes out the same.
+    let language_scopers = if general_scopers
+        .iter()
+        .all(|scoper| scoper.maybe_in_scope(source))
+    {
+        language_scopers
+    } else {
+        debug!("Nothing can be in scope, skipping language scopers.");
+        &[]
     };
 
-    Ok(())
+    if global_options.join_language_scopes {
+        // All at once, as a slice: hits a specific, 'joining' `impl`
+        builder.explode(&language_scopers);
+    } else {
+        // One by one: hits a different, 'intersecting' `impl`
+        for scoper in language_scopers {
+            builder.explode(scoper);
+        }
+    }
+
+    for scoper in general_scopers {
+        // One by one, each narrowing down the view further
+        builder.explode(scoper);
+    }
+
+    if !global_options.excluded_scopers.0.is_empty() && !language_scopers.is_empty() {
+        // Queries need to parse the entire input, so cannot be applied per scope
+        builder.subtract(&global_options.excluded_scopers.0.as_slice());
+    }
+
+    if !global_options.lines.is_empty() {
+        // Line numbers refer to the entire input, so cannot be applied per scope
+        let lines = Lines::new(
+            global_options
+                .lines
+                .iter()
+                .flat_map(|lines| lines.ranges().iter().cloned())
+                .collect(),
+        );
+        builder.intersect(&lines);
+    }
+
+    if let (Some(changed_lines), Some(file)) = (&global_options.git_changed_lines, file) {
+        // Files without changed lines (only deletions) have nothing in scope
+        builder.intersect(changed_lines.get(file).unwrap_or(&Lines::new(Vec::new())));
+    }
+
+    if global_options.invert_scope {
+        builder.invert();
+    }
+
+    if !global_options.occurrence.is_empty() {
+        builder.select(&Occurrences::new(
+            global_options
+                .occurrence
+                .iter()
+                .flat_map(|occurrences| occurrences.occurrences().iter().cloned())
+                .collect(),
+        ));
+    }
+
+    if let Some(n) = global_options.max_count {
+        builder.take(n);
+    }
+
+    let view = builder.build();
+    debug!("Done building view: {view:?}");
+
+    view
 }
 
 /// Runs the actual core processing, returning whether anything changed in the output
@@ -682,34 +2289,38 @@ fn process_path(
 ///
 /// TODO: The way this interacts with [`process_path`] etc. is just **awful** spaghetti
 /// of the most imperative, procedural kind. Refactor needed.
-#[allow(clippy::borrowed_box)] // Used throughout, not much of a pain
+#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_arguments)]
 fn apply(
     global_options: &cli::GlobalOptions,
-    standalone_action: StandaloneAction,
+    view_action: ViewAction,
     source: &str,
+    location: Location<'_>,
     // Use a string to avoid repeated and unnecessary bytes -> utf8 conversions and
     // corresponding checks.
     destination: &mut String,
-    general_scoper: &Box<dyn Scoper>,
+    general_scopers: &[Box<dyn Scoper>],
     language_scopers: &[Box<dyn LanguageScoper>],
     pipeline: Pipeline<'_>,
+    stats: Option<&Stats>,
 ) -> std::result::Result<bool, ApplicationError> {
-    debug!("Building view.");
-    let mut builder = ScopedViewBuilder::new(source);
-
-    if global_options.join_language_scopes {
-        // All at once, as a slice: hits a specific, 'joining' `impl`
-        builder.explode(&language_scopers);
-    } else {
-        // One by one: hits a different, 'intersecting' `impl`
-        for scoper in language_scopers {
-            builder.explode(scoper);
-        }
+    let mut view = build_view(
+        global_options,
+        source,
+        location.file,
+        general_scopers,
+        language_scopers,
+    );
+    if let Some(file) = location.file {
+        view.set_file(file.to_owned());
     }
+    view.set_first_line(location.line);
 
-    builder.explode(general_scoper);
-    let mut view = builder.build();
-    debug!("Done building view: {view:?}");
+    if let Some(stats) = stats {
+        stats
+            .matches
+            .fetch_add(count_in_scope(&view, true), Ordering::Relaxed);
+    }
 
     if global_options.fail_none && !view.has_any_in_scope() {
         return Err(ApplicationError::NoneInScope);
@@ -717,61 +2328,287 @@ fn apply(
 
     if global_options.fail_any && view.has_any_in_scope() {
         return Err(ApplicationError::SomeInScope);
-    };
+    }
+
+    if global_options.format == cli::OutputFormat::Sarif {
+        if let Some(stats) = stats {
+            stats
+                .findings
+                .lock()
+                .unwrap()
+                .extend(findings(&view, location));
+        }
+
+        // Reporting is read-only.
+        return Ok(false);
+    }
+
+    if global_options.count || global_options.count_matches {
+        let n = count_in_scope(&view, global_options.count_matches);
+
+        debug!("Counted {n} items in scope, skipping actions.");
+        writeln!(destination, "{n}").expect("writing to `String` is infallible");
+
+        // Counting is read-only.
+        return Ok(false);
+    }
+
+    // Positions refer to the input, so take them before actions change it
+    let positions =
+        (global_options.column || global_options.byte_offset).then(|| line_positions(&view));
 
     debug!("Applying actions to view.");
-    if matches!(standalone_action, StandaloneAction::Squeeze) {
-        view.squeeze();
+    view_action.apply(&mut view);
+
+    // Give each pipeline its own fresh view
+    let mut views = vec![view; pipeline.len()];
+
+    for (i, (actions, view)) in pipeline.iter().zip_eq(&mut views).enumerate() {
+        // Only the last pipeline holds the actions as requested, see `Stats::new`
+        let stats = stats.filter(|_| i + 1 == pipeline.len());
+
+        map_actions(view, actions, stats)?;
+    }
+
+    debug!("Writing to destination.");
+    let line_based =
+        global_options.matching_lines || global_options.line_numbers || global_options.byte_offset;
+    if global_options.only_matching {
+        // Actions as requested are held by the last pipeline, see `Stats::new`
+        let view = views.last().expect("pipeline always has an entry");
+
+        for (i, line) in view.lines().into_iter().enumerate() {
+            for (j, part) in in_scope(&line).iter().enumerate() {
+                let part = part.strip_suffix('\n').unwrap_or(part);
+                if part.is_empty() {
+                    continue;
+                }
+
+                write_line_prefix(
+                    destination,
+                    global_options,
+                    positions.as_deref(),
+                    i,
+                    Some(j),
+                    ':',
+                );
+                destination.push_str(part);
+                destination.push('\n');
+            }
+        }
+    } else if line_based {
+        let line_based_views = views
+            .iter()
+            .map(|v| v.lines().into_iter())
+            .collect_vec()
+            .into_iter()
+            .parallel_zip()
+            .collect_vec();
+
+        let in_context = lines_in_context(
+            &line_based_views
+                .iter()
+                .map(|lines| lines.iter().any(ScopedView::has_any_in_scope))
+                .collect_vec(),
+            global_options.before_context(),
+            global_options.after_context(),
+        );
+        let has_context = global_options.before_context() > 0 || global_options.after_context() > 0;
+
+        let mut last_printed = None;
+        for (i, lines) in line_based_views.into_iter().enumerate() {
+            let is_match = lines.iter().any(ScopedView::has_any_in_scope);
+
+            let (lines, separator) = if !global_options.matching_lines {
+                (lines, ':')
+            } else if is_match {
+                let lines = lines.into_iter().filter(ScopedView::has_any_in_scope);
+                (lines.collect_vec(), ':')
+            } else if in_context[i] {
+                // Context lines are unchanged by actions, so all pipelines agree on
+                // them. Print only once.
+                (lines.into_iter().take(1).collect_vec(), '-')
+            } else {
+                continue;
+            };
+
+            if global_options.matching_lines
+                && has_context
+                && last_printed.is_some_and(|last| last + 1 != i)
+            {
+                // Separate non-adjacent hunks, like grep does.
+                destination.push_str("--\n");
+            }
+            last_printed = Some(i);
+
+            for line in lines {
+                write_line_prefix(
+                    destination,
+                    global_options,
+                    positions.as_deref(),
+                    i,
+                    None,
+                    separator,
+                );
+                destination.push_str(&line.to_string());
+            }
+        }
+    } else {
+        assert_eq!(
+            views.len(),
+            1,
+            // Multiple views are useful for e.g. diffing, which works line-based (see
+            // `dry_run`). When not line-based, they *currently* do not make sense, as
+            // there's neither any code path where there *would* be multiple views at
+            // this point, *nor* a valid use case. Printing multiple views here would
+            // probably wreak havoc.
+            "Multiple views at this stage make no sense."
+        );
+
+        for view in views {
+            destination.push_str(&view.to_string());
+        }
+    }
+    debug!("Done writing to destination.");
+
+    Ok(source != *destination)
+}
+
+/// Maps `view` with all `actions` in order, counting how many matches each changed into
+/// `stats`, if any.
+fn map_actions(
+    view: &mut ScopedView<'_>,
+    actions: &[Box<dyn Action>],
+    stats: Option<&Stats>,
+) -> std::result::Result<(), ActionError> {
+    for (j, action) in actions.iter().enumerate() {
+        let n_changed = stats
+            .and_then(|stats| stats.changed_by_action.get(j))
+            .map(|(_, n)| n);
+        let before = n_changed.map(|_| in_scope(view));
+
+        view.map_with_context(action)?;
+
+        if let (Some(n_changed), Some(before)) = (n_changed, before) {
+            let n = before
+                .into_iter()
+                .zip(in_scope(view))
+                .filter(|(before, after)| before != after)
+                .count();
+            n_changed.fetch_add(n, Ordering::Relaxed);
+        }
+    }
+
+    Ok(())
+}
+
+/// Counts the number of lines containing anything in scope, or if `matches` is set,
+/// the number of individual items in scope.
+fn count_in_scope(view: &ScopedView<'_>, matches: bool) -> usize {
+    if matches {
+        view.scopes()
+            .0
+            .iter()
+            .filter(|s| matches!(s, RWScope(In(..))))
+            .count()
+    } else {
+        view.lines()
+            .into_iter()
+            .filter(ScopedView::has_any_in_scope)
+            .count()
     }
+}
+
+/// Byte offsets of the lines of `view`, each alongside the offsets (relative to the
+/// line) of the parts in scope on it.
+fn line_positions(view: &ScopedView<'_>) -> Vec<(usize, Vec<usize>)> {
+    let mut offset = 0;
+
+    view.lines()
+        .into_iter()
+        .map(|line| {
+            let start = offset;
+            let mut parts = Vec::new();
+
+            for scope in &line.scopes().0 {
+                if let RWScope(In(..)) = scope {
+                    parts.push(offset - start);
+                }
+                offset += <&str>::from(scope).len();
+            }
+
+            (start, parts)
+        })
+        .collect()
+}
+
+/// Writes the prefix of an output line: its line number, the column of the part in
+/// scope on it, and its byte offset, as requested. For a `part`, column and offset are
+/// those of that `part` on the line; otherwise, the column is the one of the first part
+/// in scope on the line, and the offset the one of the line itself.
+fn write_line_prefix(
+    destination: &mut String,
+    global_options: &cli::GlobalOptions,
+    positions: Option<&[(usize, Vec<usize>)]>,
+    line: usize,
+    part: Option<usize>,
+    separator: char,
+) {
+    if global_options.line_numbers {
+        write!(
+            destination,
+            "{}{separator}",
+            global_options
+                .line_number_style()
+                .act(&(line + 1).to_string())
+        )
+        .expect("writing to `String` is infallible");
+    }
+
+    let Some((start, parts)) = positions.and_then(|positions| positions.get(line)) else {
+        return;
+    };
+    let column = part.map_or_else(|| parts.first(), |part| parts.get(part));
 
-    // Give each pipeline its own fresh view
-    let mut views = vec![view; pipeline.len()];
+    if let (true, Some(column)) = (global_options.column, column) {
+        write!(destination, "{}{separator}", column + 1)
+            .expect("writing to `String` is infallible");
+    }
 
-    for (actions, view) in pipeline.iter().zip_eq(&mut views) {
-        for action in *actions {
-            view.map_with_context(action)?;
-        }
+    if global_options.byte_offset {
+        let offset = start + part.and(column).copied().unwrap_or_default();
+        write!(destination, "{offset}{separator}").expect("writing to `String` is infallible");
     }
+}
 
-    debug!("Writing to destination.");
-    let line_based = global_options.only_matching || global_options.line_numbers;
-    if line_based {
-        let line_based_views = views.iter().map(|v| v.lines().into_iter()).collect_vec();
+/// The contents of all items in scope of `view`, in order.
+fn in_scope(view: &ScopedView<'_>) -> Vec<String> {
+    view.scopes()
+        .0
+        .iter()
+        .filter_map(|s| match s {
+            RWScope(In(s, _)) => Some(s.to_string()),
+            RWScope(Out(_)) => None,
+        })
+        .collect()
+}
 
-        for (i, lines) in line_based_views.into_iter().parallel_zip().enumerate() {
-            let i = i + 1;
-            for line in lines {
-                if !global_options.only_matching || line.has_any_in_scope() {
-                    if global_options.line_numbers {
-                        // `ColoredString` needs to be 'evaluated' to do anything; make sure
-                        // to not forget even if this is moved outside of `format!`.
-                        #[allow(clippy::to_string_in_format_args)]
-                        destination.push_str(&format!("{}:", i.to_string().green().to_string()));
-                    }
+/// For each line, given whether it matches, indicates whether it is to be printed, as
+/// it either matches itself or is within `before` or `after` lines of context of a
+/// matching line.
+fn lines_in_context(matches: &[bool], before: usize, after: usize) -> Vec<bool> {
+    let mut in_context = vec![false; matches.len()];
 
-                    destination.push_str(&line.to_string());
-                }
-            }
-        }
-    } else {
-        assert_eq!(
-            views.len(),
-            1,
-            // Multiple views are useful for e.g. diffing, which works line-based (see
-            // `dry_run`). When not line-based, they *currently* do not make sense, as
-            // there's neither any code path where there *would* be multiple views at
-            // this point, *nor* a valid use case. Printing multiple views here would
-            // probably wreak havoc.
-            "Multiple views at this stage make no sense."
-        );
+    for (i, _) in matches.iter().enumerate().filter(|(_, m)| **m) {
+        let start = i.saturating_sub(before);
+        let end = i.saturating_add(after).min(matches.len() - 1);
 
-        for view in views {
-            destination.push_str(&view.to_string());
+        for item in &mut in_context[start..=end] {
+            *item = true;
         }
-    };
-    debug!("Done writing to destination.");
+    }
 
-    Ok(source != *destination)
+    in_context
 }
 
 /// Top-level, user-facing errors, affecting and possibly terminating program execution
@@ -788,12 +2625,24 @@ enum ProgramError {
     NothingProcessed,
     /// Files were found but some input ended up being processed, unexpectedly.
     SomethingProcessed,
+    /// Some files failed to be processed, but processing kept going.
+    FilesFailed(usize),
+    /// Some files would be changed, but only a check was requested.
+    WouldChange(usize),
+    /// More matches than allowed were found (found, maximum).
+    TooManyMatches(usize, usize),
+    /// Fewer matches than required were found (found, minimum).
+    TooFewMatches(usize, usize),
     /// I/O error.
     IoError(io::Error),
     /// Error while processing files for walking.
     IgnoreError(ignore::Error),
     /// The given query failed to parse
     QueryError(TSQueryError),
+    /// None of the queries has a capture of the given name.
+    NoSuchCapture(String),
+    /// No language has a prepared query of the given name.
+    NoSuchQuery(String),
 }
 
 impl fmt::Display for ProgramError {
@@ -804,11 +2653,23 @@ impl fmt::Display for ProgramError {
             Self::NoFilesFound => write!(f, "No files found"),
             Self::NothingProcessed => write!(f, "No input was in scope"),
             Self::SomethingProcessed => write!(f, "Some input was in scope"),
+            Self::FilesFailed(n) => write!(f, "Failed to process {n} file(s)"),
+            Self::WouldChange(n) => write!(f, "{n} file(s) would change"),
+            Self::TooManyMatches(n, max) => {
+                write!(f, "Found {n} match(es), more than the allowed {max}")
+            }
+            Self::TooFewMatches(n, min) => {
+                write!(f, "Found {n} match(es), fewer than the required {min}")
+            }
             Self::IoError(e) => write!(f, "I/O error: {e}"),
             Self::IgnoreError(e) => write!(f, "Error walking files: {e}"),
             Self::QueryError(e) => {
                 write!(f, "Error occurred while creating a tree-sitter query: {e}")
             }
+            Self::NoSuchCapture(name) => write!(f, "No query has a capture named '{name}'"),
+            Self::NoSuchQuery(name) => {
+                write!(f, "No language has a prepared query named '{name}'")
+            }
         }
     }
 }
@@ -837,6 +2698,16 @@ impl From<ignore::Error> for ProgramError {
     }
 }
 
+impl From<WalkError> for ProgramError {
+    fn from(err: WalkError) -> Self {
+        match err {
+            WalkError::Walk(e) => Self::IgnoreError(e),
+            WalkError::Io { path, error } => PathProcessingError::IoError(error, Some(path)).into(),
+            WalkError::Action { error, .. } => ApplicationError::ActionError(error).into(),
+        }
+    }
+}
+
 impl From<TSQueryError> for ProgramError {
     fn from(err: TSQueryError) -> Self {
         Self::QueryError(err)
@@ -879,8 +2750,6 @@ impl Error for ApplicationError {}
 enum PathProcessingError {
     /// I/O error.
     IoError(io::Error, Option<PathBuf>),
-    /// Item was not a file (directory, symlink, ...).
-    NotAFile,
     /// Item is a file but is unsuitable for processing.
     InvalidFile,
     /// Error when applying.
@@ -892,7 +2761,6 @@ impl fmt::Display for PathProcessingError {
         match self {
             Self::IoError(e, None) => write!(f, "I/O error: {e}"),
             Self::IoError(e, Some(path)) => write!(f, "I/O error at path {}: {e}", path.display()),
-            Self::NotAFile => write!(f, "Item is not a file"),
             Self::InvalidFile => write!(f, "Item is not a valid file"),
             Self::ApplicationError(e) => write!(f, "Error applying: {e}"),
         }
@@ -913,46 +2781,117 @@ impl From<ApplicationError> for PathProcessingError {
 
 impl Error for PathProcessingError {}
 
-#[derive(Debug)]
-enum ScoperBuildError {
-    RegexError(RegexError),
-    LiteralError(LiteralError),
-}
-
-impl From<LiteralError> for ScoperBuildError {
-    fn from(e: LiteralError) -> Self {
-        Self::LiteralError(e)
+/// Builds the main scoper from the `scope` and any further patterns (literals,
+/// patterns file), any of which may match.
+fn get_main_scoper(
+    options: &cli::GlobalOptions,
+    scope: Option<String>,
+    extra_patterns: impl Iterator<Item = String>,
+) -> Result<Box<dyn Scoper>> {
+    let mut patterns: Vec<String> = scope.into_iter().collect();
+    patterns.extend(options.literals.iter().cloned());
+    if let Some(path) = &options.patterns_file {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed reading patterns file '{}'", path.display()))?;
+        patterns.extend(parse_patterns(&contents));
     }
-}
+    patterns.extend(extra_patterns);
 
-impl From<RegexError> for ScoperBuildError {
-    fn from(e: RegexError) -> Self {
-        Self::RegexError(e)
+    if let [pattern] = patterns.as_slice() {
+        return get_general_scoper(options, pattern.clone(), options.capture_group.clone());
     }
+
+    Ok(if options.literal_string || patterns.is_empty() {
+        // Without any patterns, this conveniently scopes nothing
+        Box::new(Literals::try_from(patterns).context("Failed building literal strings")?)
+    } else {
+        let alternation = patterns.iter().map(|p| format!("(?:{p})")).join("|");
+        get_general_scoper(options, alternation, options.capture_group.clone())?
+    })
 }
 
-impl fmt::Display for ScoperBuildError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::RegexError(e) => write!(f, "Regex error: {e}"),
-            Self::LiteralError(e) => write!(f, "Literal error: {e}"),
-        }
-    }
+/// Reads the key-value pairs of a replacement mapping file, in a format according to
+/// its extension.
+fn read_replace_map(path: &Path) -> Result<Vec<(String, String)>> {
+    let format = match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("csv") => MappingFormat::Csv,
+        Some(ext) if ext.eq_ignore_ascii_case("tsv") => MappingFormat::Tsv,
+        Some(ext) if ext.eq_ignore_ascii_case("json") => MappingFormat::Json,
+        _ => bail!(
+            "Unknown format of replacement mapping file '{}', expected extension \
+            '.csv', '.tsv' or '.json'",
+            path.display()
+        ),
+    };
+
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed reading replacement mapping '{}'", path.display()))?;
+
+    format
+        .parse(&contents)
+        .with_context(|| format!("Failed parsing replacement mapping '{}'", path.display()))
 }
 
-impl Error for ScoperBuildError {}
+/// Patterns from a patterns file: one per line, skipping empty lines.
+fn parse_patterns(contents: &str) -> impl Iterator<Item = String> + '_ {
+    contents
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(ToOwned::to_owned)
+}
 
-fn get_general_scoper(options: &cli::GlobalOptions, scope: String) -> Result<Box<dyn Scoper>> {
+fn get_general_scoper(
+    options: &cli::GlobalOptions,
+    scope: String,
+    capture_group: Option<CaptureGroup>,
+) -> Result<Box<dyn Scoper>> {
     Ok(if options.literal_string {
         Box::new(Literal::try_from(scope).context("Failed building literal string")?)
     } else {
-        Box::new(Regex::try_from(scope).context("Failed building regex")?)
+        let scope = if options.scope_anchored {
+            format!(r"\A(?:{scope})\z")
+        } else {
+            scope
+        };
+        let regex =
+            Regex::try_from(regex_flags(options) + &scope).context("Failed building regex")?;
+
+        match capture_group {
+            Some(group) => Box::new(
+                regex
+                    .with_capture_group(group.clone())
+                    .with_context(|| format!("Regex has no capture group {group}"))?,
+            ),
+            None => Box::new(regex),
+        }
     })
 }
 
+/// Inline flags group (like `(?ms)`) for regex scopes, as requested via options.
+fn regex_flags(options: &cli::GlobalOptions) -> String {
+    let flags: String = [
+        (options.case_insensitive, 'i'),
+        (options.multiline, 'm'),
+        (options.dot_all, 's'),
+        (options.swap_greed, 'U'),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, flag)| enabled.then_some(flag))
+    .collect();
+
+    if flags.is_empty() {
+        flags
+    } else {
+        format!("(?{flags})")
+    }
+}
+
 fn assemble_common_actions(
     composable_actions: &cli::ComposableActions,
-    standalone_actions: StandaloneAction,
+    titlecase_options: &cli::TitlecaseOptions,
+    standalone_actions: &cli::StandaloneActions,
+    invert: bool,
+    comment_syntax: Option<CommentSyntax>,
 ) -> Result<Vec<Box<dyn Action>>> {
     let mut actions: Vec<Box<dyn Action>> = Vec::new();
 
@@ -963,34 +2902,541 @@ fn assemble_common_actions(
         debug!("Loaded action: Replacement");
     }
 
-    if matches!(standalone_actions, StandaloneAction::Delete) {
+    if standalone_actions.delete {
         actions.push(Box::<Deletion>::default());
         debug!("Loaded action: Deletion");
     }
 
+    if let Some(command) = &standalone_actions.exec {
+        actions.push(Box::new(Exec::from_command_line(command).with_context(
+            || format!("Failed parsing command line for execution: '{command}'"),
+        )?));
+        debug!("Loaded action: Exec");
+    }
+
+    if composable_actions.dedupe == Some(cli::DedupeMode::Lines) {
+        actions.push(Box::<Dedupe>::default());
+        debug!("Loaded action: Dedupe");
+    }
+
+    if let Some(delta) = composable_actions.increment {
+        actions.push(Box::new(Increment::new(delta)));
+        debug!("Loaded action: Increment");
+    }
+
+    let locale = composable_actions.locale.to_action();
+
     if composable_actions.upper {
-        actions.push(Box::<Upper>::default());
+        actions.push(Box::new(Upper::new(
+            composable_actions.uppercase_eszett.to_action(),
+            locale,
+        )));
         debug!("Loaded action: Upper");
     }
 
     if composable_actions.lower {
-        actions.push(Box::<Lower>::default());
+        actions.push(Box::new(Lower::new(locale)));
         debug!("Loaded action: Lower");
     }
 
     if composable_actions.titlecase {
-        actions.push(Box::<Titlecase>::default());
+        actions.push(Box::new(titlecase_options.to_action(locale)));
         debug!("Loaded action: Titlecase");
     }
 
+    actions.extend(assemble_case_conversion_actions(composable_actions));
+
     if composable_actions.normalize {
-        actions.push(Box::<Normalization>::default());
+        actions.push(Box::new(Normalization::new(
+            match composable_actions.normalize_form {
+                cli::NormalizationForm::Nfc => NormalizationForm::Nfc,
+                cli::NormalizationForm::Nfd => NormalizationForm::Nfd,
+                cli::NormalizationForm::Nfkc => NormalizationForm::Nfkc,
+                cli::NormalizationForm::Nfkd => NormalizationForm::Nfkd,
+            },
+            !composable_actions.keep_marks,
+        )));
         debug!("Loaded action: Normalization");
     }
 
+    actions.extend(assemble_trim_actions(composable_actions));
+
+    actions.extend(assemble_encoding_actions(composable_actions, invert));
+
+    if let Some(mode) = composable_actions.redact {
+        actions.push(Box::new(match mode {
+            cli::RedactionMode::Mask => Redact::Mask {
+                with: composable_actions.redact_char,
+            },
+            cli::RedactionMode::Hash => Redact::Hash,
+            cli::RedactionMode::Truncate => Redact::Truncate {
+                keep: composable_actions.redact_keep,
+            },
+        }));
+        debug!("Loaded action: Redact");
+    }
+
+    if composable_actions.comment_out || composable_actions.uncomment {
+        let Some(syntax) = comment_syntax else {
+            bail!("Commenting requires a language scope");
+        };
+
+        if composable_actions.comment_out {
+            actions.push(Box::new(CommentOut::new(syntax)));
+            debug!("Loaded action: CommentOut");
+        } else {
+            actions.push(Box::new(Uncomment::new(syntax)));
+            debug!("Loaded action: Uncomment");
+        }
+    }
+
+    if let Some(template) = &composable_actions.wrap {
+        actions.push(Box::new(Surround::from_template(template).with_context(
+            || {
+                format!(
+                    "Wrap template '{template}' is missing the '{}' placeholder",
+                    Surround::PLACEHOLDER
+                )
+            },
+        )?));
+        debug!("Loaded action: Surround");
+    } else if composable_actions.prefix.is_some() || composable_actions.suffix.is_some() {
+        actions.push(Box::new(Surround::new(
+            composable_actions.prefix.clone().unwrap_or_default(),
+            composable_actions.suffix.clone().unwrap_or_default(),
+        )));
+        debug!("Loaded action: Surround");
+    }
+
     Ok(actions)
 }
 
+/// Assembles the actions converting between identifier cases (`snake_case` etc.).
+fn assemble_case_conversion_actions(
+    composable_actions: &cli::ComposableActions,
+) -> Vec<Box<dyn Action>> {
+    let mut actions: Vec<Box<dyn Action>> = Vec::new();
+
+    if composable_actions.snakecase {
+        actions.push(Box::<Snakecase>::default());
+        debug!("Loaded action: Snakecase");
+    }
+
+    if composable_actions.screaming_snakecase {
+        actions.push(Box::<ScreamingSnakecase>::default());
+        debug!("Loaded action: ScreamingSnakecase");
+    }
+
+    if composable_actions.kebabcase {
+        actions.push(Box::<Kebabcase>::default());
+        debug!("Loaded action: Kebabcase");
+    }
+
+    if composable_actions.camelcase {
+        actions.push(Box::<Camelcase>::default());
+        debug!("Loaded action: Camelcase");
+    }
+
+    if composable_actions.pascalcase {
+        actions.push(Box::<Pascalcase>::default());
+        debug!("Loaded action: Pascalcase");
+    }
+
+    actions
+}
+
+/// Assembles the actions trimming whitespace.
+fn assemble_trim_actions(composable_actions: &cli::ComposableActions) -> Vec<Box<dyn Action>> {
+    let mut actions: Vec<Box<dyn Action>> = Vec::new();
+
+    if composable_actions.trim {
+        actions.push(Box::<Trim>::default());
+        debug!("Loaded action: Trim");
+    }
+
+    if composable_actions.trim_start {
+        actions.push(Box::<TrimStart>::default());
+        debug!("Loaded action: TrimStart");
+    }
+
+    if composable_actions.trim_end {
+        actions.push(Box::<TrimEnd>::default());
+        debug!("Loaded action: TrimEnd");
+    }
+
+    if composable_actions.trim_lines {
+        actions.push(Box::<TrimLines>::default());
+        debug!("Loaded action: TrimLines");
+    }
+
+    actions
+}
+
+/// Assembles the encoding and decoding actions. Encoders and decoders are each other's
+/// inverse, so if `invert` is set, they are simply swapped.
+fn assemble_encoding_actions(
+    composable_actions: &cli::ComposableActions,
+    invert: bool,
+) -> Vec<Box<dyn Action>> {
+    let mut actions: Vec<Box<dyn Action>> = Vec::new();
+
+    let (base64_encode, base64_decode) = swap_if(
+        invert,
+        composable_actions.base64_encode,
+        composable_actions.base64_decode,
+    );
+
+    if base64_encode {
+        actions.push(Box::<Base64Encode>::default());
+        debug!("Loaded action: Base64Encode");
+    }
+
+    if base64_decode {
+        actions.push(Box::<Base64Decode>::default());
+        debug!("Loaded action: Base64Decode");
+    }
+
+    let (url_encode, url_decode) = swap_if(
+        invert,
+        composable_actions.url_encode,
+        composable_actions.url_decode,
+    );
+
+    if url_encode {
+        actions.push(Box::<UrlEncode>::default());
+        debug!("Loaded action: UrlEncode");
+    }
+
+    if url_decode {
+        actions.push(Box::<UrlDecode>::default());
+        debug!("Loaded action: UrlDecode");
+    }
+
+    let (hex_encode, hex_decode) = swap_if(
+        invert,
+        composable_actions.hex_encode,
+        composable_actions.hex_decode,
+    );
+
+    if hex_encode {
+        actions.push(Box::<HexEncode>::default());
+        debug!("Loaded action: HexEncode");
+    }
+
+    if hex_decode {
+        actions.push(Box::<HexDecode>::default());
+        debug!("Loaded action: HexDecode");
+    }
+
+    actions
+}
+
+/// Writes languages and their prepared queries, each with a one-line description.
+fn write_prepared_queries(
+    languages: &[(&str, Vec<PossibleValue>)],
+    format: cli::ListFormat,
+    mut to: impl Write,
+) -> io::Result<()> {
+    let description = |query: &PossibleValue| {
+        query
+            .get_help()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned()
+    };
+
+    match format {
+        cli::ListFormat::Text => {
+            for (language, queries) in languages {
+                writeln!(to, "{language}")?;
+
+                let width = queries.iter().map(|q| q.get_name().len()).max();
+                for query in queries {
+                    let name = query.get_name();
+                    let width = width.unwrap_or_default();
+                    writeln!(to, "  {name:width$}  {}", description(query))?;
+                }
+            }
+        }
+        cli::ListFormat::Json => {
+            let languages = languages
+                .iter()
+                .map(|(language, queries)| LanguageListing {
+                    language,
+                    queries: queries
+                        .iter()
+                        .map(|query| QueryListing {
+                            name: query.get_name(),
+                            description: description(query),
+                        })
+                        .collect(),
+                })
+                .collect_vec();
+
+            serde_json::to_writer(&mut to, &languages)?;
+            writeln!(to)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A language and its prepared queries, as listed with `--list-queries --format json`.
+#[derive(Serialize)]
+struct LanguageListing<'a> {
+    language: &'a str,
+    queries: Vec<QueryListing<'a>>,
+}
+
+/// A prepared query, as listed with `--list-queries --format json`.
+#[derive(Serialize)]
+struct QueryListing<'a> {
+    name: &'a str,
+    description: String,
+}
+
+/// Returns `(a, b)`, or `(b, a)` if `swap` is set.
+const fn swap_if(swap: bool, a: bool, b: bool) -> (bool, bool) {
+    if swap {
+        (b, a)
+    } else {
+        (a, b)
+    }
+}
+
+/// Name of the project-local configuration file, looked up in the current directory
+/// and its ancestors.
+const PROJECT_CONFIG_FILENAME: &str = ".srgn.toml";
+
+/// Named presets, mapping to the command-line arguments each expands to.
+type Presets = HashMap<String, Vec<String>>;
+
+/// Command-line arguments, with each `--preset NAME` replaced by the arguments of the
+/// named preset.
+///
+/// Presets are spliced in place, so options given *after* `--preset` take precedence
+/// over the preset's own. Arguments after `--` are left alone.
+fn expand_presets(args: impl IntoIterator<Item = OsString>) -> Result<Vec<OsString>> {
+    let mut presets: Option<Presets> = None;
+    let mut expanded = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let name = match arg.to_str() {
+            Some("--") => {
+                expanded.push(arg);
+                expanded.extend(args);
+                break;
+            }
+            Some("--preset") => Some(args.next().context("Option '--preset' requires a name")?),
+            Some(other) => other.strip_prefix("--preset=").map(OsString::from),
+            None => None,
+        };
+        let Some(name) = name else {
+            expanded.push(arg);
+            continue;
+        };
+        let name = name.to_string_lossy();
+
+        let presets = match presets {
+            Some(ref presets) => presets,
+            None => presets.insert(load_presets()?),
+        };
+        let preset = get_preset(presets, &name)?;
+        debug!("Expanding preset '{name}' to: {preset:?}");
+
+        expanded.extend(preset.iter().map(OsString::from));
+    }
+
+    Ok(expanded)
+}
+
+/// The arguments of the preset called `name`.
+fn get_preset<'a>(presets: &'a Presets, name: &str) -> Result<&'a [String]> {
+    presets
+        .get(name)
+        .map(Vec::as_slice)
+        .with_context(|| format!("No preset named '{name}' found"))
+}
+
+/// Presets from the user configuration file and the closest project-local one, the
+/// latter taking precedence for presets of the same name.
+fn load_presets() -> Result<Presets> {
+    let mut presets = Presets::new();
+
+    for path in [user_config_path(), project_config_path()]
+        .into_iter()
+        .flatten()
+    {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed reading configuration file '{}'", path.display())
+                })
+            }
+        };
+
+        debug!("Loading presets from '{}'", path.display());
+        presets.extend(
+            parse_presets(&contents).with_context(|| {
+                format!("Failed parsing configuration file '{}'", path.display())
+            })?,
+        );
+    }
+
+    Ok(presets)
+}
+
+/// Location of the user configuration file: `srgn/config.toml` inside
+/// `$XDG_CONFIG_HOME`, falling back to `$HOME/.config`.
+fn user_config_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("srgn").join("config.toml"))
+}
+
+/// Location of the project-local configuration file closest to the current
+/// directory, if any.
+fn project_config_path() -> Option<PathBuf> {
+    env::current_dir()
+        .ok()?
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILENAME))
+        .find(|path| path.is_file())
+}
+
+/// A configuration file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    /// Named presets.
+    #[serde(default)]
+    presets: HashMap<String, Preset>,
+}
+
+/// A single preset of a configuration file.
+#[derive(Debug, Deserialize)]
+struct Preset {
+    /// The scope, as the respective positional argument.
+    scope: Option<String>,
+    /// The replacement, as the respective positional argument.
+    replacement: Option<String>,
+    /// Long options by name, in order of appearance.
+    #[serde(flatten)]
+    options: toml::Table,
+}
+
+/// Presets from the TOML contents of a configuration file.
+///
+/// Presets are tables below `presets`, like `[presets.fix-todos]`. Each key is the name
+/// of a long option: strings and numbers are passed as its value, `true` passes it as
+/// a flag (`false` omits it), and arrays pass it once per element. The special keys
+/// `scope` and `replacement` are passed as the respective positional arguments.
+fn parse_presets(contents: &str) -> Result<Presets> {
+    let config: Config = toml::from_str(contents)?;
+
+    config
+        .presets
+        .into_iter()
+        .map(|(name, preset)| {
+            if preset.replacement.is_some() && preset.scope.is_none() {
+                bail!("The replacement of preset '{name}' requires a scope");
+            }
+
+            let mut args = Vec::new();
+            for (key, value) in preset.options {
+                if key == "preset" {
+                    bail!("Preset '{name}' cannot refer to other presets");
+                }
+
+                let option = format!("--{key}");
+                match value {
+                    toml::Value::Boolean(flag) => {
+                        if flag {
+                            args.push(option);
+                        }
+                    }
+                    toml::Value::Array(elements) => {
+                        for element in elements {
+                            let element = scalar_arg(element).with_context(|| {
+                                format!("Unsupported array element for '{key}' of preset '{name}'")
+                            })?;
+                            args.extend([option.clone(), element]);
+                        }
+                    }
+                    value => {
+                        let value = scalar_arg(value).with_context(|| {
+                            format!("Unsupported value for '{key}' of preset '{name}'")
+                        })?;
+                        args.extend([option, value]);
+                    }
+                }
+            }
+
+            // Positionals up front, so no option can mistake them for its value.
+            let args = preset
+                .scope
+                .into_iter()
+                .chain(preset.replacement)
+                .chain(args)
+                .collect();
+
+            Ok((name, args))
+        })
+        .collect()
+}
+
+/// A string or number value as a single argument.
+fn scalar_arg(value: toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s),
+        toml::Value::Integer(i) => Some(i.to_string()),
+        toml::Value::Float(f) => Some(f.to_string()),
+        _ => None,
+    }
+}
+
+/// Words from a file containing one word per line. Surrounding whitespace and empty
+/// lines are ignored.
+#[cfg(feature = "german")]
+fn read_word_list(path: &Path) -> Result<Vec<String>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed reading word list '{}'", path.display()))?;
+
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Custom symbols from the TOML contents of a symbols map, as `(from, to)` pairs in
+/// order of appearance.
+///
+/// The map is a flat table of strings, like `"<=>" = "⇔"`.
+#[cfg(feature = "symbols")]
+fn parse_symbols_map(contents: &str) -> Result<Vec<(String, String)>> {
+    let table: toml::Table = toml::from_str(contents)?;
+
+    table
+        .into_iter()
+        .map(|(from, to)| match to {
+            _ if from.is_empty() => bail!("Symbols map cannot contain empty keys"),
+            toml::Value::String(to) => Ok((from, to)),
+            toml::Value::Table(_) => bail!("Symbols map must be a flat table of strings"),
+            _ => bail!("Symbol '{from}' must map to a string"),
+        })
+        .collect()
+}
+
 /// To the default log level found in the environment, adds the requested additional
 /// verbosity level, clamped to the maximum available.
 ///
@@ -1015,21 +3461,34 @@ fn level_filter_from_env_and_verbosity(additional_verbosity: u8) -> LevelFilter
 }
 
 mod cli {
+    use std::borrow::Cow;
+    use std::collections::HashMap;
     use std::num::NonZero;
     use std::path::PathBuf;
+    use std::sync::Arc;
     use std::{fs, io};
 
-    use clap::builder::ArgPredicate;
-    use clap::{ArgAction, Command, CommandFactory, Parser};
+    use anyhow::bail;
+    use clap::builder::{ArgPredicate, PossibleValue};
+    use clap::error::ErrorKind;
+    use clap::{ArgAction, ArgGroup, Command, CommandFactory, Parser, ValueEnum};
     use clap_complete::{generate, Generator, Shell};
-    use log::info;
+    use colored::Color;
+    use log::{debug, info};
+    use srgn::actions::{Style, Titlecase};
+    use srgn::find::GitAttributes;
     use srgn::scoping::langs::{
-        c, csharp, go, hcl, python, rust, typescript, LanguageScoper, QuerySource,
+        c, csharp, go, hcl, javascript, python, rust, toml, tsx, typescript, yaml, CaptureFilter,
+        GenericQuery, LanguageScoper, QuerySource,
     };
+    use srgn::scoping::lines::Lines;
+    use srgn::scoping::occurrences::Occurrences;
+    use srgn::scoping::regex::CaptureGroup;
+    use srgn::walker::{Encoding, MmapPolicy};
     use srgn::GLOBAL_SCOPE;
     use tree_sitter::QueryError as TSQueryError;
 
-    use crate::{ProgramError, StandaloneAction};
+    use crate::ProgramError;
 
     /// Main CLI entrypoint.
     ///
@@ -1046,6 +3505,13 @@ mod cli {
         // this very source file which is wrapped at *below* that, so it fits and clap
         // doesn't touch our manually formatted doc strings anymore.
         term_width = 90,
+        args_override_self = true,
+        // Anything explicitly scoping, for actions requiring such a scope.
+        group(
+            ArgGroup::new("scopes")
+                .args(["scope", "literals", "patterns_file", "replace_map"])
+                .multiple(true)
+        ),
     )]
     pub struct Args {
         /// Scope to apply to, as a regular expression pattern.
@@ -1062,9 +3528,12 @@ mod cli {
             value_name = "SCOPE",
             default_value = GLOBAL_SCOPE,
             verbatim_doc_comment,
-            default_value_if("literal_string", ArgPredicate::IsPresent, None)
+            default_value_if("literal_string", ArgPredicate::IsPresent, None),
+            default_value_if("literals", ArgPredicate::IsPresent, None),
+            default_value_if("patterns_file", ArgPredicate::IsPresent, None),
+            default_value_if("replace_map", ArgPredicate::IsPresent, None)
         )]
-        pub(super) scope: String,
+        pub(super) scope: Option<String>,
 
         /// Print shell completions for the given shell.
         #[arg(long = "completions", value_enum, verbatim_doc_comment)]
@@ -1073,6 +3542,59 @@ mod cli {
         // alter `next_help_heading`.
         pub(super) shell: Option<Shell>,
 
+        /// Expand the named preset, as if its arguments were given in its place.
+        ///
+        /// Presets are read from the user configuration file,
+        /// `$XDG_CONFIG_HOME/srgn/config.toml` (defaulting to
+        /// `~/.config/srgn/config.toml`), and from the closest `.srgn.toml` in the
+        /// current directory or its ancestors. Presets of the latter take precedence
+        /// over same-named ones of the former.
+        ///
+        /// Arguments given after this option override those of the preset. Can be
+        /// given multiple times.
+        #[arg(long, value_name = "NAME", verbatim_doc_comment)]
+        // Never actually populated: presets are expanded before parsing, this is for
+        // `--help` only.
+        pub(super) preset: Vec<String>,
+
+        /// Print the full command line the named preset expands to, then exit.
+        ///
+        /// The output is quoted for POSIX shells, for sharing presets or embedding
+        /// them into scripts and CI, where no configuration file is around.
+        #[arg(long, value_name = "NAME", verbatim_doc_comment)]
+        pub(super) expand_preset: Option<String>,
+
+        /// Print all languages and their prepared queries, then exit.
+        #[arg(
+            long,
+            value_name = "FORMAT",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "text",
+            verbatim_doc_comment
+        )]
+        pub(super) list_queries: Option<ListFormat>,
+
+        /// Print the tree-sitter queries of the given language scope, then exit.
+        ///
+        /// Prepared queries are printed in full, for copying and adjusting into a
+        /// custom query.
+        #[arg(long, verbatim_doc_comment)]
+        pub(super) explain_query: bool,
+
+        /// Print the syntax tree of stdin, as parsed for the language of the given
+        /// language scope, then exit.
+        ///
+        /// Helps writing custom queries: the tree is printed as an indented
+        /// S-expression of named nodes, prefixed by their field names, if any. The
+        /// queries of the language scope are irrelevant.
+        #[arg(long, verbatim_doc_comment)]
+        pub(super) dump_ast: bool,
+
+        /// Annotate each node of `--dump-ast` with the range of input bytes it spans.
+        #[arg(long, requires = "dump_ast", verbatim_doc_comment)]
+        pub(super) byte_ranges: bool,
+
         #[command(flatten)]
         pub(super) composable_actions: ComposableActions,
 
@@ -1085,6 +3607,13 @@ mod cli {
         #[command(flatten)]
         pub(super) languages_scopes: LanguageScopes,
 
+        #[command(flatten)]
+        pub(super) titlecase_options: TitlecaseOptions,
+
+        #[cfg(feature = "symbols")]
+        #[command(flatten)]
+        pub(super) symbols_options: SymbolsOptions,
+
         #[cfg(feature = "german")]
         #[command(flatten)]
         pub(super) german_options: GermanOptions,
@@ -1109,12 +3638,72 @@ mod cli {
         ///
         /// If actions are applied, they overwrite files in-place.
         ///
+        /// Can be given multiple times; files matching any of the globs are worked on.
+        ///
         /// For supported glob syntax, see:
         /// <https://docs.rs/glob/0.3.1/glob/struct.Pattern.html>
         ///
         /// Names of processed files are written to stdout.
         #[arg(short('G'), long, verbatim_doc_comment, alias = "files")]
-        pub glob: Option<glob::Pattern>,
+        pub glob: Vec<glob::Pattern>,
+        /// Glob of files to exclude from working on.
+        ///
+        /// Can be given multiple times; files matching any of the globs are skipped.
+        /// Takes precedence over `--glob` and over files found by language scopers.
+        #[arg(long, verbatim_doc_comment)]
+        pub exclude: Vec<glob::Pattern>,
+        /// Glob of directories not to descend into, like `node_modules`.
+        ///
+        /// Matched against both the directory's name and its path relative to the
+        /// current directory. Unlike `--exclude`, pruned directories are not walked at
+        /// all, saving the cost of traversing them. Can be given multiple times.
+        #[arg(long, value_name = "GLOB", verbatim_doc_comment)]
+        pub prune: Vec<glob::Pattern>,
+        /// Descend at most this many directories deep when walking.
+        ///
+        /// Files in the starting directory are at depth 1.
+        #[arg(long, value_name = "N", verbatim_doc_comment)]
+        pub max_depth: Option<usize>,
+        /// Read the list of files to work on from this file, instead of walking the
+        /// file tree. Use `-` to read the list from stdin, for example from `fd` or
+        /// `git ls-files`.
+        ///
+        /// Entries are separated by newlines, or by NUL bytes with `--null-data`.
+        /// Files are processed in the listed order. Listed files not matching
+        /// `--glob` (or not belonging to the language, if scoping by one) or
+        /// matching `--exclude` are skipped.
+        #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+        pub files_from: Option<PathBuf>,
+        /// Entries of `--files-from` are separated by NUL bytes instead of
+        /// newlines, as produced by `find -print0` and `git ls-files -z`.
+        #[arg(short('0'), long, requires = "files_from", verbatim_doc_comment)]
+        pub null_data: bool,
+        /// Work on files changed relative to this git ref, instead of walking the
+        /// file tree.
+        ///
+        /// Defaults to `HEAD`, i.e. uncommitted changes. Use e.g. `--git-diff=main` to
+        /// work on everything changed on a branch, including uncommitted changes. Paths are
+        /// relative to (and restricted to) the current directory. Deleted files are
+        /// left out, untracked ones are not considered. Changed files not matching
+        /// `--glob` (or not belonging to the language, if scoping by one) or
+        /// matching `--exclude` are skipped.
+        #[arg(
+            long,
+            value_name = "BASE",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "HEAD",
+            conflicts_with = "files_from",
+            verbatim_doc_comment
+        )]
+        pub git_diff: Option<String>,
+        /// With `--git-diff`, only consider lines added or modified relative to the
+        /// base to be in scope, such that changes can be applied incrementally.
+        #[arg(long, requires = "git_diff", verbatim_doc_comment)]
+        pub git_diff_lines: bool,
+        /// Changed lines per file, as determined by git; not a command line option.
+        #[arg(skip)]
+        pub git_changed_lines: Option<HashMap<PathBuf, Lines>>,
         /// Fail if working on files (e.g. globbing is requested) but none are found.
         ///
         /// Processing no files is not an error condition in itself, but might be an
@@ -1135,32 +3724,207 @@ mod cli {
         ///
         /// - symbols: '≠' <-> '!=' etc.
         ///
+        /// - quotes: '“' and '”' <-> '"' etc.
+        ///
+        /// - german-swiss: 'ß' -> 'ss', turning Eszett into Swiss orthography
+        ///
+        /// - encodings: encode <-> decode, e.g. '--base64-encode' acts as
+        ///   '--base64-decode'
+        ///
         /// Other actions:
         ///
-        /// - german: inverting e.g. 'Ä' is ambiguous (can be 'Ae' or 'AE')
+        /// - german: inverting e.g. 'Ä' is ambiguous (can be 'Ae' or 'AE'), so only
+        ///   Eszett is inverted, and only with '--german-swiss'
         ///
         /// - upper, lower, deletion, squeeze: inversion is impossible as information is
         ///   lost
         ///
         /// These may still be passed, but will be ignored for inversion and applied
         /// normally.
-        #[cfg(feature = "symbols")]
-        #[arg(short, long, env, requires = "symbols", verbatim_doc_comment)]
+        #[arg(short, long, env, requires = "invertible", verbatim_doc_comment)]
         pub invert: bool,
         /// Do not interpret the scope as a regex. Instead, interpret it as a literal
         /// string. Will require a scope to be passed.
         #[arg(short('L'), long, env, verbatim_doc_comment)]
         pub literal_string: bool,
+        /// Literal string to scope, in addition to any others.
+        ///
+        /// Can be given multiple times: anything matching any literal, including the
+        /// main scope if given, is in scope. Implies `--literal-string`. Scales to
+        /// large numbers of literals.
+        #[arg(
+            short('F'),
+            long("literal"),
+            value_name = "LITERAL",
+            verbatim_doc_comment
+        )]
+        pub literals: Vec<String>,
+        /// Read additional scopes from this file, one per line.
+        ///
+        /// Like `grep -f`: anything matching any of the patterns, including the main
+        /// scope if given, is in scope. Patterns are regular expressions, or literal
+        /// strings with `--literal-string`. Empty lines are ignored; an empty file
+        /// leaves nothing in scope. Helpful for large block lists or dictionaries.
+        #[arg(short('f'), long, value_name = "FILE", verbatim_doc_comment)]
+        pub patterns_file: Option<PathBuf>,
+        /// Only scope the given capture group of the main scope's matches, not the
+        /// entire match.
+        ///
+        /// Takes a group number or name. For example, with a scope of
+        /// `(\w+) = "(\w+)"`, a value of `2` scopes only the value of assignments, not
+        /// the key. Matches the group does not participate in are out of scope. All
+        /// capture groups remain available as variables for replacements.
+        #[arg(
+            long,
+            value_name = "N|NAME",
+            conflicts_with_all = ["literal_string", "literals"],
+            verbatim_doc_comment
+        )]
+        pub capture_group: Option<CaptureGroup>,
+        /// Only keep the captures of the given name of language scope queries in scope.
+        ///
+        /// Queries can capture multiple parts under different names, as in
+        /// `(pair key: (_) @key value: (_) @value)`. By default, all captures are in
+        /// scope. Can be given multiple times. Queries without any of the given
+        /// captures are unaffected.
+        ///
+        /// Regardless of this option, captures are available as variables in
+        /// replacements, like named regex capture groups: `${key}` is what the `key`
+        /// capture matched.
+        #[arg(long, value_name = "NAME", verbatim_doc_comment)]
+        pub capture: Vec<String>,
+        /// Take parts hit by the given query out of scope.
+        ///
+        /// The query is written for the language of the language scope, which is
+        /// required. This allows scoping something, but not inside something else,
+        /// much like ignoring captures named `_SRGN_IGNORE` in custom queries. For
+        /// example, `--python comments --not-inside '(class_definition) @class'`
+        /// scopes all comments outside of classes. Can be given multiple times, then
+        /// taking out parts hit by any query.
+        #[arg(long, value_name = TREE_SITTER_QUERY_VALUE, verbatim_doc_comment)]
+        pub not_inside: Vec<String>,
+        /// Compiled `--not-inside` queries; not a command line option.
+        #[arg(skip)]
+        pub excluded_scopers: crate::ExcludedScopers,
+        /// Compiled `--auto` queries; not a command line option.
+        #[arg(skip)]
+        pub auto_scopers: crate::AutoScopers,
+        /// Attributes from `.gitattributes`; not a command line option.
+        #[arg(skip)]
+        pub gitattributes: Arc<GitAttributes>,
+        /// Match regex scopes case-insensitively, like the inline `(?i)` flag.
+        #[arg(long, conflicts_with_all = ["literal_string", "literals"], verbatim_doc_comment)]
+        pub case_insensitive: bool,
+        /// Let `^` and `$` in regex scopes match at the start and end of lines, not
+        /// just of the entire input, like the inline `(?m)` flag.
+        #[arg(long, conflicts_with_all = ["literal_string", "literals"], verbatim_doc_comment)]
+        pub multiline: bool,
+        /// Let `.` in regex scopes match newlines as well, like the inline `(?s)` flag.
+        #[arg(long, conflicts_with_all = ["literal_string", "literals"], verbatim_doc_comment)]
+        pub dot_all: bool,
+        /// Swap the meaning of greedy and lazy quantifiers in regex scopes (`a*` becomes
+        /// lazy, `a*?` greedy), like the inline `(?U)` flag.
+        #[arg(long, conflicts_with_all = ["literal_string", "literals"], verbatim_doc_comment)]
+        pub swap_greed: bool,
+        /// Require regex scopes to match an in-scope part in its entirety, as if
+        /// anchored at both ends.
+        ///
+        /// For example, with a language scope for function names, a scope of `get`
+        /// then only matches functions named exactly `get`, not `get_value` or
+        /// `forget`. Parts only partially matched are out of scope. Without a language
+        /// scope, the entire input has to match.
+        #[arg(long, conflicts_with_all = ["literal_string", "literals"], verbatim_doc_comment)]
+        pub scope_anchored: bool,
+        /// Additional scope to narrow down the view with, after the main scope.
+        ///
+        /// Can be given multiple times. Each is applied in turn, only within what
+        /// remained in scope previously, so all of them have to match for something to
+        /// be in scope. Interpreted like the main scope (regex, or literal string with
+        /// `--literal-string`).
+        #[arg(long, value_name = "SCOPE", verbatim_doc_comment)]
+        pub scope_and: Vec<String>,
+        /// Only consider the given lines to be in scope.
+        ///
+        /// Takes comma-separated, inclusive ranges of (1-based) line numbers, such as
+        /// `10..50`, `20..` (to the end), `..5` (from the start) or `7`. Can be given
+        /// multiple times; a line in any range is in scope. Applied after all other
+        /// scopes, narrowing them down to the given lines.
+        #[arg(long, value_name = "RANGES", verbatim_doc_comment)]
+        pub lines: Vec<Lines>,
+        /// Invert the final scope: actions apply to everything *not* in scope.
+        ///
+        /// Inversion happens last, after all scopes (language, main and additional
+        /// scopes) have been applied. For example, this allows acting on everything
+        /// outside of strings.
+        #[arg(long("not"), visible_alias = "invert-scope", verbatim_doc_comment)]
+        pub invert_scope: bool,
+        /// Consider only the first N matches in scope per file (or standard input),
+        /// after all other scoping.
+        ///
+        /// Later matches are out of scope: they are neither printed in search mode
+        /// nor acted upon. For example, `--max-count 1` only replaces the first
+        /// occurrence.
+        #[arg(long, short('m'), value_name = "N", verbatim_doc_comment)]
+        pub max_count: Option<usize>,
+        /// Only consider the given occurrences of what is in scope, per file (or
+        /// standard input), after all other scoping.
+        ///
+        /// Takes comma-separated, inclusive ranges of (1-based) occurrences, such as
+        /// `1`, `2..4`, `3..` (to the end) or `last`. Can be given multiple times; an
+        /// occurrence selected by any of them is in scope. For example,
+        /// `--occurrence 1` only acts on the first occurrence, `--occurrence last`
+        /// only on the last one.
+        #[arg(long, value_name = "OCCURRENCES", verbatim_doc_comment)]
+        pub occurrence: Vec<Occurrences>,
         /// If anything at all is found to be in scope, fail.
         ///
         /// The default is to continue processing normally.
         #[arg(long, verbatim_doc_comment)]
         pub fail_any: bool,
+        /// Report everything in scope, and fail afterwards if there was anything.
+        ///
+        /// Unlike `--fail-any`, which stops at the first input with anything in
+        /// scope, all input is processed first, printing all offending lines (as with
+        /// `--highlight`). Nothing is written back.
+        #[arg(long, conflicts_with = "fail_any", verbatim_doc_comment)]
+        pub fail_any_report: bool,
         /// If nothing is found to be in scope, fail.
         ///
         /// The default is to return the input unchanged (without failure).
         #[arg(long, verbatim_doc_comment)]
         pub fail_none: bool,
+        /// Fail if more than this many matches are found in total, across all
+        /// input.
+        ///
+        /// Unlike `--fail-any`, all input is processed before deciding. For example,
+        /// `--fail-if-more-than 0` fails if anything at all is found, and a higher
+        /// value allows for some leeway, like a maximum number of 'TODO' comments.
+        #[arg(long, value_name = "N", verbatim_doc_comment)]
+        pub fail_if_more_than: Option<usize>,
+        /// Fail if fewer than this many matches are found in total, across all
+        /// input.
+        ///
+        /// Unlike `--fail-none`, all input is processed before deciding. For
+        /// example, `--fail-if-fewer-than 1` fails if nothing at all is found.
+        #[arg(long, value_name = "N", verbatim_doc_comment)]
+        pub fail_if_fewer_than: Option<usize>,
+        /// Exit like grep: with `0` if anything was in scope anywhere, `1` if
+        /// nothing was, and `2` on errors.
+        ///
+        /// Nothing being in scope is not reported as an error, making this suitable
+        /// for shell conditionals. Output is unaffected.
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "fail_any",
+                "fail_any_report",
+                "fail_none",
+                "fail_if_more_than",
+                "fail_if_fewer_than"
+            ],
+            verbatim_doc_comment
+        )]
+        pub grep_exit_codes: bool,
         /// Join (logical 'OR') multiple language scopes, instead of intersecting them.
         ///
         /// The default when multiple language scopes are given is to intersect their
@@ -1174,6 +3938,48 @@ mod cli {
         /// non-language scopers (regex pattern etc.), which always intersect.
         #[arg(short('j'), long, verbatim_doc_comment)]
         pub join_language_scopes: bool,
+        /// Highlight anything in scope, printing matching lines only, with line
+        /// numbers.
+        ///
+        /// This is the search mode otherwise entered only when a language scope is
+        /// given without any actions. Use it to search for regular expressions only,
+        /// like grep. Actions given are applied before highlighting, but never
+        /// written back to files.
+        #[arg(long, conflicts_with = "print", verbatim_doc_comment)]
+        pub highlight: bool,
+        /// When to color output.
+        ///
+        /// In `auto` mode, colors are used when writing to a terminal, unless the
+        /// `NO_COLOR` environment variable is set.
+        #[arg(
+            long,
+            value_name = "WHEN",
+            value_enum,
+            default_value_t = ColorMode::Auto,
+            verbatim_doc_comment
+        )]
+        pub color: ColorMode,
+        /// Style of anything in scope in search mode [default: red,bold].
+        ///
+        /// A comma-separated list, like `black,on-yellow,italic`: a color sets the
+        /// foreground, one prefixed by `on-` the background. Other items are styles
+        /// such as `bold`, `dimmed`, `italic` or `underline`. `none` styles nothing.
+        ///
+        /// Styles can also be set in the `SRGN_COLORS` environment variable, as
+        /// `;`-separated `KIND=STYLE` pairs, like
+        /// `match=blue,bold;path=cyan;line-number=yellow`. Options take precedence.
+        #[arg(long, value_name = "STYLE", verbatim_doc_comment)]
+        pub color_match: Option<Style>,
+        /// Style of file paths in output [default: magenta].
+        ///
+        /// See `--color-match` for the format.
+        #[arg(long, value_name = "STYLE", verbatim_doc_comment)]
+        pub color_path: Option<Style>,
+        /// Style of line numbers in output [default: green].
+        ///
+        /// See `--color-match` for the format.
+        #[arg(long, value_name = "STYLE", verbatim_doc_comment)]
+        pub color_line_number: Option<Style>,
         /// Prepend line numbers to output.
         #[arg(long, hide(true), verbatim_doc_comment)]
         // Hidden: internal use. Not really useful to expose.
@@ -1181,21 +3987,337 @@ mod cli {
         /// Print only matching lines.
         #[arg(long, hide(true), verbatim_doc_comment)]
         // Hidden: internal use. Not really useful to expose.
+        pub matching_lines: bool,
+        /// Print only the parts in scope, each on a line of its own.
+        ///
+        /// Useful for extracting items like versions, email addresses or imports.
+        /// Parts spanning multiple lines are split at line breaks. When working on
+        /// files, implies search mode: files are never written to.
+        #[arg(short('o'), long, verbatim_doc_comment)]
         pub only_matching: bool,
+        /// Print the (1-based) column of the first part in scope on each line, after
+        /// the line number.
+        ///
+        /// Columns count bytes. With `--only-matching`, each part has its own column.
+        /// Implies `--line-numbers`. When working on files, implies search mode.
+        #[arg(long, verbatim_doc_comment)]
+        pub column: bool,
+        /// Print the (0-based) byte offset of each line within its input, after the
+        /// line number and column, if any.
+        ///
+        /// With `--only-matching`, the offset of each part itself is printed instead.
+        /// Offsets refer to the input, before any actions. When working on files,
+        /// implies search mode.
+        #[arg(short('b'), long, verbatim_doc_comment)]
+        pub byte_offset: bool,
+        /// Print this many lines of trailing context after each matching line.
+        ///
+        /// Only has an effect in search mode. Non-adjacent groups of lines are separated
+        /// by a line containing `--`.
+        #[arg(short('A'), long, value_name = "NUM", verbatim_doc_comment)]
+        pub after_context: Option<usize>,
+        /// Print this many lines of leading context before each matching line.
+        ///
+        /// Only has an effect in search mode. Non-adjacent groups of lines are separated
+        /// by a line containing `--`.
+        #[arg(short('B'), long, value_name = "NUM", verbatim_doc_comment)]
+        pub before_context: Option<usize>,
+        /// Print this many lines of context before and after each matching line.
+        ///
+        /// Shorthand for setting both `--before-context` and `--after-context`, which
+        /// take precedence if given.
+        #[arg(short('C'), long, value_name = "NUM", verbatim_doc_comment)]
+        pub context: Option<usize>,
+        /// Print only the number of matching lines, instead of the lines themselves.
+        ///
+        /// When working on files, counts are printed per file, prefixed by the file
+        /// path. Files without matches are not listed. Nothing is modified: any actions
+        /// are ignored.
+        #[arg(
+            short('c'),
+            long,
+            verbatim_doc_comment,
+            conflicts_with = "count_matches"
+        )]
+        pub count: bool,
+        /// Print only the number of matches, instead of the matching lines.
+        ///
+        /// Same as `--count`, but multiple matches on a single line are counted
+        /// individually.
+        #[arg(long, verbatim_doc_comment)]
+        pub count_matches: bool,
+        /// Format to write results in.
+        ///
+        /// `sarif` writes a single SARIF 2.1.0 report of everything in scope, with the
+        /// file, region and text of each match, suitable for GitHub code scanning and
+        /// other CI annotation tools. The rule of all results is named after the
+        /// language queries and scope.
+        ///
+        /// `patch` writes a unified diff of all changes instead of applying them,
+        /// ready for `git apply` or `patch -p1`.
+        ///
+        /// Files are never modified in either case.
+        #[arg(
+            long,
+            value_enum,
+            value_name = "FORMAT",
+            default_value_t = OutputFormat::Text,
+            conflicts_with_all = [
+                "count",
+                "count_matches",
+                "print",
+                "output_dir",
+                "dry_run",
+                "rename_files",
+                "force_write"
+            ],
+            verbatim_doc_comment
+        )]
+        pub format: OutputFormat,
         /// Do not ignore hidden files and directories.
         #[arg(short('H'), long, verbatim_doc_comment)]
         pub hidden: bool,
         /// Do not ignore `.gitignore`d files and directories.
         #[arg(long, verbatim_doc_comment)]
         pub gitignored: bool,
-        /// Process files in lexicographically sorted order, by file path.
+        /// Descend into symlinked directories when walking.
+        ///
+        /// Symlinks to files are processed regardless, see `--symlinks`.
+        #[arg(long, verbatim_doc_comment)]
+        pub follow: bool,
+        /// What to do with symlinks to files.
+        #[arg(
+            long,
+            value_enum,
+            value_name = "POLICY",
+            default_value_t = SymlinkPolicy::Target,
+            verbatim_doc_comment
+        )]
+        pub symlinks: SymlinkPolicy,
+        /// Do not respect any ignore files: `.gitignore`, `.ignore`, `.srgnignore`,
+        /// and those given via `--ignore-file`.
+        ///
+        /// Hidden files are still skipped, see `--hidden`.
+        #[arg(long, verbatim_doc_comment)]
+        pub no_ignore: bool,
+        /// Do not respect version control ignore files: `.gitignore`, as well as
+        /// git's global excludes and `.git/info/exclude`.
+        ///
+        /// Other ignore files such as `.ignore` and `.srgnignore` are still respected.
+        #[arg(long, verbatim_doc_comment)]
+        pub no_ignore_vcs: bool,
+        /// Do not respect `.gitattributes` when detecting languages.
+        ///
+        /// By default, language scopes honor the GitHub Linguist attributes in the
+        /// `.gitattributes` file of the current directory: files marked
+        /// `linguist-generated` or `linguist-vendored` are skipped, and
+        /// `linguist-language` overrides the language detected from file extensions
+        /// and shebang lines.
+        #[arg(long, verbatim_doc_comment)]
+        pub no_gitattributes: bool,
+        /// Additional ignore file, in `.gitignore` format, whose patterns are
+        /// respected when walking files.
         ///
-        /// In search mode, this emits results in sorted order. Otherwise, it processes
-        /// files in sorted order.
+        /// Can be given multiple times. These have lower precedence than ignore
+        /// files found in the walked directories. Files named `.srgnignore` are
+        /// always respected, like `.ignore` files.
+        #[arg(long, value_name = "PATH", verbatim_doc_comment)]
+        pub ignore_file: Vec<PathBuf>,
+        /// Emit results in lexicographically sorted order, by file path.
         ///
-        /// Sorted processing disables parallel processing.
+        /// Files are still processed in parallel, but their results are held back and
+        /// only emitted once all files are done, in stable order. This makes output
+        /// reproducible, for example for diffing in CI.
         #[arg(long, verbatim_doc_comment)]
         pub sorted: bool,
+        /// Keep a backup of files before modifying them in place.
+        ///
+        /// The backup is written next to the original, with the same name plus the
+        /// given suffix appended (for example, `.bak`). Files which are not modified do
+        /// not get a backup.
+        #[arg(
+            long,
+            value_name = "SUFFIX",
+            value_parser = clap::builder::NonEmptyStringValueParser::new(),
+            verbatim_doc_comment
+        )]
+        pub backup: Option<String>,
+        /// Write processed files below this directory, instead of overwriting them.
+        ///
+        /// The directory structure is mirrored: a file at `docs/a.md` (relative to the
+        /// current directory) is written to `DIR/docs/a.md`. All processed files are
+        /// written, including ones without changes, such that the output is complete.
+        /// Files below the output directory itself are not processed.
+        #[arg(
+            long,
+            value_name = "DIR",
+            conflicts_with = "backup",
+            verbatim_doc_comment
+        )]
+        pub output_dir: Option<PathBuf>,
+        /// Rename processed files whose name matches the regex PATTERN, replacing
+        /// matches with REPLACEMENT.
+        ///
+        /// Only file names are renamed, not directories: `--rename-files foo baz`
+        /// renames `foo/foo_test.py` to `foo/baz_test.py`. Capture groups are
+        /// available in the replacement as `$1` etc. Files are renamed after all
+        /// contents are processed, and never onto existing files. With '--dry-run',
+        /// renames are only reported. Can be given multiple times, renaming in order.
+        #[arg(
+            long,
+            num_args = 2,
+            value_names = ["PATTERN", "REPLACEMENT"],
+            conflicts_with_all = ["print", "output_dir"],
+            verbatim_doc_comment
+        )]
+        pub rename_files: Vec<String>,
+        /// Compiled `--rename-files` patterns; not a command line option.
+        #[arg(skip)]
+        pub file_renames: Vec<(fancy_regex::Regex, String)>,
+        /// Print the contents of processed files to stdout, instead of overwriting
+        /// them.
+        ///
+        /// Allows previewing results, or using srgn as a filter in pipelines when
+        /// working on files. Contents of multiple files are printed one after
+        /// another; see `--with-filename` to tell them apart.
+        #[arg(
+            long,
+            conflicts_with_all = ["output_dir", "count", "count_matches"],
+            verbatim_doc_comment
+        )]
+        pub print: bool,
+        /// Only report files whose contents changed.
+        ///
+        /// Files written to '--output-dir' are all still written, but only changed
+        /// ones are listed; with '--print', only changed files are printed. Files
+        /// overwritten in place are only listed if changed, unless '--force-write' is
+        /// given. The output can then serve as a manifest of changed files for
+        /// downstream steps.
+        #[arg(long, verbatim_doc_comment)]
+        pub changed_only: bool,
+        /// Write files back in place even if their contents did not change.
+        ///
+        /// By default, unchanged files are not touched, keeping their modification
+        /// times intact such that build tools and file watchers are not triggered.
+        #[arg(
+            long,
+            conflicts_with_all = ["print", "output_dir", "dry_run"],
+            verbatim_doc_comment
+        )]
+        pub force_write: bool,
+        /// Only check whether any files would change, without modifying them.
+        ///
+        /// Files which would change are listed, and the exit code is nonzero if there
+        /// are any, like `cargo fmt --check`. This allows enforcing conventions in CI.
+        #[arg(
+            long,
+            conflicts_with_all = ["print", "output_dir", "dry_run", "force_write", "rename_files", "format"],
+            verbatim_doc_comment
+        )]
+        pub check: bool,
+        /// Keep the access and modification times of files written in place.
+        ///
+        /// Permissions (like the executable bit) and, where permitted, ownership are
+        /// always kept. Times are not by default, as build tools going by them would
+        /// otherwise miss the changes.
+        #[arg(
+            long,
+            conflicts_with_all = ["print", "output_dir", "dry_run"],
+            verbatim_doc_comment
+        )]
+        pub preserve_times: bool,
+        /// With `--print`, print the name of each file on a line of its own before
+        /// its contents.
+        ///
+        /// In search mode, file names are always printed, unless `--no-filename`.
+        #[arg(long, overrides_with = "no_filename", verbatim_doc_comment)]
+        pub with_filename: bool,
+        /// In search mode, do not print file names.
+        #[arg(long, overrides_with = "with_filename", verbatim_doc_comment)]
+        pub no_filename: bool,
+        /// In search mode, print file names as a heading above their matches.
+        ///
+        /// This is the default.
+        #[arg(long, overrides_with = "no_heading", verbatim_doc_comment)]
+        pub heading: bool,
+        /// In search mode, print file names at the start of each matching line
+        /// instead of as a heading, like `grep`.
+        #[arg(long, overrides_with = "heading", verbatim_doc_comment)]
+        pub no_heading: bool,
+        /// Follow file names in output by a NUL byte, instead of a newline or `:`.
+        ///
+        /// For consumption by other programs, as file names might contain any of
+        /// these otherwise.
+        #[arg(long, verbatim_doc_comment)]
+        pub null: bool,
+        /// Skip files larger than this size, in bytes.
+        ///
+        /// The suffixes `K`, `M` and `G` (powers of 1024) are supported, for example
+        /// `10M`.
+        #[arg(long, value_name = "BYTES", value_parser = parse_filesize, verbatim_doc_comment)]
+        pub max_filesize: Option<u64>,
+        /// Encoding of files to work on.
+        ///
+        /// Files are transcoded to UTF-8 for processing, and back to their encoding
+        /// when written. With `auto`, files starting with a UTF-16 byte order mark are
+        /// read as UTF-16, valid UTF-8 as UTF-8, and anything else as Latin-1.
+        #[arg(long, value_enum, default_value_t = InputEncoding::Utf8, verbatim_doc_comment)]
+        pub encoding: InputEncoding,
+        /// How to handle files which are not valid in their encoding (usually: invalid
+        /// UTF-8).
+        #[arg(long, value_enum, default_value_t = BinaryMode::Skip, verbatim_doc_comment)]
+        pub binary_mode: BinaryMode,
+        /// When to memory-map files instead of reading them into memory.
+        ///
+        /// Mapping saves copying file contents around, reducing peak memory usage.
+        /// It has setup costs though, so by default (`auto`) only large files are
+        /// mapped. Files written back in place are never mapped.
+        #[arg(
+            long,
+            value_enum,
+            value_name = "WHEN",
+            default_value_t = MmapMode::Auto,
+            verbatim_doc_comment
+        )]
+        pub mmap: MmapMode,
+        /// Keep going when processing a file fails, instead of aborting.
+        ///
+        /// At the end, a summary of how many files were processed successfully,
+        /// skipped and failed is printed to stderr. The exit code is nonzero if any
+        /// file failed.
+        #[arg(long, verbatim_doc_comment)]
+        pub keep_going: bool,
+        /// Print a summary of the run to stderr when done.
+        ///
+        /// The summary holds the number of files scanned and changed, the total
+        /// number of matches, how many of those each action changed, as well as the
+        /// elapsed time and throughput.
+        #[arg(long, verbatim_doc_comment)]
+        pub stats: bool,
+        /// Show progress on stderr while processing multiple files.
+        ///
+        /// Displays the number of files discovered, processed and changed, matches
+        /// found so far, the elapsed time and, if the number of files is known upfront
+        /// (`--files-from`, `--git-diff`), an estimate of the time remaining. On a
+        /// terminal, the progress line is updated in place.
+        #[arg(long, verbatim_doc_comment)]
+        pub progress: bool,
+        /// Do not skip binary files.
+        ///
+        /// By default, files containing NUL bytes in their beginning are considered
+        /// binary and skipped. Files which are not valid UTF-8 are always skipped.
+        #[arg(long, verbatim_doc_comment)]
+        pub binary: bool,
+        /// Process stdin line by line, instead of reading it in full first.
+        ///
+        /// Memory usage stays constant regardless of input size, making this suitable
+        /// for very large inputs such as log files. As a consequence, scopes cannot
+        /// span multiple lines, and output is produced as input arrives.
+        ///
+        /// Only has an effect when reading from stdin without language scopes, and
+        /// outside of search mode. Otherwise, input is read in full as usual.
+        #[arg(long, verbatim_doc_comment)]
+        pub stream: bool,
         /// Override detection heuristics for stdin readability, and force to value.
         ///
         /// `true` will always attempt to read from stdin. `false` will never read from
@@ -1206,9 +4328,23 @@ mod cli {
         /// Number of threads to run processing on, when working with files.
         ///
         /// If not specified, will default to available parallelism. Set to 1 for
-        /// sequential, deterministic (but not sorted) output.
+        /// sequential, deterministic (but not sorted) output; see `--sorted` for
+        /// sorted output.
         #[arg(long, verbatim_doc_comment)]
         pub threads: Option<NonZero<usize>>,
+        /// Benchmark processing of stdin instead of writing output, running all
+        /// stages this many times and reporting per-stage timings.
+        #[arg(
+            long,
+            hide(true),
+            value_name = "N",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "100",
+            verbatim_doc_comment
+        )]
+        // Hidden: for troubleshooting slow runs, not regular use.
+        pub self_bench: Option<NonZero<u32>>,
         /// Increase log verbosity level.
         ///
         /// The base log level to use is read from the `RUST_LOG` environment variable
@@ -1221,11 +4357,30 @@ mod cli {
             verbatim_doc_comment
         )]
         pub additional_verbosity: u8,
+        /// Format to write log messages in.
+        ///
+        /// With 'json', each message is a single line holding a JSON object, with
+        /// fields 'timestamp', 'level', 'target', 'message', 'file', 'line', and
+        /// 'elapsed' (microseconds since startup), for consumption by log
+        /// aggregation tools.
+        #[arg(
+            long,
+            value_enum,
+            value_name = "FORMAT",
+            default_value_t = LogFormat::Text,
+            verbatim_doc_comment
+        )]
+        pub log_format: LogFormat,
     }
 
     #[derive(Parser, Debug)]
     #[group(required = false, multiple = true)]
-    #[command(next_help_heading = "Composable Actions")]
+    #[command(
+        next_help_heading = "Composable Actions",
+        group(ArgGroup::new("invertible").multiple(true)),
+        group(ArgGroup::new("uppercasing").multiple(true)),
+        group(ArgGroup::new("casing").multiple(true))
+    )]
     #[allow(clippy::struct_excessive_bools)]
     pub struct ComposableActions {
         /// Replace anything in scope with this value.
@@ -1235,24 +4390,293 @@ mod cli {
         /// replacement value using `$1` etc. for numbered, `$NAME` etc. for named
         /// capture groups.
         ///
+        /// Built-in variables are available as well (capture groups of the same name
+        /// take precedence):
+        ///
+        /// - `$match`, `$match_upper`, `$match_lower`: the content in scope (as-is,
+        ///   uppercased, lowercased)
+        ///
+        /// - `$line`: the line number the content in scope starts on
+        ///
+        /// - `$file`: the file being processed (unavailable for stdin), and its
+        ///   parts `$file_name`, `$file_stem`, `$file_ext` and `$file_dir`
+        ///
+        /// - `$uuid`: a random UUID (v4), fresh for each replacement
+        ///
+        /// - `$date`: the current date; pass a format as in `${date:%d.%m.%Y}`, see
+        ///   <https://docs.rs/chrono/latest/chrono/format/strftime/index.html>
+        ///
         /// This action is specially treated as a positional argument for ergonomics and
         /// compatibility with `tr`.
         ///
         /// If given, will run before any other action.
         #[arg(value_name = "REPLACEMENT", env, verbatim_doc_comment)]
         pub replace: Option<String>,
+        /// Squeeze consecutive occurrences of scope into one.
+        ///
+        /// Happens before any other action (including the replacement), which then
+        /// only see the first of each run of occurrences.
+        #[arg(
+            short,
+            long,
+            visible_alias("squeeze-repeats"),
+            env,
+            requires = "scopes",
+            verbatim_doc_comment
+        )]
+        pub squeeze: bool,
+        /// Remove duplicates, keeping only the first occurrence of each.
+        ///
+        /// With `lines` (the default), removes duplicate lines within each part in
+        /// scope. As the default scope does not match across lines, add `--dot-all`
+        /// to deduplicate the lines of entire files.
+        ///
+        /// With `matches`, removes all parts in scope whose content appeared in scope
+        /// before, across the entire file.
+        ///
+        /// Unlike `--squeeze`, duplicates need not be adjacent.
+        #[arg(
+            long,
+            value_name = "MODE",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "lines",
+            conflicts_with = "squeeze",
+            verbatim_doc_comment
+        )]
+        pub dedupe: Option<DedupeMode>,
+        /// Replace anything in scope according to a mapping file, for bulk renames.
+        ///
+        /// The file holds pairs of keys and values, in a format according to its
+        /// extension:
+        ///
+        /// - `.csv`: `key,value` per line, no header; fields may be quoted with `"`
+        /// - `.tsv`: `key<TAB>value` per line, no header
+        /// - `.json`: an object of strings, like `{"key": "value"}`
+        ///
+        /// All keys are added to the scope, like with `--patterns-file`: they are
+        /// regular expressions, or literal strings with `--literal-string`. Each part
+        /// in scope is replaced by the value of the (first) key matching it entirely,
+        /// or left as is if none does. Values are used verbatim. If given, will run
+        /// before any other action.
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with = "replace",
+            verbatim_doc_comment
+        )]
+        pub replace_map: Option<PathBuf>,
+        /// Add this number to anything in scope that is an integer.
+        ///
+        /// Negative values decrement. Anything in scope that is not an integer is left
+        /// unchanged, so a scope like `\d+` is recommended. Leading zeros are
+        /// preserved (`007` -> `008`).
+        #[arg(
+            long,
+            env,
+            value_name = "DELTA",
+            allow_negative_numbers = true,
+            verbatim_doc_comment
+        )]
+        pub increment: Option<i64>,
         /// Uppercase anything in scope.
-        #[arg(short, long, env, verbatim_doc_comment)]
+        #[arg(
+            short,
+            long,
+            env,
+            group = "uppercasing",
+            group = "casing",
+            verbatim_doc_comment
+        )]
         pub upper: bool,
+        /// How to uppercase 'ß', both for '--upper' and when '--german' produces
+        /// uppercase words, like 'SCHLIESSEN'.
+        #[arg(
+            long,
+            env,
+            value_name = "STYLE",
+            default_value = "capital",
+            requires = "uppercasing",
+            verbatim_doc_comment
+        )]
+        pub uppercase_eszett: UppercaseEszett,
         /// Lowercase anything in scope.
-        #[arg(short, long, env, verbatim_doc_comment)]
+        #[arg(short, long, env, group = "casing", verbatim_doc_comment)]
         pub lower: bool,
         /// Titlecase anything in scope.
-        #[arg(short, long, env, verbatim_doc_comment)]
+        ///
+        /// See the titlecase options for how words are cased.
+        #[arg(
+            short,
+            long,
+            env,
+            group = "casing",
+            verbatim_doc_comment,
+            default_value_if("titlecase-opts", ArgPredicate::IsPresent, "true")
+        )]
         pub titlecase: bool,
+        /// Locale to apply '--upper', '--lower' and '--titlecase' in.
+        ///
+        /// Some languages tailor casing: in Turkish and Azerbaijani, 'i' uppercases
+        /// to 'İ' and 'I' lowercases to 'ı'.
+        #[arg(
+            long,
+            value_name = "LOCALE",
+            default_value = "root",
+            requires = "casing",
+            verbatim_doc_comment
+        )]
+        pub locale: Locale,
+        /// Convert anything in scope to `snake_case`.
+        ///
+        /// Words are split at non-alphanumeric characters and at changes in case
+        /// (`fooBar`, `HTTPServer`). Leading and trailing non-alphanumeric characters
+        /// are kept, such that `_private` stays private. Same for the other case
+        /// conversions.
+        #[arg(long, env, verbatim_doc_comment, alias = "snake-case")]
+        pub snakecase: bool,
+        /// Convert anything in scope to `SCREAMING_SNAKE_CASE`.
+        #[arg(long, env, verbatim_doc_comment, alias = "screaming-snake-case")]
+        pub screaming_snakecase: bool,
+        /// Convert anything in scope to `kebab-case`.
+        #[arg(long, env, verbatim_doc_comment, alias = "kebab-case")]
+        pub kebabcase: bool,
+        /// Convert anything in scope to `camelCase`.
+        #[arg(long, env, verbatim_doc_comment, alias = "camel-case")]
+        pub camelcase: bool,
+        /// Convert anything in scope to `PascalCase`.
+        #[arg(long, env, verbatim_doc_comment, alias = "pascal-case")]
+        pub pascalcase: bool,
         /// Normalize (Normalization Form D) anything in scope, and throw away marks.
+        ///
+        /// See `--normalize-form` and `--keep-marks` for genuine Unicode normalization
+        /// instead of this de-accenting.
         #[arg(short, long, env, verbatim_doc_comment)]
         pub normalize: bool,
+        /// Unicode normalization form to use for `--normalize`.
+        #[arg(
+            long,
+            env,
+            value_enum,
+            value_name = "FORM",
+            default_value_t = NormalizationForm::Nfd,
+            requires = "normalize",
+            verbatim_doc_comment
+        )]
+        pub normalize_form: NormalizationForm,
+        /// Keep marks (like accents) when using `--normalize`.
+        ///
+        /// Marks only remain separate from the characters they belong to with the
+        /// decomposing forms (`nfd`, `nfkd`); only then can they be thrown away.
+        #[arg(long, env, requires = "normalize", verbatim_doc_comment)]
+        pub keep_marks: bool,
+        /// Remove leading and trailing whitespace from anything in scope.
+        ///
+        /// Note this applies to each match as a whole: with a scope spanning multiple
+        /// lines, only the very first and last lines are affected. See
+        /// `--trim-lines` for trimming each line.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub trim: bool,
+        /// Remove leading whitespace from anything in scope.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub trim_start: bool,
+        /// Remove trailing whitespace from anything in scope.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub trim_end: bool,
+        /// Remove leading and trailing whitespace from each line of anything in scope.
+        ///
+        /// Line endings are kept.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub trim_lines: bool,
+        /// Comment out anything in scope, using the comment syntax of the language
+        /// scope.
+        ///
+        /// Parts in scope on a single line are wrapped in a block comment if the
+        /// language has them, like `/* ... */`. Otherwise, each line is prefixed with
+        /// a line comment, like `# `, after its indentation.
+        ///
+        /// As the default scope does not match across lines, parts spanning multiple
+        /// lines are only treated as a whole with `--dot-all`.
+        #[arg(long, env, conflicts_with = "uncomment", verbatim_doc_comment)]
+        pub comment_out: bool,
+        /// Uncomment anything in scope, using the comment syntax of the language
+        /// scope.
+        ///
+        /// Undoes `--comment-out`: block comments wrapping a part in scope are
+        /// unwrapped, line comments at the start of its lines are removed.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub uncomment: bool,
+        /// Insert this value before anything in scope.
+        ///
+        /// Runs after all other actions, so the value itself is not transformed by
+        /// them. Same for `--suffix` and `--wrap`.
+        #[arg(long, env, value_name = "PREFIX", verbatim_doc_comment)]
+        pub prefix: Option<String>,
+        /// Insert this value after anything in scope.
+        #[arg(long, env, value_name = "SUFFIX", verbatim_doc_comment)]
+        pub suffix: Option<String>,
+        /// Wrap anything in scope using a template, with `...` standing in for it.
+        ///
+        /// For example, `--wrap '<...>'` turns `https://example.com` into
+        /// `<https://example.com>`. Shorthand for `--prefix` and `--suffix`.
+        #[arg(
+            long,
+            env,
+            value_name = "TEMPLATE",
+            conflicts_with_all = ["prefix", "suffix"],
+            verbatim_doc_comment
+        )]
+        pub wrap: Option<String>,
+        /// Encode anything in scope as Base64 (standard alphabet, with padding).
+        #[arg(long, env, group = "invertible", verbatim_doc_comment)]
+        pub base64_encode: bool,
+        /// Decode anything in scope from Base64.
+        ///
+        /// Anything which is not valid Base64, or does not decode to valid UTF-8, is
+        /// left unchanged. Same for the other decoders.
+        #[arg(long, env, group = "invertible", verbatim_doc_comment)]
+        pub base64_decode: bool,
+        /// Percent-encode anything in scope, for use in URLs.
+        ///
+        /// All but unreserved characters (letters, digits, '-', '.', '_', '~') are
+        /// encoded.
+        #[arg(long, env, group = "invertible", verbatim_doc_comment)]
+        pub url_encode: bool,
+        /// Decode percent-encoded anything in scope.
+        #[arg(long, env, group = "invertible", verbatim_doc_comment)]
+        pub url_decode: bool,
+        /// Encode anything in scope as hexadecimal, two lowercase digits per byte.
+        #[arg(long, env, group = "invertible", verbatim_doc_comment)]
+        pub hex_encode: bool,
+        /// Decode anything in scope from hexadecimal.
+        #[arg(long, env, group = "invertible", verbatim_doc_comment)]
+        pub hex_decode: bool,
+        /// Redact anything in scope, for example to anonymize secrets before sharing
+        /// logs.
+        ///
+        /// Runs after all other actions but `--prefix`, `--suffix` and `--wrap`.
+        #[arg(long, env, value_enum, value_name = "MODE", verbatim_doc_comment)]
+        pub redact: Option<RedactionMode>,
+        /// Character to mask with, for `--redact mask`.
+        #[arg(
+            long,
+            env,
+            value_name = "CHAR",
+            default_value_t = '*',
+            requires = "redact",
+            verbatim_doc_comment
+        )]
+        pub redact_char: char,
+        /// Number of leading characters to keep, for `--redact truncate`.
+        #[arg(
+            long,
+            env,
+            value_name = "N",
+            default_value_t = 4,
+            requires = "redact",
+            verbatim_doc_comment
+        )]
+        pub redact_keep: usize,
         /// Perform substitutions on German words, such as 'Abenteuergruesse' to
         /// 'Abenteuergrüße', for anything in scope.
         ///
@@ -1269,6 +4693,7 @@ mod cli {
             short,
             long,
             verbatim_doc_comment,
+            group = "uppercasing",
             // `true` as string is very ugly, but there's no other way?
             default_value_if("german-opts", ArgPredicate::IsPresent, "true")
         )]
@@ -1276,10 +4701,278 @@ mod cli {
         /// Perform substitutions on symbols, such as '!=' to '≠', '->' to '→', on
         /// anything in scope.
         ///
-        /// Helps translate 'ASCII art' into native Unicode representations.
+        /// Helps translate 'ASCII art' into native Unicode representations. With
+        /// '--invert', only symbols of the selected categories (see the symbols
+        /// options) are turned back into ASCII.
         #[cfg(feature = "symbols")]
-        #[arg(short = 'S', long, verbatim_doc_comment)]
+        #[arg(
+            short = 'S',
+            long,
+            group = "invertible",
+            verbatim_doc_comment,
+            default_value_if("symbols-opts", ArgPredicate::IsPresent, "true")
+        )]
         pub symbols: bool,
+        /// Replace straight quotes with typographic ones, in the given style, on
+        /// anything in scope. Three dots become an ellipsis.
+        ///
+        /// For example, `"it's"` becomes `“it’s”` in the (default) English style,
+        /// `„it’s“` in German and `«it’s»` in French.
+        #[cfg(feature = "symbols")]
+        #[arg(
+            long,
+            value_name = "STYLE",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "english",
+            group = "invertible",
+            verbatim_doc_comment
+        )]
+        pub quotes: Option<QuoteStyle>,
+    }
+
+    /// Typographic style of quotes, see [`srgn::actions::QuoteStyle`].
+    #[cfg(feature = "symbols")]
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub enum QuoteStyle {
+        /// “Double” and ‘single’ quotes.
+        English,
+        /// „Double“ and ‚single‘ quotes.
+        German,
+        /// «Double» and ‹single› quotes.
+        French,
+    }
+
+    /// How to uppercase Eszett, see [`srgn::actions::UppercaseEszett`].
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub enum UppercaseEszett {
+        /// 'ẞ', official since 2017.
+        Capital,
+        /// 'SS', as before 2017.
+        Ss,
+        /// Leave 'ß' alone.
+        Keep,
+    }
+
+    impl UppercaseEszett {
+        /// The library equivalent of this policy.
+        pub(super) const fn to_action(self) -> srgn::actions::UppercaseEszett {
+            match self {
+                Self::Capital => srgn::actions::UppercaseEszett::Capital,
+                Self::Ss => srgn::actions::UppercaseEszett::Ss,
+                Self::Keep => srgn::actions::UppercaseEszett::Keep,
+            }
+        }
+    }
+
+    impl TitlecaseOptions {
+        /// The titlecasing action configured by these options.
+        pub(super) fn to_action(&self, locale: srgn::actions::Locale) -> Titlecase {
+            let mut titlecase = Titlecase::new(locale);
+            if let Some(style) = self.titlecase_style {
+                titlecase.style(style.to_action());
+            }
+            if !self.titlecase_stop_word.is_empty() {
+                titlecase.stop_words(self.titlecase_stop_word.iter().cloned());
+            }
+            titlecase.force_case(self.titlecase_keep.iter().cloned());
+
+            titlecase
+        }
+    }
+
+    /// Style for titlecasing, see [`srgn::actions::TitlecaseStyle`].
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub enum TitlecaseStyle {
+        /// John Gruber's style: short articles, conjunctions and prepositions are
+        /// lowercase.
+        Gruber,
+        /// Associated Press: prepositions of four letters or more are capitalized.
+        Ap,
+        /// Chicago Manual of Style: all prepositions are lowercase.
+        Chicago,
+    }
+
+    impl TitlecaseStyle {
+        /// The library equivalent of this style.
+        pub(super) const fn to_action(self) -> srgn::actions::TitlecaseStyle {
+            match self {
+                Self::Gruber => srgn::actions::TitlecaseStyle::Gruber,
+                Self::Ap => srgn::actions::TitlecaseStyle::Ap,
+                Self::Chicago => srgn::actions::TitlecaseStyle::Chicago,
+            }
+        }
+    }
+
+    /// Locale for casing, see [`srgn::actions::Locale`].
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub enum Locale {
+        /// Unicode's default casing.
+        Root,
+        /// Turkish.
+        Tr,
+        /// Azerbaijani.
+        Az,
+    }
+
+    impl Locale {
+        /// The library equivalent of this locale.
+        pub(super) const fn to_action(self) -> srgn::actions::Locale {
+            match self {
+                Self::Root => srgn::actions::Locale::Root,
+                Self::Tr => srgn::actions::Locale::Turkish,
+                Self::Az => srgn::actions::Locale::Azerbaijani,
+            }
+        }
+    }
+
+    /// What to deduplicate.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum DedupeMode {
+        /// Lines within each part in scope.
+        Lines,
+        /// Entire parts in scope, across the input.
+        Matches,
+    }
+
+    /// Output format of listings.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum ListFormat {
+        /// Human-readable, aligned columns.
+        Text,
+        /// JSON, for consumption by other programs.
+        Json,
+    }
+
+    /// When to color output.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum ColorMode {
+        /// Color when writing to a terminal, and `NO_COLOR` is not set.
+        Auto,
+        /// Always color.
+        Always,
+        /// Never color.
+        Never,
+    }
+
+    /// Encoding of input files, see [`srgn::walker::Encoding`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum InputEncoding {
+        /// UTF-8.
+        Utf8,
+        /// ISO-8859-1.
+        Latin1,
+        /// UTF-16, little endian.
+        Utf16le,
+        /// UTF-16, big endian.
+        Utf16be,
+        /// Detect from file contents.
+        Auto,
+    }
+
+    impl InputEncoding {
+        /// The encoding to use for files, or [`None`] to detect it per file.
+        pub const fn encoding(self) -> Option<Encoding> {
+            match self {
+                Self::Utf8 => Some(Encoding::Utf8),
+                Self::Latin1 => Some(Encoding::Latin1),
+                Self::Utf16le => Some(Encoding::Utf16Le),
+                Self::Utf16be => Some(Encoding::Utf16Be),
+                Self::Auto => None,
+            }
+        }
+    }
+
+    /// How to handle invalid input data.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum BinaryMode {
+        /// Treat as an error. Aborts when working on files in place.
+        Strict,
+        /// Replace invalid sequences with '�' (U+FFFD). Files written back will contain
+        /// those replacements instead of the original bytes!
+        Lossy,
+        /// Skip files with a warning. For stdin, same as `strict`.
+        Skip,
+    }
+
+    /// Format to write results in.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum OutputFormat {
+        /// Plain text, as shaped by the other output options.
+        Text,
+        /// A SARIF report of everything in scope.
+        Sarif,
+        /// A unified diff of all changes.
+        Patch,
+    }
+
+    /// Format to write log messages in.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum LogFormat {
+        /// Human-readable lines.
+        Text,
+        /// One JSON object per line.
+        Json,
+    }
+
+    /// How to handle symlinks to files.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum SymlinkPolicy {
+        /// Process the file linked to, writing changes through to it. The link
+        /// itself stays intact.
+        Target,
+        /// Process the file linked to, but write changes to a regular file replacing
+        /// the link. The file linked to stays intact.
+        Replace,
+        /// Do not process symlinks.
+        Skip,
+    }
+
+    /// When to memory-map files, see [`srgn::walker::MmapPolicy`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+    pub enum MmapMode {
+        /// Map files large enough for it to pay off.
+        Auto,
+        /// Always map files.
+        Always,
+        /// Never map files, always read them.
+        Never,
+    }
+
+    impl MmapMode {
+        /// The policy for the file processor.
+        pub const fn policy(self) -> MmapPolicy {
+            match self {
+                Self::Auto => MmapPolicy::Auto,
+                Self::Always => MmapPolicy::Always,
+                Self::Never => MmapPolicy::Never,
+            }
+        }
+    }
+
+    /// Unicode normalization form, see [`srgn::actions::NormalizationForm`].
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub enum NormalizationForm {
+        /// Canonical composition.
+        Nfc,
+        /// Canonical decomposition.
+        Nfd,
+        /// Compatibility composition.
+        Nfkc,
+        /// Compatibility decomposition.
+        Nfkd,
+    }
+
+    /// How to redact, see [`srgn::actions::Redact`].
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub enum RedactionMode {
+        /// Replace each character with `--redact-char`.
+        Mask,
+        /// Replace with the SHA-256 hash (hexadecimal). Equal inputs give equal outputs,
+        /// so values can still be correlated.
+        Hash,
+        /// Keep only the first `--redact-keep` characters, followed by '…'.
+        Truncate,
     }
 
     #[derive(Parser, Debug)]
@@ -1294,33 +4987,39 @@ mod cli {
         #[arg(
             short,
             long,
-            requires = "scope",
+            requires = "scopes",
             conflicts_with = stringify!(ComposableActions),
             verbatim_doc_comment
         )]
         pub delete: bool,
-        /// Squeeze consecutive occurrences of scope into one.
+        /// Delete entire lines with anything in scope, including their line endings.
+        ///
+        /// Cannot be used with any other action, like `--delete`.
+        #[arg(
+            long,
+            requires = "scopes",
+            conflicts_with = stringify!(ComposableActions),
+            verbatim_doc_comment
+        )]
+        pub delete_lines: bool,
+        /// Pipe anything in scope through this external command, replacing it with
+        /// the command's output.
+        ///
+        /// The command runs once per match, receiving it on its stdin. For example,
+        /// `--exec 'jq -c .'` compacts JSON. Quoting works as in POSIX shells, but no
+        /// shell is invoked: for pipes etc., run one explicitly, as in
+        /// `--exec "sh -c 'sort | uniq'"`.
+        ///
+        /// If the output ends in a newline but the match did not, that newline is
+        /// dropped. The command exiting unsuccessfully is an error.
         #[arg(
-            short,
             long,
-            visible_alias("squeeze-repeats"),
             env,
-            requires = "scope",
+            value_name = "COMMAND",
+            conflicts_with = stringify!(ComposableActions),
             verbatim_doc_comment
         )]
-        pub squeeze: bool,
-    }
-
-    impl From<StandaloneActions> for StandaloneAction {
-        fn from(value: StandaloneActions) -> Self {
-            if value.delete {
-                Self::Delete
-            } else if value.squeeze {
-                Self::Squeeze
-            } else {
-                Self::None
-            }
-        }
+        pub exec: Option<String>,
     }
 
     /// For use as <https://docs.rs/clap/latest/clap/struct.Arg.html#method.value_name>
@@ -1333,6 +5032,28 @@ mod cli {
             #[group(required = false, multiple = false)]
             #[command(next_help_heading = "Language scopes")]
             pub struct LanguageScopes {
+                /// Scope code using the prepared query of the given name, detecting the
+                /// language per file.
+                ///
+                /// Files are matched to languages like for the individual language
+                /// options: by extension or, lacking one, by the interpreter in their
+                /// shebang line. Languages without a prepared query of the given name are
+                /// left out. For example, `--auto comments` scopes comments across a
+                /// repository of Python, Rust and Go code alike. Can be given multiple
+                /// times. Requires files to work on, not stdin.
+                ///
+                /// The generic names `comments`, `strings`, `imports` and `function-names`
+                /// resolve to the closest prepared query of each language, such as
+                /// `uses` for Rust imports. Other names must match a language's prepared
+                /// query exactly.
+                #[arg(
+                    long,
+                    env,
+                    value_name = "QUERY",
+                    conflicts_with_all = [$(stringify!($lang_scope),)+ "capture", "not_inside"],
+                    verbatim_doc_comment
+                )]
+                auto: Vec<String>,
                 $(
                     #[command(flatten)]
                     $lang_flag: Option<$lang_scope>,
@@ -1341,20 +5062,129 @@ mod cli {
 
             impl LanguageScopes {
                 /// Finds the first language field set, if any, and compiles the `QuerySourceOrPath`'s into a list of `LanguageScoper`'s.
-                pub(super) fn compile_query_sources_to_scopes(self) -> Result<Option<crate::ScoperList>, ProgramError> {
+                ///
+                /// Alongside, compiles the `not_inside` queries for the same language.
+                pub(super) fn compile_query_sources_to_scopes(self, captures: &[String], not_inside: &[String]) -> Result<Option<(crate::ScoperList, crate::ScoperList)>, ProgramError> {
                     assert_exclusive_lang_scope(&[
                         $(self.$lang_flag.is_some(),)+
                     ]);
 
                     $(
                         if let Some(s) = self.$lang_flag {
-                            let s = accumulate_scopes::<$lang_flag::CompiledQuery, _>(s.$lang_flag, s.$lang_query_flag, s.$lang_query_file_flag,)?;
-                            return Ok(Some(s));
+                            let s = accumulate_scopes::<$lang_flag::CompiledQuery, _>(s.$lang_flag, s.$lang_query_flag, s.$lang_query_file_flag, captures)?;
+                            let not_inside = not_inside.iter().cloned().map(QueryLiteral::from).collect();
+                            let excluded = accumulate_scopes::<$lang_flag::CompiledQuery, $lang_flag::PreparedQuery>(Vec::new(), not_inside, Vec::new(), &[])?;
+                            return Ok(Some((s, excluded)));
+                        }
+                    )+
+
+                    Ok(None)
+                }
+
+                /// Compiles the prepared queries named via `--auto` for all languages
+                /// offering all of them, one list of scopers per language.
+                pub(super) fn compile_auto_scopes(&self) -> Result<Option<crate::AutoScopers>, ProgramError> {
+                    if self.auto.is_empty() {
+                        return Ok(None);
+                    }
+
+                    let mut scopers = Vec::new();
+                    let mut found = vec![false; self.auto.len()];
+                    $(
+                        let queries = self.auto.iter().map(|name| match <GenericQuery as ValueEnum>::from_str(name, false) {
+                            Ok(generic) => $lang_flag::PreparedQuery::from_generic(generic).ok_or(()),
+                            Err(_) => <$lang_flag::PreparedQuery as ValueEnum>::from_str(name, false).map_err(drop),
+                        }).collect::<Vec<_>>();
+                        for (found, query) in found.iter_mut().zip(&queries) {
+                            *found |= query.is_ok();
+                        }
+
+                        if let Ok(queries) = queries.into_iter().collect::<Result<Vec<_>, _>>() {
+                            scopers.push(accumulate_scopes::<$lang_flag::CompiledQuery, _>(queries, Vec::new(), Vec::new(), &[])?);
+                        } else {
+                            debug!("Language {} lacks some of the queries, leaving it out", stringify!($lang_flag));
+                        }
+                    )+
+
+                    if let Some((name, _)) = self.auto.iter().zip(found).find(|(_, found)| !found) {
+                        return Err(ProgramError::NoSuchQuery(name.clone()));
+                    }
+
+                    Ok(Some(crate::AutoScopers(Arc::new(scopers))))
+                }
+
+                /// Short labels of the queries of the language scope given (or of
+                /// `--auto`), such as `python/comments`.
+                pub(super) fn query_labels(&self) -> Vec<String> {
+                    let mut labels = self.auto.iter().map(|name| format!("auto/{name}")).collect::<Vec<_>>();
+
+                    $(
+                        if let Some(s) = &self.$lang_flag {
+                            let lang = stringify!($lang_flag);
+                            for query in &s.$lang_flag {
+                                let name = query.to_possible_value().map(|v| v.get_name().to_owned()).unwrap_or_default();
+                                labels.push(format!("{lang}/{name}"));
+                            }
+                            for _ in 0..s.$lang_query_flag.len() + s.$lang_query_file_flag.len() {
+                                labels.push(format!("{lang}/query"));
+                            }
+                        }
+                    )+
+
+                    labels
+                }
+
+                /// Sources of all queries of the language scope given, if any, each labeled
+                /// with the option it originates from.
+                pub(super) fn query_sources(&self) -> io::Result<Option<Vec<(String, String)>>> {
+                    $(
+                        if let Some(s) = &self.$lang_flag {
+                            let flag = |name: &str| format!("--{}", name.replace('_', "-"));
+                            let mut sources = Vec::new();
+
+                            for query in &s.$lang_flag {
+                                let name = query.to_possible_value().map(|v| v.get_name().to_owned()).unwrap_or_default();
+                                sources.push((format!("{} {name}", flag(stringify!($lang_flag))), dedent_query(query.as_str())));
+                            }
+                            for query in &s.$lang_query_flag {
+                                sources.push((flag(stringify!($lang_query_flag)), query.0.clone()));
+                            }
+                            for path in &s.$lang_query_file_flag {
+                                sources.push((format!("{} {}", flag(stringify!($lang_query_file_flag)), path.display()), fs::read_to_string(path)?));
+                            }
+
+                            return Ok(Some(sources));
                         }
                     )+
 
                     Ok(None)
                 }
+
+                /// The tree-sitter language of the language scope given, if any.
+                pub(super) fn lang(&self) -> Option<tree_sitter::Language> {
+                    $(
+                        if self.$lang_flag.is_some() {
+                            return Some(<$lang_flag::CompiledQuery as LanguageScoper>::lang());
+                        }
+                    )+
+
+                    None
+                }
+
+                /// Names of all languages, alongside their prepared queries.
+                pub(super) fn prepared_queries() -> Vec<(&'static str, Vec<PossibleValue>)> {
+                    vec![
+                        $(
+                            (
+                                stringify!($lang_flag),
+                                $lang_flag::PreparedQuery::value_variants()
+                                    .iter()
+                                    .filter_map(ValueEnum::to_possible_value)
+                                    .collect(),
+                            ),
+                        )+
+                    ]
+                }
             }
         };
     }
@@ -1364,14 +5194,23 @@ mod cli {
         (csharp, csharp_query, csharp_query_file, CSharpScope),
         (go, go_query, go_query_file, GoScope),
         (hcl, hcl_query, hcl_query_file, HclScope),
+        (
+            javascript,
+            javascript_query,
+            javascript_query_file,
+            JavaScriptScope
+        ),
         (python, python_query, python_query_file, PythonScope),
         (rust, rust_query, rust_query_file, RustScope),
+        (toml, toml_query, toml_query_file, TomlScope),
+        (tsx, tsx_query, tsx_query_file, TsxScope),
         (
             typescript,
             typescript_query,
             typescript_query_file,
             TypeScriptScope
         ),
+        (yaml, yaml_query, yaml_query_file, YamlScope),
     );
 
     /// Assert that either zero or one lang field is set.
@@ -1383,45 +5222,111 @@ mod cli {
         if set_fields_count > 1 {
             let mut cmd = Args::command();
             cmd.error(
-                clap::error::ErrorKind::ArgumentConflict,
+                ErrorKind::ArgumentConflict,
                 "Can only use one language at a time.",
             )
             .exit();
         }
     }
 
+    /// A prepared query's source, with its leading indentation and surrounding blank
+    /// lines removed.
+    fn dedent_query(query: &str) -> String {
+        let query = query.trim_matches('\n').trim_start();
+        let mut lines = query.lines();
+        let first = lines.next().unwrap_or_default();
+        let rest: Vec<&str> = lines.map(str::trim_end).collect();
+        let indent = rest
+            .iter()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or_default();
+
+        std::iter::once(first.trim_end())
+            .chain(
+                rest.iter()
+                    .map(|line| line.get(indent..).unwrap_or_default()),
+            )
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Convert the prepared queries and the literal queries into `CompiledQuery`'s
+    ///
+    /// Queries with any of the given `captures` keep only those in scope.
     fn accumulate_scopes<CQ, PQ>(
         prepared_queries: Vec<PQ>,
         literal_queries: Vec<QueryLiteral>,
         file_queries: Vec<PathBuf>,
+        captures: &[String],
     ) -> Result<super::ScoperList, ProgramError>
     where
         CQ: LanguageScoper + TryFrom<QuerySource, Error = TSQueryError> + 'static,
         PQ: Into<CQ>,
     {
-        let mut scopers: crate::ScoperList = Vec::new();
+        let mut compiled_queries: Vec<CQ> = Vec::new();
 
         for prepared_query in prepared_queries {
             let compiled_query = prepared_query.into();
-            scopers.push(Box::new(compiled_query));
+            compiled_queries.push(compiled_query);
         }
 
         for query_literal in literal_queries {
             let query_source = query_literal.into();
             let compiled_query = CQ::try_from(query_source)?;
-            scopers.push(Box::new(compiled_query));
+            compiled_queries.push(compiled_query);
         }
 
         for file_query in file_queries {
             let query_source = read_query_from_file(file_query)?;
             let compiled_query = CQ::try_from(query_source)?;
-            scopers.push(Box::new(compiled_query));
+            compiled_queries.push(compiled_query);
+        }
+
+        let has_capture =
+            |query: &CQ, name: &String| query.pos_query().capture_names().contains(&name.as_str());
+
+        if let Some(name) = captures.iter().find(|name| {
+            !compiled_queries
+                .iter()
+                .any(|query| has_capture(query, name))
+        }) {
+            return Err(ProgramError::NoSuchCapture(name.clone()));
+        }
+
+        let mut scopers: crate::ScoperList = Vec::new();
+        for compiled_query in compiled_queries {
+            if captures
+                .iter()
+                .any(|name| has_capture(&compiled_query, name))
+            {
+                let names = captures.to_vec();
+                scopers.push(Box::new(CaptureFilter::new(compiled_query, names)));
+            } else {
+                scopers.push(Box::new(compiled_query));
+            }
         }
 
         Ok(scopers)
     }
 
+    /// Parse a file size such as `512`, `8K` or `10M`.
+    pub fn parse_filesize(s: &str) -> Result<u64, String> {
+        let (digits, factor) = match s.char_indices().last() {
+            Some((i, 'K' | 'k')) => (&s[..i], 1 << 10),
+            Some((i, 'M' | 'm')) => (&s[..i], 1 << 20),
+            Some((i, 'G' | 'g')) => (&s[..i], 1 << 30),
+            _ => (s, 1),
+        };
+
+        digits
+            .parse::<u64>()
+            .map_err(|e| e.to_string())?
+            .checked_mul(factor)
+            .ok_or_else(|| "file size too large".to_owned())
+    }
+
     /// Read a literal query as a file.
     fn read_query_from_file(path: PathBuf) -> io::Result<QuerySource> {
         info!("Reading query from file at '{}'", path.display());
@@ -1497,6 +5402,22 @@ mod cli {
         go_query_file: Vec<PathBuf>,
     }
 
+    #[derive(Parser, Debug, Clone)]
+    #[group(required = false, multiple = false)]
+    struct JavaScriptScope {
+        /// Scope JavaScript code (and JSX) using a prepared query.
+        #[arg(long, env, verbatim_doc_comment, visible_alias = "js")]
+        javascript: Vec<javascript::PreparedQuery>,
+
+        /// Scope JavaScript code (and JSX) using a custom tree-sitter query.
+        #[arg(long, env, verbatim_doc_comment, value_name = TREE_SITTER_QUERY_VALUE)]
+        javascript_query: Vec<QueryLiteral>,
+
+        /// Scope JavaScript code (and JSX) using a custom tree-sitter query from file.
+        #[arg(long, env, verbatim_doc_comment, value_name = TREE_SITTER_QUERY_FILENAME)]
+        javascript_query_file: Vec<PathBuf>,
+    }
+
     #[derive(Parser, Debug, Clone)]
     #[group(required = false, multiple = false)]
     struct PythonScope {
@@ -1529,6 +5450,22 @@ mod cli {
         rust_query_file: Vec<PathBuf>,
     }
 
+    #[derive(Parser, Debug, Clone)]
+    #[group(required = false, multiple = false)]
+    struct TsxScope {
+        /// Scope TypeScript with JSX (TSX) code using a prepared query.
+        #[arg(long, env, verbatim_doc_comment)]
+        tsx: Vec<tsx::PreparedQuery>,
+
+        /// Scope TSX code using a custom tree-sitter query.
+        #[arg(long, env, verbatim_doc_comment, value_name = TREE_SITTER_QUERY_VALUE)]
+        tsx_query: Vec<QueryLiteral>,
+
+        /// Scope TSX code using a custom tree-sitter query from file.
+        #[arg(long, env, verbatim_doc_comment, value_name = TREE_SITTER_QUERY_FILENAME)]
+        tsx_query_file: Vec<PathBuf>,
+    }
+
     #[derive(Parser, Debug, Clone)]
     #[group(required = false, multiple = false)]
     struct TypeScriptScope {
@@ -1545,17 +5482,121 @@ mod cli {
         typescript_query_file: Vec<PathBuf>,
     }
 
+    #[derive(Parser, Debug, Clone)]
+    #[group(required = false, multiple = false)]
+    struct TomlScope {
+        /// Scope TOML code using a prepared query.
+        #[arg(long, env, verbatim_doc_comment)]
+        toml: Vec<toml::PreparedQuery>,
+
+        /// Scope TOML code using a custom tree-sitter query.
+        #[arg(long, env, verbatim_doc_comment, value_name = TREE_SITTER_QUERY_VALUE)]
+        toml_query: Vec<QueryLiteral>,
+
+        /// Scope TOML code using a custom tree-sitter query from file.
+        #[arg(long, env, verbatim_doc_comment, value_name = TREE_SITTER_QUERY_FILENAME)]
+        toml_query_file: Vec<PathBuf>,
+    }
+
+    #[derive(Parser, Debug, Clone)]
+    #[group(required = false, multiple = false)]
+    struct YamlScope {
+        /// Scope YAML code using a prepared query.
+        #[arg(long, env, verbatim_doc_comment, visible_alias = "yml")]
+        yaml: Vec<yaml::PreparedQuery>,
+
+        /// Scope YAML code using a custom tree-sitter query.
+        #[arg(long, env, verbatim_doc_comment, value_name = TREE_SITTER_QUERY_VALUE)]
+        yaml_query: Vec<QueryLiteral>,
+
+        /// Scope YAML code using a custom tree-sitter query from file.
+        #[arg(long, env, verbatim_doc_comment, value_name = TREE_SITTER_QUERY_FILENAME)]
+        yaml_query_file: Vec<PathBuf>,
+    }
+
+    #[cfg(feature = "symbols")]
+    #[derive(Parser, Debug)]
+    #[group(required = false, multiple = true, id("symbols-opts"))]
+    #[command(next_help_heading = "Options (symbols)")]
+    #[allow(clippy::struct_excessive_bools)]
+    #[allow(clippy::struct_field_names)] // Prefixes make for the flag names
+    pub struct SymbolsOptions {
+        /// Also replace the fractions '1/2', '1/4' and '3/4' with '½', '¼' and '¾'.
+        ///
+        /// Fractions which are part of larger numbers or dates, like '1/25' or
+        /// '1/2/2024', are left alone.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub symbols_fractions: bool,
+        /// Also replace 'x' between numbers with '×', as in '3x4' or '3 x 4'.
+        ///
+        /// Hexadecimal literals like '0x4' are left alone.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub symbols_multiplication: bool,
+        /// Also replace '+-' with '±'.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub symbols_plus_minus: bool,
+        /// Also replace '(c)', '(tm)' and '(r)' with '©', '™' and '®'.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub symbols_legal: bool,
+        /// Do not replace dashes, like '--' with '–'.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub symbols_no_dashes: bool,
+        /// Do not replace arrows, like '->' with '→'.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub symbols_no_arrows: bool,
+        /// Do not replace comparisons, like '!=' with '≠'.
+        #[arg(long, env, verbatim_doc_comment)]
+        pub symbols_no_math: bool,
+        /// Additionally replace custom symbols from a TOML file.
+        ///
+        /// The file is a flat table of strings, like `"<=>" = "⇔"`. Custom symbols
+        /// take precedence over built-in ones.
+        #[arg(long, env, verbatim_doc_comment, value_name = "FILE")]
+        pub symbols_map: Option<PathBuf>,
+    }
+
+    #[derive(Parser, Debug)]
+    #[group(required = false, multiple = true, id("titlecase-opts"))]
+    #[command(next_help_heading = "Options (titlecase)")]
+    #[allow(clippy::struct_field_names)] // Prefixes make for the flag names
+    pub struct TitlecaseOptions {
+        /// Style guide deciding which words are kept lowercase.
+        ///
+        /// The first and last words, and those around a colon etc., are always
+        /// capitalized.
+        #[arg(
+            long,
+            env,
+            value_name = "STYLE",
+            group = "casing",
+            verbatim_doc_comment
+        )]
+        pub titlecase_style: Option<TitlecaseStyle>,
+        /// Keep this word lowercase, instead of the words of the style.
+        ///
+        /// Can be given multiple times.
+        #[arg(long, value_name = "WORD", group = "casing", verbatim_doc_comment)]
+        pub titlecase_stop_word: Vec<String>,
+        /// Always spell this word exactly as given, like 'iOS' or 'macOS', matching
+        /// regardless of casing.
+        ///
+        /// Can be given multiple times.
+        #[arg(long, value_name = "WORD", group = "casing", verbatim_doc_comment)]
+        pub titlecase_keep: Vec<String>,
+    }
+
     #[cfg(feature = "german")]
     #[derive(Parser, Debug)]
     #[group(required = false, multiple = true, id("german-opts"))]
     #[command(next_help_heading = "Options (german)")]
+    #[allow(clippy::struct_field_names)] // Prefixes make for the flag names
     pub struct GermanOptions {
         /// When some original version and its replacement are equally legal, prefer the
         /// original and do not modify.
         ///
         /// For example, "Busse" (original) and "Buße" (replacement) are equally legal
         /// words: by default, the tool would prefer the latter.
-        #[arg(long, env, verbatim_doc_comment)]
+        #[arg(long, env, group = "uppercasing", verbatim_doc_comment)]
         // More fine-grained control is not available. We are not in the business of
         // natural language processing or LLMs, so that's all we can offer...
         pub german_prefer_original: bool,
@@ -1564,8 +5605,46 @@ mod cli {
         ///
         /// Useful for names, which are otherwise not modifiable as they do not occur in
         /// dictionaries. Called 'naive' as this does not perform legal checks.
-        #[arg(long, env, verbatim_doc_comment)]
+        #[arg(long, env, group = "uppercasing", verbatim_doc_comment)]
         pub german_naive: bool,
+        /// Consider the words in the given file valid, in addition to the built-in
+        /// word list.
+        ///
+        /// The file contains one word per line, in proper spelling and casing, like
+        /// 'Schrödinger'. Useful for domain-specific vocabulary and proper nouns.
+        #[arg(
+            long,
+            env,
+            group = "uppercasing",
+            verbatim_doc_comment,
+            value_name = "FILE"
+        )]
+        pub german_words: Option<PathBuf>,
+        /// Never replace anything in the words in the given file, like brand names or
+        /// identifiers.
+        ///
+        /// The file contains one word per line. Words have to match exactly, including
+        /// casing.
+        #[arg(
+            long,
+            env,
+            group = "uppercasing",
+            verbatim_doc_comment,
+            value_name = "FILE"
+        )]
+        pub german_exceptions: Option<PathBuf>,
+        /// Follow Swiss orthography: never produce 'ß', keeping 'ss' instead.
+        ///
+        /// Umlauts are still replaced, so 'Gruesse' becomes 'Grüsse'. With '--invert',
+        /// existing 'ß' is turned into 'ss' instead.
+        #[arg(
+            long,
+            env,
+            group = "invertible",
+            group = "uppercasing",
+            verbatim_doc_comment
+        )]
+        pub german_swiss: bool,
     }
 
     /// The literal query as read in from the CLI.
@@ -1584,9 +5663,95 @@ mod cli {
         }
     }
 
+    impl GlobalOptions {
+        /// Number of lines of leading context to print around matches.
+        pub(super) fn before_context(&self) -> usize {
+            self.before_context.or(self.context).unwrap_or_default()
+        }
+
+        /// Number of lines of trailing context to print around matches.
+        pub(super) fn after_context(&self) -> usize {
+            self.after_context.or(self.context).unwrap_or_default()
+        }
+
+        /// Style of anything in scope in search mode.
+        pub(super) fn match_style(&self) -> Style {
+            self.color_match.clone().unwrap_or_else(Style::red_bold)
+        }
+
+        /// Style of file paths in output.
+        pub(super) fn path_style(&self) -> Cow<'_, Style> {
+            self.color_path.as_ref().map_or_else(
+                || {
+                    Cow::Owned(Style {
+                        fg: Some(Color::Magenta),
+                        ..Style::default()
+                    })
+                },
+                Cow::Borrowed,
+            )
+        }
+
+        /// Style of line numbers in output.
+        pub(super) fn line_number_style(&self) -> Cow<'_, Style> {
+            self.color_line_number.as_ref().map_or_else(
+                || {
+                    Cow::Owned(Style {
+                        fg: Some(Color::Green),
+                        ..Style::default()
+                    })
+                },
+                Cow::Borrowed,
+            )
+        }
+
+        /// Fills in styles not given as options from `spec`, a `;`-separated list of
+        /// `KIND=STYLE` pairs, as found in the `SRGN_COLORS` environment variable.
+        pub(super) fn fill_colors_from(&mut self, spec: &str) -> anyhow::Result<()> {
+            for pair in spec.split(';').filter(|pair| !pair.trim().is_empty()) {
+                let Some((kind, style)) = pair.split_once('=') else {
+                    bail!("Expected 'KIND=STYLE', got '{pair}'");
+                };
+                let style: Style = style.parse()?;
+
+                let field = match kind.trim() {
+                    "match" => &mut self.color_match,
+                    "path" => &mut self.color_path,
+                    "line-number" => &mut self.color_line_number,
+                    other => bail!("Unknown kind of color: '{other}'"),
+                };
+                field.get_or_insert(style);
+            }
+
+            Ok(())
+        }
+    }
+
     impl Args {
         pub(super) fn init() -> Self {
-            Self::parse()
+            let args = match crate::expand_presets(std::env::args_os()) {
+                Ok(args) => Self::parse_from(args),
+                Err(e) => Self::command()
+                    .error(ErrorKind::InvalidValue, format!("{e:#}"))
+                    .exit(),
+            };
+
+            // Not expressible declaratively: the scope has a default, unless in literal
+            // mode, where it's only optional if other patterns are given.
+            if args.scope.is_none()
+                && args.options.literals.is_empty()
+                && args.options.patterns_file.is_none()
+                && args.composable_actions.replace_map.is_none()
+            {
+                Self::command()
+                    .error(
+                        ErrorKind::MissingRequiredArgument,
+                        "The following required argument was not provided: scope",
+                    )
+                    .exit();
+            }
+
+            args
         }
 
         pub(super) fn command() -> Command {
@@ -1601,9 +5766,28 @@ mod tests {
 
     use env_logger::DEFAULT_FILTER_ENV;
     use log::LevelFilter;
+    use rstest::rstest;
 
     use super::*;
 
+    #[rstest]
+    #[case(&[], 1, 1, &[])]
+    #[case(&[false, false], 1, 1, &[false, false])]
+    #[case(&[true], 0, 0, &[true])]
+    #[case(&[true], 5, 5, &[true])]
+    #[case(&[false, true, false], 0, 0, &[false, true, false])]
+    #[case(&[false, true, false], 1, 0, &[true, true, false])]
+    #[case(&[false, true, false], 0, 1, &[false, true, true])]
+    #[case(&[false, false, true, false, false, false, true], 1, 1, &[false, true, true, true, false, true, true])]
+    fn test_lines_in_context(
+        #[case] matches: &[bool],
+        #[case] before: usize,
+        #[case] after: usize,
+        #[case] expected: &[bool],
+    ) {
+        assert_eq!(lines_in_context(matches, before, after), expected);
+    }
+
     /// This test has to run **sequentially**, as env variable access and manipulation
     /// is *not* thread-safe.
     ///
@@ -1671,11 +5855,206 @@ mod tests {
             // Sanity check for sequential tests
             let i_am_not_sure_if_this_test_really_runs_sequentially = false;
             if i_am_not_sure_if_this_test_really_runs_sequentially {
-                std::thread::sleep(std::time::Duration::from_secs(2));
+                thread::sleep(Duration::from_secs(2));
             }
 
             let result = level_filter_from_env_and_verbosity(additional_verbosity);
             assert_eq!(result, expected);
         }
     }
+
+    #[rstest]
+    #[case("0", Ok(0))]
+    #[case("512", Ok(512))]
+    #[case("8K", Ok(8 * 1024))]
+    #[case("10m", Ok(10 * 1024 * 1024))]
+    #[case("1G", Ok(1024 * 1024 * 1024))]
+    #[case("", Err(()))]
+    #[case("K", Err(()))]
+    #[case("-1", Err(()))]
+    #[case("1.5M", Err(()))]
+    #[case("1T", Err(()))]
+    #[case("99999999999999999999G", Err(()))]
+    fn test_parse_filesize(#[case] input: &str, #[case] expected: Result<u64, ()>) {
+        assert_eq!(cli::parse_filesize(input).map_err(|_| ()), expected);
+    }
+
+    #[rstest]
+    #[case("", false, &[])]
+    #[case("a.py\nsub/b.py\n", false, &["a.py", "sub/b.py"])]
+    #[case("a.py\n\nb.py", false, &["a.py", "b.py"])]
+    #[case("a.py\r\nb.py\r\n", false, &["a.py", "b.py"])]
+    #[case("a b.py\0c\nd.py\0", true, &["a b.py", "c\nd.py"])]
+    #[case("a.py\0\0b.py", true, &["a.py", "b.py"])]
+    fn test_parse_file_list(
+        #[case] contents: &str,
+        #[case] null_separated: bool,
+        #[case] expected: &[&str],
+    ) {
+        let expected = expected.iter().map(PathBuf::from).collect_vec();
+
+        assert_eq!(parse_file_list(contents, null_separated), expected);
+    }
+
+    #[test]
+    fn test_parse_git_diff_hunks() {
+        let diff = "\
+diff --git a/a.txt b/a.txt
+index 1..2 100644
+--- a/a.txt
++++ b/a.txt
+@@ -1 +1 @@
+-x
++y
+@@ -5,0 +6,2 @@ fn context()
++z
++z
+@@ -9,3 +10,0 @@
+-gone
+diff --git a/sub/new.txt b/sub/new.txt
+new file mode 100644
+--- /dev/null
++++ b/sub/new.txt
+@@ -0,0 +1,3 @@
++a
++b
++c
+diff --git a/deleted-lines-only.txt b/deleted-lines-only.txt
+--- a/deleted-lines-only.txt
++++ b/deleted-lines-only.txt
+@@ -2 +1,0 @@
+-gone
+";
+
+        let mut hunks = parse_git_diff_hunks(diff)
+            .into_iter()
+            .map(|(path, lines)| (path, lines.ranges().to_vec()))
+            .collect_vec();
+        hunks.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            hunks,
+            vec![
+                (PathBuf::from("a.txt"), vec![1..=1, 6..=7]),
+                (PathBuf::from("deleted-lines-only.txt"), vec![]),
+                (PathBuf::from("sub/new.txt"), vec![1..=3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_presets() {
+        let contents = r#"
+# A comment
+[presets.fix-todos]
+literal = ["TODO", 'FIXME'] # Trailing comment
+python = "comments"
+scope = "\\bx\\b"
+replacement = 'y'
+upper = true
+lower = false
+max-filesize = 1024
+
+[presets."quoted name"]
+prefix = """
+>"""
+
+[presets]
+inline = { lower = true, suffix = "!" }
+"#;
+
+        let presets = parse_presets(contents).unwrap();
+
+        assert_eq!(
+            presets.into_iter().sorted().collect_vec(),
+            vec![
+                (
+                    "fix-todos".into(),
+                    vec![
+                        r"\bx\b",
+                        "y",
+                        "--literal",
+                        "TODO",
+                        "--literal",
+                        "FIXME",
+                        "--python",
+                        "comments",
+                        "--upper",
+                        "--max-filesize",
+                        "1024",
+                    ]
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+                ),
+                (
+                    "inline".into(),
+                    vec!["--lower".into(), "--suffix".into(), "!".into()]
+                ),
+                ("quoted name".into(), vec!["--prefix".into(), ">".into()]),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("[presets.x", "line 1, column 1")]
+    #[case(
+        "[presets.x]\nscope = 1",
+        "invalid type: integer `1`, expected a string"
+    )]
+    #[case(
+        "[presets.x]\nreplacement = 'y'",
+        "The replacement of preset 'x' requires a scope"
+    )]
+    #[case(
+        "[presets.x]\npreset = 'y'",
+        "Preset 'x' cannot refer to other presets"
+    )]
+    #[case(
+        "[presets.x]\nupper = {}",
+        "Unsupported value for 'upper' of preset 'x'"
+    )]
+    #[case("[other]\nupper = true", "unknown field `other`")]
+    #[case("[presets.x]\nupper = true\nupper = false", "duplicate key `upper`")]
+    #[case("[[presets.x]]\nupper = true", "invalid type: sequence")]
+    fn test_parse_presets_invalid(#[case] contents: &str, #[case] expected: &str) {
+        let err = parse_presets(contents).unwrap_err();
+
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    #[cfg(feature = "symbols")]
+    #[test]
+    fn test_parse_symbols_map() {
+        let contents = r#"
+# A comment
+"<=>" = "⇔"
+'(+)' = '⊕' # Trailing comment
+deg = "\u00B0"
+"#;
+
+        let symbols = parse_symbols_map(contents).unwrap();
+
+        assert_eq!(
+            symbols,
+            vec![
+                ("<=>".into(), "⇔".into()),
+                ("(+)".into(), "⊕".into()),
+                ("deg".into(), "°".into()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "symbols")]
+    #[rstest]
+    #[case("\"<=>\" \"⇔\"", "line 1, column")]
+    #[case("[table]\nx = 'y'", "Symbols map must be a flat table of strings")]
+    #[case("x = 1", "Symbol 'x' must map to a string")]
+    #[case("\"\" = 'y'", "Symbols map cannot contain empty keys")]
+    #[case("x = 'y'\nx = 'z'", "duplicate key `x`")]
+    fn test_parse_symbols_map_invalid(#[case] contents: &str, #[case] expected: &str) {
+        let err = parse_symbols_map(contents).unwrap_err();
+
+        assert!(err.to_string().contains(expected), "{err}");
+    }
 }
@@ -10,8 +10,12 @@ use crate::scoping::{scope::Scope, view::ScopedView};
 pub mod dosfix;
 /// Create scoped views using programming language grammar-aware types.
 pub mod langs;
+/// Create scoped views using line numbers.
+pub mod lines;
 /// Create scoped views using string literals.
 pub mod literal;
+/// Select occurrences of what is in scope by their position.
+pub mod occurrences;
 /// Create scoped views using regular expressions.
 pub mod regex;
 /// [`Scope`] and its various wrappers.
@@ -36,6 +40,16 @@ pub trait Scoper: Send + Sync {
     ///
     /// Raw ranges are those not turned into [`ROScopes`] yet.
     fn scope_raw<'viewee>(&self, input: &'viewee str) -> RangesWithContext<'viewee>;
+
+    /// Cheaply checks whether scoping the given `input`, or any part of it, might yield
+    /// anything in scope at all.
+    ///
+    /// Allows skipping expensive work upfront. Implementations must only return
+    /// `false` if nothing can ever be in scope; by default, `true` is returned.
+    fn maybe_in_scope(&self, input: &str) -> bool {
+        let _ = input; // Mark variable as used
+        true
+    }
 }
 
 // https://www.reddit.com/r/rust/comments/droxdg/why_arent_traits_impld_for_boxdyn_trait/
@@ -43,4 +57,8 @@ impl Scoper for Box<dyn Scoper> {
     fn scope_raw<'viewee>(&self, input: &'viewee str) -> RangesWithContext<'viewee> {
         self.as_ref().scope_raw(input)
     }
+
+    fn maybe_in_scope(&self, input: &str) -> bool {
+        self.as_ref().maybe_in_scope(input)
+    }
 }
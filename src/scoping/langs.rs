@@ -1,13 +1,19 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::Range;
 
+use clap::ValueEnum;
 use log::{debug, info, trace};
 use streaming_iterator::StreamingIterator; // TODO: remove once https://github.com/tree-sitter/tree-sitter/pull/4034 is released
 use tree_sitter::{
-    Language as TSLanguage, Parser as TSParser, Query as TSQuery, QueryCursor as TSQueryCursor,
-    QueryError as TSQueryError,
+    Language as TSLanguage, Node as TSNode, Parser as TSParser, Query as TSQuery,
+    QueryCursor as TSQueryCursor, QueryError as TSQueryError, QueryErrorKind as TSQueryErrorKind,
+    Tree as TSTree,
 };
 
-use super::scope::RangesWithContext;
+use super::regex::CaptureGroup;
+use super::scope::{inherit_context, RangesWithContext, ScopeContext};
 use super::Scoper;
 use crate::find::Find;
 use crate::ranges::Ranges;
@@ -25,23 +31,28 @@ pub mod csharp;
 pub mod go;
 /// Hashicorp Configuration Language
 pub mod hcl;
+/// JavaScript (and JSX).
+pub mod javascript;
 /// Python.
 pub mod python;
 /// Rust.
 pub mod rust;
+/// TOML.
+pub mod toml;
 mod tree_sitter_hcl;
+/// TypeScript with JSX (TSX).
+pub mod tsx;
 /// TypeScript.
 pub mod typescript;
+/// YAML.
+pub mod yaml;
 
 /// Represents query compiled for a (programming) language L.
 #[derive(Debug)]
 struct CompiledQuery {
     /// The *positive* query: it will be run against input and its results used for
-    /// scoping.
+    /// scoping. Results of captures to be [ignored][`IGNORE`] are *subtracted*.
     positive_query: TSQuery,
-    /// The *negative* query: if present (if [`IGNORE`] is present) will be run and
-    /// *subtracted* from the positive query.
-    negative_query: Option<TSQuery>,
 }
 
 impl CompiledQuery {
@@ -60,37 +71,63 @@ impl CompiledQuery {
 
     fn from_str(lang: &TSLanguage, query: &str) -> Result<Self, TSQueryError> {
         let positive_query = TSQuery::new(lang, query)?;
+        check_predicates(&positive_query, query)?;
 
-        let is_ignored = |name: &str| name.starts_with(IGNORE);
-        let has_ignored_captures = positive_query
-            .capture_names()
-            .iter()
-            .any(|name| is_ignored(name));
-
-        let negative_query = has_ignored_captures
-            .then(|| {
-                let mut query = TSQuery::new(lang, query)?;
-                let acknowledged_captures = query
-                    .capture_names()
-                    .iter()
-                    .filter(|name| !is_ignored(name))
-                    .map(|s| String::from(*s))
-                    .collect::<Vec<_>>();
+        Ok(Self { positive_query })
+    }
+}
 
-                for name in acknowledged_captures {
-                    trace!("Disabling capture for: {:?}", name);
-                    query.disable_capture(&name);
-                }
+/// Ensures all predicates of the `query` are supported, as any others would silently
+/// be ignored when running it.
+///
+/// Supported are the text predicates `eq?`, `match?` and `any-of?`, including their
+/// `not-` and `any-` variants, which tree-sitter evaluates itself. Directives like
+/// `set!` are allowed as well, but have no effect.
+fn check_predicates(query: &TSQuery, source: &str) -> Result<(), TSQueryError> {
+    for pattern in 0..query.pattern_count() {
+        let property_operators = query
+            .property_predicates(pattern)
+            .iter()
+            .map(|(_, is_positive)| if *is_positive { "is?" } else { "is-not?" });
+        let general_operators = query
+            .general_predicates(pattern)
+            .iter()
+            .map(|predicate| &*predicate.operator);
+
+        if let Some(operator) = property_operators.chain(general_operators).next() {
+            let offset = query.start_byte_for_pattern(pattern);
+            let preceding = &source[..offset];
+            let row = preceding.matches('\n').count();
+            let column = offset - preceding.rfind('\n').map_or(0, |i| i + 1);
+
+            return Err(TSQueryError {
+                row,
+                column,
+                offset,
+                message: format!("unsupported predicate `#{operator}`"),
+                kind: TSQueryErrorKind::Predicate,
+            });
+        }
+    }
 
-                Ok(query)
-            })
-            .transpose()?;
+    Ok(())
+}
 
-        Ok(Self {
-            positive_query,
-            negative_query,
-        })
-    }
+/// Queries available across languages under the same name.
+///
+/// Each language maps these onto its closest prepared query, if it has one: for
+/// example, [`GenericQuery::Imports`] are `use` declarations in Rust, but `#include`s
+/// in C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GenericQuery {
+    /// Comments.
+    Comments,
+    /// Strings.
+    Strings,
+    /// Imports (modules, `use` declarations, `#include`s, ...).
+    Imports,
+    /// Function names, at the definition site.
+    FunctionNames,
 }
 
 /// An uncompiled source for a query over a language, for scoping.
@@ -105,6 +142,15 @@ impl From<String> for QuerySource {
     }
 }
 
+/// The syntax for comments in a language.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommentSyntax {
+    /// The prefix of comments running to the end of the line, like `//`.
+    pub line: Option<&'static str>,
+    /// The delimiters of comments spanning a block, like `/*` and `*/`.
+    pub block: Option<(&'static str, &'static str)>,
+}
+
 /// In a query, use this name to mark a capture to be ignored.
 ///
 /// Useful for queries where tree-sitter doesn't natively support a fitting node type,
@@ -114,7 +160,7 @@ pub(super) const IGNORE: &str = "_SRGN_IGNORE";
 /// A scoper for a language.
 ///
 /// Functions much the same, but provides specific language-related functionality.
-pub trait LanguageScoper: Scoper + Find + Send + Sync {
+pub trait LanguageScoper: Scoper + TreeScoper + Find + Send + Sync {
     /// The language's tree-sitter language.
     fn lang() -> TSLanguage
     where
@@ -122,17 +168,29 @@ pub trait LanguageScoper: Scoper + Find + Send + Sync {
 
     /// The language's *positive* tree-sitter query.
     ///
-    /// Its results indicate items in scope.
+    /// Its results indicate items in scope, except for those of captures to be
+    /// [ignored][`IGNORE`], which are *subtracted*.
     fn pos_query(&self) -> &TSQuery
     where
         Self: Sized; // Exclude from trait object
 
-    /// The language's *negative* tree-sitter query.
+    /// The language's syntax for comments.
+    ///
+    /// By default, the language has none.
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax::default()
+    }
+
+    /// Whether parts hit by the query capture of the given `name` are in scope.
     ///
-    /// Its results will be *subtracted* from the positive ones.
-    fn neg_query(&self) -> Option<&TSQuery>
+    /// By default, all captures are.
+    fn keeps_capture(&self, name: &str) -> bool
     where
-        Self: Sized; // Exclude from trait object
+        Self: Sized, // Exclude from trait object
+    {
+        let _ = name; // Mark variable as used
+        true
+    }
 
     /// The language's tree-sitter parser.
     #[must_use]
@@ -150,61 +208,173 @@ pub trait LanguageScoper: Scoper + Find + Send + Sync {
 
     /// Scope the given input using the language's query.
     ///
-    /// In principle, this is the same as [`Scoper::scope`].
-    fn scope_via_query(&self, input: &str) -> Ranges<usize>
+    /// In principle, this is the same as [`Scoper::scope`]. Each resulting range
+    /// carries the query captures it overlaps as context, as
+    /// [`ScopeContext::CaptureGroups`]: capture names map to the content they captured.
+    fn scope_via_query<'viewee>(&self, input: &'viewee str) -> RangesWithContext<'viewee>
     where
         Self: Sized, // Exclude from trait object
     {
+        self.scope_tree(&self.parse(input), input)
+    }
+}
+
+/// A single match of a query: the range and name of each of its captures.
+type QueryMatch<'q> = Vec<(Range<usize>, &'q str)>;
+
+/// Runs the `query` on the tree at `root`, returning all its matches.
+fn run_query<'q>(query: &'q TSQuery, root: TSNode<'_>, input: &str) -> Vec<QueryMatch<'q>> {
+    trace!("Running query: {:?}", query);
+
+    let names = query.capture_names();
+    let mut qc = TSQueryCursor::new();
+    let mut matches = qc.matches(query, root, input.as_bytes());
+
+    // The size hint is hard-coded to 0 currently so there's no effect; use it
+    // regardless as it might be useful in the future.
+    let mut res = Vec::with_capacity(matches.size_hint().1.unwrap_or_default());
+    while let Some(m) = matches.next() {
+        res.push(
+            m.captures
+                .iter()
+                .map(|capture| (capture.node.byte_range(), names[capture.index as usize]))
+                .collect(),
+        );
+    }
+
+    res
+}
+
+/// The captures of all `matches` whose names are kept, alongside the index of the
+/// match they belong to.
+fn kept_captures<'a>(
+    matches: &'a [QueryMatch<'_>],
+    keeps_capture: impl Fn(&str) -> bool,
+) -> Vec<(usize, &'a Range<usize>)> {
+    matches
+        .iter()
+        .enumerate()
+        .flat_map(|(i, m)| m.iter().map(move |capture| (i, capture)))
+        .filter(|(_, (_, name))| keeps_capture(name))
+        .map(|(i, (range, _))| (i, range))
+        .collect()
+}
+
+/// Pairs each of the (ordered, non-overlapping) `ranges` with all captures of the
+/// `matches` which have a kept capture overlapping that range. Capture names map to
+/// the content they captured, similar to capture groups of a regex match.
+///
+/// Captures to be ignored are not part of any context. If a name was captured more
+/// than once, the first capture wins.
+fn with_capture_context<'viewee>(
+    input: &'viewee str,
+    ranges: Ranges<usize>,
+    matches: &[QueryMatch<'_>],
+    keeps_capture: impl Fn(&str) -> bool,
+) -> RangesWithContext<'viewee> {
+    let ranges = ranges.into_iter().collect::<Vec<_>>();
+    let mut contexts = vec![HashMap::new(); ranges.len()];
+
+    for (i, capture) in kept_captures(matches, keeps_capture) {
+        let first = ranges.partition_point(|range| range.end <= capture.start);
+        for (range, groups) in ranges[first..].iter().zip(&mut contexts[first..]) {
+            if range.start >= capture.end {
+                break;
+            }
+
+            for (capture, name) in &matches[i] {
+                if !name.starts_with(IGNORE) {
+                    groups
+                        .entry(CaptureGroup::Named((*name).to_owned()))
+                        .or_insert_with(|| &input[capture.clone()]);
+                }
+            }
+        }
+    }
+
+    ranges
+        .into_iter()
+        .zip(contexts)
+        .map(|(range, groups)| (range, Some(ScopeContext::CaptureGroups(groups))))
+        .collect()
+}
+
+/// Scoping by a query over input already parsed into a syntax tree.
+///
+/// Implemented for all [`LanguageScoper`]s. Allows scoping the same input by multiple
+/// queries of one language (for example, when joining language scopes) using a
+/// single parse, also for trait objects.
+pub trait TreeScoper {
+    /// The tree-sitter language of the query.
+    fn language(&self) -> TSLanguage;
+
+    /// Parses `input` into a syntax tree of [the language][`TreeScoper::language`].
+    fn parse(&self, input: &str) -> TSTree;
+
+    /// Scope the given `input`, previously [parsed][`TreeScoper::parse`] into `tree`,
+    /// using the query. See [`LanguageScoper::scope_via_query`].
+    fn scope_tree<'viewee>(&self, tree: &TSTree, input: &'viewee str)
+        -> RangesWithContext<'viewee>;
+}
+
+impl<T> TreeScoper for T
+where
+    T: LanguageScoper,
+{
+    fn language(&self) -> TSLanguage {
+        T::lang()
+    }
+
+    fn parse(&self, input: &str) -> TSTree {
         // tree-sitter is about incremental parsing, which we don't use here
         let old_tree = None;
 
-        trace!("Parsing into AST: {:?}", input);
+        trace!("Parsing {} bytes of input into AST", input.len());
 
-        let tree = Self::parser()
+        T::parser()
             .parse(input, old_tree)
-            .expect("No language set in parser, or other unrecoverable error");
+            .expect("No language set in parser, or other unrecoverable error")
+    }
 
+    fn scope_tree<'viewee>(
+        &self,
+        tree: &TSTree,
+        input: &'viewee str,
+    ) -> RangesWithContext<'viewee> {
         let root = tree.root_node();
         debug!(
             "S expression of parsed source code is: {:?}",
             root.to_sexp()
         );
 
-        let run = |query: &TSQuery| {
-            trace!("Running query: {:?}", query);
-
-            let mut qc = TSQueryCursor::new();
-            let mut matches = qc.matches(query, root, input.as_bytes());
-
-            let mut ranges: Ranges<usize> = {
-                // The size hint is hard-coded to 0 currently so there's no effect; use it
-                // regardless as it might be useful in the future.
-                let mut ranges = Vec::with_capacity(matches.size_hint().1.unwrap_or_default());
+        let matches = run_query(self.pos_query(), root, input);
 
-                while let Some(m) = matches.next() {
-                    for capture in m.captures {
-                        ranges.push(capture.node.byte_range());
-                    }
-                }
-
-                ranges.into_iter().collect()
-            };
-
-            // ⚠️ tree-sitter queries with multiple captures will return them in some
-            // mixed order (not ordered, and not merged), but we later rely on cleanly
-            // ordered, non-overlapping ranges (a bit unfortunate we have to know about
-            // that remote part over here).
-            ranges.merge();
-            trace!("Querying yielded ranges: {:?}", ranges);
+        let mut ranges: Ranges<usize> = kept_captures(&matches, |name| self.keeps_capture(name))
+            .into_iter()
+            .map(|(_, range)| range.clone())
+            .collect();
+        // ⚠️ tree-sitter queries with multiple captures will return them in some
+        // mixed order (not ordered, and not merged), but we later rely on cleanly
+        // ordered, non-overlapping ranges (a bit unfortunate we have to know about
+        // that remote part over here).
+        ranges.merge();
+        trace!("Querying yielded ranges: {:?}", ranges);
 
-            ranges
-        };
+        // Taken from the same matches, so these are subject to the same predicates.
+        let mut ignored: Ranges<usize> = matches
+            .iter()
+            .flatten()
+            .filter(|(_, name)| name.starts_with(IGNORE))
+            .map(|(range, _)| range.clone())
+            .collect();
+        if !ignored.is_empty() {
+            ignored.merge();
+            trace!("Ignoring ranges: {:?}", ignored);
 
-        let ranges = run(self.pos_query());
-        match &self.neg_query() {
-            Some(nq) => ranges - run(nq),
-            None => ranges,
+            ranges = ranges - ignored;
         }
+
+        with_capture_context(input, ranges, &matches, |name| self.keeps_capture(name))
     }
 }
 
@@ -213,7 +383,7 @@ where
     T: LanguageScoper,
 {
     fn scope_raw<'viewee>(&self, input: &'viewee str) -> RangesWithContext<'viewee> {
-        self.scope_via_query(input).into()
+        self.scope_via_query(input)
     }
 }
 
@@ -237,24 +407,263 @@ impl Scoper for &[Box<dyn LanguageScoper>] {
             return vec![(0..input.len(), None)].into_iter().collect();
         }
 
-        // This is slightly leaky in that it drops down to a more 'primitive' layer and
-        // uses `Ranges`.
-        let mut ranges: Ranges<usize> = self
-            .iter()
-            .flat_map(|s| s.scope_raw(input))
-            .map(|(range, ctx)| {
-                assert!(
-                    ctx.is_none(),
-                    "When language scoping runs, no contexts exist yet."
-                );
-                range
-            })
-            .collect();
-        ranges.merge();
+        // Parse only once per language, no matter how many queries there are
+        let mut trees: Vec<(TSLanguage, TSTree)> = Vec::new();
+        let mut scoped = RangesWithContext::new();
+        for scoper in *self {
+            let lang = scoper.language();
+            let i = trees
+                .iter()
+                .position(|(l, _)| *l == lang)
+                .unwrap_or_else(|| {
+                    trees.push((lang, scoper.parse(input)));
+                    trees.len() - 1
+                });
+            scoped.extend(scoper.scope_tree(&trees[i].1, input));
+        }
+        scoped.sort_by_key(|(range, _)| range.start);
+
+        // Same as merging `Ranges`, but joining the contexts of merged ranges as well.
+        let mut ranges: RangesWithContext<'_> = Vec::with_capacity(scoped.len());
+        for (range, ctx) in scoped {
+            match ranges.last_mut() {
+                // Overlapping or bordering
+                Some((last, last_ctx)) if last.end >= range.start => {
+                    last.end = last.end.max(range.end);
+                    inherit_context(last_ctx, ctx.as_ref());
+                }
+                _ => ranges.push((range, ctx)),
+            }
+        }
         info!("New ranges after scoping many: {ranges:?}");
 
-        let ranges: RangesWithContext<'_> = ranges.into_iter().map(|r| (r, None)).collect();
-
         ranges
     }
 }
+
+/// A [`LanguageScoper`] keeping only captures of the given names in scope.
+///
+/// Useful for queries capturing multiple parts, only some of which are of interest,
+/// as in `(pair key: (_) @key value: (_) @value)`.
+#[derive(Debug)]
+pub struct CaptureFilter<L> {
+    scoper: L,
+    names: Vec<String>,
+}
+
+impl<L> CaptureFilter<L> {
+    /// Create a new filter keeping only the captures of the given `names` of the
+    /// `scoper`'s query in scope.
+    #[must_use]
+    pub const fn new(scoper: L, names: Vec<String>) -> Self {
+        Self { scoper, names }
+    }
+}
+
+impl<L: LanguageScoper> LanguageScoper for CaptureFilter<L> {
+    fn lang() -> TSLanguage {
+        L::lang()
+    }
+
+    fn pos_query(&self) -> &TSQuery {
+        self.scoper.pos_query()
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        self.scoper.comment_syntax()
+    }
+
+    fn keeps_capture(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name) && self.scoper.keeps_capture(name)
+    }
+}
+
+impl<L: Find> Find for CaptureFilter<L> {
+    fn extensions(&self) -> &'static [&'static str] {
+        self.scoper.extensions()
+    }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        self.scoper.linguist_names()
+    }
+
+    fn interpreters(&self) -> Option<&'static [&'static str]> {
+        self.scoper.interpreters()
+    }
+
+    fn is_path_invalid(&self, path: &std::path::Path) -> bool {
+        self.scoper.is_path_invalid(path)
+    }
+}
+
+/// Renders the syntax tree of `input`, as parsed for `lang`, as an indented
+/// S-expression.
+///
+/// Like [`TSNode::to_sexp`], only named (and missing) nodes are shown, prefixed by
+/// their field names, if any. Each node is on a line of its own. With `byte_ranges`,
+/// nodes are annotated with the range of input bytes they span.
+///
+/// ## Panics
+///
+/// Panics if the grammar of `lang` cannot be loaded.
+#[must_use]
+pub fn dump_tree(lang: &TSLanguage, input: &str, byte_ranges: bool) -> String {
+    let mut parser = TSParser::new();
+    parser
+        .set_language(lang)
+        .expect("Should be able to load language grammar and parser");
+    let tree = parser
+        .parse(input, None)
+        .expect("No language set in parser, or other unrecoverable error");
+
+    let mut out = String::new();
+    write_node(&mut out, tree.root_node(), None, 0, byte_ranges);
+    out.push('\n');
+
+    out
+}
+
+fn write_node(
+    out: &mut String,
+    node: TSNode<'_>,
+    field: Option<&str>,
+    depth: usize,
+    byte_ranges: bool,
+) {
+    out.push_str(&"  ".repeat(depth));
+    if let Some(field) = field {
+        out.push_str(field);
+        out.push_str(": ");
+    }
+    out.push('(');
+    if node.is_missing() {
+        out.push_str("MISSING ");
+    }
+    out.push_str(node.kind());
+    if byte_ranges {
+        write!(out, " [{:?}]", node.byte_range()).expect("writing to `String` is infallible");
+    }
+
+    let mut cursor = node.walk();
+    if cursor.goto_first_child() {
+        loop {
+            let child = cursor.node();
+            if child.is_named() || child.is_missing() {
+                out.push('\n');
+                write_node(out, child, cursor.field_name(), depth + 1, byte_ranges);
+            }
+
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    out.push(')');
+}
+
+#[cfg(test)]
+mod tests {
+    use const_format::formatcp;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_scope_tree_shares_parse() {
+        let input = "x = 1  # one\ndef f(): pass";
+        let queries = ["(comment) @c", "(integer) @i"]
+            .map(|q| python::CompiledQuery::try_from(QuerySource::from(q.to_owned())).unwrap());
+
+        let tree = queries[0].parse(input);
+        for query in &queries {
+            assert_eq!(query.scope_tree(&tree, input), query.scope_via_query(input));
+        }
+    }
+
+    #[rstest]
+    #[case(
+        false,
+        "(module\n  (expression_statement\n    (assignment\n      left: (identifier)\n      right: (integer))))\n"
+    )]
+    #[case(
+        true,
+        "(module [0..5]\n  (expression_statement [0..5]\n    (assignment [0..5]\n      left: (identifier [0..1])\n      right: (integer [4..5]))))\n"
+    )]
+    fn test_dump_tree(#[case] byte_ranges: bool, #[case] expected: &str) {
+        assert_eq!(
+            dump_tree(&python::CompiledQuery::lang(), "x = 1", byte_ranges),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_query_captures_as_context() {
+        let query = "(assignment left: (_) @lhs right: (_) @rhs)";
+        let query = python::CompiledQuery::try_from(QuerySource::from(query.to_owned())).unwrap();
+        let input = "x = 1";
+
+        let context = |pairs: &[(&str, &'static str)]| {
+            Some(ScopeContext::CaptureGroups(
+                pairs
+                    .iter()
+                    .map(|(name, content)| (CaptureGroup::Named((*name).to_owned()), *content))
+                    .collect(),
+            ))
+        };
+        let both = context(&[("lhs", "x"), ("rhs", "1")]);
+
+        assert_eq!(
+            query.scope_raw(input),
+            vec![(0..1, both.clone()), (4..5, both.clone())]
+        );
+
+        let filtered = CaptureFilter::new(query, vec!["rhs".to_owned()]);
+        assert_eq!(filtered.scope_raw(input), vec![(4..5, both)]);
+    }
+
+    #[test]
+    fn test_ignored_captures_respect_predicates() {
+        #[allow(clippy::needless_raw_string_hashes)] // Doesn't play nice with the macro
+        let query = formatcp!(
+            r#"
+            (assignment) @assignment
+            (assignment
+                left: (identifier) @name
+                right: (_) @{0}
+                (#eq? @name "x")
+            )
+            "#,
+            IGNORE
+        );
+        let query = python::CompiledQuery::try_from(QuerySource::from(query.to_owned())).unwrap();
+
+        let ranges = query
+            .scope_raw("x = 1\ny = 2")
+            .into_iter()
+            .map(|(range, _)| range)
+            .collect::<Vec<_>>();
+
+        // Only the value of `x` is ignored, not the one of `y`.
+        assert_eq!(ranges, vec![0..4, 6..11]);
+    }
+
+    #[rstest]
+    #[case(r#"((identifier) @id (#eq? @id "x"))"#, true)]
+    #[case(r#"((identifier) @id (#not-match? @id "^_"))"#, true)]
+    #[case(r#"((identifier) @id (#any-of? @id "x" "y"))"#, true)]
+    #[case(r#"((identifier) @id (#set! kind "name"))"#, true)]
+    #[case(r"((identifier) @id (#is? local))", false)]
+    #[case("(string) @s\n((identifier) @id (#contains? @id \"x\"))", false)]
+    fn test_unsupported_predicates_are_rejected(#[case] query: &str, #[case] ok: bool) {
+        let res = python::CompiledQuery::try_from(QuerySource::from(query.to_owned()));
+
+        match res {
+            Ok(_) => assert!(ok),
+            Err(err) => {
+                assert!(!ok, "{err}");
+                assert_eq!(err.kind, TSQueryErrorKind::Predicate);
+            }
+        }
+    }
+}
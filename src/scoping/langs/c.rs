@@ -2,7 +2,9 @@ use std::fmt::Debug;
 
 use clap::ValueEnum;
 
-use super::{LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError};
+use super::{
+    CommentSyntax, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError,
+};
 use crate::find::Find;
 
 /// A compiled query for the C language.
@@ -76,7 +78,20 @@ pub enum PreparedQuery {
 }
 
 impl PreparedQuery {
-    const fn as_str(self) -> &'static str {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports => Some(Self::Includes),
+            GenericQuery::FunctionNames => None,
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Comments => "(comment) @comment",
             Self::Strings => "[(string_literal) (system_lib_string)] @string",
@@ -112,8 +127,11 @@ impl LanguageScoper for CompiledQuery {
         &self.0.positive_query
     }
 
-    fn neg_query(&self) -> Option<&TSQuery> {
-        self.0.negative_query.as_ref()
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("//"),
+            block: Some(("/*", "*/")),
+        }
     }
 }
 
@@ -121,4 +139,8 @@ impl Find for CompiledQuery {
     fn extensions(&self) -> &'static [&'static str] {
         &["c", "h"]
     }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["C"]
+    }
 }
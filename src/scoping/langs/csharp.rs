@@ -3,7 +3,9 @@ use std::fmt::Debug;
 use clap::ValueEnum;
 use const_format::formatcp;
 
-use super::{LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError};
+use super::{
+    CommentSyntax, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError,
+};
 use crate::find::Find;
 use crate::scoping::langs::IGNORE;
 
@@ -45,8 +47,12 @@ pub enum PreparedQuery {
     /// Raw strings are [not yet
     /// supported](https://github.com/tree-sitter/tree-sitter-c-sharp/pull/240).
     Strings,
+    /// Interpolated strings (in their entirety; incl. `$`, quotes and interpolations).
+    InterpolatedStrings,
     /// `using` directives (including periods).
     Usings,
+    /// Namespace names (including periods; block and file-scoped declarations).
+    Namespaces,
     /// `struct` definitions (in their entirety).
     Struct,
     /// `enum` definitions (in their entirety).
@@ -55,8 +61,12 @@ pub enum PreparedQuery {
     Interface,
     /// `class` definitions (in their entirety).
     Class,
+    /// Names of `class` definitions.
+    ClassNames,
     /// Method definitions (in their entirety).
     Method,
+    /// Names of method definitions.
+    MethodNames,
     /// Variable declarations (in their entirety).
     VariableDeclaration,
     /// Property definitions (in their entirety).
@@ -74,7 +84,20 @@ pub enum PreparedQuery {
 }
 
 impl PreparedQuery {
-    const fn as_str(self) -> &'static str {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports => Some(Self::Usings),
+            GenericQuery::FunctionNames => Some(Self::MethodNames),
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Comments => "(comment) @comment",
             Self::Usings => r"(using_directive [(identifier) (qualified_name)] @import)",
@@ -92,11 +115,22 @@ impl PreparedQuery {
                     IGNORE
                 )
             }
+            Self::InterpolatedStrings => "(interpolated_string_expression) @string",
+            Self::Namespaces => {
+                r"
+                [
+                    (namespace_declaration name: (_) @namespace)
+                    (file_scoped_namespace_declaration name: (_) @namespace)
+                ]
+                "
+            }
             Self::Struct => "(struct_declaration) @struct",
             Self::Enum => "(enum_declaration) @enum",
             Self::Interface => "(interface_declaration) @interface",
             Self::Class => "(class_declaration) @class",
+            Self::ClassNames => "(class_declaration name: (identifier) @name)",
             Self::Method => "(method_declaration) @method",
+            Self::MethodNames => "(method_declaration name: (identifier) @name)",
             Self::VariableDeclaration => "(variable_declaration) @variable",
             Self::Property => "(property_declaration) @property",
             Self::Constructor => "(constructor_declaration) @constructor",
@@ -117,8 +151,11 @@ impl LanguageScoper for CompiledQuery {
         &self.0.positive_query
     }
 
-    fn neg_query(&self) -> Option<&TSQuery> {
-        self.0.negative_query.as_ref()
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("//"),
+            block: Some(("/*", "*/")),
+        }
     }
 }
 
@@ -126,4 +163,8 @@ impl Find for CompiledQuery {
     fn extensions(&self) -> &'static [&'static str] {
         &["cs"]
     }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["C#", "csharp"]
+    }
 }
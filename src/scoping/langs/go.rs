@@ -4,7 +4,9 @@ use std::path::{Component, Path};
 use clap::ValueEnum;
 use const_format::formatcp;
 
-use super::{LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError};
+use super::{
+    CommentSyntax, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError,
+};
 use crate::find::Find;
 use crate::scoping::langs::IGNORE;
 
@@ -80,10 +82,31 @@ pub enum PreparedQuery {
     Goto,
     /// Struct tags.
     StructTags,
+    /// Names of `func` definitions, methods and interface method declarations.
+    FuncNames,
+    /// Names of `struct` and `interface` type definitions.
+    TypeNames,
+    /// Names of fields in `struct` type definitions (excluding embedded fields).
+    StructFields,
+    /// Build constraint comments (`//go:build` and legacy `// +build`; top-level only).
+    BuildTags,
 }
 
 impl PreparedQuery {
-    const fn as_str(self) -> &'static str {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports => Some(Self::Imports),
+            GenericQuery::FunctionNames => Some(Self::FuncNames),
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Comments => "(comment) @comment",
             Self::Strings => {
@@ -129,6 +152,35 @@ impl PreparedQuery {
             Self::Labeled => "(labeled_statement) @labeled",
             Self::Goto => "(goto_statement) @goto",
             Self::StructTags => "(field_declaration tag: (raw_string_literal) @tag)",
+            Self::FuncNames => {
+                r"
+                [
+                    (function_declaration name: (identifier) @name)
+                    (method_declaration name: (field_identifier) @name)
+                    (method_elem name: (field_identifier) @name)
+                ]
+                "
+            }
+            Self::TypeNames => {
+                r"(type_spec
+                    name: (type_identifier) @name
+                    type: [(struct_type) (interface_type)]
+                )"
+            }
+            Self::StructFields => {
+                r"(struct_type
+                    (field_declaration_list
+                        (field_declaration name: (field_identifier) @field)
+                    )
+                )"
+            }
+            Self::BuildTags => {
+                // Only top-level comments: build constraints have no meaning elsewhere.
+                r#"(source_file
+                    (comment) @comment
+                    (#match? @comment "^//(go:build| [+]build) ")
+                )"#
+            }
         }
     }
 }
@@ -142,8 +194,11 @@ impl LanguageScoper for CompiledQuery {
         &self.0.positive_query
     }
 
-    fn neg_query(&self) -> Option<&TSQuery> {
-        self.0.negative_query.as_ref()
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("//"),
+            block: Some(("/*", "*/")),
+        }
     }
 }
 
@@ -152,6 +207,10 @@ impl Find for CompiledQuery {
         &["go"]
     }
 
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["Go", "golang"]
+    }
+
     fn is_path_invalid(&self, path: &Path) -> bool {
         for component in path.components() {
             if let Component::Normal(item) = component {
@@ -3,7 +3,10 @@ use std::fmt::Debug;
 use clap::ValueEnum;
 use const_format::formatcp;
 
-use super::{tree_sitter_hcl, LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError};
+use super::{
+    tree_sitter_hcl, CommentSyntax, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery,
+    TSQueryError,
+};
 use crate::find::Find;
 use crate::scoping::langs::IGNORE;
 
@@ -71,6 +74,26 @@ pub enum PreparedQuery {
     ///
     /// In `data "a" "b"`, only "a" is matched.
     DataSources,
+    /// `module` name declarations and usages.
+    ///
+    /// In `module "a"` and `module.a.b`, only "a" is matched.
+    ModuleNames,
+    /// Local value name declarations (inside `locals` blocks) and usages.
+    ///
+    /// In `a = 1` and `local.a`, only "a" is matched.
+    LocalNames,
+    /// `provider` name declarations.
+    ///
+    /// In `provider "a"`, only "a" is matched.
+    ProviderNames,
+    /// `output` name declarations.
+    ///
+    /// In `output "a"`, only "a" is matched.
+    OutputNames,
+    /// Attribute names.
+    ///
+    /// In `a = "b"`, only "a" is matched.
+    Attributes,
     /// Comments.
     Comments,
     /// Literal strings.
@@ -80,8 +103,20 @@ pub enum PreparedQuery {
 }
 
 impl PreparedQuery {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports | GenericQuery::FunctionNames => None,
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
     #[allow(clippy::too_many_lines)] // No good way to avoid
-    const fn as_str(self) -> &'static str {
+    pub const fn as_str(self) -> &'static str {
         // Seems to not play nice with the macro. Put up here, else interpolation is
         // affected.
         #[allow(clippy::needless_raw_string_hashes)]
@@ -310,6 +345,89 @@ impl PreparedQuery {
                     IGNORE
                 )
             }
+            Self::ModuleNames => {
+                // Capturing nodes with names, such as `@id`, requires names to be
+                // unique across the *entire* query, else things break. Hence, us
+                // `@a.b` syntax (which seems undocumented).
+                formatcp!(
+                    r#"
+                        [
+                            (block
+                                (identifier) @{0}.declaration
+                                (string_lit (template_literal) @name.declaration)
+                                (#eq? @{0}.declaration "module")
+                            )
+                            (
+                                (variable_expr
+                                    (identifier) @{0}.usage
+                                    (#eq? @{0}.usage "module")
+                                )
+                                .
+                                (get_attr
+                                    (identifier) @name.usage
+                                )
+                            )
+                        ]
+                    "#,
+                    IGNORE
+                )
+            }
+            Self::LocalNames => {
+                // Capturing nodes with names, such as `@id`, requires names to be
+                // unique across the *entire* query, else things break. Hence, us
+                // `@a.b` syntax (which seems undocumented).
+                formatcp!(
+                    r#"
+                        [
+                            (block
+                                (identifier) @{0}.declaration
+                                (body
+                                    (attribute
+                                        (identifier) @name.declaration
+                                    )
+                                )
+                                (#eq? @{0}.declaration "locals")
+                            )
+                            (
+                                (variable_expr
+                                    (identifier) @{0}.usage
+                                    (#eq? @{0}.usage "local")
+                                )
+                                .
+                                (get_attr
+                                    (identifier) @name.usage
+                                )
+                            )
+                        ]
+                    "#,
+                    IGNORE
+                )
+            }
+            Self::ProviderNames => {
+                formatcp!(
+                    r#"
+                        (block
+                            (identifier) @{0}
+                            (string_lit (template_literal) @name)
+                            (#eq? @{0} "provider")
+                        )
+                    "#,
+                    IGNORE
+                )
+            }
+            Self::OutputNames => {
+                formatcp!(
+                    r#"
+                        (block
+                            (identifier) @{0}
+                            (string_lit (template_literal) @name)
+                            (#eq? @{0} "output")
+                        )
+                    "#,
+                    IGNORE
+                )
+            }
+            Self::Attributes => "(attribute (identifier) @name)",
             Self::Comments => "(comment) @comment",
             Self::Strings => {
                 r"
@@ -341,8 +459,11 @@ impl LanguageScoper for CompiledQuery {
         &self.0.positive_query
     }
 
-    fn neg_query(&self) -> Option<&TSQuery> {
-        self.0.negative_query.as_ref()
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("#"),
+            block: Some(("/*", "*/")),
+        }
     }
 }
 
@@ -350,4 +471,8 @@ impl Find for CompiledQuery {
     fn extensions(&self) -> &'static [&'static str] {
         &["hcl", "tf"]
     }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["HCL", "terraform"]
+    }
 }
@@ -0,0 +1,149 @@
+use std::fmt::Debug;
+
+use clap::ValueEnum;
+use const_format::formatcp;
+
+use super::{
+    CommentSyntax, Find, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery,
+    TSQueryError, IGNORE,
+};
+
+/// A compiled query for the JavaScript language (JSX included).
+#[derive(Debug)]
+pub struct CompiledQuery(super::CompiledQuery);
+
+impl TryFrom<QuerySource> for CompiledQuery {
+    type Error = TSQueryError;
+
+    /// Create a new compiled query for the JavaScript language.
+    ///
+    /// # Errors
+    ///
+    /// See the concrete type of the [`TSQueryError`](tree_sitter::QueryError) variant for when this method errors.
+    fn try_from(query: QuerySource) -> Result<Self, Self::Error> {
+        let q =
+            super::CompiledQuery::from_source(&tree_sitter_javascript::LANGUAGE.into(), &query)?;
+        Ok(Self(q))
+    }
+}
+
+impl From<PreparedQuery> for CompiledQuery {
+    fn from(query: PreparedQuery) -> Self {
+        Self(super::CompiledQuery::from_prepared_query(
+            &tree_sitter_javascript::LANGUAGE.into(),
+            query.as_str(),
+        ))
+    }
+}
+
+/// Prepared tree-sitter queries for JavaScript.
+///
+/// The JavaScript grammar includes JSX, and is more lenient towards legacy code than
+/// the TypeScript one.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PreparedQuery {
+    /// Comments.
+    Comments,
+    /// Strings (literal, template, JSX attribute values).
+    Strings,
+    /// Template literals (in their entirety; backticks and substitutions included).
+    TemplateStrings,
+    /// Imports (module specifiers of `import` statements, dynamic `import()` and
+    /// `require()` calls).
+    Imports,
+    /// Names of functions, generators and methods, including those of function
+    /// expressions assigned to variables (`const f = () => {}`).
+    FunctionNames,
+}
+
+impl PreparedQuery {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports => Some(Self::Imports),
+            GenericQuery::FunctionNames => Some(Self::FunctionNames),
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        // Seems to not play nice with the macro. Put up here, else interpolation is
+        // affected.
+        #[allow(clippy::needless_raw_string_hashes)]
+        match self {
+            Self::Comments => "(comment) @comment",
+            Self::Strings => "(string_fragment) @string",
+            Self::TemplateStrings => "(template_string) @template_string",
+            Self::Imports => {
+                formatcp!(
+                    r#"
+                    (import_statement source: (string (string_fragment) @sf))
+                    (call_expression
+                        function: (import)
+                        arguments: (arguments . (string (string_fragment) @sf))
+                    )
+                    (call_expression
+                        function: (identifier) @{0}
+                        arguments: (arguments . (string (string_fragment) @sf))
+                        (#eq? @{0} "require")
+                    )
+                    "#,
+                    IGNORE
+                )
+            }
+            Self::FunctionNames => {
+                r"
+                [
+                    (function_declaration name: (identifier) @name)
+                    (generator_function_declaration name: (identifier) @name)
+                    (function_expression name: (identifier) @name)
+                    (method_definition name: (_) @name)
+                    (variable_declarator
+                        name: (identifier) @name
+                        value: [
+                            (arrow_function)
+                            (function_expression)
+                            (generator_function)
+                        ]
+                    )
+                ]
+                "
+            }
+        }
+    }
+}
+
+impl LanguageScoper for CompiledQuery {
+    fn lang() -> TSLanguage {
+        tree_sitter_javascript::LANGUAGE.into()
+    }
+
+    fn pos_query(&self) -> &TSQuery {
+        &self.0.positive_query
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("//"),
+            block: Some(("/*", "*/")),
+        }
+    }
+}
+
+impl Find for CompiledQuery {
+    fn extensions(&self) -> &'static [&'static str] {
+        &["js", "jsx", "mjs", "cjs"]
+    }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["JavaScript", "js", "node"]
+    }
+
+    fn interpreters(&self) -> Option<&'static [&'static str]> {
+        Some(&["node", "nodejs"])
+    }
+}
@@ -3,7 +3,10 @@ use std::fmt::Debug;
 use clap::ValueEnum;
 use const_format::formatcp;
 
-use super::{Find, LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError};
+use super::{
+    CommentSyntax, Find, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery,
+    TSQueryError,
+};
 use crate::scoping::langs::IGNORE;
 
 /// A compiled query for the Python language.
@@ -48,6 +51,12 @@ pub enum PreparedQuery {
     FunctionNames,
     /// Function calls.
     FunctionCalls,
+    /// Class names, at the definition site.
+    ClassNames,
+    /// Method names, at the definition site (functions directly inside `class` bodies).
+    MethodNames,
+    /// Decorators (incl. `@`; excl. the decorated definition).
+    Decorators,
     /// Class definitions (in their entirety).
     Class,
     /// Function definitions (*all* `def` block in their entirety).
@@ -77,8 +86,21 @@ pub enum PreparedQuery {
 }
 
 impl PreparedQuery {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports => Some(Self::Imports),
+            GenericQuery::FunctionNames => Some(Self::FunctionNames),
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
     #[allow(clippy::too_many_lines)]
-    const fn as_str(self) -> &'static str {
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Comments => "(comment) @comment",
             Self::Strings => "(string_content) @string",
@@ -131,6 +153,28 @@ impl PreparedQuery {
                 )
                 "
             }
+            Self::ClassNames => {
+                r"
+                (class_definition
+                    name: (identifier) @class-name
+                )
+                "
+            }
+            Self::MethodNames => {
+                r"
+                (class_definition
+                    body: (block
+                        [
+                            (function_definition name: (identifier) @method-name)
+                            (decorated_definition
+                                definition: (function_definition name: (identifier) @method-name)
+                            )
+                        ]
+                    )
+                )
+                "
+            }
+            Self::Decorators => "(decorator) @decorator",
             Self::Class => "(class_definition) @class",
             Self::Def => "(function_definition) @def",
             Self::AsyncDef => r#"((function_definition) @def (#match? @def "^async "))"#,
@@ -198,8 +242,11 @@ impl LanguageScoper for CompiledQuery {
         &self.0.positive_query
     }
 
-    fn neg_query(&self) -> Option<&TSQuery> {
-        self.0.negative_query.as_ref()
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("#"),
+            block: None,
+        }
     }
 }
 
@@ -208,7 +255,11 @@ impl Find for CompiledQuery {
         &["py"]
     }
 
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["Python", "python3"]
+    }
+
     fn interpreters(&self) -> Option<&'static [&'static str]> {
-        Some(&["python", "python3"])
+        Some(&["python", "python3", "pypy", "pypy3"])
     }
 }
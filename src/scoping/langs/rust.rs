@@ -3,7 +3,10 @@ use std::fmt::Debug;
 use clap::ValueEnum;
 use const_format::formatcp;
 
-use super::{Find, LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError, IGNORE};
+use super::{
+    CommentSyntax, Find, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery,
+    TSQueryError, IGNORE,
+};
 
 /// A compiled query for the Rust language.
 #[derive(Debug)]
@@ -50,6 +53,8 @@ pub enum PreparedQuery {
     Attribute,
     /// `struct` definitions.
     Struct,
+    /// Names of `struct` definitions.
+    StructName,
     /// `struct` definitions not marked `pub`.
     PrivStruct,
     /// `struct` definitions marked `pub`.
@@ -62,6 +67,8 @@ pub enum PreparedQuery {
     PubSuperStruct,
     /// `enum` definitions.
     Enum,
+    /// Names of `enum` definitions.
+    EnumName,
     /// `enum` definitions not marked `pub`.
     PrivEnum,
     /// `enum` definitions marked `pub`.
@@ -76,6 +83,9 @@ pub enum PreparedQuery {
     EnumVariant,
     /// Function definitions.
     Fn,
+    /// Names of function definitions and declarations (incl. methods, and function
+    /// signatures in traits).
+    FnName,
     /// Function definitions inside `impl` blocks (associated functions/methods).
     ImplFn,
     /// Function definitions not marked `pub`.
@@ -101,6 +111,8 @@ pub enum PreparedQuery {
     TestFn,
     /// `trait` definitions.
     Trait,
+    /// Names of `trait` definitions.
+    TraitName,
     /// `impl` blocks.
     Impl,
     /// `impl` blocks for types (`impl SomeType {}`).
@@ -125,8 +137,21 @@ pub enum PreparedQuery {
 }
 
 impl PreparedQuery {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports => Some(Self::Uses),
+            GenericQuery::FunctionNames => Some(Self::FnName),
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
     #[allow(clippy::too_many_lines)]
-    const fn as_str(self) -> &'static str {
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Comments => {
                 r#"
@@ -166,6 +191,7 @@ impl PreparedQuery {
             Self::Strings => "(string_content) @string",
             Self::Attribute => "(attribute) @attribute",
             Self::Struct => "(struct_item) @struct_item",
+            Self::StructName => "(struct_item name: (type_identifier) @name)",
             Self::PrivStruct => {
                 r"(struct_item
                     .
@@ -194,6 +220,7 @@ impl PreparedQuery {
                 ) @struct_item"
             }
             Self::Enum => "(enum_item) @enum_item",
+            Self::EnumName => "(enum_item name: (type_identifier) @name)",
             Self::PrivEnum => {
                 r"(enum_item
                     .
@@ -223,6 +250,14 @@ impl PreparedQuery {
             }
             Self::EnumVariant => "(enum_variant) @enum_variant",
             Self::Fn => "(function_item) @function_item",
+            Self::FnName => {
+                r"
+                [
+                    (function_item name: (identifier) @name)
+                    (function_signature_item name: (identifier) @name)
+                ]
+                "
+            }
             Self::ImplFn => {
                 r"(impl_item
                     body: (_ (function_item) @function)
@@ -298,6 +333,7 @@ impl PreparedQuery {
                 )
             }
             Self::Trait => "(trait_item) @trait_item",
+            Self::TraitName => "(trait_item name: (type_identifier) @name)",
             Self::Impl => "(impl_item) @impl_item",
             Self::ImplType => {
                 r"(impl_item
@@ -367,8 +403,11 @@ impl LanguageScoper for CompiledQuery {
         &self.0.positive_query
     }
 
-    fn neg_query(&self) -> Option<&TSQuery> {
-        self.0.negative_query.as_ref()
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("//"),
+            block: Some(("/*", "*/")),
+        }
     }
 }
 
@@ -376,4 +415,12 @@ impl Find for CompiledQuery {
     fn extensions(&self) -> &'static [&'static str] {
         &["rs"]
     }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["Rust", "rs"]
+    }
+
+    fn interpreters(&self) -> Option<&'static [&'static str]> {
+        Some(&["rust-script"])
+    }
 }
@@ -0,0 +1,134 @@
+use std::fmt::Debug;
+
+use clap::ValueEnum;
+
+use super::{
+    CommentSyntax, Find, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery,
+    TSQueryError,
+};
+
+/// A compiled query for the TOML language.
+#[derive(Debug)]
+pub struct CompiledQuery(super::CompiledQuery);
+
+impl TryFrom<QuerySource> for CompiledQuery {
+    type Error = TSQueryError;
+
+    /// Create a new compiled query for the TOML language.
+    ///
+    /// # Errors
+    ///
+    /// See the concrete type of the [`TSQueryError`](tree_sitter::QueryError) variant for when this method errors.
+    fn try_from(query: QuerySource) -> Result<Self, Self::Error> {
+        let q = super::CompiledQuery::from_source(&tree_sitter_toml_ng::LANGUAGE.into(), &query)?;
+        Ok(Self(q))
+    }
+}
+
+impl From<PreparedQuery> for CompiledQuery {
+    fn from(query: PreparedQuery) -> Self {
+        Self(super::CompiledQuery::from_prepared_query(
+            &tree_sitter_toml_ng::LANGUAGE.into(),
+            query.as_str(),
+        ))
+    }
+}
+
+/// Prepared tree-sitter queries for TOML.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PreparedQuery {
+    /// Comments.
+    Comments,
+    /// Keys of key/value pairs, including in inline tables (bare, quoted and dotted;
+    /// quotes included, if any).
+    ///
+    /// Table headers are excluded.
+    Keys,
+    /// String values (basic, literal and multi-line; quotes included).
+    Strings,
+    /// Names of tables (`[name]`) and arrays of tables (`[[name]]`), without brackets.
+    TableHeaders,
+}
+
+impl PreparedQuery {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports | GenericQuery::FunctionNames => None,
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Comments => "(comment) @comment",
+            Self::Keys => {
+                // The key is always the first named child
+                r"
+                (pair
+                    .
+                    [
+                        (bare_key)
+                        (quoted_key)
+                        (dotted_key)
+                    ] @key
+                )
+                "
+            }
+            Self::Strings => "(string) @string",
+            Self::TableHeaders => {
+                r"
+                [
+                    (table
+                        .
+                        [
+                            (bare_key)
+                            (quoted_key)
+                            (dotted_key)
+                        ] @header
+                    )
+                    (table_array_element
+                        .
+                        [
+                            (bare_key)
+                            (quoted_key)
+                            (dotted_key)
+                        ] @header
+                    )
+                ]
+                "
+            }
+        }
+    }
+}
+
+impl LanguageScoper for CompiledQuery {
+    fn lang() -> TSLanguage {
+        tree_sitter_toml_ng::LANGUAGE.into()
+    }
+
+    fn pos_query(&self) -> &TSQuery {
+        &self.0.positive_query
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("#"),
+            block: None,
+        }
+    }
+}
+
+impl Find for CompiledQuery {
+    fn extensions(&self) -> &'static [&'static str] {
+        &["toml"]
+    }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["TOML"]
+    }
+}
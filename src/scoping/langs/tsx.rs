@@ -0,0 +1,136 @@
+use std::fmt::Debug;
+
+use clap::ValueEnum;
+
+use super::{
+    typescript, CommentSyntax, Find, GenericQuery, LanguageScoper, QuerySource, TSLanguage,
+    TSQuery, TSQueryError,
+};
+
+/// A compiled query for the TypeScript language with JSX (TSX).
+#[derive(Debug)]
+pub struct CompiledQuery(super::CompiledQuery);
+
+impl TryFrom<QuerySource> for CompiledQuery {
+    type Error = TSQueryError;
+
+    /// Create a new compiled query for the TSX language.
+    ///
+    /// # Errors
+    ///
+    /// See the concrete type of the [`TSQueryError`](tree_sitter::QueryError) variant for when this method errors.
+    fn try_from(query: QuerySource) -> Result<Self, Self::Error> {
+        let q = super::CompiledQuery::from_source(
+            &tree_sitter_typescript::LANGUAGE_TSX.into(),
+            &query,
+        )?;
+        Ok(Self(q))
+    }
+}
+
+impl From<PreparedQuery> for CompiledQuery {
+    fn from(query: PreparedQuery) -> Self {
+        Self(super::CompiledQuery::from_prepared_query(
+            &tree_sitter_typescript::LANGUAGE_TSX.into(),
+            query.as_str(),
+        ))
+    }
+}
+
+/// Prepared tree-sitter queries for TSX.
+///
+/// The TSX grammar is a superset of the TypeScript one, except for angle-bracket type
+/// assertions (`<T>value`), which clash with JSX syntax.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PreparedQuery {
+    /// Comments.
+    Comments,
+    /// Strings (literal, template, JSX attribute values).
+    Strings,
+    /// Template literals (in their entirety; backticks and substitutions included).
+    TemplateStrings,
+    /// Imports (module specifiers).
+    Imports,
+    /// Names of `interface` definitions.
+    InterfaceNames,
+    /// Names of type alias declarations.
+    TypeAliasNames,
+    /// JSX element names, in opening, closing and self-closing tags (`div`,
+    /// `Foo.Bar`, ...).
+    JsxElementNames,
+    /// JSX attribute (prop) names.
+    JsxAttributeNames,
+}
+
+impl PreparedQuery {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports => Some(Self::Imports),
+            GenericQuery::FunctionNames => None,
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Comments => typescript::PreparedQuery::Comments.as_str(),
+            Self::Strings => typescript::PreparedQuery::Strings.as_str(),
+            Self::TemplateStrings => typescript::PreparedQuery::TemplateStrings.as_str(),
+            Self::Imports => typescript::PreparedQuery::Imports.as_str(),
+            Self::InterfaceNames => typescript::PreparedQuery::InterfaceNames.as_str(),
+            Self::TypeAliasNames => typescript::PreparedQuery::TypeAliasNames.as_str(),
+            Self::JsxElementNames => {
+                r"
+                [
+                    (jsx_opening_element name: (_) @name)
+                    (jsx_closing_element name: (_) @name)
+                    (jsx_self_closing_element name: (_) @name)
+                ]
+                "
+            }
+            Self::JsxAttributeNames => {
+                r"
+                (jsx_attribute
+                    .
+                    [
+                        (property_identifier)
+                        (jsx_namespace_name)
+                    ] @name
+                )
+                "
+            }
+        }
+    }
+}
+
+impl LanguageScoper for CompiledQuery {
+    fn lang() -> TSLanguage {
+        tree_sitter_typescript::LANGUAGE_TSX.into()
+    }
+
+    fn pos_query(&self) -> &TSQuery {
+        &self.0.positive_query
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("//"),
+            block: Some(("/*", "*/")),
+        }
+    }
+}
+
+impl Find for CompiledQuery {
+    fn extensions(&self) -> &'static [&'static str] {
+        &["tsx"]
+    }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["TSX"]
+    }
+}
@@ -2,7 +2,10 @@ use std::fmt::Debug;
 
 use clap::ValueEnum;
 
-use super::{Find, LanguageScoper, QuerySource, TSLanguage, TSQuery, TSQueryError};
+use super::{
+    CommentSyntax, Find, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery,
+    TSQueryError,
+};
 
 /// A compiled query for the TypeScript language.
 #[derive(Debug)]
@@ -41,6 +44,8 @@ pub enum PreparedQuery {
     Comments,
     /// Strings (literal, template).
     Strings,
+    /// Template literals (in their entirety; backticks and substitutions included).
+    TemplateStrings,
     /// Imports (module specifiers).
     Imports,
     /// Any `function` definitions.
@@ -59,6 +64,8 @@ pub enum PreparedQuery {
     Enum,
     /// `interface` definitions.
     Interface,
+    /// Names of `interface` definitions.
+    InterfaceNames,
     /// `try`/`catch`/`finally` blocks.
     TryCatch,
     /// Variable declarations (`let`, `const`, `var`).
@@ -73,6 +80,8 @@ pub enum PreparedQuery {
     TypeParams,
     /// Type alias declarations.
     TypeAlias,
+    /// Names of type alias declarations.
+    TypeAliasNames,
     /// `namespace` blocks.
     Namespace,
     /// `export` blocks.
@@ -80,11 +89,25 @@ pub enum PreparedQuery {
 }
 
 impl PreparedQuery {
-    const fn as_str(self) -> &'static str {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings => Some(Self::Strings),
+            GenericQuery::Imports => Some(Self::Imports),
+            GenericQuery::FunctionNames => None,
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Comments => "(comment) @comment",
             Self::Imports => r"(import_statement source: (string (string_fragment) @sf))",
             Self::Strings => "(string_fragment) @string",
+            Self::TemplateStrings => "(template_string) @template_string",
             Self::Function => "(function_declaration) @func",
             Self::AsyncFunction => {
                 r#"(
@@ -105,6 +128,7 @@ impl PreparedQuery {
             Self::Class => "(class_declaration) @class",
             Self::Enum => "(enum_declaration) @enum",
             Self::Interface => "(interface_declaration) @interface",
+            Self::InterfaceNames => "(interface_declaration name: (type_identifier) @name)",
             Self::TryCatch => "(try_statement) @try",
             Self::VarDecl => "(variable_declarator) @var_decl",
             Self::Let => {
@@ -124,6 +148,7 @@ impl PreparedQuery {
             }
             Self::TypeParams => "(type_parameters) @type_parameters",
             Self::TypeAlias => "(type_alias_declaration) @type_alias_declaration",
+            Self::TypeAliasNames => "(type_alias_declaration name: (type_identifier) @name)",
             Self::Namespace => "(internal_module) @internal_module",
             Self::Export => "(export_statement) @export",
         }
@@ -139,8 +164,11 @@ impl LanguageScoper for CompiledQuery {
         &self.0.positive_query
     }
 
-    fn neg_query(&self) -> Option<&TSQuery> {
-        self.0.negative_query.as_ref()
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("//"),
+            block: Some(("/*", "*/")),
+        }
     }
 }
 
@@ -148,4 +176,12 @@ impl Find for CompiledQuery {
     fn extensions(&self) -> &'static [&'static str] {
         &["ts", "tsx"]
     }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["TypeScript", "ts"]
+    }
+
+    fn interpreters(&self) -> Option<&'static [&'static str]> {
+        Some(&["ts-node", "tsx", "deno", "bun"])
+    }
 }
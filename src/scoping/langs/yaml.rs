@@ -0,0 +1,130 @@
+use std::fmt::Debug;
+
+use clap::ValueEnum;
+use const_format::formatcp;
+
+use super::{
+    CommentSyntax, Find, GenericQuery, LanguageScoper, QuerySource, TSLanguage, TSQuery,
+    TSQueryError, IGNORE,
+};
+
+/// A compiled query for the YAML language.
+#[derive(Debug)]
+pub struct CompiledQuery(super::CompiledQuery);
+
+impl TryFrom<QuerySource> for CompiledQuery {
+    type Error = TSQueryError;
+
+    /// Create a new compiled query for the YAML language.
+    ///
+    /// # Errors
+    ///
+    /// See the concrete type of the [`TSQueryError`](tree_sitter::QueryError) variant for when this method errors.
+    fn try_from(query: QuerySource) -> Result<Self, Self::Error> {
+        let q = super::CompiledQuery::from_source(&tree_sitter_yaml::LANGUAGE.into(), &query)?;
+        Ok(Self(q))
+    }
+}
+
+impl From<PreparedQuery> for CompiledQuery {
+    fn from(query: PreparedQuery) -> Self {
+        Self(super::CompiledQuery::from_prepared_query(
+            &tree_sitter_yaml::LANGUAGE.into(),
+            query.as_str(),
+        ))
+    }
+}
+
+/// Prepared tree-sitter queries for YAML.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PreparedQuery {
+    /// Comments.
+    Comments,
+    /// Keys of mappings (block and flow style; quotes included, if any).
+    Keys,
+    /// String values (plain, quoted, block scalars; quotes and indicators included).
+    ///
+    /// Keys are excluded, as are non-string scalars such as numbers and booleans.
+    Values,
+    /// Anchor names, at both their definition (`&name`) and usage (`*name`) sites.
+    Anchors,
+}
+
+impl PreparedQuery {
+    /// The prepared query corresponding to the given generic one, if any.
+    #[must_use]
+    pub const fn from_generic(query: GenericQuery) -> Option<Self> {
+        match query {
+            GenericQuery::Comments => Some(Self::Comments),
+            GenericQuery::Strings | GenericQuery::Imports | GenericQuery::FunctionNames => None,
+        }
+    }
+
+    /// The tree-sitter query source of this prepared query.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Comments => "(comment) @comment",
+            Self::Keys => {
+                r"
+                [
+                    (block_mapping_pair key: (_) @key)
+                    (flow_pair key: (_) @key)
+                ]
+                "
+            }
+            Self::Values => {
+                // Keys can be strings just the same; capture everything string-like
+                // first, then subtract keys.
+                formatcp!(
+                    r"
+                    [
+                        (string_scalar) @value
+                        (single_quote_scalar) @value
+                        (double_quote_scalar) @value
+                        (block_scalar) @value
+                        (block_mapping_pair key: (_) @{0}.block)
+                        (flow_pair key: (_) @{0}.flow)
+                    ]
+                    ",
+                    IGNORE
+                )
+            }
+            Self::Anchors => {
+                r"
+                [
+                    (anchor (anchor_name) @anchor)
+                    (alias (alias_name) @alias)
+                ]
+                "
+            }
+        }
+    }
+}
+
+impl LanguageScoper for CompiledQuery {
+    fn lang() -> TSLanguage {
+        tree_sitter_yaml::LANGUAGE.into()
+    }
+
+    fn pos_query(&self) -> &TSQuery {
+        &self.0.positive_query
+    }
+
+    fn comment_syntax(&self) -> CommentSyntax {
+        CommentSyntax {
+            line: Some("#"),
+            block: None,
+        }
+    }
+}
+
+impl Find for CompiledQuery {
+    fn extensions(&self) -> &'static [&'static str] {
+        &["yaml", "yml"]
+    }
+
+    fn linguist_names(&self) -> &'static [&'static str] {
+        &["YAML", "yml"]
+    }
+}
@@ -0,0 +1,168 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+
+use log::trace;
+
+use super::scope::RangesWithContext;
+use super::Scoper;
+use crate::ranges::Ranges;
+#[cfg(doc)]
+use crate::scoping::view::ScopedViewBuilder;
+
+/// Scopes lines by their (1-based) line numbers.
+///
+/// Lines are scoped including their line ending. As line numbers only make sense for
+/// the entire input, use this with [`ScopedViewBuilder::intersect`] instead of
+/// [`ScopedViewBuilder::explode`] when combining with other scopers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lines(Vec<RangeInclusive<usize>>);
+
+impl Lines {
+    /// Creates a new scoper for all lines in any of the given (inclusive) `ranges`.
+    ///
+    /// Use [`usize::MAX`] as the end for ranges open to the end of input.
+    #[must_use]
+    pub const fn new(ranges: Vec<RangeInclusive<usize>>) -> Self {
+        Self(ranges)
+    }
+
+    /// The (inclusive) ranges of line numbers in scope.
+    #[must_use]
+    pub fn ranges(&self) -> &[RangeInclusive<usize>] {
+        &self.0
+    }
+
+    fn contains(&self, line: usize) -> bool {
+        self.0.iter().any(|range| range.contains(&line))
+    }
+}
+
+/// An error that can occur when parsing [`Lines`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinesError {
+    /// A range could not be parsed.
+    InvalidRange(String),
+}
+
+impl fmt::Display for LinesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRange(range) => write!(
+                f,
+                "Invalid line range '{range}', expected e.g. '7', '10..50', '20..' or '..5' \
+                (line numbers start at 1)"
+            ),
+        }
+    }
+}
+
+impl Error for LinesError {}
+
+impl FromStr for Lines {
+    type Err = LinesError;
+
+    /// Parses comma-separated, inclusive ranges of line numbers, such as `1..3,7,20..`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|range| {
+                let invalid = || LinesError::InvalidRange(range.to_owned());
+                let number = |n: &str| n.trim().parse::<usize>().map_err(|_| invalid());
+
+                let (start, end) = if let Some((start, end)) = range.split_once("..") {
+                    let start = if start.trim().is_empty() {
+                        1
+                    } else {
+                        number(start)?
+                    };
+                    let end = if end.trim().is_empty() {
+                        usize::MAX
+                    } else {
+                        number(end)?
+                    };
+                    (start, end)
+                } else {
+                    let line = number(range)?;
+                    (line, line)
+                };
+
+                if start == 0 || start > end {
+                    return Err(invalid());
+                }
+
+                Ok(start..=end)
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl Scoper for Lines {
+    fn scope_raw<'viewee>(&self, input: &'viewee str) -> RangesWithContext<'viewee> {
+        let mut ranges: Ranges<usize> = input
+            .split_inclusive('\n')
+            .scan(0, |offset, line| {
+                let start = *offset;
+                *offset += line.len();
+                Some(start..*offset)
+            })
+            .zip(1..)
+            .filter(|(_, line)| self.contains(*line))
+            .map(|(range, _)| range)
+            .collect::<Ranges<usize>>();
+        ranges.merge();
+
+        trace!("Ranges in scope for {:?}: {:?}", self, ranges);
+
+        ranges
+            .into_iter()
+            .map(|r: Range<usize>| (r, None))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("7", Ok(vec![7..=7]))]
+    #[case("10..50", Ok(vec![10..=50]))]
+    #[case("20..", Ok(vec![20..=usize::MAX]))]
+    #[case("..5", Ok(vec![1..=5]))]
+    #[case("..", Ok(vec![1..=usize::MAX]))]
+    #[case("1..3,7, 9 .. 10", Ok(vec![1..=3, 7..=7, 9..=10]))]
+    #[case("3..3", Ok(vec![3..=3]))]
+    #[case("", Err(()))]
+    #[case("0", Err(()))]
+    #[case("0..3", Err(()))]
+    #[case("5..3", Err(()))]
+    #[case("a..3", Err(()))]
+    #[case("1..3,", Err(()))]
+    #[case("1-3", Err(()))]
+    fn test_parse(#[case] input: &str, #[case] expected: Result<Vec<RangeInclusive<usize>>, ()>) {
+        assert_eq!(
+            input.parse::<Lines>().map_err(|_| ()),
+            expected.map(Lines::new)
+        );
+    }
+
+    #[rstest]
+    #[case("a\nb\nc\n", "2", vec![2..4])]
+    #[case("a\nb\nc\n", "2..", vec![2..6])]
+    #[case("a\nb\nc", "3", vec![4..5])] // No trailing newline
+    #[case("a\nb\nc\n", "1,3", vec![0..2, 4..6])]
+    #[case("a\nb\nc\n", "1,2", vec![0..4])] // Adjacent lines are merged
+    #[case("a\r\nb\r\n", "1", vec![0..3])]
+    #[case("a\nb\n", "5..", vec![])]
+    #[case("", "1", vec![])]
+    fn test_scope(#[case] input: &str, #[case] lines: &str, #[case] expected: Vec<Range<usize>>) {
+        let lines: Lines = lines.parse().unwrap();
+        let expected: RangesWithContext<'_> = expected.into_iter().map(|r| (r, None)).collect();
+
+        assert_eq!(lines.scope_raw(input), expected);
+    }
+}
@@ -2,6 +2,7 @@ use std::error::Error;
 use std::fmt;
 use std::ops::Range;
 
+use aho_corasick::{AhoCorasick, MatchKind};
 use log::trace;
 use unescape::unescape;
 
@@ -18,6 +19,8 @@ pub struct Literal(String);
 pub enum LiteralError {
     /// The literal contains invalid escape sequences.
     InvalidEscapeSequences(String),
+    /// The literals are too many or too large to search for.
+    TooLarge(String),
 }
 
 impl fmt::Display for LiteralError {
@@ -26,6 +29,7 @@ impl fmt::Display for LiteralError {
             Self::InvalidEscapeSequences(literal) => {
                 write!(f, "Contains invalid escape sequences: '{literal}'")
             }
+            Self::TooLarge(msg) => write!(f, "Too many or too large literals: {msg}"),
         }
     }
 }
@@ -37,7 +41,7 @@ impl TryFrom<String> for Literal {
 
     fn try_from(literal: String) -> Result<Self, Self::Error> {
         let unescaped = unescape(&literal)
-            .ok_or_else(|| LiteralError::InvalidEscapeSequences(literal.to_string()))?;
+            .ok_or_else(|| LiteralError::InvalidEscapeSequences(literal.clone()))?;
 
         Ok(Self(unescaped))
     }
@@ -63,6 +67,49 @@ impl Scoper for Literal {
 
         ranges.into()
     }
+
+    fn maybe_in_scope(&self, input: &str) -> bool {
+        input.contains(&self.0)
+    }
+}
+
+/// Multiple literal strings for querying, any of which is in scope.
+///
+/// Backed by [Aho-Corasick](https://en.wikipedia.org/wiki/Aho%E2%80%93Corasick_algorithm),
+/// so this scales to large numbers of literals. Where literals overlap, the leftmost
+/// match wins, and of those the longest.
+#[derive(Debug)]
+pub struct Literals(AhoCorasick);
+
+impl TryFrom<Vec<String>> for Literals {
+    type Error = LiteralError;
+
+    fn try_from(literals: Vec<String>) -> Result<Self, Self::Error> {
+        let unescaped = literals
+            .into_iter()
+            .map(|literal| unescape(&literal).ok_or(LiteralError::InvalidEscapeSequences(literal)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(unescaped)
+            .map_err(|e| LiteralError::TooLarge(e.to_string()))?;
+
+        Ok(Self(automaton))
+    }
+}
+
+impl Scoper for Literals {
+    fn scope_raw<'viewee>(&self, input: &'viewee str) -> RangesWithContext<'viewee> {
+        let ranges: Ranges<usize> = self.0.find_iter(input).map(|m| m.range()).collect();
+        trace!("Ranges in scope for {:?}: {:?}", self, ranges);
+
+        ranges.into()
+    }
+
+    fn maybe_in_scope(&self, input: &str) -> bool {
+        self.0.is_match(input)
+    }
 }
 
 #[cfg(test)]
@@ -161,4 +208,29 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case("a b c", &["b"], "a B c")]
+    #[case("a b c", &["a", "c"], "A b C")]
+    #[case("foobar foo", &["foo", "foobar"], "FOOBAR FOO")] // Longest wins
+    #[case("abc", &["bc", "ab"], "ABc")] // Leftmost wins
+    #[case("a\tb", &[r"\t", "b"], "a\tB")]
+    #[case("abc", &["x"], "abc")]
+    fn test_literals_scoping(
+        #[case] input: &str,
+        #[case] literals: &[&str],
+        #[case] expected: &str,
+    ) {
+        let mut builder = crate::scoping::view::ScopedViewBuilder::new(input);
+        let literals =
+            Literals::try_from(literals.iter().map(ToString::to_string).collect::<Vec<_>>())
+                .unwrap();
+        assert_eq!(literals.maybe_in_scope(input), input != expected);
+
+        builder.explode(&literals);
+        let mut view = builder.build();
+        view.upper();
+
+        assert_eq!(view.to_string(), expected);
+    }
 }
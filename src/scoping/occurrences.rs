@@ -0,0 +1,157 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+#[cfg(doc)]
+use crate::scoping::scope::Scope::In;
+#[cfg(doc)]
+use crate::scoping::view::ScopedViewBuilder;
+
+/// Selects occurrences of [`In`] scopes by their (1-based) index, counted in order of
+/// appearance.
+///
+/// Unlike scopers, this cannot be applied to any input on its own, as it depends on
+/// what is already in scope. See [`ScopedViewBuilder::select`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrences(Vec<Occurrence>);
+
+/// A single selection of [`Occurrences`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Occurrence {
+    /// An inclusive range of occurrences. Use [`usize::MAX`] as the end for ranges
+    /// open to the end.
+    Range(RangeInclusive<usize>),
+    /// The last occurrence, however many there are.
+    Last,
+}
+
+impl Occurrences {
+    /// Creates a new selection of all occurrences selected by any of `occurrences`.
+    #[must_use]
+    pub const fn new(occurrences: Vec<Occurrence>) -> Self {
+        Self(occurrences)
+    }
+
+    /// The individual selections of occurrences.
+    #[must_use]
+    pub fn occurrences(&self) -> &[Occurrence] {
+        &self.0
+    }
+
+    /// Whether the `nth` (1-based) of `total` occurrences is selected.
+    #[must_use]
+    pub fn contains(&self, nth: usize, total: usize) -> bool {
+        self.0.iter().any(|occurrence| match occurrence {
+            Occurrence::Range(range) => range.contains(&nth),
+            Occurrence::Last => nth == total,
+        })
+    }
+}
+
+/// An error that can occur when parsing [`Occurrences`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OccurrencesError {
+    /// An occurrence could not be parsed.
+    InvalidOccurrence(String),
+}
+
+impl fmt::Display for OccurrencesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidOccurrence(occurrence) => write!(
+                f,
+                "Invalid occurrence '{occurrence}', expected e.g. '1', '2..4', '3..' or \
+                'last' (occurrences start at 1)"
+            ),
+        }
+    }
+}
+
+impl Error for OccurrencesError {}
+
+impl FromStr for Occurrences {
+    type Err = OccurrencesError;
+
+    /// Parses comma-separated occurrences, such as `1,3..5,last`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|occurrence| {
+                let invalid = || OccurrencesError::InvalidOccurrence(occurrence.to_owned());
+                let number = |n: &str| n.trim().parse::<usize>().map_err(|_| invalid());
+
+                if occurrence.trim() == "last" {
+                    return Ok(Occurrence::Last);
+                }
+
+                let (start, end) = if let Some((start, end)) = occurrence.split_once("..") {
+                    let start = if start.trim().is_empty() {
+                        1
+                    } else {
+                        number(start)?
+                    };
+                    let end = if end.trim().is_empty() {
+                        usize::MAX
+                    } else {
+                        number(end)?
+                    };
+                    (start, end)
+                } else {
+                    let nth = number(occurrence)?;
+                    (nth, nth)
+                };
+
+                if start == 0 || start > end {
+                    return Err(invalid());
+                }
+
+                Ok(Occurrence::Range(start..=end))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("1", Ok(vec![Occurrence::Range(1..=1)]))]
+    #[case("2..4", Ok(vec![Occurrence::Range(2..=4)]))]
+    #[case("3..", Ok(vec![Occurrence::Range(3..=usize::MAX)]))]
+    #[case("..2", Ok(vec![Occurrence::Range(1..=2)]))]
+    #[case("last", Ok(vec![Occurrence::Last]))]
+    #[case("1, last", Ok(vec![Occurrence::Range(1..=1), Occurrence::Last]))]
+    #[case("", Err(()))]
+    #[case("0", Err(()))]
+    #[case("3..2", Err(()))]
+    #[case("first", Err(()))]
+    #[case("last..", Err(()))]
+    fn test_parse(#[case] input: &str, #[case] expected: Result<Vec<Occurrence>, ()>) {
+        assert_eq!(
+            input.parse::<Occurrences>().map_err(|_| ()),
+            expected.map(Occurrences::new)
+        );
+    }
+
+    #[rstest]
+    #[case("1", 1, 3, true)]
+    #[case("1", 2, 3, false)]
+    #[case("2..", 3, 3, true)]
+    #[case("last", 3, 3, true)]
+    #[case("last", 2, 3, false)]
+    #[case("1,last", 1, 1, true)]
+    fn test_contains(
+        #[case] occurrences: &str,
+        #[case] nth: usize,
+        #[case] total: usize,
+        #[case] expected: bool,
+    ) {
+        let occurrences: Occurrences = occurrences.parse().unwrap();
+
+        assert_eq!(occurrences.contains(nth, total), expected);
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
 use super::scope::{RangesWithContext, ScopeContext};
 use super::Scoper;
@@ -11,6 +13,10 @@ use crate::{RegexPattern, GLOBAL_SCOPE};
 pub struct Regex {
     pattern: RegexPattern,
     captures: Vec<CaptureGroup>,
+    scoped_capture: Option<CaptureGroup>,
+    /// Whether any match in part of an input is also a match in the entire input.
+    /// Holds if the pattern contains no look-around, anchors or word boundaries.
+    prefilterable: bool,
 }
 
 /// A capture group in a regex, which can be either named (`(?<name>REGEX)`) or numbered
@@ -33,6 +39,17 @@ impl fmt::Display for CaptureGroup {
     }
 }
 
+impl FromStr for CaptureGroup {
+    type Err = Infallible;
+
+    /// Parses numbers as [`CaptureGroup::Numbered`], anything else as
+    /// [`CaptureGroup::Named`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.parse()
+            .map_or_else(|_| Self::Named(s.to_owned()), Self::Numbered))
+    }
+}
+
 impl Regex {
     /// Create a new regular expression.
     #[must_use]
@@ -47,11 +64,32 @@ impl Regex {
             })
             .collect();
 
+        // Patterns using fancy features (backreferences, ...) fail to parse here, and are
+        // then simply not prefiltered.
+        let prefilterable = regex_syntax::parse(pattern.as_str())
+            .is_ok_and(|hir| hir.properties().look_set().is_empty());
+
         Self {
             pattern,
             captures: capture_names,
+            scoped_capture: None,
+            prefilterable,
         }
     }
+
+    /// Scope only the span of the given capture `group` of each match, instead of the
+    /// entire match. Matches where the group did not participate are not in scope.
+    ///
+    /// All capture groups remain available as context (for example, for
+    /// [`Replacement`][`crate::actions::Replacement`]). Returns [`None`] if the pattern
+    /// contains no such group.
+    #[must_use]
+    pub fn with_capture_group(mut self, group: CaptureGroup) -> Option<Self> {
+        self.captures.contains(&group).then(|| {
+            self.scoped_capture = Some(group);
+            self
+        })
+    }
 }
 
 /// An error that can occur when parsing a regular expression.
@@ -102,12 +140,21 @@ impl Scoper for Regex {
                         })
                         .collect();
 
-                    ranges.push((
-                        cap.get(0)
-                            .expect("index 0 guaranteed to contain whole match")
-                            .range(),
-                        Some(ScopeContext::CaptureGroups(capture_context)),
-                    ));
+                    let range = match &self.scoped_capture {
+                        Some(CaptureGroup::Named(name)) => cap.name(name.as_str()),
+                        Some(CaptureGroup::Numbered(number)) => cap.get(*number),
+                        None => Some(
+                            cap.get(0)
+                                .expect("index 0 guaranteed to contain whole match"),
+                        ),
+                    };
+
+                    if let Some(range) = range {
+                        ranges.push((
+                            range.range(),
+                            Some(ScopeContext::CaptureGroups(capture_context)),
+                        ));
+                    }
                 }
                 // Let's blow up on purpose instead of silently continuing; any of
                 // these errors a user will likely want to know about, as they
@@ -126,6 +173,11 @@ impl Scoper for Regex {
 
         ranges
     }
+
+    fn maybe_in_scope(&self, input: &str) -> bool {
+        // A runtime error will surface when actually scoping, so do not hide it here
+        !self.prefilterable || self.pattern.is_match(input).unwrap_or(true)
+    }
 }
 
 #[cfg(test)]
@@ -461,13 +513,68 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[rstest]
+    #[case(r#"key = "value""#, r#"\w+ = "(\w+)""#, "1", Some(r#"key = "VALUE""#))]
+    #[case(
+        r#"key = "value""#,
+        r#"(?<k>\w+) = "\w+""#,
+        "k",
+        Some(r#"KEY = "value""#)
+    )]
+    #[case("a1 b c3", r"[a-z](\d)?", "1", Some("a1 b c3"))] // Unmatched group skipped
+    #[case("a1 b c3", r"[a-z](\d)?", "0", Some("A1 B C3"))]
+    #[case("a", r"(a)", "2", None)]
+    #[case("a", r"(?<x>a)", "y", None)]
+    fn test_regex_capture_group(
+        #[case] input: &str,
+        #[case] pattern: &str,
+        #[case] group: CaptureGroup,
+        #[case] expected: Option<&str>,
+    ) {
+        let Some(regex) = Regex::new(RegexPattern::new(pattern).unwrap()).with_capture_group(group)
+        else {
+            assert_eq!(expected, None);
+            return;
+        };
+
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&regex);
+        let mut view = builder.build();
+        view.upper();
+
+        assert_eq!(Some(view.to_string()), expected.map(str::to_owned));
+    }
+
+    #[rstest]
+    #[case("foo bar", r"ba+r", true)]
+    #[case("foo", r"bar", false)]
+    #[case("abd", r"(?i)ABC", false)]
+    #[case("abc", r"(?i)ABC", true)]
+    #[case("", r".*", true)]
+    // Might match in parts of the input only, so cannot be ruled out:
+    #[case("xfoo", r"\bfoo", true)]
+    #[case("xfoo", r"^foo", true)]
+    #[case("xfoo", r"(?<=y)foo", true)]
+    #[case("xfoo", r"(f)\1", true)]
+    fn test_regex_maybe_in_scope(
+        #[case] input: &str,
+        #[case] pattern: &str,
+        #[case] expected: bool,
+    ) {
+        let regex = Regex::new(RegexPattern::new(pattern).unwrap());
+
+        assert_eq!(regex.maybe_in_scope(input), expected);
+        if !expected {
+            assert!(regex.scope_raw(input).is_empty());
+        }
+    }
+
     mod fuzzyish {
         use std::time::{Duration, Instant};
 
         use log::info;
-        use rand;
         use rand::seq::SliceRandom;
-        use rand::Rng;
+        use rand::{self, Rng};
 
         use super::*;
         use crate::scoping::scope::ROScope;
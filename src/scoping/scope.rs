@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::ops::Range;
+use std::path::Path;
 
 use itertools::Itertools;
 use log::{debug, trace};
@@ -216,6 +217,60 @@ pub enum ScopeContext<'viewee> {
     CaptureGroups(HashMap<CaptureGroup, &'viewee str>),
 }
 
+impl ScopeContext<'_> {
+    /// Adds all items of `other` not yet present in this context, so items already
+    /// present take precedence.
+    ///
+    /// Only [named][`CaptureGroup::Named`] capture groups are inherited: numbered ones
+    /// refer to the pattern that produced them, so `$1` of a nested scope never
+    /// resolves to a group of the outer one.
+    pub fn inherit(&mut self, other: &Self) {
+        match (self, other) {
+            (Self::CaptureGroups(groups), Self::CaptureGroups(other)) => {
+                for (group, content) in other {
+                    if let CaptureGroup::Named(_) = group {
+                        groups.entry(group.clone()).or_insert(content);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Lets `ctx` [inherit][`ScopeContext::inherit`] from `other`, if any.
+pub(crate) fn inherit_context<'viewee>(
+    ctx: &mut Option<ScopeContext<'viewee>>,
+    other: Option<&ScopeContext<'viewee>>,
+) {
+    match (ctx, other) {
+        (Some(ctx), Some(other)) => ctx.inherit(other),
+        (ctx @ None, Some(other)) => {
+            let mut inherited = ScopeContext::CaptureGroups(HashMap::new());
+            inherited.inherit(other);
+            *ctx = Some(inherited);
+        }
+        (_, None) => {}
+    }
+}
+
+/// Where a scope is located in the overall input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location<'a> {
+    /// The file the input stems from, if any.
+    pub file: Option<&'a Path>,
+    /// The (1-based) line number the scope starts on.
+    pub line: usize,
+}
+
+impl Default for Location<'_> {
+    fn default() -> Self {
+        Self {
+            file: None,
+            line: 1,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
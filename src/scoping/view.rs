@@ -1,15 +1,19 @@
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fmt;
+use std::path::PathBuf;
 
 use itertools::Itertools;
 use log::{debug, trace, warn};
 
 use crate::actions::{self, Action, ActionError};
+use crate::ranges::Ranges;
 use crate::scoping::dosfix::DosFix;
+use crate::scoping::occurrences::Occurrences;
 use crate::scoping::scope::Scope::{In, Out};
 #[cfg(doc)]
 use crate::scoping::scope::ScopeContext;
-use crate::scoping::scope::{ROScope, ROScopes, RWScope, RWScopes};
+use crate::scoping::scope::{inherit_context, Location, ROScope, ROScopes, RWScope, RWScopes};
 use crate::scoping::Scoper;
 
 /// A view of some input, sorted into parts, which are either [`In`] or [`Out`] of scope
@@ -22,6 +26,8 @@ use crate::scoping::Scoper;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ScopedView<'viewee> {
     scopes: RWScopes<'viewee>,
+    file: Option<PathBuf>,
+    first_line: usize,
 }
 
 /// Core implementations.
@@ -29,7 +35,27 @@ impl<'viewee> ScopedView<'viewee> {
     /// Create a new view from the given scopes.
     #[must_use]
     pub const fn new(scopes: RWScopes<'viewee>) -> Self {
-        Self { scopes }
+        Self {
+            scopes,
+            file: None,
+            first_line: 1,
+        }
+    }
+
+    /// Set the file this view's input stems from, made available to [`Action`]s as
+    /// part of their [`Location`].
+    pub fn set_file(&mut self, file: PathBuf) -> &mut Self {
+        self.file = Some(file);
+
+        self
+    }
+
+    /// Set the (1-based) line number this view's input starts on, in case it is only
+    /// part of a larger input. Defaults to `1`.
+    pub fn set_first_line(&mut self, line: usize) -> &mut Self {
+        self.first_line = line;
+
+        self
     }
 
     /// Access the scopes contained in this view.
@@ -77,25 +103,41 @@ impl<'viewee> ScopedView<'viewee> {
         action: &impl Action,
         use_context: bool,
     ) -> Result<&mut Self, ActionError> {
+        let mut line = self.first_line;
+
         for scope in &mut self.scopes.0 {
+            let newlines = <&str>::from(&*scope).matches('\n').count();
+
             match scope {
                 RWScope(In(s, ctx)) => {
                     debug!("Mapping with context: {:?}", ctx);
-                    let res = match (&ctx, use_context) {
-                        (Some(c), true) => action.act_with_context(s, c)?,
-                        _ => action.act(s),
+                    let res = if use_context {
+                        let location = Location {
+                            file: self.file.as_deref(),
+                            line,
+                        };
+                        action.act_at(s, ctx.as_ref(), &location)?
+                    } else {
+                        action.act(s)
                     };
-                    debug!(
-                        "Replacing '{}' with '{}'",
-                        s.escape_debug(),
-                        res.escape_debug()
-                    );
-                    *scope = RWScope(In(Cow::Owned(res), ctx.clone()));
+                    if res == **s {
+                        // Keep borrowing instead of holding a copy of the same thing
+                        trace!("Unchanged, keeping '{}'", s.escape_debug());
+                    } else {
+                        debug!(
+                            "Replacing '{}' with '{}'",
+                            s.escape_debug(),
+                            res.escape_debug()
+                        );
+                        *s = Cow::Owned(res);
+                    }
                 }
                 RWScope(Out(s)) => {
                     debug!("Appending '{}'", s.escape_debug());
                 }
             }
+
+            line += newlines;
         }
 
         Ok(self)
@@ -118,6 +160,71 @@ impl<'viewee> ScopedView<'viewee> {
         self
     }
 
+    /// Remove all [`In`] scopes whose content is the same as that of an earlier one,
+    /// keeping only the first occurrence of each.
+    pub fn dedupe(&mut self) -> &mut Self {
+        debug!("Deduplicating view by removing all repeated in-scope occurrences.");
+
+        let mut seen = HashSet::new();
+        self.scopes.0.retain(|scope| match scope {
+            RWScope(In(s, _)) => seen.insert(s.clone()),
+            RWScope(Out(_)) => true,
+        });
+
+        debug!("Deduplicated: {:?}", self.scopes);
+
+        self
+    }
+
+    /// Delete all lines with anything [`In`] scope entirely, including their line
+    /// endings. All that remains is [`Out`] of scope.
+    pub fn delete_lines(&mut self) -> &mut Self {
+        debug!("Deleting all lines with anything in scope.");
+
+        let mut kept = Vec::with_capacity(self.scopes.0.len());
+        let mut line = Vec::new();
+        let mut line_in_scope = false;
+
+        for scope in self.scopes.0.drain(..) {
+            match scope {
+                RWScope(In(s, _)) => {
+                    for part in s.split_inclusive('\n') {
+                        line_in_scope = true;
+
+                        if part.ends_with('\n') {
+                            // Entire line is dropped
+                            line.clear();
+                            line_in_scope = false;
+                        }
+                    }
+                }
+                RWScope(Out(s)) => {
+                    for part in s.split_inclusive('\n') {
+                        line.push(RWScope(Out(part)));
+
+                        if part.ends_with('\n') {
+                            if !line_in_scope {
+                                kept.append(&mut line);
+                            }
+                            line.clear();
+                            line_in_scope = false;
+                        }
+                    }
+                }
+            }
+        }
+
+        if !line_in_scope {
+            // Tail without trailing newline
+            kept.append(&mut line);
+        }
+
+        self.scopes.0 = kept;
+        debug!("Deleted lines: {:?}", self.scopes);
+
+        self
+    }
+
     /// Check whether anything is [`In`] scope for this view.
     #[must_use]
     pub fn has_any_in_scope(&self) -> bool {
@@ -185,6 +292,30 @@ impl<'viewee> IntoIterator for ScopedViewLines<'viewee> {
 ///
 /// Where actions don't take arguments, neither do the methods.
 impl ScopedView<'_> {
+    /// Apply the default [`actions::Base64Decode`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn base64_decode(&mut self) -> &mut Self {
+        let action = actions::Base64Decode::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::Base64Encode`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn base64_encode(&mut self) -> &mut Self {
+        let action = actions::Base64Encode::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::Camelcase`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn camelcase(&mut self) -> &mut Self {
+        let action = actions::Camelcase::default();
+
+        self.map_without_context(&action)
+    }
+
     /// Apply the default [`actions::Deletion`] action to this view (see
     /// [`Self::map_without_context`]).
     pub fn delete(&mut self) -> &mut Self {
@@ -193,6 +324,32 @@ impl ScopedView<'_> {
         self.map_without_context(&action)
     }
 
+    /// Apply the given [`actions::Exec`] action to this view (see
+    /// [`Self::map_with_context`]).
+    ///
+    /// ## Errors
+    ///
+    /// If running the external program fails, see [`actions::ExecError`].
+    pub fn exec(&mut self, action: &actions::Exec) -> Result<&mut Self, ActionError> {
+        self.map_with_context(action)
+    }
+
+    /// Apply the default [`actions::HexDecode`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn hex_decode(&mut self) -> &mut Self {
+        let action = actions::HexDecode::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::HexEncode`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn hex_encode(&mut self) -> &mut Self {
+        let action = actions::HexEncode::default();
+
+        self.map_without_context(&action)
+    }
+
     /// Apply the default [`actions::German`] action to this view (see
     /// [`Self::map_without_context`]).
     #[cfg(feature = "german")]
@@ -202,6 +359,22 @@ impl ScopedView<'_> {
         self.map_without_context(&action)
     }
 
+    /// Apply the [`actions::Increment`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn increment(&mut self, delta: i64) -> &mut Self {
+        let action = actions::Increment::new(delta);
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::Kebabcase`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn kebabcase(&mut self) -> &mut Self {
+        let action = actions::Kebabcase::default();
+
+        self.map_without_context(&action)
+    }
+
     /// Apply the default [`actions::Lower`] action to this view (see
     /// [`Self::map_without_context`]).
     pub fn lower(&mut self) -> &mut Self {
@@ -218,6 +391,38 @@ impl ScopedView<'_> {
         self.map_without_context(&action)
     }
 
+    /// Apply the default [`actions::Pascalcase`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn pascalcase(&mut self) -> &mut Self {
+        let action = actions::Pascalcase::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the [`actions::Quotes`] action of the given `style` to this view (see
+    /// [`Self::map_without_context`]).
+    #[cfg(feature = "symbols")]
+    pub fn quotes(&mut self, style: actions::QuoteStyle) -> &mut Self {
+        let action = actions::Quotes::new(style);
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the [`actions::QuotesInversion`] action to this view (see
+    /// [`Self::map_without_context`]).
+    #[cfg(feature = "symbols")]
+    pub fn invert_quotes(&mut self) -> &mut Self {
+        let action = actions::QuotesInversion::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the given [`actions::Redact`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn redact(&mut self, action: actions::Redact) -> &mut Self {
+        self.map_without_context(&action)
+    }
+
     /// Apply the [`actions::Replacement`] action to this view (see
     /// [`Self::map_with_context`]).
     ///
@@ -231,6 +436,30 @@ impl ScopedView<'_> {
         self.map_with_context(&action)
     }
 
+    /// Apply the default [`actions::ScreamingSnakecase`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn screaming_snakecase(&mut self) -> &mut Self {
+        let action = actions::ScreamingSnakecase::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::Snakecase`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn snakecase(&mut self) -> &mut Self {
+        let action = actions::Snakecase::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the [`actions::Surround`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn surround(&mut self, prefix: String, suffix: String) -> &mut Self {
+        let action = actions::Surround::new(prefix, suffix);
+
+        self.map_without_context(&action)
+    }
+
     /// Apply the [`actions::Symbols`] action to this view (see
     /// [`Self::map_without_context`]).
     #[cfg(feature = "symbols")]
@@ -257,6 +486,38 @@ impl ScopedView<'_> {
         self.map_without_context(&action)
     }
 
+    /// Apply the default [`actions::Trim`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn trim(&mut self) -> &mut Self {
+        let action = actions::Trim::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::TrimEnd`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn trim_end(&mut self) -> &mut Self {
+        let action = actions::TrimEnd::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::TrimLines`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn trim_lines(&mut self) -> &mut Self {
+        let action = actions::TrimLines::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::TrimStart`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn trim_start(&mut self) -> &mut Self {
+        let action = actions::TrimStart::default();
+
+        self.map_without_context(&action)
+    }
+
     /// Apply the default [`actions::Upper`] action to this view (see
     /// [`Self::map_without_context`]).
     pub fn upper(&mut self) -> &mut Self {
@@ -264,6 +525,22 @@ impl ScopedView<'_> {
 
         self.map_without_context(&action)
     }
+
+    /// Apply the default [`actions::UrlDecode`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn url_decode(&mut self) -> &mut Self {
+        let action = actions::UrlDecode::default();
+
+        self.map_without_context(&action)
+    }
+
+    /// Apply the default [`actions::UrlEncode`] action to this view (see
+    /// [`Self::map_without_context`]).
+    pub fn url_encode(&mut self) -> &mut Self {
+        let action = actions::UrlEncode::default();
+
+        self.map_without_context(&action)
+    }
 }
 
 impl fmt::Display for ScopedView<'_> {
@@ -307,9 +584,9 @@ impl<'viewee> ScopedViewBuilder<'viewee> {
     pub fn build(mut self) -> ScopedView<'viewee> {
         self.apply_dos_line_endings_fix();
 
-        ScopedView {
-            scopes: RWScopes(self.scopes.0.into_iter().map(Into::into).collect()),
-        }
+        ScopedView::new(RWScopes(
+            self.scopes.0.into_iter().map(Into::into).collect(),
+        ))
     }
 
     /// See [`DosFix`].
@@ -343,6 +620,9 @@ impl<'viewee> ScopedViewBuilder<'viewee> {
     ///
     /// after application. Anything [`Out`] out of scope can never be brought back.
     ///
+    /// New [`In`] scopes [inherit][`ScopeContext::inherit`] the named capture groups of
+    /// the scope they were exploded from.
+    ///
     /// ## Panics
     ///
     /// Panics if the [`Scoper`] scopes such that the view is no longer consistent, i.e.
@@ -360,9 +640,14 @@ impl<'viewee> ScopedViewBuilder<'viewee> {
             }
 
             match scope {
-                ROScope(In(s, ..)) => {
+                ROScope(In(s, ctx)) => {
                     let mut new_scopes = scoper.scope(s);
                     new_scopes.0.retain(|s| !s.is_empty());
+                    for scope in &mut new_scopes.0 {
+                        if let ROScope(In(_, new_ctx)) = scope {
+                            inherit_context(new_ctx, ctx.as_ref());
+                        }
+                    }
                     new.extend(new_scopes.0);
                 }
                 // Be explicit about the `Out(_)` case, so changing the enum is a
@@ -392,6 +677,162 @@ impl<'viewee> ScopedViewBuilder<'viewee> {
 
         self
     }
+
+    /// Using a `scoper`, keep only those parts of [`In`] scopes currently contained in
+    /// this view under construction which the scoper scopes.
+    ///
+    /// Unlike [`Self::explode`], the scoper is applied to the *entire* input once, not
+    /// to each [`In`] scope individually. This is required for scopers which depend on
+    /// the position in the input, such as [`Lines`][`crate::scoping::lines::Lines`].
+    /// Any [`ScopeContext`] of [`In`] scopes is kept. As with [`Self::explode`], a view
+    /// can only be narrowed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the view is no longer consistent after application, which would be an
+    /// internal bug.
+    pub fn intersect(&mut self, scoper: &impl Scoper) -> &mut Self {
+        let mut allowed: Ranges<usize> = scoper
+            .scope_raw(self.viewee)
+            .into_iter()
+            .map(|(range, _)| range)
+            .collect();
+        allowed.merge();
+        trace!("Intersecting scopes {:?} with {:?}", self.scopes, allowed);
+
+        self.keep_only(&allowed)
+    }
+
+    /// Using a `scoper`, take those parts of [`In`] scopes currently contained in this
+    /// view under construction which the scoper scopes [`Out`] of scope.
+    ///
+    /// The opposite of [`Self::intersect`]; the scoper is likewise applied to the
+    /// *entire* input once. This is required for scopers which cannot handle
+    /// fragments of the input, such as
+    /// [`LanguageScoper`][`crate::scoping::langs::LanguageScoper`]s, which parse it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the view is no longer consistent after application, which would be an
+    /// internal bug.
+    pub fn subtract(&mut self, scoper: &impl Scoper) -> &mut Self {
+        let mut excluded: Ranges<usize> = scoper
+            .scope_raw(self.viewee)
+            .into_iter()
+            .map(|(range, _)| range)
+            .collect();
+        excluded.merge();
+        trace!("Subtracting {:?} from scopes {:?}", excluded, self.scopes);
+
+        let everything: Ranges<usize> = std::iter::once(0..self.viewee.len()).collect();
+        self.keep_only(&(everything - excluded))
+    }
+
+    /// Keep only those parts of [`In`] scopes covered by the `allowed` (sorted,
+    /// non-overlapping) ranges of the entire input in scope.
+    fn keep_only(&mut self, allowed: &Ranges<usize>) -> &mut Self {
+        let mut new = Vec::with_capacity(self.scopes.0.len());
+        let mut offset = 0;
+        // Scopes and ranges are both sorted, so a single pass over each suffices.
+        let mut ranges = allowed.iter().peekable();
+        for scope in self.scopes.0.drain(..) {
+            let (s, ctx) = match scope {
+                ROScope(In(s, ctx)) => (s, ctx),
+                ROScope(Out(s)) => {
+                    offset += s.len();
+                    new.push(ROScope(Out(s)));
+                    continue;
+                }
+            };
+
+            let (start, end) = (offset, offset + s.len());
+            let mut pos = start;
+            // Ranges lying entirely before this scope cannot apply to later ones either
+            while ranges.next_if(|range| range.end <= start).is_some() {}
+            while let Some(range) = ranges.peek().filter(|range| range.start < end) {
+                let (from, to) = (range.start.max(start), range.end.min(end));
+                if from < to {
+                    if pos < from {
+                        new.push(ROScope(Out(&s[pos - start..from - start])));
+                    }
+                    new.push(ROScope(In(&s[from - start..to - start], ctx.clone())));
+                    pos = to;
+                }
+
+                if range.end > end {
+                    // Continues into the next scope(s)
+                    break;
+                }
+                ranges.next();
+            }
+            if pos < end {
+                new.push(ROScope(Out(&s[pos - start..])));
+            }
+
+            offset = end;
+        }
+
+        self.scopes.0 = new;
+
+        assert_eq!(
+            self.scopes,
+            self.viewee,
+            "Post-condition violated: narrowing scopes resulted in inconsistent view. \
+            Aborting, as this is an unrecoverable bug in a scoper. \
+            Please report at {}.",
+            env!("CARGO_PKG_REPOSITORY")
+        );
+
+        self
+    }
+
+    /// Inverts all scopes currently contained in this view under construction: what
+    /// was [`In`] scope is now [`Out`] of scope, and vice versa.
+    ///
+    /// Unlike [`Self::explode`], this can bring back into scope what was previously
+    /// [`Out`] of scope. Any [`ScopeContext`] of previously [`In`] scopes is lost.
+    pub fn invert(&mut self) -> &mut Self {
+        let scopes = ROScopes(std::mem::take(&mut self.scopes.0)).invert();
+        self.scopes.0 = scopes.0.into_iter().filter(|s| !s.is_empty()).collect();
+
+        self
+    }
+
+    /// Keep only the first `n` [`In`] scopes currently contained in this view under
+    /// construction in scope; all later ones are put [`Out`] of scope.
+    pub fn take(&mut self, n: usize) -> &mut Self {
+        self.keep_nth(|nth, _| nth <= n)
+    }
+
+    /// Keep only those [`In`] scopes currently contained in this view under
+    /// construction in scope which are among the selected `occurrences`; all others
+    /// are put [`Out`] of scope.
+    pub fn select(&mut self, occurrences: &Occurrences) -> &mut Self {
+        self.keep_nth(|nth, total| occurrences.contains(nth, total))
+    }
+
+    /// Put [`Out`] of scope all [`In`] scopes for which `keep`, given their (1-based)
+    /// index and the total number of [`In`] scopes, is false.
+    fn keep_nth(&mut self, keep: impl Fn(usize, usize) -> bool) -> &mut Self {
+        let total = self
+            .scopes
+            .0
+            .iter()
+            .filter(|scope| matches!(scope, ROScope(In(..))))
+            .count();
+
+        let mut nth = 0;
+        for scope in &mut self.scopes.0 {
+            if let ROScope(In(s, _)) = scope {
+                nth += 1;
+                if !keep(nth, total) {
+                    *scope = ROScope(Out(s));
+                }
+            }
+        }
+
+        self
+    }
 }
 
 impl<'viewee> IntoIterator for ScopedViewBuilder<'viewee> {
@@ -406,12 +847,15 @@ impl<'viewee> IntoIterator for ScopedViewBuilder<'viewee> {
 
 #[cfg(test)]
 mod tests {
+    use std::borrow::Cow;
+
     use itertools::Itertools;
     use rstest::rstest;
 
     use super::ScopedView;
-    use crate::scoping::scope::RWScopes;
+    use crate::scoping::regex::CaptureGroup;
     use crate::scoping::scope::Scope::{self, In, Out};
+    use crate::scoping::scope::{RWScopes, ScopeContext};
     use crate::scoping::view::ScopedViewBuilder;
     use crate::RegexPattern;
 
@@ -532,6 +976,218 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[rstest]
+    #[case("", "a", "")]
+    #[case("a", "a", "a")]
+    #[case("b", "a", "B")]
+    #[case("abc", "b", "AbC")]
+    #[case("abcb", "b", "AbCb")]
+    #[case("x = 'y'", r"'.*'", "X = 'y'")]
+    fn test_invert(#[case] input: &str, #[case] pattern: RegexPattern, #[case] expected: &str) {
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&crate::scoping::regex::Regex::new(pattern));
+        builder.invert();
+        let mut view = builder.build();
+
+        view.upper();
+        let result = view.to_string();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("abc", "b", "abc", true)]
+    #[case("abc", "b", "aBc", false)]
+    #[case("abc", "x", "abc", true)] // Nothing in scope
+    fn test_map_keeps_unchanged_borrowed(
+        #[case] input: &str,
+        #[case] pattern: RegexPattern,
+        #[case] expected: &str,
+        #[case] borrowed: bool,
+    ) {
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&crate::scoping::regex::Regex::new(pattern));
+        let mut view = builder.build();
+
+        if borrowed {
+            view.map_without_context(&|s: &str| s.to_owned());
+        } else {
+            view.upper();
+        }
+
+        assert_eq!(view.to_string(), expected);
+        assert_eq!(
+            view.scopes()
+                .0
+                .iter()
+                .all(|scope| matches!(scope, super::RWScope(In(Cow::Borrowed(_), _) | Out(_)))),
+            borrowed
+        );
+    }
+
+    #[rstest]
+    #[case(r"(?<outer>a)(b)", r"b", &[(CaptureGroup::Numbered(0), "b"), (CaptureGroup::Named("outer".into()), "a")])]
+    #[case(r"(a)(b)", r"(b)", &[(CaptureGroup::Numbered(0), "b"), (CaptureGroup::Numbered(1), "b")])]
+    #[case(r"(a)(b)", r"b", &[(CaptureGroup::Numbered(0), "b")])]
+    fn test_explode_inherits_named_groups_only(
+        #[case] outer: RegexPattern,
+        #[case] inner: RegexPattern,
+        #[case] expected: &[(CaptureGroup, &str)],
+    ) {
+        let mut builder = ScopedViewBuilder::new("xab");
+        builder.explode(&crate::scoping::regex::Regex::new(outer));
+        builder.explode(&crate::scoping::regex::Regex::new(inner));
+        let view = builder.build();
+
+        let contexts = view
+            .scopes()
+            .0
+            .iter()
+            .filter_map(|scope| match &scope.0 {
+                In(_, ctx) => ctx.clone(),
+                Out(_) => None,
+            })
+            .collect_vec();
+        assert_eq!(
+            contexts,
+            [ScopeContext::CaptureGroups(
+                expected.iter().cloned().collect()
+            )]
+        );
+    }
+
+    #[rstest]
+    #[case("a\nb\nc\n", "a", "2", "a\nb\nc\n")]
+    #[case("a\na\na\n", "a", "2..", "a\nA\nA\n")]
+    #[case("ab\nab\n", r"\w+", "1", "AB\nab\n")]
+    #[case("ab\nab\n", r"b\na", "1", "aB\nab\n")] // Scope spans lines
+    #[case("ab\nab\n", r".", "1,2", "AB\nAB\n")]
+    #[case("ab\nab\n", r".", "3..", "ab\nab\n")]
+    fn test_intersect(
+        #[case] input: &str,
+        #[case] pattern: RegexPattern,
+        #[case] lines: &str,
+        #[case] expected: &str,
+    ) {
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&crate::scoping::regex::Regex::new(pattern));
+        builder.intersect(&lines.parse::<crate::scoping::lines::Lines>().unwrap());
+        let mut view = builder.build();
+
+        view.upper();
+        let result = view.to_string();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("ab\nab\n", r".", "1", "ab\nAB\n")]
+    #[case("ab\nab\n", r"b\na", "1", "ab\nAb\n")] // Scope spans lines
+    #[case("ab\nab\n", r".", "1,2", "ab\nab\n")]
+    #[case("ab\nab\n", r".", "3..", "AB\nAB\n")]
+    fn test_subtract(
+        #[case] input: &str,
+        #[case] pattern: RegexPattern,
+        #[case] lines: &str,
+        #[case] expected: &str,
+    ) {
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&crate::scoping::regex::Regex::new(pattern));
+        builder.subtract(&lines.parse::<crate::scoping::lines::Lines>().unwrap());
+        let mut view = builder.build();
+
+        view.upper();
+        let result = view.to_string();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("", "a", "")]
+    #[case("abc", "b", "abc")]
+    #[case("a b a b", r"\w", "a b  ")]
+    #[case("x=1, y=2, z=1", r"\d", "x=1, y=2, z=")]
+    #[case("foo bar foo", r"foo|bar", "foo bar ")]
+    fn test_dedupe(#[case] input: &str, #[case] pattern: RegexPattern, #[case] expected: &str) {
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&crate::scoping::regex::Regex::new(pattern));
+        let mut view = builder.build();
+
+        view.dedupe();
+
+        assert_eq!(view.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("", "a", "")]
+    #[case("a\nb\nc\n", "b", "a\nc\n")]
+    #[case("a\nxbx\nc\n", "b", "a\nc\n")]
+    #[case("a\nb\nc", "c", "a\nb\n")] // No trailing newline
+    #[case("a\nb\nc\n", r"a\nb", "c\n")] // Scope spans lines
+    #[case("a\nb\nc\n", r"\n", "")]
+    #[case("a\r\nb\r\n", "a", "b\r\n")]
+    #[case("a\nb\n", "x", "a\nb\n")]
+    fn test_delete_lines(
+        #[case] input: &str,
+        #[case] pattern: RegexPattern,
+        #[case] expected: &str,
+    ) {
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&crate::scoping::regex::Regex::new(pattern));
+        let mut view = builder.build();
+
+        view.delete_lines();
+
+        assert_eq!(view.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("a a a", "a", "1", "A a a")]
+    #[case("a a a", "a", "last", "a a A")]
+    #[case("a a a", "a", "2..", "a A A")]
+    #[case("a a a", "a", "1,last", "A a A")]
+    #[case("a", "a", "2", "a")]
+    fn test_select(
+        #[case] input: &str,
+        #[case] pattern: RegexPattern,
+        #[case] occurrences: &str,
+        #[case] expected: &str,
+    ) {
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&crate::scoping::regex::Regex::new(pattern));
+        builder.select(&occurrences.parse().unwrap());
+        let mut view = builder.build();
+
+        view.upper();
+        let result = view.to_string();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("", "a", 1, "")]
+    #[case("aaa", "a", 0, "aaa")]
+    #[case("aaa", "a", 1, "Aaa")]
+    #[case("a a a", "a", 2, "A A a")]
+    #[case("a a a", "a", 5, "A A A")]
+    #[case("ab\nab\n", r"\w+", 1, "AB\nab\n")]
+    fn test_take(
+        #[case] input: &str,
+        #[case] pattern: RegexPattern,
+        #[case] n: usize,
+        #[case] expected: &str,
+    ) {
+        let mut builder = ScopedViewBuilder::new(input);
+        builder.explode(&crate::scoping::regex::Regex::new(pattern));
+        builder.take(n);
+        let mut view = builder.build();
+
+        view.upper();
+        let result = view.to_string();
+
+        assert_eq!(result, expected);
+    }
+
     #[rstest]
     #[case(
         // New newline at all: still works
@@ -650,9 +1306,7 @@ mod tests {
         #[case] input: Vec<Scope<'_, &str>>,
         #[case] expected: Vec<Vec<Scope<'_, &str>>>,
     ) {
-        let view = ScopedView {
-            scopes: input.into(),
-        };
+        let view = ScopedView::new(input.into());
         let result = view.lines().into_iter().collect_vec();
         let expected = expected
             .into_iter()
@@ -0,0 +1,1408 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::num::NonZero;
+use std::ops::{ControlFlow, Deref};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use glob::Pattern;
+use ignore::{WalkBuilder, WalkState};
+use log::{debug, error, info, trace};
+use memmap2::Mmap;
+use pathdiff::diff_paths;
+
+use crate::actions::ActionError;
+use crate::SrgnBuilder;
+
+mod encoding;
+
+pub use encoding::Encoding;
+
+/// Name of ignore files specific to this crate, in `.gitignore` format. Respected
+/// when walking, in addition to `.gitignore` and `.ignore` files.
+pub const IGNORE_FILENAME: &str = ".srgnignore";
+
+/// How many bytes from the start of a file are inspected by [`is_binary`]. Same as git.
+const BINARY_SNIFF_LEN: usize = 8000;
+
+/// Heuristically, whether `contents` are binary (as opposed to text): whether there is
+/// a NUL byte near the start.
+#[must_use]
+pub fn is_binary(contents: &[u8]) -> bool {
+    contents.iter().take(BINARY_SNIFF_LEN).any(|b| *b == 0)
+}
+
+/// Decides whether a file (path relative to the root) should be processed.
+pub type Filter = Box<dyn Fn(&Path) -> bool + Send + Sync>;
+
+/// When to memory-map files instead of reading them into memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MmapPolicy {
+    /// Map files large enough for it to pay off.
+    Auto,
+    /// Always map files.
+    Always,
+    /// Never map files, always read them.
+    Never,
+}
+
+impl MmapPolicy {
+    /// Files at least this large are mapped in [`MmapPolicy::Auto`] mode.
+    const AUTO_THRESHOLD: u64 = 16 * 1024 * 1024;
+
+    /// Whether to map a file of the given `filesize`.
+    #[must_use]
+    pub const fn should_map(self, filesize: u64) -> bool {
+        match self {
+            // Mapping empty files fails on some platforms, and is pointless anyway.
+            _ if filesize == 0 => false,
+            Self::Auto => filesize >= Self::AUTO_THRESHOLD,
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// Walks a directory tree, running a [`SrgnBuilder`] on each file found.
+///
+/// For full control over what happens to each file, see [`FileProcessor::walk`] and
+/// [`FileProcessor::write_back`], which [`FileProcessor::run`] is built from.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::ops::ControlFlow;
+/// use srgn::actions::Upper;
+/// use srgn::walker::FileProcessor;
+/// use srgn::SrgnBuilder;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// std::fs::write(dir.path().join("a.txt"), "hello").unwrap();
+///
+/// let mut srgn = SrgnBuilder::new();
+/// srgn.action(Upper::default());
+///
+/// let mut processor = FileProcessor::new(dir.path());
+/// processor.in_place(true);
+///
+/// let stats = processor.run(&srgn, |result| {
+///     let file = result.unwrap();
+///     assert_eq!(file.contents, "HELLO");
+///     ControlFlow::Continue(())
+/// });
+///
+/// assert_eq!(stats.files_changed, 1);
+/// assert_eq!(std::fs::read_to_string(dir.path().join("a.txt")).unwrap(), "HELLO");
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+pub struct FileProcessor {
+    root: PathBuf,
+    paths: Option<Vec<PathBuf>>,
+    hidden: bool,
+    gitignored: bool,
+    no_ignore: bool,
+    no_ignore_vcs: bool,
+    follow_links: bool,
+    max_depth: Option<usize>,
+    prune: Vec<Pattern>,
+    ignore_files: Vec<PathBuf>,
+    sorted: bool,
+    threads: Option<NonZero<usize>>,
+    filter: Option<Filter>,
+    max_filesize: Option<u64>,
+    binary: bool,
+    encoding: Option<Encoding>,
+    lossy: bool,
+    mmap: MmapPolicy,
+    in_place: bool,
+    force_write: bool,
+    preserve_times: bool,
+    backup_suffix: Option<String>,
+    output_dir: Option<PathBuf>,
+    write_through_symlinks: bool,
+}
+
+impl FileProcessor {
+    /// Create a new processor, walking the tree starting at `root`.
+    ///
+    /// By default, hidden, ignored and binary files are skipped, files are processed in
+    /// parallel and nothing is written.
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            paths: None,
+            hidden: false,
+            gitignored: false,
+            no_ignore: false,
+            no_ignore_vcs: false,
+            follow_links: false,
+            max_depth: None,
+            prune: Vec::new(),
+            ignore_files: Vec::new(),
+            sorted: false,
+            threads: None,
+            filter: None,
+            max_filesize: None,
+            binary: false,
+            encoding: Some(Encoding::Utf8),
+            lossy: false,
+            mmap: MmapPolicy::Never,
+            in_place: false,
+            force_write: false,
+            preserve_times: false,
+            backup_suffix: None,
+            output_dir: None,
+            write_through_symlinks: false,
+        }
+    }
+
+    /// Instead of walking the tree, process exactly these `paths` (relative to the
+    /// root), sequentially and in the order given.
+    ///
+    /// Options concerning the walk itself (hidden and ignored files, ...) have no
+    /// effect then.
+    pub fn paths(&mut self, paths: impl IntoIterator<Item = PathBuf>) -> &mut Self {
+        self.paths = Some(paths.into_iter().collect());
+
+        self
+    }
+
+    /// Whether to also walk hidden files and directories.
+    pub fn hidden(&mut self, hidden: bool) -> &mut Self {
+        self.hidden = hidden;
+
+        self
+    }
+
+    /// Whether to also walk files ignored by `.gitignore` and similar.
+    pub fn gitignored(&mut self, gitignored: bool) -> &mut Self {
+        self.gitignored = gitignored;
+
+        self
+    }
+
+    /// Whether to disregard all ignore files: `.gitignore`, `.ignore`,
+    /// [`IGNORE_FILENAME`] and those added via [`FileProcessor::ignore_file`].
+    pub fn no_ignore(&mut self, no_ignore: bool) -> &mut Self {
+        self.no_ignore = no_ignore;
+
+        self
+    }
+
+    /// Whether to disregard version control ignore files: `.gitignore`, as well as
+    /// git's global excludes and `.git/info/exclude`.
+    pub fn no_ignore_vcs(&mut self, no_ignore_vcs: bool) -> &mut Self {
+        self.no_ignore_vcs = no_ignore_vcs;
+
+        self
+    }
+
+    /// Whether to descend into symlinked directories.
+    pub fn follow_links(&mut self, follow_links: bool) -> &mut Self {
+        self.follow_links = follow_links;
+
+        self
+    }
+
+    /// Descend at most this many directories below the root.
+    pub fn max_depth(&mut self, max_depth: Option<usize>) -> &mut Self {
+        self.max_depth = max_depth;
+
+        self
+    }
+
+    /// Do not descend into directories whose path (relative to the root) or name
+    /// matches `pattern`. Can be given multiple times.
+    pub fn prune(&mut self, pattern: Pattern) -> &mut Self {
+        self.prune.push(pattern);
+
+        self
+    }
+
+    /// Additionally respect the ignore file at `path`, in `.gitignore` format. Can be
+    /// given multiple times.
+    pub fn ignore_file(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.ignore_files.push(path.into());
+
+        self
+    }
+
+    /// Whether to walk sequentially, in sorted order, on the current thread. Makes
+    /// results deterministic.
+    pub fn sorted(&mut self, sorted: bool) -> &mut Self {
+        self.sorted = sorted;
+
+        self
+    }
+
+    /// The number of threads to use for parallel walking. Defaults to a heuristic.
+    pub fn threads(&mut self, threads: NonZero<usize>) -> &mut Self {
+        self.threads = Some(threads);
+
+        self
+    }
+
+    /// Only process files for which `filter` returns `true`. The path passed is
+    /// relative to the root.
+    pub fn filter(&mut self, filter: impl Fn(&Path) -> bool + Send + Sync + 'static) -> &mut Self {
+        self.filter = Some(Box::new(filter));
+
+        self
+    }
+
+    /// Skip files larger than `bytes`.
+    pub fn max_filesize(&mut self, bytes: u64) -> &mut Self {
+        self.max_filesize = Some(bytes);
+
+        self
+    }
+
+    /// Whether to also process binary files (see [`is_binary`]). Files which are not
+    /// valid in their encoding are reported as errors regardless, unless reading
+    /// [lossily][`FileProcessor::lossy`].
+    pub fn binary(&mut self, binary: bool) -> &mut Self {
+        self.binary = binary;
+
+        self
+    }
+
+    /// The encoding files are in, or [`None`] to [detect][`Encoding::detect`] it per
+    /// file. Defaults to UTF-8. Files are written back in the same encoding.
+    pub fn encoding(&mut self, encoding: Option<Encoding>) -> &mut Self {
+        self.encoding = encoding;
+
+        self
+    }
+
+    /// Whether to replace sequences invalid in a file's encoding with
+    /// [`char::REPLACEMENT_CHARACTER`], instead of failing. Files written back then
+    /// contain those replacements instead of the original bytes!
+    pub fn lossy(&mut self, lossy: bool) -> &mut Self {
+        self.lossy = lossy;
+
+        self
+    }
+
+    /// When to memory-map files instead of reading them. Defaults to
+    /// [`MmapPolicy::Never`]. Files written back in place are never mapped, see
+    /// [`FileProcessor::write_back`].
+    pub fn mmap(&mut self, mmap: MmapPolicy) -> &mut Self {
+        self.mmap = mmap;
+
+        self
+    }
+
+    /// Whether to write changed files back in place (atomically, see
+    /// [`write_atomically`]).
+    pub fn in_place(&mut self, in_place: bool) -> &mut Self {
+        self.in_place = in_place;
+
+        self
+    }
+
+    /// When writing in place, write back all files, not only changed ones.
+    ///
+    /// Unchanged files are skipped by default, keeping their modification times
+    /// intact, such that build tools and file watchers are not triggered needlessly.
+    pub fn force_write(&mut self, force_write: bool) -> &mut Self {
+        self.force_write = force_write;
+
+        self
+    }
+
+    /// When writing in place, keep the access and modification times of files (see
+    /// [`restore_times`]).
+    pub fn preserve_times(&mut self, preserve_times: bool) -> &mut Self {
+        self.preserve_times = preserve_times;
+
+        self
+    }
+
+    /// When writing in place, first back up the original file next to it, with this
+    /// suffix appended to its name.
+    pub fn backup(&mut self, suffix: impl Into<String>) -> &mut Self {
+        self.backup_suffix = Some(suffix.into());
+
+        self
+    }
+
+    /// Instead of in place, write files into `dir` (relative to the root), mirroring the
+    /// directory structure (see [`write_mirrored`]). Files inside `dir` are not
+    /// processed.
+    pub fn output_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.output_dir = Some(dir.into());
+
+        self
+    }
+
+    /// Whether writing to a symlink writes through to the file linked to, keeping the
+    /// link intact. By default, the link is replaced by a regular file.
+    pub fn write_through_symlinks(&mut self, write_through_symlinks: bool) -> &mut Self {
+        self.write_through_symlinks = write_through_symlinks;
+
+        self
+    }
+
+    /// Walk all files, processing each with `srgn`.
+    ///
+    /// `on_result` is called once per processed file (or failure). It can end the walk
+    /// early by returning [`ControlFlow::Break`]. When walking in parallel, it is called
+    /// from multiple threads, in no particular order.
+    ///
+    /// Failures do not stop the walk by themselves; they are reported to `on_result`,
+    /// which decides.
+    pub fn run<F>(&self, srgn: &SrgnBuilder, on_result: F) -> WalkStats
+    where
+        F: Fn(Result<ProcessedFile, WalkError>) -> ControlFlow<()> + Send + Sync,
+    {
+        let files_seen = AtomicUsize::new(0);
+        let files_changed = AtomicUsize::new(0);
+
+        let walked = self.walk(|file| {
+            if !matches!(file, Err(WalkError::Walk(_))) {
+                files_seen.fetch_add(1, Ordering::Relaxed);
+            }
+
+            let result = file.and_then(|file| self.process(srgn, &file));
+            if result.as_ref().is_ok_and(|file| file.changed) {
+                files_changed.fetch_add(1, Ordering::Relaxed);
+            }
+
+            on_result(result)
+        });
+
+        if let Err(e) = walked {
+            // Nothing was walked, so there is nothing left to stop either
+            let _ = on_result(Err(e));
+        }
+
+        WalkStats {
+            files_seen: files_seen.into_inner(),
+            files_changed: files_changed.into_inner(),
+        }
+    }
+
+    /// Walk all files, reading each and handing it to `visit`, without processing or
+    /// writing anything.
+    ///
+    /// Files which are skipped (filtered, too large, binary, ...) are not visited.
+    /// Failures to read a file, or to walk at all, are handed to `visit` as well. It
+    /// can end the walk early by returning [`ControlFlow::Break`]. When walking in
+    /// parallel, it is called from multiple threads, in no particular order.
+    ///
+    /// # Errors
+    ///
+    /// If the walk cannot be set up, for example if an [ignore
+    /// file][`FileProcessor::ignore_file`] cannot be read.
+    pub fn walk<F>(&self, visit: F) -> Result<(), WalkError>
+    where
+        F: Fn(Result<SourceFile, WalkError>) -> ControlFlow<()> + Send + Sync,
+    {
+        let visit_path = |path: Result<&Path, ignore::Error>| match path {
+            Ok(path) => match self.read(path) {
+                Ok(Some(file)) => visit(Ok(file)),
+                Ok(None) => ControlFlow::Continue(()),
+                Err(error) => visit(Err(WalkError::Io {
+                    path: path.to_owned(),
+                    error,
+                })),
+            },
+            Err(e) => visit(Err(e.into())),
+        };
+
+        if let Some(paths) = &self.paths {
+            debug!("Processing {} given path(s) in order", paths.len());
+
+            for path in paths {
+                if visit_path(Ok(&self.root.join(path))).is_break() {
+                    break;
+                }
+            }
+
+            return Ok(());
+        }
+
+        let mut builder = self.walk_builder()?;
+
+        if self.sorted {
+            debug!("Walking sequentially, in sorted order: {:?}", self.root);
+
+            for entry in builder.sort_by_file_path(Ord::cmp).build() {
+                if visit_path(
+                    entry
+                        .as_ref()
+                        .map(ignore::DirEntry::path)
+                        .map_err(Clone::clone),
+                )
+                .is_break()
+                {
+                    break;
+                }
+            }
+        } else {
+            debug!("Walking in parallel: {:?}", self.root);
+
+            if let Some(threads) = self.threads {
+                builder.threads(threads.get());
+            }
+
+            let done = AtomicBool::new(false);
+            builder.build_parallel().run(|| {
+                Box::new(|entry| {
+                    if done.load(Ordering::Relaxed) {
+                        return WalkState::Quit;
+                    }
+
+                    match visit_path(
+                        entry
+                            .as_ref()
+                            .map(ignore::DirEntry::path)
+                            .map_err(Clone::clone),
+                    ) {
+                        ControlFlow::Continue(()) => WalkState::Continue,
+                        ControlFlow::Break(()) => {
+                            done.store(true, Ordering::Relaxed);
+                            WalkState::Quit
+                        }
+                    }
+                })
+            });
+        }
+
+        Ok(())
+    }
+
+    /// A walker starting at the root, configured as requested (hidden files, ignore
+    /// files, ...).
+    fn walk_builder(&self) -> Result<WalkBuilder, WalkError> {
+        let no_ignore_vcs = self.no_ignore || self.no_ignore_vcs;
+
+        let mut builder = WalkBuilder::new(&self.root);
+        builder
+            .hidden(!self.hidden)
+            .ignore(!self.no_ignore)
+            .parents(!self.no_ignore)
+            .git_ignore(!(self.gitignored || no_ignore_vcs))
+            .git_global(!no_ignore_vcs)
+            .git_exclude(!no_ignore_vcs)
+            .follow_links(self.follow_links)
+            // Depth 0 is the root itself
+            .max_depth(self.max_depth);
+
+        if !self.prune.is_empty() {
+            let prune = self.prune.clone();
+            let root = self.root.clone();
+            builder.filter_entry(move |entry| {
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                let path = entry.path();
+                let relative = path.strip_prefix(&root).unwrap_or(path);
+                let pruned = is_dir
+                    && prune.iter().any(|pattern| {
+                        pattern.matches_path(relative)
+                            || pattern.matches(&entry.file_name().to_string_lossy())
+                    });
+
+                if pruned {
+                    debug!("Pruning directory: {:?}", relative);
+                }
+                !pruned
+            });
+        }
+
+        if !self.no_ignore {
+            builder.add_custom_ignore_filename(IGNORE_FILENAME);
+
+            for path in &self.ignore_files {
+                debug!("Adding ignore file: {}", path.display());
+                if let Some(e) = builder.add_ignore(path) {
+                    return Err(e.into());
+                }
+            }
+        }
+
+        Ok(builder)
+    }
+
+    /// Reads the file at `path`, unless it is to be skipped (not a file, filtered, too
+    /// large, binary, ...).
+    fn read(&self, path: &Path) -> io::Result<Option<SourceFile>> {
+        if !path.is_file() {
+            trace!("Skipping path (not a file): {:?}", path);
+            return Ok(None);
+        }
+
+        if let Some(output_dir) = &self.output_dir {
+            if path.starts_with(self.root.join(output_dir)) {
+                trace!("Skipping path (in output directory): {:?}", path);
+                return Ok(None);
+            }
+        }
+
+        // Paths given explicitly might lie outside the root
+        let relative = diff_paths(path, &self.root).unwrap_or_else(|| path.to_owned());
+        if self
+            .filter
+            .as_ref()
+            .is_some_and(|filter| !filter(&relative))
+        {
+            trace!("Skipping path (filtered): {:?}", path);
+            return Ok(None);
+        }
+
+        let mut file = File::open(path)?;
+
+        let size = file.metadata().map_or(0, |m| m.len());
+        if let Some(max) = self.max_filesize {
+            if size > max {
+                info!("Skipping path (larger than {} bytes): {:?}", max, path);
+                return Ok(None);
+            }
+        }
+
+        let bytes = if self.mmap_policy().should_map(size) {
+            trace!("Memory-mapping path: {:?}", path);
+            #[allow(unsafe_code)]
+            // Undefined behavior if the file is modified by others while mapped, a risk
+            // ripgrep takes as well. We ourselves never modify mapped files: files
+            // written back in place are not mapped to begin with (see `mmap_policy`).
+            FileBytes::Mapped(unsafe { Mmap::map(&file)? })
+        } else {
+            let mut bytes =
+                Vec::with_capacity(size.try_into().unwrap_or(/* no perf gains for you */ 0));
+            file.read_to_end(&mut bytes)?;
+            FileBytes::Read(bytes)
+        };
+
+        let encoding = self.encoding.unwrap_or_else(|| Encoding::detect(&bytes));
+        debug!("Using encoding {:?} for path: {:?}", encoding, path);
+
+        // UTF-16 is full of NUL bytes, so sniffing for them is pointless.
+        let utf16 = matches!(encoding, Encoding::Utf16Le | Encoding::Utf16Be);
+        if !self.binary && !utf16 && is_binary(&bytes) {
+            info!("Skipping path (binary): {:?}", path);
+            return Ok(None);
+        }
+
+        Ok(Some(SourceFile {
+            path: path.to_owned(),
+            relative,
+            bytes,
+            size,
+            encoding,
+            lossy: self.lossy,
+        }))
+    }
+
+    /// The policy files are actually mapped by. Files are written back while still
+    /// held (see [`FileProcessor::write_back`]), and replacing a file that is mapped
+    /// fails on some platforms (Windows), so never map when writing in place.
+    const fn mmap_policy(&self) -> MmapPolicy {
+        if self.in_place && self.output_dir.is_none() {
+            MmapPolicy::Never
+        } else {
+            self.mmap
+        }
+    }
+
+    /// Processes `file` with `srgn`, writing it back if requested.
+    fn process(&self, srgn: &SrgnBuilder, file: &SourceFile) -> Result<ProcessedFile, WalkError> {
+        debug!("Processing path: {:?}", file.path);
+
+        let io_error = |error| WalkError::Io {
+            path: file.path.clone(),
+            error,
+        };
+
+        let source = file.contents().map_err(io_error)?;
+        let contents =
+            srgn.run_on_file(&source, &file.relative)
+                .map_err(|error| WalkError::Action {
+                    path: file.path.clone(),
+                    error,
+                })?;
+
+        let changed = contents != source;
+        let written = self.in_place && (changed || self.force_write);
+        if written {
+            debug!("Got new file contents, writing to file: {:?}", file.path);
+            self.write_back(file, &contents).map_err(io_error)?;
+        }
+
+        Ok(ProcessedFile {
+            path: file.path.clone(),
+            contents,
+            changed,
+            written,
+        })
+    }
+
+    /// Writes `contents` as the new contents of `file`: into the [output
+    /// directory][`FileProcessor::output_dir`] if set, otherwise in place (atomically,
+    /// see [`write_atomically`]), as configured. Returns the path written to.
+    ///
+    /// # Errors
+    ///
+    /// Any I/O error. Also errors if `contents` are empty while the file is not (as a
+    /// failsafe against wiping files by accident), or if `contents` cannot be
+    /// represented in the file's encoding.
+    pub fn write_back(&self, file: &SourceFile, contents: &str) -> io::Result<PathBuf> {
+        if file.size > 0 && contents.is_empty() {
+            error!(
+                "Failsafe triggered: file {} is nonempty ({} bytes), but new contents are empty. Will not wipe file.",
+                file.path.display(),
+                file.size
+            );
+            return Err(io::Error::other(
+                "attempt to wipe non-empty file (failsafe guard)",
+            ));
+        }
+
+        let bytes = file.encoding.encode(contents).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("new contents cannot be encoded as {:?}", file.encoding),
+            )
+        })?;
+
+        if let Some(output_dir) = &self.output_dir {
+            let destination = mirror(
+                &file.path,
+                &file.relative,
+                &bytes,
+                &self.root.join(output_dir),
+            )?;
+            debug!("Wrote {:?} to {:?}", file.path, destination);
+
+            return Ok(destination);
+        }
+
+        // Writing atomically replaces whatever is at the path, links included
+        let target = if self.write_through_symlinks && file.path.is_symlink() {
+            let target = fs::canonicalize(&file.path)?;
+            debug!("Writing through symlink {:?} to {:?}", file.path, target);
+            target
+        } else {
+            file.path.clone()
+        };
+
+        let original = self
+            .preserve_times
+            .then(|| fs::metadata(&target))
+            .transpose()?;
+        write_atomically(&target, &bytes, self.backup_suffix.as_deref())?;
+        if let Some(original) = original {
+            restore_times(&target, &original)?;
+        }
+
+        Ok(target)
+    }
+}
+
+impl fmt::Debug for FileProcessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileProcessor")
+            .field("root", &self.root)
+            .field("paths", &self.paths)
+            .field("hidden", &self.hidden)
+            .field("gitignored", &self.gitignored)
+            .field("no_ignore", &self.no_ignore)
+            .field("no_ignore_vcs", &self.no_ignore_vcs)
+            .field("follow_links", &self.follow_links)
+            .field("max_depth", &self.max_depth)
+            .field("prune", &self.prune)
+            .field("ignore_files", &self.ignore_files)
+            .field("sorted", &self.sorted)
+            .field("threads", &self.threads)
+            .field("filter", &self.filter.is_some())
+            .field("max_filesize", &self.max_filesize)
+            .field("binary", &self.binary)
+            .field("encoding", &self.encoding)
+            .field("lossy", &self.lossy)
+            .field("mmap", &self.mmap)
+            .field("in_place", &self.in_place)
+            .field("force_write", &self.force_write)
+            .field("preserve_times", &self.preserve_times)
+            .field("backup_suffix", &self.backup_suffix)
+            .field("output_dir", &self.output_dir)
+            .field("write_through_symlinks", &self.write_through_symlinks)
+            .finish()
+    }
+}
+
+/// Raw contents of a file, either read into memory or memory-mapped.
+enum FileBytes {
+    Read(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl Deref for FileBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Read(bytes) => bytes,
+            Self::Mapped(mmap) => mmap,
+        }
+    }
+}
+
+/// A file read by [`FileProcessor::walk`], ready for processing.
+pub struct SourceFile {
+    path: PathBuf,
+    relative: PathBuf,
+    bytes: FileBytes,
+    size: u64,
+    encoding: Encoding,
+    lossy: bool,
+}
+
+impl SourceFile {
+    /// The path of the file, as walked (starting with the root).
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The path of the file, relative to the root.
+    #[must_use]
+    pub fn relative_path(&self) -> &Path {
+        &self.relative
+    }
+
+    /// The size of the file on disk, in bytes.
+    #[must_use]
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The encoding of the file, as configured or detected.
+    #[must_use]
+    pub const fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// The file's contents, decoded from its encoding.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind [`io::ErrorKind::InvalidData`] if the contents are not valid
+    /// in the file's encoding, unless reading [lossily][`FileProcessor::lossy`].
+    pub fn contents(&self) -> io::Result<Cow<'_, str>> {
+        if self.lossy {
+            return Ok(self.encoding.decode_lossy(&self.bytes));
+        }
+
+        self.encoding.decode(&self.bytes).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("input is not valid {:?}", self.encoding),
+            )
+        })
+    }
+}
+
+impl fmt::Debug for SourceFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceFile")
+            .field("path", &self.path)
+            .field("relative", &self.relative)
+            .field("size", &self.size)
+            .field("encoding", &self.encoding)
+            .field("lossy", &self.lossy)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A file which was processed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedFile {
+    /// The path of the file, as walked (starting with the root).
+    pub path: PathBuf,
+    /// The file's contents after processing.
+    pub contents: String,
+    /// Whether processing changed the file's contents.
+    pub changed: bool,
+    /// Whether the new contents were written back to the file.
+    pub written: bool,
+}
+
+/// Statistics about a finished walk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WalkStats {
+    /// Number of files found and attempted to be processed (after filtering).
+    pub files_seen: usize,
+    /// Number of files whose contents changed.
+    pub files_changed: usize,
+}
+
+/// An error while walking or processing a single file.
+#[derive(Debug)]
+pub enum WalkError {
+    /// Error while walking itself, e.g. a directory being unreadable.
+    Walk(ignore::Error),
+    /// I/O error for a file. Files which are not valid in their encoding (binary
+    /// files, ...) show up as [`io::ErrorKind::InvalidData`].
+    Io {
+        /// The file in question.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// An action failed for a file.
+    Action {
+        /// The file in question.
+        path: PathBuf,
+        /// The underlying error.
+        error: ActionError,
+    },
+}
+
+impl fmt::Display for WalkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Walk(e) => write!(f, "Error walking: {e}"),
+            Self::Io { path, error } => write!(f, "I/O error at {}: {error}", path.display()),
+            Self::Action { path, error } => {
+                write!(f, "Action failed at {}: {error}", path.display())
+            }
+        }
+    }
+}
+
+impl Error for WalkError {}
+
+impl From<ignore::Error> for WalkError {
+    fn from(value: ignore::Error) -> Self {
+        Self::Walk(value)
+    }
+}
+
+/// Writes `contents` to `path`, replacing its previous contents atomically.
+///
+/// Contents are written to a temporary file in the same directory first, which is then
+/// renamed over the original. A crash midway hence leaves the original intact, instead
+/// of a truncated file. The original's permissions carry over, as does its ownership
+/// where permitted (on Unix, only privileged users may hand files to other users).
+/// Timestamps are not preserved, see [`restore_times`] for that.
+///
+/// If `backup_suffix` is given, the original is first copied to a file of the same
+/// name, with the suffix appended (`sed -i.bak`-style).
+///
+/// # Errors
+///
+/// Any I/O error, for example if `path` does not exist.
+pub fn write_atomically(
+    path: &Path,
+    contents: &[u8],
+    backup_suffix: Option<&str>,
+) -> io::Result<()> {
+    let dir = match path.parent() {
+        // Relative paths of files in the current directory have an empty parent.
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let metadata = fs::metadata(path)?;
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    tmp.write_all(contents)?;
+    tmp.as_file().sync_all()?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{fchown, MetadataExt};
+
+        // Before setting permissions, as changing owners might clear setuid bits.
+        if let Err(e) = fchown(tmp.as_file(), Some(metadata.uid()), Some(metadata.gid())) {
+            debug!("Cannot preserve ownership of {}: {}", path.display(), e);
+        }
+    }
+    tmp.as_file().set_permissions(metadata.permissions())?;
+
+    if let Some(suffix) = backup_suffix {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(suffix);
+        debug!("Backing up {} to {:?}", path.display(), backup);
+        fs::copy(path, backup)?;
+    }
+
+    tmp.persist(path)?;
+
+    Ok(())
+}
+
+/// Sets the access and modification times of the file at `path` to those of
+/// `original`, for example after [`write_atomically`], such that the file appears
+/// untouched to tools going by timestamps.
+///
+/// # Errors
+///
+/// Any I/O error, for example if `path` does not exist or the times are unavailable on
+/// the platform.
+pub fn restore_times(path: &Path, original: &fs::Metadata) -> io::Result<()> {
+    let times = fs::FileTimes::new()
+        .set_accessed(original.accessed()?)
+        .set_modified(original.modified()?);
+
+    File::options().write(true).open(path)?.set_times(times)
+}
+
+/// Writes `contents` of the file at (relative) `path` below `output_dir` instead.
+///
+/// The directory structure is mirrored, creating missing directories, and the
+/// original's permissions carry over. Returns the path written to.
+///
+/// # Errors
+///
+/// Any I/O error. If `path` is absolute or contains `..`, such that the result would
+/// not be below `output_dir`, an error of kind [`io::ErrorKind::InvalidInput`].
+pub fn write_mirrored(path: &Path, contents: &[u8], output_dir: &Path) -> io::Result<PathBuf> {
+    mirror(path, path, contents, output_dir)
+}
+
+/// Like [`write_mirrored`], for the file at `source` found at `relative` below the root.
+fn mirror(
+    source: &Path,
+    relative: &Path,
+    contents: &[u8],
+    output_dir: &Path,
+) -> io::Result<PathBuf> {
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "cannot mirror {} below output directory",
+                relative.display()
+            ),
+        ));
+    }
+
+    let destination = output_dir.join(relative);
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&destination, contents)?;
+    fs::set_permissions(&destination, fs::metadata(source)?.permissions())?;
+
+    Ok(destination)
+}
+
+/// Renames the file at `from` to `to`, refusing to replace an existing file at `to`.
+///
+/// # Errors
+///
+/// If `to` already exists, or the rename itself fails.
+pub fn rename_file(from: &Path, to: &Path) -> io::Result<()> {
+    if to.try_exists()? {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("cannot rename {}, {} exists", from.display(), to.display()),
+        ));
+    }
+
+    fs::rename(from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::{Duration, SystemTime};
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::actions::{Replacement, Upper};
+    use crate::scoping::regex::Regex;
+    use crate::RegexPattern;
+
+    fn tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        fs::write(dir.path().join("b.md"), "b").unwrap();
+        fs::write(dir.path().join("sub").join("c.txt"), "c").unwrap();
+        fs::write(dir.path().join(".hidden.txt"), "h").unwrap();
+        fs::write(dir.path().join("bin.txt"), [0xff, 0xfe]).unwrap();
+        fs::write(dir.path().join("ignored.txt"), "i").unwrap();
+        fs::write(dir.path().join("nul.txt"), "n\0").unwrap();
+        fs::write(dir.path().join(IGNORE_FILENAME), "ignored.txt\n").unwrap();
+
+        dir
+    }
+
+    fn upper() -> SrgnBuilder {
+        let mut srgn = SrgnBuilder::new();
+        srgn.action(Upper::default());
+        srgn
+    }
+
+    /// Runs, collecting names of files processed OK, sorted.
+    fn names(processor: &FileProcessor, srgn: &SrgnBuilder) -> (Vec<String>, WalkStats) {
+        let names = Mutex::new(Vec::new());
+        let stats = processor.run(srgn, |result| {
+            if let Ok(file) = result {
+                let name = file
+                    .path
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned();
+                names.lock().unwrap().push(name);
+            }
+            ControlFlow::Continue(())
+        });
+
+        let mut names = names.into_inner().unwrap();
+        names.sort();
+        (names, stats)
+    }
+
+    #[rstest]
+    fn test_walks_all_files(#[values(true, false)] sorted: bool) {
+        let dir = tree();
+        let mut processor = FileProcessor::new(dir.path());
+        processor.sorted(sorted);
+
+        let (names, stats) = names(&processor, &upper());
+
+        assert_eq!(names, ["a.txt", "b.md", "c.txt"]);
+        assert_eq!(
+            stats,
+            WalkStats {
+                files_seen: 4, // Including the binary file
+                files_changed: 3,
+            }
+        );
+
+        // Nothing written by default
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_hidden_and_filter() {
+        let dir = tree();
+        let mut processor = FileProcessor::new(dir.path());
+        processor
+            .hidden(true)
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"));
+
+        let (names, _) = names(&processor, &upper());
+
+        assert_eq!(names, [".hidden.txt", "a.txt", "c.txt"]);
+    }
+
+    #[rstest]
+    #[case(b"", false)]
+    #[case(b"text\n", false)]
+    #[case(b"\xff\xfe", false)]
+    #[case(b"a\0b", true)]
+    fn test_is_binary(#[case] contents: &[u8], #[case] expected: bool) {
+        assert_eq!(is_binary(contents), expected);
+    }
+
+    #[test]
+    fn test_nul_late_is_not_binary() {
+        let mut contents = vec![b'a'; BINARY_SNIFF_LEN];
+        contents.push(0);
+
+        assert!(!is_binary(&contents));
+    }
+
+    #[test]
+    fn test_max_filesize_and_binary() {
+        let dir = tree();
+        fs::write(dir.path().join("large.txt"), "large").unwrap();
+
+        let mut processor = FileProcessor::new(dir.path());
+        processor.max_filesize(2).binary(true);
+
+        let (names, _) = names(&processor, &upper());
+
+        assert_eq!(names, ["a.txt", "b.md", "c.txt", "nul.txt"]);
+    }
+
+    #[test]
+    fn test_detected_encoding_roundtrips() {
+        let dir = tree();
+        fs::write(dir.path().join("latin1.txt"), b"gr\xFCn").unwrap();
+
+        let mut processor = FileProcessor::new(dir.path());
+        processor.encoding(None).in_place(true);
+
+        let (names, _) = names(&processor, &upper());
+
+        // Latin-1 decodes anything, so no more errors
+        assert_eq!(names, ["a.txt", "b.md", "bin.txt", "c.txt", "latin1.txt"]);
+        assert_eq!(fs::read(dir.path().join("latin1.txt")).unwrap(), b"GR\xDCN");
+    }
+
+    #[test]
+    fn test_lossy() {
+        let dir = tree();
+        let mut processor = FileProcessor::new(dir.path());
+        processor.lossy(true);
+
+        let (names, _) = names(&processor, &upper());
+
+        assert_eq!(names, ["a.txt", "b.md", "bin.txt", "c.txt"]);
+    }
+
+    #[rstest]
+    fn test_mmap(#[values(MmapPolicy::Always, MmapPolicy::Never)] mmap: MmapPolicy) {
+        let dir = tree();
+        let mut processor = FileProcessor::new(dir.path());
+        processor.mmap(mmap).in_place(true).output_dir("out");
+
+        let (names, _) = names(&processor, &upper());
+
+        assert_eq!(names, ["a.txt", "b.md", "c.txt"]);
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "a");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("out").join("a.txt")).unwrap(),
+            "A"
+        );
+    }
+
+    #[rstest]
+    #[case::read_only(false, false, MmapPolicy::Always)]
+    #[case::in_place(true, false, MmapPolicy::Never)]
+    #[case::output_dir(true, true, MmapPolicy::Always)]
+    fn test_mmap_policy(
+        #[case] in_place: bool,
+        #[case] output_dir: bool,
+        #[case] expected: MmapPolicy,
+    ) {
+        let mut processor = FileProcessor::new(".");
+        processor.mmap(MmapPolicy::Always).in_place(in_place);
+        if output_dir {
+            processor.output_dir("out");
+        }
+
+        assert_eq!(processor.mmap_policy(), expected);
+    }
+
+    #[test]
+    fn test_explicit_paths_in_order() {
+        let dir = tree();
+        let visited = Mutex::new(Vec::new());
+        let mut processor = FileProcessor::new(dir.path());
+        processor.paths([
+            PathBuf::from("sub/c.txt"),
+            PathBuf::from("ignored.txt"),
+            PathBuf::from("a.txt"),
+        ]);
+
+        processor
+            .walk(|file| {
+                visited
+                    .lock()
+                    .unwrap()
+                    .push(file.unwrap().relative_path().to_owned());
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+
+        // Given paths are not subject to ignore files
+        assert_eq!(
+            visited.into_inner().unwrap(),
+            [
+                PathBuf::from("sub/c.txt"),
+                PathBuf::from("ignored.txt"),
+                PathBuf::from("a.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_output_dir() {
+        let dir = tree();
+        let mut processor = FileProcessor::new(dir.path());
+        processor.in_place(true).output_dir("out");
+
+        let (written, stats) = names(&processor, &upper());
+
+        assert_eq!(written, ["a.txt", "b.md", "c.txt"]);
+        assert_eq!(stats.files_changed, 3);
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "a");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("out").join("sub").join("c.txt")).unwrap(),
+            "C"
+        );
+
+        // Files inside the output directory are not picked up again
+        let (_, stats) = names(&processor, &upper());
+        assert_eq!(stats.files_seen, 4);
+    }
+
+    #[test]
+    fn test_errors_reported() {
+        let dir = tree();
+        let errors = Mutex::new(Vec::new());
+
+        FileProcessor::new(dir.path()).run(&upper(), |result| {
+            if let Err(e) = result {
+                errors.lock().unwrap().push(e);
+            }
+            ControlFlow::Continue(())
+        });
+
+        let errors = errors.into_inner().unwrap();
+        assert!(matches!(
+            errors.as_slice(),
+            [WalkError::Io { path, error }]
+                if path.ends_with("bin.txt") && error.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_break_stops_walk() {
+        let dir = tree();
+        let calls = AtomicUsize::new(0);
+        let mut processor = FileProcessor::new(dir.path());
+        processor.sorted(true);
+
+        processor.run(&upper(), |_| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            ControlFlow::Break(())
+        });
+
+        assert_eq!(calls.into_inner(), 1);
+    }
+
+    #[test]
+    fn test_in_place_with_backup() {
+        let dir = tree();
+        let mut srgn = SrgnBuilder::new();
+        srgn.scope(Regex::new(RegexPattern::new(r"a").unwrap()))
+            .action(Replacement::try_from("${file}".to_owned()).unwrap());
+
+        let mut processor = FileProcessor::new(dir.path());
+        processor.in_place(true).backup(".bak");
+
+        let (names, stats) = names(&processor, &srgn);
+
+        assert_eq!(names, ["a.txt", "b.md", "c.txt"]);
+        assert_eq!(stats.files_changed, 1);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "a.txt"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt.bak")).unwrap(),
+            "a"
+        );
+        assert!(!dir.path().join("b.md.bak").exists());
+    }
+
+    #[rstest]
+    fn test_preserve_times(#[values(true, false)] preserve_times: bool) {
+        let dir = tree();
+        let path = dir.path().join("a.txt");
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let mut processor = FileProcessor::new(dir.path());
+        processor.in_place(true).preserve_times(preserve_times);
+        let (_, stats) = names(&processor, &upper());
+
+        assert!(stats.files_changed > 0);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A");
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        assert_eq!(modified == old, preserve_times);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomically_keeps_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tree();
+        let path = dir.path().join("a.txt");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o754)).unwrap();
+
+        write_atomically(&path, b"#!/bin/sh", Some(".orig")).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o754);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt.orig")).unwrap(),
+            "a"
+        );
+    }
+
+    #[rstest]
+    fn test_unchanged_files_not_written(#[values(true, false)] force_write: bool) {
+        let dir = tree();
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        File::options()
+            .write(true)
+            .open(dir.path().join("b.md"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let mut srgn = upper();
+        srgn.scope(Regex::new(RegexPattern::new(r"a").unwrap()));
+
+        let mut processor = FileProcessor::new(dir.path());
+        processor.in_place(true).force_write(force_write);
+
+        let files = Mutex::new(Vec::new());
+        processor.run(&srgn, |result| {
+            if let Ok(file) = result {
+                files.lock().unwrap().push(file);
+            }
+            ControlFlow::Continue(())
+        });
+
+        let files = files.into_inner().unwrap();
+        assert_eq!(files.iter().filter(|file| file.changed).count(), 1);
+        assert!(files
+            .iter()
+            .all(|file| file.written == (file.changed || force_write)));
+
+        let modified = fs::metadata(dir.path().join("b.md"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        assert_eq!(modified == old, !force_write);
+    }
+
+    #[rstest]
+    #[case("../a.txt")]
+    #[case("sub/../../a.txt")]
+    #[case("/a.txt")]
+    fn test_write_mirrored_rejects_escaping_paths(#[case] path: &str) {
+        let dir = tree();
+
+        let err = write_mirrored(Path::new(path), b"", dir.path()).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_rename_file_does_not_overwrite() {
+        let dir = tree();
+        let a = dir.path().join("a.txt");
+
+        let err = rename_file(&a, &dir.path().join("b.md")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(dir.path().join("b.md")).unwrap(), "b");
+
+        rename_file(&a, &dir.path().join("z.txt")).unwrap();
+        assert!(!a.exists());
+        assert_eq!(fs::read_to_string(dir.path().join("z.txt")).unwrap(), "a");
+    }
+}
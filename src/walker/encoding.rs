@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+/// A text encoding files can be in.
+///
+/// Decoding and encoding are lossless: a byte order mark (BOM), if any, is kept as
+/// `U+FEFF` at the start of the decoded text, and written back as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8.
+    Utf8,
+    /// ISO-8859-1, mapping each byte to the Unicode code point of the same value.
+    Latin1,
+    /// UTF-16, little endian.
+    Utf16Le,
+    /// UTF-16, big endian.
+    Utf16Be,
+}
+
+impl Encoding {
+    /// Guesses the encoding of `bytes`.
+    ///
+    /// A UTF-16 BOM indicates the respective UTF-16 variant. Otherwise, valid UTF-8 is
+    /// taken as such, and anything else as [`Encoding::Latin1`] (which can decode any
+    /// bytes).
+    #[must_use]
+    pub fn detect(bytes: &[u8]) -> Self {
+        match bytes {
+            [0xFF, 0xFE, ..] => Self::Utf16Le,
+            [0xFE, 0xFF, ..] => Self::Utf16Be,
+            _ if std::str::from_utf8(bytes).is_ok() => Self::Utf8,
+            _ => Self::Latin1,
+        }
+    }
+
+    /// Decodes `bytes` into text, if they are valid in this encoding.
+    ///
+    /// Valid UTF-8 is borrowed as is, without copying.
+    #[must_use]
+    pub fn decode(self, bytes: &[u8]) -> Option<Cow<'_, str>> {
+        match self {
+            Self::Utf8 => std::str::from_utf8(bytes).ok().map(Cow::Borrowed),
+            Self::Latin1 => Some(bytes.iter().copied().map(char::from).collect()),
+            Self::Utf16Le | Self::Utf16Be => {
+                if bytes.len() % 2 != 0 {
+                    return None;
+                }
+
+                let units = bytes.chunks_exact(2).map(|pair| {
+                    let pair = [pair[0], pair[1]];
+                    if self == Self::Utf16Le {
+                        u16::from_le_bytes(pair)
+                    } else {
+                        u16::from_be_bytes(pair)
+                    }
+                });
+
+                char::decode_utf16(units).collect::<Result<_, _>>().ok()
+            }
+        }
+    }
+
+    /// Decodes `bytes` into text, replacing invalid sequences with
+    /// [`char::REPLACEMENT_CHARACTER`].
+    #[must_use]
+    pub fn decode_lossy(self, bytes: &[u8]) -> Cow<'_, str> {
+        match self {
+            Self::Utf8 => String::from_utf8_lossy(bytes),
+            Self::Latin1 => bytes.iter().copied().map(char::from).collect(),
+            Self::Utf16Le | Self::Utf16Be => {
+                let units = bytes.chunks(2).map(|pair| match (pair, self) {
+                    ([a, b], Self::Utf16Le) => u16::from_le_bytes([*a, *b]),
+                    ([a, b], _) => u16::from_be_bytes([*a, *b]),
+                    // Dangling byte; an unpaired surrogate gets replaced below.
+                    _ => 0xD800,
+                });
+
+                char::decode_utf16(units)
+                    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect()
+            }
+        }
+    }
+
+    /// Encodes `text`, if it is representable in this encoding.
+    #[must_use]
+    pub fn encode(self, text: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Utf8 => Some(text.as_bytes().to_vec()),
+            Self::Latin1 => text.chars().map(|c| u8::try_from(c).ok()).collect(),
+            Self::Utf16Le => Some(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            Self::Utf16Be => Some(text.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(b"", Encoding::Utf8)]
+    #[case(b"abc", Encoding::Utf8)]
+    #[case(b"\xEF\xBB\xBFabc", Encoding::Utf8)]
+    #[case(b"\xC3\xBC", Encoding::Utf8)]
+    #[case(b"\xFC", Encoding::Latin1)]
+    #[case(b"\xFF\xFEa\x00", Encoding::Utf16Le)]
+    #[case(b"\xFE\xFF\x00a", Encoding::Utf16Be)]
+    fn test_detect(#[case] bytes: &[u8], #[case] expected: Encoding) {
+        assert_eq!(Encoding::detect(bytes), expected);
+    }
+
+    #[rstest]
+    #[case(Encoding::Utf8, b"gr\xC3\xBC\xC3\x9Fe", "grüße")]
+    #[case(Encoding::Utf8, b"\xEF\xBB\xBFa", "\u{FEFF}a")]
+    #[case(Encoding::Latin1, b"gr\xFC\xDFe", "grüße")]
+    #[case(Encoding::Utf16Le, b"\xFF\xFEa\x00\xFC\x00", "\u{FEFF}aü")]
+    #[case(Encoding::Utf16Be, b"\x00a\x00\xFC", "aü")]
+    #[case(Encoding::Utf16Le, b"=\xD8\x00\xDE", "😀")] // Surrogate pair
+    fn test_roundtrip(#[case] encoding: Encoding, #[case] bytes: &[u8], #[case] text: &str) {
+        assert_eq!(encoding.decode(bytes).as_deref(), Some(text));
+        assert_eq!(encoding.encode(text).as_deref(), Some(bytes));
+    }
+
+    #[rstest]
+    #[case(Encoding::Utf8, b"\xFC")]
+    #[case(Encoding::Utf16Le, b"a")] // Odd length
+    #[case(Encoding::Utf16Le, b"\x00\xD8")] // Unpaired surrogate
+    fn test_decode_invalid(#[case] encoding: Encoding, #[case] bytes: &[u8]) {
+        assert_eq!(encoding.decode(bytes), None);
+    }
+
+    #[rstest]
+    #[case(Encoding::Utf8, b"a\xFCb", "a\u{FFFD}b")]
+    #[case(Encoding::Utf8, b"ok", "ok")]
+    #[case(Encoding::Latin1, b"\xFC", "ü")]
+    #[case(Encoding::Utf16Le, b"a\x00b", "a\u{FFFD}")]
+    #[case(Encoding::Utf16Be, b"\xD8\x00\x00a", "\u{FFFD}a")]
+    fn test_decode_lossy(#[case] encoding: Encoding, #[case] bytes: &[u8], #[case] expected: &str) {
+        assert_eq!(encoding.decode_lossy(bytes), expected);
+    }
+
+    #[rstest]
+    #[case("€")]
+    #[case("😀")]
+    fn test_encode_latin1_unrepresentable(#[case] text: &str) {
+        assert_eq!(Encoding::Latin1.encode(text), None);
+    }
+}
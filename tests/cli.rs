@@ -226,577 +226,3194 @@ Heizoelrueckstossabdaempfung.
         ],
         Some("# A comment\nx = \"A string\"\ndef A(): return \"A string in a func\"\nclass A: pass"),
     )]
-    //
-    // Set up baseline for subsequent tests
     #[case(
-        "only-matching-baseline-outside-search-mode",
+        "python-search-stdin-with-context",
         false,
         &[
-            "A",
-            "X",
+            "--python",
+            "strings",
+            "--context",
+            "1",
+            "is",
         ],
-        Some("A\nB"),
+        Some(include_str!("langs/python/base.py")),
     )]
     #[case(
-        "only-matching-outside-search-mode",
+        "python-search-stdin-with-before-and-after-context",
         false,
         &[
-            "--only-matching",
-            "A",
-            "X",
+            "--python",
+            "strings",
+            "--before-context",
+            "2",
+            "--after-context",
+            "0",
+            "is",
         ],
-        Some("A\nB"),
+        Some(include_str!("langs/python/base.py")),
     )]
     #[case(
-        "line-numbers-outside-search-mode",
+        "python-count-stdin",
         false,
         &[
-            "--line-numbers",
-            "A",
-            "X",
+            "--python",
+            "strings",
+            "--count",
+            "is",
         ],
-        Some("A\nB"),
+        Some(include_str!("langs/python/base.py")),
     )]
     #[case(
-        "only-matching-and-line-numbers-outside-search-mode",
+        "python-count-matches-stdin",
         false,
         &[
-            "--only-matching",
-            "--line-numbers",
-            "A",
-            "X",
+            "--python",
+            "strings",
+            "--count-matches",
+            "is",
         ],
-        Some("A\nB"),
+        Some(include_str!("langs/python/base.py")),
     )]
-    // Taking no action etc., but sure enough prints line numbers...
     #[case(
-        "only-matching-and-line-numbers-no-actions-outside-search-mode",
+        "count-ignores-actions",
         false,
         &[
-            "--only-matching",
-            "--line-numbers",
+            "--count",
+            "--upper",
+            "a",
         ],
-        Some("A\nB"),
+        Some("a\nb\naa\n"),
     )]
-    fn test_cli(
-        #[case] mut snapshot_name: String,
-        #[case] os_dependent: bool,
-        #[case] args: &[&str],
-        #[case] stdin: Option<&str>,
-    ) {
-        if os_dependent {
-            // Thanks to Windows, (some) snapshots are actually OS-dependent if they
-            // involve file system paths :( Careful: `cargo insta test
-            // --unreferenced=delete` will wipe snapshot of foreign OSes, but that'll
-            // break in CI!
-            snapshot_name.push('-');
-            snapshot_name.push_str(std::env::consts::OS);
-        }
-
-        // Should rebuild the binary to `target/debug/<name>`. This works if running as
-        // an integration test (insides `tests/`), but not if running as a unit test
-        // (inside `src/main.rs` etc.).
-        let mut cmd = get_cmd();
-
-        let args: Vec<String> = args.iter().map(|&s| s.to_owned()).collect();
-
-        cmd.args(args.clone());
-        cmd.args(["--threads", "1"]); // Be deterministic
-        if let Some(stdin) = stdin {
-            cmd.write_stdin(stdin);
-        } else {
-            cmd.args(
-                // Override; `Command` is detected as providing stdin but we're working on
-                // files here.
-                ["--stdin-override-to", "false"],
-            );
-        }
-
-        let output = cmd.output().expect("failed to execute process");
-
-        let exit_code = output
-            .status
-            .code()
-            .expect("Process unexpectedly terminated via signal, not `exit`.");
-        let stdout = String::from_utf8(output.stdout).unwrap();
-        let stderr = String::from_utf8(output.stderr).unwrap();
-
-        // For debugging, include this, but do not rely on it for snapshot
-        // validity/correctness. We do not want changes in error messages etc. break
-        // tests, seems excessive.
-        let info = CommandInfo::new(&stderr);
-
-        with_settings!({
-            info => &info,
-        }, {
-            insta::assert_yaml_snapshot!(
-                snapshot_name,
-                CommandSnap {
-                    args,
-                    stdin: stdin.map(|s| s.split_inclusive('\n').map(ToOwned::to_owned).collect_vec()),
-                    stdout: stdout.split_inclusive('\n').map(ToOwned::to_owned).collect_vec(),
-                    exit_code,
-                }
-            );
-        });
-    }
-
-    #[rstest]
-    #[case::files_inplace_python(
-        "files-inplace-python",
-        "tests/files/files-python/in",
+    #[case(
+        "scope-and-stdin",
+        false,
         &[
-            "-vvvv", // Trigger logging lines, just for more coverage
-            "--sorted",
-            "--glob",
-            "**/*.py",
-            "foo",
-            "baz"
+            "--scope-and",
+            "[a-z]+",
+            "--upper",
+            "TODO.*",
         ],
+        Some("TODO: fix this\nnot this\nTODO: and that\n"),
+    )]
+    #[case(
+        "scope-and-multiple-stdin",
         false,
+        &[
+            "--scope-and",
+            "[a-z]+",
+            "--scope-and",
+            "^t",
+            "--upper",
+            "TODO.*",
+        ],
+        Some("TODO: fix this\nnot this\nTODO: and that\n"),
     )]
-    #[case::language_scoping_inplace_python(
-        "language-scoping-inplace-python",
-        "tests/files/language-scoping-python/in",
+    #[case(
+        "scope-and-literal-string-stdin",
+        false,
+        &[
+            "--literal-string",
+            "--scope-and",
+            ".",
+            "--delete",
+            "a.b",
+        ],
+        Some("a.b axb .\n"),
+    )]
+    #[case(
+        "not-stdin",
+        false,
+        &[
+            "--not",
+            "--upper",
+            r"\d+",
+        ],
+        Some("abc 123 def 456\n"),
+    )]
+    #[case(
+        "not-with-language-scope-stdin",
+        false,
+        &[
+            "--invert-scope",
+            "--python",
+            "strings",
+            "--upper",
+        ],
+        Some("x = 'hello'  # world\n"),
+    )]
+    #[case(
+        "snakecase-python-function-names",
+        false,
         &[
-            "-vvvv", // Trigger logging lines, just for more coverage
-            "--sorted",
             "--python",
             "function-names",
-            "foo",
-            "baz"
+            "--snakecase",
         ],
+        Some("def getHTTPResponse(): pass\ndef _privateHelper(): getHTTPResponse()\n"),
+    )]
+    #[case(
+        "pascalcase-stdin",
         false,
+        &[
+            "--pascalcase",
+            r"\w+",
+        ],
+        Some("fooBar, baz_qux and HTTPServer\n"),
     )]
-    #[case::language_scoping_and_files_inplace_python(
-        "language-scoping-and-files-inplace-python",
-        "tests/files/language-scoping-and-files-python/in",
+    #[case(
+        "trim-python-comments",
+        false,
         &[
-            "-vvvv", // Trigger logging lines, just for more coverage
-            "--sorted",
             "--python",
-            "function-names",
-            "--glob", // Will override language scoper
-            "subdir/**/*.py",
-            "foo",
-            "baz"
+            "comments",
+            "--trim-end",
         ],
+        Some("x = 1  # trailing whitespace   \n# and here\t\ny = 2   \n"),
+    )]
+    #[case(
+        "trim-lines-stdin",
         false,
+        &[
+            "--trim-lines",
+        ],
+        Some("  indented\n\ttabbed\t\r\nplain\n   \n"),
     )]
-    #[case::language_scoping_and_files_inplace_python(
-        "language-scoping-and-files-inplace-python",
-        "tests/files/language-scoping-and-files-python/in",
+    #[case(
+        "wrap-stdin",
+        false,
+        &[
+            "--wrap",
+            "<...>",
+            r"https?://\S+",
+        ],
+        Some("See https://example.com and http://example.org for details.\n"),
+    )]
+    #[case(
+        "prefix-suffix-after-other-actions-stdin",
+        false,
+        &[
+            "--prefix",
+            "'",
+            "--suffix",
+            "'x",
+            "--upper",
+            r"\b[a-z]+\b",
+        ],
+        Some("quote these words\n"),
+    )]
+    #[case(
+        "wrap-without-placeholder-fails",
+        false,
+        &[
+            "--wrap",
+            "<>",
+            "a",
+        ],
+        Some("a\n"),
+    )]
+    #[case(
+        "increment-stdin",
+        false,
+        &[
+            "--increment",
+            "1",
+            "--scope-and",
+            r#"\d+(?=")"#,
+            r#"version = "[^"]+""#,
+        ],
+        Some("version = \"1.2.9\"\nother = \"1.2.3\"\n"),
+    )]
+    #[case(
+        "decrement-stdin",
+        false,
+        &[
+            "--increment",
+            "-8000",
+            r"\d+",
+        ],
+        Some("port 8080, port 9000, id 08001\n"),
+    )]
+    #[case(
+        "base64-encode-stdin",
+        false,
+        &[
+            "--base64-encode",
+            r"(?<=password: ).+",
+        ],
+        Some("user: admin\npassword: hunter2\n"),
+    )]
+    #[case(
+        "base64-encode-inverted-stdin",
+        false,
+        &[
+            "--base64-encode",
+            "--invert",
+            r"(?<=password: ).+",
+        ],
+        Some("user: admin\npassword: aHVudGVyMg==\n"),
+    )]
+    #[case(
+        "url-encode-python-strings",
+        false,
         &[
-            "-vvvv", // Trigger logging lines, just for more coverage
             "--python",
-            "function-names",
-            "--glob", // Will override language scoper
-            "subdir/**/*.py",
-            "foo",
-            "baz"
+            "strings",
+            "--url-encode",
+            r"(?<=q=)[^&']+",
         ],
-        // NOT `--sorted`, so not deterministic; use to test that directories are
-        // equivalent even if running parallel, unsorted. Output will be random,
-        // breaking snapshot testing.
-        true,
+        Some("url = 'https://example.com/?q=hello world & more&page=1'\n"),
     )]
-    #[case::binary_data_sorted(
-        "binary-data-sorted",
-        "tests/files/binary-data/in",
+    #[case(
+        "hex-decode-stdin",
+        false,
         &[
-            "-vvvv", // Trigger logging lines, just for more coverage
-            "--sorted",
-            "--glob",
-            "**/*",
-            "0a1a09c8-2995-4ac5-9d60-01a0f02920e8",
-            "gone"
+            "--hex-decode",
+            r"\b[0-9a-f]{2,}\b",
         ],
+        Some("68656c6c6f 7a7a 776f726c64\n"),
+    )]
+    #[case(
+        "redact-hash-stdin",
         false,
+        &[
+            "--redact",
+            "hash",
+            r"\b\d{3}-\d{2}-\d{4}\b",
+        ],
+        Some("id=1 ssn=123-45-6789\nid=2 ssn=987-65-4321\nid=3 ssn=123-45-6789\n"),
     )]
-    #[case::binary_data_unsorted(
-        "binary-data-sorted",
-        "tests/files/binary-data/in",
+    #[case(
+        "redact-mask-stdin",
+        false,
         &[
-            "-vvvv", // Trigger logging lines, just for more coverage
-            "--glob",
-            "**/*",
-            "0a1a09c8-2995-4ac5-9d60-01a0f02920e8",
-            "gone"
+            "--redact",
+            "mask",
+            "--redact-char",
+            "x",
+            r"\b\d{1,3}(\.\d{1,3}){3}\b",
         ],
-        // NOT `--sorted`, so not deterministic; use to test that directories are
-        // equivalent even if running parallel, unsorted. Output will be random,
-        // breaking snapshot testing.
-        true,
+        Some("GET / from 192.168.0.1\nGET /about from 10.0.0.12\n"),
     )]
-    fn test_cli_files(
-        #[case] mut snapshot_name: String,
-        #[case] input: PathBuf,
-        #[case] args: &[&str],
-        #[case] skip_output_check: bool,
-        #[values(true, false)] dry_run: bool, // Check all permutations for all inputs
-    ) -> anyhow::Result<()> {
-        let args = args.iter().map(ToString::to_string).collect_vec();
-
-        // Arrange
-        let mut cmd = get_cmd();
-
-        let baseline = if dry_run {
-            // Stays the same! In dry runs, we compare against the very same directory,
-            // as it should not change.
-            input.clone()
-        } else {
-            let mut baseline = input.clone();
-            baseline.pop();
-            baseline.push("out");
-            baseline
-        };
-
-        let candidate = copy_to_tmp(&input);
-        drop(input); // Prevent misuse
-
-        cmd.current_dir(&candidate);
-        cmd.args(
-            // Override; `Command` is detected as providing stdin but we're working on
-            // files here.
-            ["--stdin-override-to", "false"],
-        );
-        cmd.args(&args);
-        if dry_run {
-            cmd.arg("--dry-run");
-        }
-
-        // Act
-        let output = cmd.output().expect("failed to execute binary under test");
-
-        // Assert
-
-        // Thing itself works
-        output.status.success().then_some(()).ok_or_else(|| {
-            anyhow::anyhow!(
-                "Binary execution itself failed: {}",
-                String::from_utf8_lossy(&output.stderr).escape_debug()
-            )
-        })?;
-
-        // Do not drop on panic, to keep tmpdir in place for manual inspection. Can then
-        // diff directories.
-        check_directories_equality(baseline, candidate.path().to_owned())?;
-
-        // Test was successful: ok to drop. Caveat: fails test if deletion fails, which
-        // is unwarranted coupling?
-        candidate.close()?;
-
-        // Let's look at command output now.
-        if !skip_output_check {
-            if dry_run {
-                snapshot_name.push_str("-dry-run");
-            }
-
-            // These are inherently platform-specific, as they deal with file paths.
-            snapshot_name.push('-');
-            snapshot_name.push_str(std::env::consts::OS);
-
-            let exit_code = output
-                .status
-                .code()
-                .expect("Process unexpectedly terminated via signal, not `exit`.");
-            let stdout = String::from_utf8(output.stdout).unwrap();
-            let stderr = String::from_utf8(output.stderr).unwrap();
-
-            let info = CommandInfo::new(&stderr);
-
-            with_settings!({
-                info => &info,
-            }, {
-                insta::assert_yaml_snapshot!(
-                    snapshot_name,
-                    CommandSnap {
-                        args,
-                        stdin: None,
-                        stdout: stdout.split_inclusive('\n').map(ToOwned::to_owned).collect_vec(),
-                        exit_code,
-                    }
-                );
-            });
-        }
-
-        Ok(())
-    }
-
-    /// Tests various *expected* failure modes.
-    #[rstest]
-    //
-    // stdin
     #[case(
-        "fail-none-implicitly-in-search-mode-stdin",
-        Some(r#"x = "y""#),
+        "redact-truncate-stdin",
+        false,
         &[
-            "--python",
-            "strings",
-            "z",
+            "--redact",
+            "truncate",
+            "--redact-keep",
+            "3",
+            r"sk-\w+",
         ],
-        None,
+        Some("API_KEY=sk-abcdef123456\n"),
     )]
     #[case(
-        "fail-none-explicitly-in-search-mode-stdin",
-        Some(r#"x = "y""#),
+        "replacement-builtin-variables-stdin",
+        false,
         &[
-            "--fail-none",
-            "--python",
-            "strings",
-            "z",
+            r"(?<kind>TODO|FIXME)",
+            "${kind}[L$line]: ${match_lower}",
         ],
-        None,
+        Some("// TODO first\nlet x = 1;\n// FIXME second\n"),
     )]
     #[case(
-        "fail-any-in-search-mode-stdin",
-        Some(r#"x = "y""#),
+        "replacement-builtin-variables-python-stdin",
+        false,
         &[
-            "--fail-any",
             "--python",
-            "strings",
-            "y",
+            "function-names",
+            r".+",
+            "${match_upper}_$line",
         ],
-        None,
+        Some("def a():\n    pass\n\n\ndef b():\n    pass\n"),
     )]
-    //
-    // Multiple files, sorted
     #[case(
-        "fail-none-implicitly-in-search-mode-sorted",
-        None,
+        "replacement-file-variable-on-stdin-fails",
+        false,
         &[
-            "--sorted",
-            "--python",
-            "strings",
-            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+            "x",
+            "$file",
         ],
-        None,
+        Some("x\n"),
     )]
     #[case(
-        "fail-none-explicitly-in-search-mode-sorted",
-        None,
+        "literals-multiple",
+        false,
+        &[
+            "-F",
+            "foo",
+            "--literal",
+            "foobar",
+            "-F",
+            "a.b",
+            "--upper",
+        ],
+        Some("foo foobar a.b axb\n"),
+    )]
+    #[case(
+        "literals-with-main-scope-and-delete",
+        false,
+        &[
+            "-F",
+            "b",
+            "a",
+            "--delete",
+        ],
+        Some("abc\n"),
+    )]
+    #[case(
+        "literals-conflict-with-regex-flags",
+        false,
+        &[
+            "-F",
+            "a",
+            "--case-insensitive",
+        ],
+        Some("a\n"),
+    )]
+    #[case(
+        "regex-flags-case-insensitive",
+        false,
+        &[
+            "--case-insensitive",
+            "--upper",
+            "hello",
+        ],
+        Some("Hello hELLo hello\n"),
+    )]
+    #[case(
+        "regex-flags-multiline",
+        false,
+        &[
+            "--multiline",
+            "--upper",
+            "^a",
+        ],
+        Some("ab\nab\nab\n"),
+    )]
+    #[case(
+        "regex-flags-dot-all-swap-greed",
+        false,
+        &[
+            "--dot-all",
+            "--swap-greed",
+            "--upper",
+            "<.*>",
+        ],
+        Some("<a\nb> c <d>\n"),
+    )]
+    #[case(
+        "regex-flags-apply-to-additional-scopes",
+        false,
+        &[
+            "--case-insensitive",
+            "--scope-and",
+            "B",
+            "--upper",
+            "a.",
+        ],
+        Some("Ab ab ac\n"),
+    )]
+    #[case(
+        "scope-anchored-with-language-scope",
+        false,
         &[
-            "--sorted",
-            "--fail-none",
             "--python",
-            "strings",
-            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+            "function-names",
+            "--scope-anchored",
+            "get",
+            "fetch",
         ],
-        None,
+        Some("def get():\n    pass\n\ndef get_value():\n    get()\n\ndef forget(): pass\n"),
     )]
     #[case(
-        "fail-none-explicitly-outside-search-mode-sorted",
-        None,
+        "scope-anchored-without-language-scope",
+        false,
         &[
-            "--sorted",
-            "--fail-none",
-            "--glob",
-            "**/*.py",
-            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+            "--scope-anchored",
+            "--upper",
+            "a",
         ],
-        None,
+        Some("a"),
     )]
     #[case(
-        "fail-any-in-search-mode-sorted",
-        None,
+        "capture-group-numbered",
+        false,
+        &[
+            "--capture-group",
+            "2",
+            "--upper",
+            r#"(\w+) = "(\w+)""#,
+        ],
+        Some("key = \"value\"\nother = \"thing\"\n"),
+    )]
+    #[case(
+        "capture-group-named-with-replacement",
+        false,
+        &[
+            "--capture-group",
+            "key",
+            r#"(?<key>\w+) = "(?<value>\w+)""#,
+            "${value}",
+        ],
+        Some("key = \"value\"\n"),
+    )]
+    #[case(
+        "capture-group-missing",
+        false,
+        &[
+            "--capture-group",
+            "3",
+            r#"(\w+) = "(\w+)""#,
+        ],
+        Some("key = \"value\"\n"),
+    )]
+    #[case(
+        "query-captures-as-replacement-variables",
+        false,
+        &[
+            "--python-query",
+            "(assignment left: (_) @lhs right: (_) @rhs)",
+            "--capture",
+            "rhs",
+            ".+",
+            "${lhs}_default",
+        ],
+        Some("x = 1\nyy = \"two\"\n"),
+    )]
+    #[case(
+        "query-capture-missing",
+        false,
+        &[
+            "--python-query",
+            "(assignment left: (_) @lhs)",
+            "--capture",
+            "rhs",
+        ],
+        Some("x = 1\n"),
+    )]
+    #[case(
+        "query-capture-without-language-scope",
+        false,
+        &[
+            "--capture",
+            "rhs",
+            "x",
+        ],
+        Some("x = 1\n"),
+    )]
+    #[case(
+        "not-inside-query",
+        false,
         &[
-            "--sorted",
-            "--fail-any",
             "--python",
-            "strings",
-            r".",
+            "comments",
+            "--not-inside",
+            "(class_definition) @class",
+            "--upper",
         ],
-        None,
+        Some("# top\nclass A:\n    # inside\n    x = 1\n# bottom\n"),
     )]
     #[case(
-        "fail-any-outside-search-mode-sorted",
-        None,
+        "not-inside-without-language-scope",
+        false,
         &[
-            "--sorted",
-            "--fail-any",
-            "--glob",
-            "**/*.py",
-            r".",
+            "--not-inside",
+            "(comment) @comment",
+            "--upper",
         ],
-        None,
+        Some("x\n"),
     )]
     #[case(
-        "fail-no-files-in-search-mode-sorted",
-        None,
+        "print-on-stdin-has-no-effect",
+        false,
         &[
-            "--sorted",
-            "--fail-no-files",
+            "--print",
+            "a",
+            "b",
+        ],
+        Some("abc\n"),
+    )]
+    #[case(
+        "lines-with-regex",
+        false,
+        &[
+            "--lines",
+            "2..3,5..",
+            "--upper",
+            "a",
+        ],
+        Some("a\na\na\na\na\na\n"),
+    )]
+    #[case(
+        "lines-multiple-with-language-scoper",
+        false,
+        &[
+            "--lines",
+            "1",
+            "--lines",
+            "4..4",
             "--python",
-            "strings",
-            r".",
+            "comments",
+            "--upper",
         ],
-        Some(Path::new("tests/langs/go")), // No Python files here...
+        Some("# a\n# b\nx = 1  # c\ny = 2  # d\n"),
     )]
     #[case(
-        "fail-no-files-outside-search-mode-sorted",
-        None,
+        "lines-stream-stdin",
+        false,
         &[
-            "--sorted",
-            "--fail-no-files",
-            "--glob",
-            "**/*.there-is-no-such-suffix",
-            r".",
+            "--stream",
+            "--lines",
+            "2",
+            "--upper",
+            "a",
         ],
-        None,
+        Some("a\na\na\n"),
     )]
-    //
-    // Multiple files, not sorted aka multi-threaded
     #[case(
-        "fail-none-implicitly-in-search-mode-multithreaded",
-        None,
-        &[
-            "--python",
-            "strings",
-            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
-        ],
-        None,
+        "lines-invalid-range",
+        false,
+        &[
+            "--lines",
+            "3..1",
+            "a",
+        ],
+        Some("a\n"),
+    )]
+    #[case(
+        "stream-stdin",
+        false,
+        &[
+            "--stream",
+            "--upper",
+            "a+",
+        ],
+        Some("a\nb\naa\nno newline at end: a"),
+    )]
+    #[case(
+        "stream-stdin-squeeze-does-not-cross-lines",
+        false,
+        &[
+            "--stream",
+            "--squeeze",
+            "\\s",
+        ],
+        Some("a  b\n\n\nc\t\td\n"),
+    )]
+    #[case(
+        "stream-stdin-fail-none-with-match",
+        false,
+        &[
+            "--stream",
+            "--fail-none",
+            "--upper",
+            "b",
+        ],
+        Some("a\nb\nc\n"),
+    )]
+    #[case(
+        "stream-stdin-fail-none-without-match",
+        false,
+        &[
+            "--stream",
+            "--fail-none",
+            "--upper",
+            "x",
+        ],
+        Some("a\nb\nc\n"),
+    )]
+    #[case(
+        "stream-stdin-falls-back-with-language-scopes",
+        false,
+        &[
+            "--stream",
+            "--python",
+            "comments",
+            "--upper",
+            "x",
+        ],
+        Some("x = 1  # x\ny = x  # xx\n"),
+    )]
+    //
+    // Set up baseline for subsequent tests
+    #[case(
+        "matching-lines-baseline-outside-search-mode",
+        false,
+        &[
+            "A",
+            "X",
+        ],
+        Some("A\nB"),
+    )]
+    #[case(
+        "matching-lines-outside-search-mode",
+        false,
+        &[
+            "--matching-lines",
+            "A",
+            "X",
+        ],
+        Some("A\nB"),
+    )]
+    #[case(
+        "line-numbers-outside-search-mode",
+        false,
+        &[
+            "--line-numbers",
+            "A",
+            "X",
+        ],
+        Some("A\nB"),
+    )]
+    #[case(
+        "matching-lines-and-line-numbers-outside-search-mode",
+        false,
+        &[
+            "--matching-lines",
+            "--line-numbers",
+            "A",
+            "X",
+        ],
+        Some("A\nB"),
+    )]
+    #[case(
+        "list-queries",
+        false,
+        &[
+            "--list-queries",
+        ],
+        None,
+    )]
+    #[case(
+        "list-queries-json",
+        false,
+        &[
+            "--list-queries=json",
+        ],
+        None,
+    )]
+    #[case(
+        "explain-query-prepared",
+        false,
+        &[
+            "--python",
+            "comments",
+            "--python",
+            "doc-strings",
+            "--explain-query",
+        ],
+        None,
+    )]
+    #[case(
+        "explain-query-custom",
+        false,
+        &[
+            "--rust-query",
+            "(line_comment) @comment",
+            "--explain-query",
+        ],
+        None,
+    )]
+    #[case(
+        "explain-query-without-language-scope",
+        false,
+        &[
+            "--explain-query",
+        ],
+        None,
+    )]
+    #[case(
+        "dump-ast",
+        false,
+        &[
+            "--python",
+            "comments",
+            "--dump-ast",
+        ],
+        Some("def f(x):\n    return x  # Done\n"),
+    )]
+    #[case(
+        "dump-ast-with-byte-ranges",
+        false,
+        &[
+            "--python",
+            "comments",
+            "--dump-ast",
+            "--byte-ranges",
+        ],
+        Some("def f(x):\n    return x  # Done\n"),
+    )]
+    #[case(
+        "dump-ast-without-language-scope",
+        false,
+        &[
+            "--dump-ast",
+        ],
+        Some("x"),
+    )]
+    #[case(
+        "highlight-regex-scope",
+        false,
+        &[
+            "--highlight",
+            r"ba\w",
+        ],
+        Some("foo bar\nqux\nbaz\n"),
+    )]
+    #[case(
+        "highlight-with-action",
+        false,
+        &[
+            "--highlight",
+            r"ba\w",
+            "--upper",
+        ],
+        Some("foo bar\nqux\nbaz\n"),
+    )]
+    #[case(
+        "color-always-with-custom-styles",
+        false,
+        &[
+            "--highlight",
+            "--color",
+            "always",
+            "--color-match",
+            "blue,underline",
+            "--color-line-number",
+            "none",
+            r"ba\w",
+        ],
+        Some("foo bar\nqux\nbaz\n"),
+    )]
+    #[case(
+        "color-invalid-style",
+        false,
+        &[
+            "--color-match",
+            "blue,shiny",
+            "x",
+        ],
+        Some("x\n"),
+    )]
+    #[case(
+        "only-matching",
+        false,
+        &[
+            "--only-matching",
+            r"\d+",
+        ],
+        Some("v1.2 and 3\nnone\n4\n"),
+    )]
+    #[case(
+        "only-matching-with-line-numbers",
+        false,
+        &[
+            "-o",
+            "--line-numbers",
+            r"\d+",
+            "--upper",
+        ],
+        Some("v1.2 and 3\nnone\n4\n"),
+    )]
+    #[case(
+        "only-matching-in-search-mode",
+        false,
+        &[
+            "--python",
+            "strings",
+            "-o",
+        ],
+        Some("x = 'a'\n# 'b'\ny = f('c', 'd')\n"),
+    )]
+    #[case(
+        "column-and-byte-offset-in-search-mode",
+        false,
+        &[
+            "--highlight",
+            "--column",
+            "--byte-offset",
+            r"\d",
+        ],
+        Some("ab1 2\nxx\n3\n"),
+    )]
+    #[case(
+        "only-matching-with-column-and-byte-offset",
+        false,
+        &[
+            "-o",
+            "--column",
+            "-b",
+            r"\d",
+            "--upper",
+        ],
+        Some("ab1 2\nxx\n3\n"),
+    )]
+    #[case(
+        "byte-offset-outside-search-mode",
+        false,
+        &[
+            "--byte-offset",
+            r"\d",
+            "--delete",
+        ],
+        Some("ab1 2\nxx\n3\n"),
+    )]
+    #[case(
+        "max-count-replace-first",
+        false,
+        &[
+            "--max-count",
+            "1",
+            r#"version = ".+""#,
+            r#"version = "2.0""#,
+        ],
+        Some("version = \"1.0\"\n[dep]\nversion = \"0.1\"\n"),
+    )]
+    #[case(
+        "max-count-in-search-mode",
+        false,
+        &[
+            "-m",
+            "2",
+            "--highlight",
+            r"\d",
+        ],
+        Some("ab1 2\nxx\n3\n"),
+    )]
+    #[case(
+        "max-count-count-matches",
+        false,
+        &[
+            "--max-count",
+            "2",
+            "--count-matches",
+            r"\d",
+        ],
+        Some("ab1 2\nxx\n3\n"),
+    )]
+    #[case(
+        "occurrence-last",
+        false,
+        &[
+            "--occurrence",
+            "last",
+            r#"version = ".+""#,
+            r#"version = "2.0""#,
+        ],
+        Some("version = \"1.0\"\n[dep]\nversion = \"0.1\"\n"),
+    )]
+    #[case(
+        "occurrence-multiple",
+        false,
+        &[
+            "--occurrence",
+            "1",
+            "--occurrence",
+            "3..",
+            r"\d",
+            "--delete",
+        ],
+        Some("ab1 2\nxx\n3 4\n"),
+    )]
+    #[case(
+        "occurrence-in-search-mode",
+        false,
+        &[
+            "--occurrence",
+            "2",
+            "--highlight",
+            r"\d",
+        ],
+        Some("ab1 2\nxx\n3\n"),
+    )]
+    #[case(
+        "occurrence-invalid",
+        false,
+        &[
+            "--occurrence",
+            "0..2",
+            r"\d",
+        ],
+        Some("ab1 2\nxx\n3\n"),
+    )]
+    #[case(
+        "squeeze-then-upper",
+        false,
+        &[
+            "--squeeze",
+            "--upper",
+            r"a",
+        ],
+        Some("baaad aa\n"),
+    )]
+    #[case(
+        "squeeze-then-replace-with-language-scope",
+        false,
+        &[
+            "--python",
+            "comments",
+            "--squeeze",
+            "#",
+            "//",
+        ],
+        Some("x = '##'  ### comment\n"),
+    )]
+    #[case(
+        "squeeze-conflicts-with-delete",
+        false,
+        &[
+            "--squeeze",
+            "--delete",
+            r"a",
+        ],
+        Some("baaad\n"),
+    )]
+    #[case(
+        "delete-lines",
+        false,
+        &[
+            "--delete-lines",
+            r"TODO",
+        ],
+        Some("keep\n// TODO: remove\nkeep too\nx = 1  # TODO\n"),
+    )]
+    #[case(
+        "delete-lines-with-language-scope",
+        false,
+        &[
+            "--python",
+            "comments",
+            "--delete-lines",
+            r"TODO",
+        ],
+        Some("print('TODO')\n# TODO: remove\nx = 1\n"),
+    )]
+    #[case(
+        "comment-out-go-calls",
+        false,
+        &[
+            "--go-query",
+            r#"((call_expression function: (identifier) @f) @call (#eq? @f "debug"))"#,
+            "--dot-all",
+            "--comment-out",
+        ],
+        Some("func main() {\n\tx := compute(1)\n\tdebug(x)\n\tdebug(\n\t\t\"multi\",\n\t)\n}\n"),
+    )]
+    #[case(
+        "uncomment-python",
+        false,
+        &[
+            "--python",
+            "comments",
+            "--uncomment",
+        ],
+        Some("def f():\n    # print('debug')\n    return 1  # the answer\n"),
+    )]
+    #[case(
+        "comment-out-without-language-scope",
+        false,
+        &[
+            "--comment-out",
+            "x",
+        ],
+        Some("x\n"),
+    )]
+    #[case(
+        "dedupe-lines",
+        false,
+        &[
+            "--dedupe",
+            "--dot-all",
+        ],
+        Some("b\na\nb\nc\na\n"),
+    )]
+    #[case(
+        "dedupe-lines-within-scope",
+        false,
+        &[
+            "--dedupe",
+            r"(?s)BEGIN.+?END",
+        ],
+        Some("x\nx\nBEGIN\nx\ny\nx\nEND\n"),
+    )]
+    #[case(
+        "dedupe-matches",
+        false,
+        &[
+            "--dedupe=matches",
+            r"\w+, ",
+        ],
+        Some("a, b, a, c, b, d\n"),
+    )]
+    #[case(
+        "dedupe-conflicts-with-squeeze",
+        false,
+        &[
+            "--dedupe",
+            "--squeeze",
+            r"a",
+        ],
+        Some("aa\n"),
+    )]
+    #[case(
+        "normalize-form-nfkc",
+        false,
+        &[
+            "--normalize",
+            "--normalize-form",
+            "nfkc",
+        ],
+        Some("ﬁle № ① Café\n"),
+    )]
+    #[case(
+        "normalize-keep-marks",
+        false,
+        &[
+            "--normalize",
+            "--normalize-form",
+            "nfkd",
+            "--keep-marks",
+        ],
+        Some("ﬁle Café\n"),
+    )]
+    #[case(
+        "normalize-form-requires-normalize",
+        false,
+        &[
+            "--normalize-form",
+            "nfc",
+        ],
+        Some("Café\n"),
+    )]
+    #[case(
+        "quotes-default-english",
+        false,
+        &[
+            "--quotes",
+        ],
+        Some("He said \"it's 'fine'\"...\n"),
+    )]
+    #[case(
+        "quotes-german",
+        false,
+        &[
+            "--quotes=german",
+        ],
+        Some("Er sagte \"geht's\"...\n"),
+    )]
+    #[case(
+        "quotes-inverted",
+        false,
+        &[
+            "--quotes",
+            "--invert",
+        ],
+        Some("«Il a dit „hallo“…»\n"),
+    )]
+    #[case(
+        "symbols-categories",
+        false,
+        &[
+            "--symbols-fractions",
+            "--symbols-multiplication",
+            "--symbols-plus-minus",
+            "--symbols-legal",
+        ],
+        Some("(c) 2024: 1/2 of 3x4 +- 1 -> 0x4, 1/2/2024\n"),
+    )]
+    #[case(
+        "symbols-categories-only-with-flag",
+        false,
+        &[
+            "--symbols",
+            "--symbols-legal",
+        ],
+        Some("(c) 1/2 of 3x4 -> 0x4\n"),
+    )]
+    #[case(
+        "symbols-no-arrows-no-math",
+        false,
+        &[
+            "--symbols-no-arrows",
+            "--symbols-no-math",
+        ],
+        Some("a -- b --> c != d\n"),
+    )]
+    #[case(
+        "symbols-invert-categories",
+        false,
+        &[
+            "--symbols",
+            "--invert",
+            "--symbols-no-dashes",
+            "--symbols-legal",
+        ],
+        Some("a – b → c ≠ d © ½\n"),
+    )]
+    #[case(
+        "german-swiss",
+        false,
+        &[
+            "--german-swiss",
+        ],
+        Some("Gruesse aus der Strasse, Straße bleibt\n"),
+    )]
+    #[case(
+        "german-swiss-inverted",
+        false,
+        &[
+            "--german-swiss",
+            "--invert",
+        ],
+        Some("Grüße aus der STRAßE\n"),
+    )]
+    #[case(
+        "uppercase-eszett-ss",
+        false,
+        &[
+            "--upper",
+            "--uppercase-eszett",
+            "ss",
+        ],
+        Some("Straße\n"),
+    )]
+    #[case(
+        "uppercase-eszett-keep-with-german",
+        false,
+        &[
+            "--german",
+            "--uppercase-eszett",
+            "keep",
+        ],
+        Some("SCHLIESSEN und Gruesse\n"),
+    )]
+    #[case(
+        "locale-turkish-upper",
+        false,
+        &[
+            "--upper",
+            "--locale",
+            "tr",
+        ],
+        Some("istanbul ırmak\n"),
+    )]
+    #[case(
+        "locale-turkish-lower",
+        false,
+        &[
+            "--lower",
+            "--locale",
+            "tr",
+        ],
+        Some("İSTANBUL IRMAK\n"),
+    )]
+    #[case(
+        "locale-turkish-titlecase",
+        false,
+        &[
+            "--titlecase",
+            "--locale",
+            "tr",
+        ],
+        Some("istanbul ırmak\n"),
+    )]
+    #[case(
+        "titlecase-style-chicago",
+        false,
+        &[
+            "--titlecase-style",
+            "chicago",
+        ],
+        Some("a guide for walking with dogs\n"),
+    )]
+    #[case(
+        "titlecase-stop-words-and-keep",
+        false,
+        &[
+            "--titlecase",
+            "--titlecase-stop-word",
+            "with",
+            "--titlecase-stop-word",
+            "the",
+            "--titlecase-keep",
+            "iOS",
+        ],
+        Some("apps for ios with the best ratings\n"),
+    )]
+    #[case(
+        "max-count-zero",
+        false,
+        &[
+            "--max-count",
+            "0",
+            r"\d",
+            "--upper",
+        ],
+        Some("ab1 2\nxx\n3\n"),
+    )]
+    // Taking no action etc., but sure enough prints line numbers...
+    #[case(
+        "matching-lines-and-line-numbers-no-actions-outside-search-mode",
+        false,
+        &[
+            "--matching-lines",
+            "--line-numbers",
+        ],
+        Some("A\nB"),
+    )]
+    fn test_cli(
+        #[case] mut snapshot_name: String,
+        #[case] os_dependent: bool,
+        #[case] args: &[&str],
+        #[case] stdin: Option<&str>,
+    ) {
+        if os_dependent {
+            // Thanks to Windows, (some) snapshots are actually OS-dependent if they
+            // involve file system paths :( Careful: `cargo insta test
+            // --unreferenced=delete` will wipe snapshot of foreign OSes, but that'll
+            // break in CI!
+            snapshot_name.push('-');
+            snapshot_name.push_str(std::env::consts::OS);
+        }
+
+        assert_cli_snapshot(snapshot_name, args, stdin);
+    }
+
+    #[cfg(unix)] // Relies on common Unix tools
+    #[rstest]
+    #[case(
+        "exec-stdin",
+        &[
+            "--exec",
+            "tr a-z A-Z",
+            r"(?<=: ).+",
+        ],
+        Some("name: alice\nrole: admin\n"),
+    )]
+    #[case(
+        "exec-python-strings",
+        &[
+            "--python",
+            "strings",
+            "--exec",
+            "sh -c 'rev | tr a-z A-Z'",
+        ],
+        Some("x = 'hello'\ny = \"world\"\n"),
+    )]
+    #[case(
+        "exec-failing-command",
+        &[
+            "--exec",
+            "sh -c 'echo nope >&2; exit 4'",
+            "a",
+        ],
+        Some("abc\n"),
+    )]
+    fn test_cli_exec(
+        #[case] snapshot_name: String,
+        #[case] args: &[&str],
+        #[case] stdin: Option<&str>,
+    ) {
+        assert_cli_snapshot(snapshot_name, args, stdin);
+    }
+
+    fn assert_cli_snapshot(snapshot_name: String, args: &[&str], stdin: Option<&str>) {
+        // Should rebuild the binary to `target/debug/<name>`. This works if running as
+        // an integration test (insides `tests/`), but not if running as a unit test
+        // (inside `src/main.rs` etc.).
+        let mut cmd = get_cmd();
+
+        let args: Vec<String> = args.iter().map(|&s| s.to_owned()).collect();
+
+        cmd.args(args.clone());
+        cmd.args(["--threads", "1"]); // Be deterministic
+        if let Some(stdin) = stdin {
+            cmd.write_stdin(stdin);
+        } else {
+            cmd.args(
+                // Override; `Command` is detected as providing stdin but we're working on
+                // files here.
+                ["--stdin-override-to", "false"],
+            );
+        }
+
+        let output = cmd.output().expect("failed to execute process");
+
+        let exit_code = output
+            .status
+            .code()
+            .expect("Process unexpectedly terminated via signal, not `exit`.");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let stderr = String::from_utf8(output.stderr).unwrap();
+
+        // For debugging, include this, but do not rely on it for snapshot
+        // validity/correctness. We do not want changes in error messages etc. break
+        // tests, seems excessive.
+        let info = CommandInfo::new(&stderr);
+
+        with_settings!({
+            info => &info,
+        }, {
+            insta::assert_yaml_snapshot!(
+                snapshot_name,
+                CommandSnap {
+                    args,
+                    stdin: stdin.map(|s| s.split_inclusive('\n').map(ToOwned::to_owned).collect_vec()),
+                    stdout: stdout.split_inclusive('\n').map(ToOwned::to_owned).collect_vec(),
+                    exit_code,
+                }
+            );
+        });
+    }
+
+    #[rstest]
+    #[case::files_inplace_python(
+        "files-inplace-python",
+        "tests/files/files-python/in",
+        &[
+            "-vvvv", // Trigger logging lines, just for more coverage
+            "--sorted",
+            "--glob",
+            "**/*.py",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_sorted_multithreaded(
+        "files-sorted-multithreaded",
+        "tests/files/files-python/in",
+        &[
+            "--sorted",
+            "--threads",
+            "4",
+            "--glob",
+            "**/*.py",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::language_scoping_inplace_python(
+        "language-scoping-inplace-python",
+        "tests/files/language-scoping-python/in",
+        &[
+            "-vvvv", // Trigger logging lines, just for more coverage
+            "--sorted",
+            "--python",
+            "function-names",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::language_scoping_and_files_inplace_python(
+        "language-scoping-and-files-inplace-python",
+        "tests/files/language-scoping-and-files-python/in",
+        &[
+            "-vvvv", // Trigger logging lines, just for more coverage
+            "--sorted",
+            "--python",
+            "function-names",
+            "--glob", // Will override language scoper
+            "subdir/**/*.py",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::language_scoping_and_files_inplace_python(
+        "language-scoping-and-files-inplace-python",
+        "tests/files/language-scoping-and-files-python/in",
+        &[
+            "-vvvv", // Trigger logging lines, just for more coverage
+            "--python",
+            "function-names",
+            "--glob", // Will override language scoper
+            "subdir/**/*.py",
+            "foo",
+            "baz"
+        ],
+        // NOT `--sorted`, so not deterministic; use to test that directories are
+        // equivalent even if running parallel, unsorted. Output will be random,
+        // breaking snapshot testing.
+        true,
+    )]
+    #[case::files_inplace_backup(
+        "files-inplace-backup",
+        "tests/files/backup/in",
+        &[
+            "--sorted",
+            "--backup",
+            ".bak",
+            "--glob",
+            "**/*.txt",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_multiple_globs_and_excludes(
+        "files-multiple-globs-and-excludes",
+        "tests/files/globs/in",
+        &[
+            "--sorted",
+            "--glob",
+            "**/*.py",
+            "--glob",
+            "**/*.pyi",
+            "--exclude",
+            "**/migrations/**",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_from(
+        "files-from",
+        "tests/files/files-from/in",
+        &[
+            "--files-from",
+            "list.txt",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_ignore_files(
+        "files-ignore-files",
+        "tests/files/ignore-files/in",
+        &[
+            "--sorted",
+            "--ignore-file",
+            "custom.ignore",
+            "--glob",
+            "**/*.txt",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_no_ignore(
+        "files-no-ignore",
+        "tests/files/ignore-files-disabled/in",
+        &[
+            "--sorted",
+            "--no-ignore",
+            "--ignore-file",
+            "custom.ignore",
+            "--glob",
+            "**/*.txt",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_patterns_file(
+        "files-patterns-file",
+        "tests/files/patterns-file/in",
+        &[
+            "--glob",
+            "*.txt",
+            "--patterns-file",
+            "patterns.list",
+            "--upper",
+        ],
+        false,
+    )]
+    #[case::files_patterns_file_literal(
+        "files-patterns-file-literal",
+        "tests/files/patterns-file-literal/in",
+        &[
+            "--glob",
+            "*.txt",
+            "-L",
+            "-f",
+            "patterns.list",
+            "a",
+            "--upper",
+        ],
+        false,
+    )]
+    #[case::files_replace_map(
+        "files-replace-map",
+        "tests/files/replace-map/in",
+        &[
+            "--glob",
+            "*.py",
+            "--replace-map",
+            "renames.csv",
+        ],
+        false,
+    )]
+    #[case::files_output_dir(
+        "files-output-dir",
+        "tests/files/output-dir/in",
+        &[
+            "--sorted",
+            "--glob",
+            "**/*.txt",
+            "--output-dir",
+            "mirror",
+            "foo",
+            "baz",
+        ],
+        false,
+    )]
+    #[case::files_print(
+        "files-print",
+        "tests/files/print/in",
+        &[
+            "--sorted",
+            "--glob",
+            "*.txt",
+            "--print",
+            "--with-filename",
+            "foo",
+            "baz",
+        ],
+        false,
+    )]
+    #[case::files_output_dir_changed_only(
+        "files-output-dir-changed-only",
+        "tests/files/output-dir-changed-only/in",
+        &[
+            "--sorted",
+            "--glob",
+            "**/*.txt",
+            "--output-dir",
+            "mirror",
+            "--changed-only",
+            "foo",
+            "baz",
+        ],
+        false,
+    )]
+    #[case::files_print_changed_only(
+        "files-print-changed-only",
+        "tests/files/print-changed-only/in",
+        &[
+            "--sorted",
+            "--glob",
+            "*.txt",
+            "--print",
+            "--with-filename",
+            "--changed-only",
+            "foo",
+            "baz",
+        ],
+        false,
+    )]
+    #[case::files_max_filesize_and_binary(
+        "files-max-filesize-and-binary",
+        "tests/files/max-filesize-binary/in",
+        &[
+            "--sorted",
+            "--max-filesize",
+            "64",
+            "--glob",
+            "**/*",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_encoding_auto(
+        "files-encoding-auto",
+        "tests/files/encoding/in",
+        &[
+            "--sorted",
+            "--encoding",
+            "auto",
+            "--glob",
+            "**/*.txt",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_mmap(
+        "files-mmap",
+        "tests/files/encoding/in",
+        &[
+            "--sorted",
+            "--mmap",
+            "always",
+            "--encoding",
+            "auto",
+            "--glob",
+            "**/*.txt",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::files_binary_mode_lossy(
+        "files-binary-mode-lossy",
+        "tests/files/binary-mode-lossy/in",
+        &[
+            "--sorted",
+            "--binary-mode",
+            "lossy",
+            "--glob",
+            "**/*.txt",
+            "foo",
+            "baz"
+        ],
+        false,
+    )]
+    #[case::binary_data_sorted(
+        "binary-data-sorted",
+        "tests/files/binary-data/in",
+        &[
+            "-vvvv", // Trigger logging lines, just for more coverage
+            "--sorted",
+            "--glob",
+            "**/*",
+            "0a1a09c8-2995-4ac5-9d60-01a0f02920e8",
+            "gone"
+        ],
+        false,
+    )]
+    #[case::binary_data_unsorted(
+        "binary-data-sorted",
+        "tests/files/binary-data/in",
+        &[
+            "-vvvv", // Trigger logging lines, just for more coverage
+            "--glob",
+            "**/*",
+            "0a1a09c8-2995-4ac5-9d60-01a0f02920e8",
+            "gone"
+        ],
+        // NOT `--sorted`, so not deterministic; use to test that directories are
+        // equivalent even if running parallel, unsorted. Output will be random,
+        // breaking snapshot testing.
+        true,
+    )]
+    fn test_cli_files(
+        #[case] mut snapshot_name: String,
+        #[case] input: PathBuf,
+        #[case] args: &[&str],
+        #[case] skip_output_check: bool,
+        #[values(true, false)] dry_run: bool, // Check all permutations for all inputs
+    ) -> anyhow::Result<()> {
+        let args = args.iter().map(ToString::to_string).collect_vec();
+
+        // Arrange
+        let mut cmd = get_cmd();
+
+        let baseline = if dry_run {
+            // Stays the same! In dry runs, we compare against the very same directory,
+            // as it should not change.
+            input.clone()
+        } else {
+            let mut baseline = input.clone();
+            baseline.pop();
+            baseline.push("out");
+            baseline
+        };
+
+        let candidate = copy_to_tmp(&input);
+        drop(input); // Prevent misuse
+
+        cmd.current_dir(&candidate);
+        cmd.args(
+            // Override; `Command` is detected as providing stdin but we're working on
+            // files here.
+            ["--stdin-override-to", "false"],
+        );
+        cmd.args(&args);
+        if dry_run {
+            cmd.arg("--dry-run");
+        }
+
+        // Act
+        let output = cmd.output().expect("failed to execute binary under test");
+
+        // Assert
+
+        // Thing itself works
+        output.status.success().then_some(()).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Binary execution itself failed: {}",
+                String::from_utf8_lossy(&output.stderr).escape_debug()
+            )
+        })?;
+
+        // Do not drop on panic, to keep tmpdir in place for manual inspection. Can then
+        // diff directories.
+        check_directories_equality(baseline, candidate.path().to_owned())?;
+
+        // Test was successful: ok to drop. Caveat: fails test if deletion fails, which
+        // is unwarranted coupling?
+        candidate.close()?;
+
+        // Let's look at command output now.
+        if !skip_output_check {
+            if dry_run {
+                snapshot_name.push_str("-dry-run");
+            }
+
+            // These are inherently platform-specific, as they deal with file paths.
+            snapshot_name.push('-');
+            snapshot_name.push_str(std::env::consts::OS);
+
+            let exit_code = output
+                .status
+                .code()
+                .expect("Process unexpectedly terminated via signal, not `exit`.");
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            let stderr = String::from_utf8(output.stderr).unwrap();
+
+            let info = CommandInfo::new(&stderr);
+
+            with_settings!({
+                info => &info,
+            }, {
+                insta::assert_yaml_snapshot!(
+                    snapshot_name,
+                    CommandSnap {
+                        args,
+                        stdin: None,
+                        stdout: stdout.split_inclusive('\n').map(ToOwned::to_owned).collect_vec(),
+                        exit_code,
+                    }
+                );
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Tests various *expected* failure modes.
+    #[rstest]
+    //
+    // stdin
+    #[case(
+        "fail-none-implicitly-in-search-mode-stdin",
+        Some(r#"x = "y""#),
+        &[
+            "--python",
+            "strings",
+            "z",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-none-explicitly-in-search-mode-stdin",
+        Some(r#"x = "y""#),
+        &[
+            "--fail-none",
+            "--python",
+            "strings",
+            "z",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-any-in-search-mode-stdin",
+        Some(r#"x = "y""#),
+        &[
+            "--fail-any",
+            "--python",
+            "strings",
+            "y",
+        ],
+        None,
+    )]
+    //
+    // Multiple files, sorted
+    #[case(
+        "fail-none-implicitly-in-search-mode-sorted",
+        None,
+        &[
+            "--sorted",
+            "--python",
+            "strings",
+            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-none-explicitly-in-search-mode-sorted",
+        None,
+        &[
+            "--sorted",
+            "--fail-none",
+            "--python",
+            "strings",
+            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-none-explicitly-outside-search-mode-sorted",
+        None,
+        &[
+            "--sorted",
+            "--fail-none",
+            "--glob",
+            "**/*.py",
+            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-any-in-search-mode-sorted",
+        None,
+        &[
+            "--sorted",
+            "--fail-any",
+            "--python",
+            "strings",
+            r".",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-any-outside-search-mode-sorted",
+        None,
+        &[
+            "--sorted",
+            "--fail-any",
+            "--glob",
+            "**/*.py",
+            r".",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-no-files-in-search-mode-sorted",
+        None,
+        &[
+            "--sorted",
+            "--fail-no-files",
+            "--python",
+            "strings",
+            r".",
+        ],
+        Some(Path::new("tests/langs/go")), // No Python files here...
+    )]
+    #[case(
+        "fail-no-files-outside-search-mode-sorted",
+        None,
+        &[
+            "--sorted",
+            "--fail-no-files",
+            "--glob",
+            "**/*.there-is-no-such-suffix",
+            r".",
+        ],
+        None,
+    )]
+    //
+    // Multiple files, not sorted aka multi-threaded
+    #[case(
+        "fail-none-implicitly-in-search-mode-multithreaded",
+        None,
+        &[
+            "--python",
+            "strings",
+            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-none-explicitly-in-search-mode-multithreaded",
+        None,
+        &[
+            "--fail-none",
+            "--python",
+            "strings",
+            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-none-explicitly-outside-search-mode-multithreaded",
+        None,
+        &[
+            "--fail-none",
+            "--glob",
+            "**/*.py",
+            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-any-in-search-mode-multithreaded",
+        None,
+        &[
+            "--fail-any",
+            "--python",
+            "strings",
+            r".",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-any-outside-search-mode-multithreaded",
+        None,
+        &[
+            "--fail-any",
+            "--glob",
+            "**/*.py",
+            r".",
+        ],
+        None,
+    )]
+    #[case(
+        "fail-no-files-in-search-mode-multithreaded",
+        None,
+        &[
+            "--fail-no-files",
+            "--python",
+            "strings",
+            r".",
+        ],
+        Some(Path::new("tests/langs/go")), // No Python files here...
+    )]
+    #[case(
+        "fail-no-files-outside-search-mode-multithreaded",
+        None,
+        &[
+            "--fail-no-files",
+            "--glob",
+            "**/*.there-is-no-such-suffix",
+            r".",
+        ],
+        None,
+    )]
+    //
+    //
+    #[case(
+        "fail-multiple-languages",
+        None,
+        &[
+            // This should be stopped very early on, in CLI entry
+            "--python",
+            "strings",
+            "--go",
+            "strings",
+        ],
+        None,
+    )]
+    #[case(
+        "uppercase-eszett-without-uppercasing",
+        Some("Straße"),
+        &[
+            "--uppercase-eszett",
+            "ss",
+        ],
+        None,
+    )]
+    #[case(
+        "locale-without-casing",
+        Some("i"),
+        &[
+            "--locale",
+            "tr",
+        ],
+        None,
+    )]
+    #[case(
+        "go-ignores-vendor-directory",
+        None,
+        &[
+            "--go",
+            "comments",
+        ],
+        Some(Path::new("tests/langs/go/vendor-dir-test/")), // Contains vendor dir
+    )]
+    fn test_cli_failure_modes(
+        #[case] snapshot_name: String,
+        #[case] stdin: Option<&str>,
+        #[case] args: &[&str],
+        #[case] cwd: Option<&Path>,
+    ) {
+        let args = args.iter().map(ToString::to_string).collect_vec();
+
+        // Arrange
+        let mut cmd = get_cmd();
+
+        if let Some(stdin) = stdin {
+            cmd.write_stdin(stdin);
+        } else {
+            cmd.args(
+                // Override; `Command` is detected as providing stdin but we're working on
+                // files here.
+                ["--stdin-override-to", "false"],
+            );
+        }
+        cmd.args(&args);
+
+        if let Some(cwd) = cwd {
+            cmd.current_dir(cwd);
+        }
+
+        // Act
+        let output = cmd.output().expect("failed to execute binary under test");
+
+        // Assert
+        let exit_code = output
+            .status
+            .code()
+            .expect("Process unexpectedly terminated via signal, not `exit`.");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let stderr = String::from_utf8(output.stderr).unwrap();
+
+        let info = CommandInfo::new(&stderr);
+
+        with_settings!({
+            info => &info,
+        }, {
+            insta::assert_yaml_snapshot!(
+                snapshot_name,
+                CommandSnap {
+                    args,
+                    stdin: None,
+                    stdout: stdout.split_inclusive('\n').map(ToOwned::to_owned).collect_vec(),
+                    exit_code,
+                }
+            );
+        });
+    }
+
+    #[rstest]
+    fn test_cli_keep_going(#[values(true, false)] sorted: bool) -> anyhow::Result<()> {
+        use predicates::str::contains;
+
+        let candidate = copy_to_tmp(Path::new("tests/files/keep-going/in"));
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&candidate);
+        cmd.args(["--stdin-override-to", "false"]);
+        if sorted {
+            cmd.arg("--sorted");
+        }
+        cmd.args([
+            "--keep-going",
+            "--binary-mode",
+            "strict",
+            "--glob",
+            "**/*.txt",
+            "foo",
+            "baz",
+        ]);
+
+        // The invalid file fails, but the others are processed regardless.
+        cmd.assert()
+            .failure()
+            .stderr(contains("2 file(s) ok, 0 skipped, 1 failed"));
+        check_directories_equality(
+            PathBuf::from("tests/files/keep-going/out"),
+            candidate.path().to_owned(),
+        )?;
+
+        candidate.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_self_bench() {
+        use predicates::prelude::PredicateBooleanExt;
+        use predicates::str::contains;
+
+        let mut cmd = get_cmd();
+        cmd.args(["--self-bench=3", "--python", "strings", "foo", "--upper"]);
+        cmd.write_stdin("x = 'foo'\n");
+
+        // Benchmarking does not touch the input, only reports on it.
+        cmd.assert().success().stdout(
+            contains("Iterations: 3\n")
+                .and(contains("Input: 10 bytes\n"))
+                .and(contains("Reading input (once): "))
+                .and(contains("Language scoping (alone): "))
+                .and(contains("General scoping (alone): "))
+                .and(contains("Actions: "))
+                .and(contains("FOO").not()),
+        );
+    }
+
+    #[rstest]
+    #[case::match_("abc\n", &["b", "B"], 0, "aBc\n")]
+    #[case::no_match("abc\n", &["x", "X"], 1, "abc\n")]
+    #[case::search_mode_match("x = 1 # c\n", &["--python", "comments"], 0, "1:x = 1 # c\n")]
+    #[case::search_mode_no_match("x = 1\n", &["--python", "comments"], 1, "")]
+    #[case::count_no_match("abc\n", &["--count", "x"], 1, "")]
+    #[case::error("abc\n", &["("], 2, "")]
+    fn test_cli_grep_exit_codes(
+        #[case] stdin: &str,
+        #[case] args: &[&str],
+        #[case] expected_code: i32,
+        #[case] expected_stdout: &str,
+    ) {
+        let mut cmd = get_cmd();
+        cmd.arg("--grep-exit-codes");
+        cmd.args(args);
+        cmd.write_stdin(stdin);
+
+        let assert = cmd
+            .assert()
+            .code(expected_code)
+            .stdout(expected_stdout.to_owned());
+        if expected_code < 2 {
+            // Nothing being in scope is no error
+            assert.stderr("");
+        }
+    }
+
+    #[rstest]
+    fn test_cli_stats(#[values(true, false)] sorted: bool) -> anyhow::Result<()> {
+        use predicates::prelude::PredicateBooleanExt;
+        use predicates::str::contains;
+
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.txt"), "foo bar\nfoo\n")?;
+        std::fs::write(dir.path().join("b.txt"), "bar\n")?;
+        std::fs::write(dir.path().join("c.txt"), "FOO\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args(["--stdin-override-to", "false"]);
+        if sorted {
+            cmd.arg("--sorted");
+        }
+        cmd.args(["--stats", "--glob", "*.txt", "(?i)foo", "--upper"]);
+
+        cmd.assert().success().stderr(
+            contains("Files scanned: 3\n")
+                .and(contains("Files changed: 1\n"))
+                .and(contains("Matches: 3\n"))
+                .and(contains("  changed by Upper: 2\n"))
+                .and(contains("Bytes scanned: 20\n"))
+                .and(contains("Elapsed: "))
+                .and(contains("Throughput: ")),
+        );
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::files(false, "A\nb A\nA\nnew A\n", "A A\n")]
+    #[case::lines(true, "a\nb A\na\nnew A\n", "A A\n")]
+    fn test_cli_git_diff(
+        #[case] lines: bool,
+        #[case] expected_changed: &str,
+        #[case] expected_other_changed: &str,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .current_dir(&dir)
+                .args(["-c", "user.name=srgn", "-c", "user.email=srgn@example.com"])
+                .args(args)
+                .assert()
+                .success();
+        };
+
+        std::fs::create_dir(dir.path().join("sub"))?;
+        std::fs::write(dir.path().join("changed.txt"), "a\na\na\n")?;
+        std::fs::write(dir.path().join("sub/other.txt"), "a\n")?;
+        std::fs::write(dir.path().join("unchanged.txt"), "a\n")?;
+        git(&["init", "--quiet"]);
+        git(&["add", "--all"]);
+        git(&["commit", "--quiet", "--message", "initial"]);
+        std::fs::write(dir.path().join("changed.txt"), "a\nb a\na\nnew a\n")?;
+        std::fs::write(dir.path().join("sub/other.txt"), "a a\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args(["--stdin-override-to", "false", "--git-diff"]);
+        if lines {
+            cmd.arg("--git-diff-lines");
+        }
+        cmd.args(["a", "A"]);
+        cmd.assert().success();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("changed.txt"))?,
+            expected_changed
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("sub/other.txt"))?,
+            expected_other_changed
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("unchanged.txt"))?,
+            "a\n"
+        );
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::user_preset(&["--preset", "shout"], "hello WORLD\n")]
+    #[case::project_preset_takes_precedence(&["--preset", "quote"], "> hello world\n")]
+    #[case::later_arguments_override(&["--preset=quote", "--prefix", "| "], "| hello world\n")]
+    #[case::multiple_presets(&["--preset", "quote", "--preset", "shout"], "hello > WORLD\n")]
+    #[case::expand_preset(&["--expand-preset", "shout"], "srgn \"w\\\\w+\" --upper\n")]
+    #[case::expand_project_preset(&["--expand-preset", "quote"], "srgn --prefix '> '\n")]
+    fn test_cli_presets(#[case] args: &[&str], #[case] expected: &str) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let config = dir.path().join("config");
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(config.join("srgn"))?;
+        std::fs::create_dir_all(project.join("sub"))?;
+        std::fs::write(
+            config.join("srgn").join("config.toml"),
+            "[presets.shout]\nscope = 'w\\w+'\nupper = true\n\n[presets.quote]\nprefix = '# '\n",
+        )?;
+        std::fs::write(
+            project.join(".srgn.toml"),
+            "[presets.quote]\nprefix = '> '\n",
+        )?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(project.join("sub"));
+        cmd.env("XDG_CONFIG_HOME", &config);
+        cmd.args(["--stdin-override-to", "true"]);
+        cmd.args(args);
+        cmd.write_stdin("hello world\n");
+
+        cmd.assert().success().stdout(expected.to_owned());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_preset_missing() {
+        use predicates::str::contains;
+
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.env("XDG_CONFIG_HOME", dir.path());
+        cmd.args(["--stdin-override-to", "true", "--preset", "nope"]);
+
+        cmd.assert()
+            .failure()
+            .stderr(contains("No preset named 'nope' found"));
+    }
+
+    #[rstest]
+    #[case::from_env(&[], "\u{1b}[33m1\u{1b}[0m:\u{1b}[1;34mx\u{1b}[0m\n")]
+    #[case::options_take_precedence(
+        &["--color-match", "red"],
+        "\u{1b}[33m1\u{1b}[0m:\u{1b}[31mx\u{1b}[0m\n"
+    )]
+    fn test_cli_colors_from_env(#[case] args: &[&str], #[case] expected: &str) {
+        let mut cmd = get_cmd();
+        cmd.env("SRGN_COLORS", "match=blue,bold;line-number=yellow");
+        cmd.args([
+            "--stdin-override-to",
+            "true",
+            "--color",
+            "always",
+            "--highlight",
+        ]);
+        cmd.args(args);
+        cmd.arg("x");
+        cmd.write_stdin("x\n");
+
+        cmd.assert().success().stdout(expected.to_owned());
+    }
+
+    #[rstest]
+    #[case::heading(&[], "a.txt\n1:a1\n3:c2\n\nb.txt\n1:3\n\n")]
+    #[case::no_heading(&["--no-heading"], "a.txt:1:a1\na.txt:3:c2\nb.txt:1:3\n")]
+    #[case::heading_overrides(&["--no-heading", "--heading"], "a.txt\n1:a1\n3:c2\n\nb.txt\n1:3\n\n")]
+    #[case::no_filename(&["--no-filename"], "1:a1\n3:c2\n1:3\n")]
+    #[case::null(&["--null"], "a.txt\x001:a1\n3:c2\n\nb.txt\x001:3\n\n")]
+    #[case::null_no_heading(&["--null", "--no-heading"], "a.txt\x001:a1\na.txt\x003:c2\nb.txt\x001:3\n")]
+    #[case::count_null(&["--count", "--null"], "a.txt\x002\nb.txt\x001\n")]
+    #[case::count_no_filename(&["--count", "--no-filename"], "2\n1\n")]
+    fn test_cli_file_name_output(
+        #[case] args: &[&str],
+        #[case] expected: &str,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.txt"), "a1\nb\nc2\n")?;
+        std::fs::write(dir.path().join("b.txt"), "3\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--sorted",
+            "--glob",
+            "*.txt",
+            "--highlight",
+        ]);
+        cmd.args(args);
+        cmd.arg(r"\d");
+
+        cmd.assert().success().stdout(expected.to_owned());
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::only_matching(&["-o"], "f.txt\n1:a\n1:a\n1:a\n3:a\n3:a\n\n")]
+    #[case::column(&["--column"], "f.txt\n1:1:a a a\n3:1:a a\n\n")]
+    #[case::byte_offset(&["-b"], "f.txt\n1:0:a a a\n3:8:a a\n\n")]
+    fn test_cli_display_flags_leave_files_untouched(
+        #[case] args: &[&str],
+        #[case] expected: &str,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let contents = "a a a\nb\na a\nc\n";
+        std::fs::write(dir.path().join("f.txt"), contents)?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args(["--stdin-override-to", "false", "--glob", "*.txt"]);
+        cmd.args(args);
+        cmd.arg("a");
+
+        cmd.assert().success().stdout(expected.to_owned());
+        assert_eq!(std::fs::read_to_string(dir.path().join("f.txt"))?, contents);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_file_variables() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("lib"))?;
+        std::fs::write(dir.path().join("lib/foo.py"), "# Module: ?\nx = 1\n")?;
+        std::fs::write(dir.path().join("lib/bar.py"), "# Module: ?\n")?;
+        std::fs::write(dir.path().join("lib/baz.txt"), "# Module: ?\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--glob",
+            "lib/*.py",
+            r"^# Module: .*",
+            "# Module: $file_stem ($file_name in $file_dir, .$file_ext)",
+        ]);
+
+        cmd.assert().success();
+
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name));
+        assert_eq!(
+            read("lib/foo.py")?,
+            "# Module: foo (foo.py in lib, .py)\nx = 1\n"
+        );
+        assert_eq!(read("lib/bar.py")?, "# Module: bar (bar.py in lib, .py)\n");
+        assert_eq!(read("lib/baz.txt")?, "# Module: ?\n");
+
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_cli_rename_files(#[values(false, true)] dry_run: bool) -> anyhow::Result<()> {
+        use predicates::str::contains;
+
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("sub"))?;
+        std::fs::write(dir.path().join("sub/foo_test.py"), "import other\n")?;
+        std::fs::write(dir.path().join("sub/other.py"), "foo = 1\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--glob",
+            "**/*.py",
+            "--rename-files",
+            "^foo_(.+)",
+            "baz_$1",
+            "--sorted",
+        ]);
+        if dry_run {
+            cmd.arg("--dry-run");
+        }
+        cmd.args(["other", "another"]);
+
+        cmd.assert()
+            .success()
+            .stdout(contains("sub/foo_test.py -> sub/baz_test.py\n"));
+
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name));
+        if dry_run {
+            assert_eq!(read("sub/foo_test.py")?, "import other\n");
+            assert!(!dir.path().join("sub/baz_test.py").exists());
+        } else {
+            assert_eq!(read("sub/baz_test.py")?, "import another\n");
+            assert!(!dir.path().join("sub/foo_test.py").exists());
+        }
+        assert!(dir.path().join("sub/other.py").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_rename_files_with_contents() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("foo.py"), "foo = 1\n")?;
+        std::fs::write(dir.path().join("bar.py"), "x = foo\n")?;
+        std::fs::write(dir.path().join("baz.py"), "taken\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--glob",
+            "*.py",
+            "--rename-files",
+            "foo",
+            "baz",
+            "--sorted",
+            "foo",
+            "baz",
+        ]);
+
+        // Renaming onto an existing file fails, but contents are still changed
+        cmd.assert().failure();
+
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name));
+        assert_eq!(read("foo.py")?, "baz = 1\n");
+        assert_eq!(read("bar.py")?, "x = baz\n");
+        assert_eq!(read("baz.py")?, "taken\n");
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::default(&[], "a.txt\n", false)]
+    #[case::force_write(&["--force-write"], "a.txt\nb.txt\n", true)]
+    #[case::force_write_changed_only(&["--force-write", "--changed-only"], "a.txt\n", true)]
+    fn test_cli_force_write(
+        #[case] args: &[&str],
+        #[case] expected: &str,
+        #[case] touched: bool,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.txt"), "foo\n")?;
+        std::fs::write(dir.path().join("b.txt"), "bar\n")?;
+
+        let old = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(dir.path().join("b.txt"))?
+            .set_modified(old)?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--sorted",
+            "--threads",
+            "1",
+            "--glob",
+            "*.txt",
+        ]);
+        cmd.args(args);
+        cmd.args(["foo", "baz"]);
+
+        cmd.assert().success().stdout(expected.to_owned());
+
+        let modified = std::fs::metadata(dir.path().join("b.txt"))?.modified()?;
+        assert_eq!(modified != old, touched);
+        assert_eq!(std::fs::read_to_string(dir.path().join("b.txt"))?, "bar\n");
+
+        Ok(())
+    }
+
+    #[rstest]
+    fn test_cli_preserve_times(#[values(true, false)] preserve_times: bool) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("run.sh");
+        std::fs::write(&path, "#!/bin/sh\necho foo\n")?;
+
+        let old = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(old)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args(["--stdin-override-to", "false", "--glob", "*.sh"]);
+        if preserve_times {
+            cmd.arg("--preserve-times");
+        }
+        cmd.args(["foo", "baz"]);
+
+        cmd.assert().success().stdout("run.sh\n");
+
+        assert_eq!(std::fs::read_to_string(&path)?, "#!/bin/sh\necho baz\n");
+        let metadata = std::fs::metadata(&path)?;
+        assert_eq!(metadata.modified()? == old, preserve_times);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o755);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    #[case::target("target", "baz\n", true)]
+    #[case::replace("replace", "foo\n", false)]
+    #[case::skip("skip", "foo\n", true)]
+    fn test_cli_symlinks(
+        #[case] policy: &str,
+        #[case] expected_target: &str,
+        #[case] still_link: bool,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let outside = tempfile::tempdir()?;
+        let target = outside.path().join("target.txt");
+        std::fs::write(&target, "foo\n")?;
+        std::os::unix::fs::symlink(&target, dir.path().join("link.txt"))?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--glob",
+            "*.txt",
+            "--symlinks",
+            policy,
+            "foo",
+            "baz",
+        ]);
+
+        cmd.assert().success();
+
+        let link = dir.path().join("link.txt");
+        assert_eq!(std::fs::read_to_string(&target)?, expected_target);
+        assert_eq!(link.is_symlink(), still_link);
+        let expected_link = if policy == "skip" { "foo\n" } else { "baz\n" };
+        assert_eq!(std::fs::read_to_string(&link)?, expected_link);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[rstest]
+    fn test_cli_follow(#[values(true, false)] follow: bool) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let outside = tempfile::tempdir()?;
+        std::fs::write(outside.path().join("a.txt"), "foo\n")?;
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("linked"))?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args(["--stdin-override-to", "false", "--glob", "**/*.txt"]);
+        if follow {
+            cmd.arg("--follow");
+        }
+        cmd.args(["foo", "baz"]);
+
+        cmd.assert().success();
+
+        let expected = if follow { "baz\n" } else { "foo\n" };
+        assert_eq!(
+            std::fs::read_to_string(outside.path().join("a.txt"))?,
+            expected
+        );
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::all(&[], "a.txt\nnode_modules/b.txt\nsub/c.txt\nsub/deeper/d.txt\nsub/node_modules/e.txt\n")]
+    #[case::prune_by_name(&["--prune", "node_modules"], "a.txt\nsub/c.txt\nsub/deeper/d.txt\n")]
+    #[case::prune_by_path(&["--prune", "sub/*"], "a.txt\nnode_modules/b.txt\nsub/c.txt\n")]
+    #[case::max_depth_1(&["--max-depth", "1"], "a.txt\n")]
+    #[case::max_depth_2(&["--max-depth", "2"], "a.txt\nnode_modules/b.txt\nsub/c.txt\n")]
+    fn test_cli_walk_depth_and_prune(
+        #[case] args: &[&str],
+        #[case] expected: &str,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for (path, contents) in [
+            ("a.txt", "foo\n"),
+            ("node_modules/b.txt", "foo\n"),
+            ("sub/c.txt", "foo\n"),
+            ("sub/deeper/d.txt", "foo\n"),
+            ("sub/node_modules/e.txt", "foo\n"),
+        ] {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(path, contents)?;
+        }
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--sorted",
+            "--glob",
+            "**/*.txt",
+        ]);
+        cmd.args(args);
+        cmd.args(["foo", "baz"]);
+
+        cmd.assert().success().stdout(expected.to_owned());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_parallel_output_not_interleaved() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let n_files = 64;
+        for i in 0..n_files {
+            let contents = format!("foo {i}\n").repeat(500);
+            std::fs::write(dir.path().join(format!("{i}.txt")), contents)?;
+        }
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--threads",
+            "8",
+            "--glob",
+            "*.txt",
+            "--highlight",
+            "foo",
+        ]);
+
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output)?;
+
+        let blocks = output.split_terminator("\n\n").collect::<Vec<_>>();
+        assert_eq!(blocks.len(), n_files);
+        for block in blocks {
+            let mut lines = block.lines();
+            let name = lines.next().unwrap();
+            let i = name.strip_suffix(".txt").unwrap();
+
+            let lines = lines.collect::<Vec<_>>();
+            assert_eq!(lines.len(), 500);
+            assert!(lines
+                .iter()
+                .all(|line| line.ends_with(&format!(":foo {i}"))));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_progress() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for (path, contents) in [("a.txt", "foo\n"), ("b.txt", "bar\n"), ("c.txt", "foo\n")] {
+            std::fs::write(dir.path().join(path), contents)?;
+        }
+        std::fs::write(dir.path().join("files.txt"), "a.txt\nb.txt\nc.txt\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--files-from",
+            "files.txt",
+            "--progress",
+            "foo",
+            "baz",
+        ]);
+
+        cmd.assert()
+            .success()
+            .stdout("a.txt\nc.txt\n")
+            .stderr(predicates::str::contains(
+                "Files: 3 discovered, 3 processed (of 3), 2 changed | Matches: 2 |",
+            ));
+        assert_eq!(std::fs::read_to_string(dir.path().join("a.txt"))?, "baz\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_log_format_json() {
+        let mut cmd = get_cmd();
+        cmd.args([
+            "--stdin-override-to",
+            "true",
+            "-vvvv",
+            "--log-format",
+            "json",
+            "foo",
+        ]);
+        cmd.write_stdin("foo\n");
+
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), "foo\n");
+
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(r#""level":"INFO","target":"srgn""#));
+        for line in stderr.lines() {
+            assert!(line.starts_with(r#"{"timestamp":""#), "{line}");
+            assert!(line.contains(r#","elapsed":"#), "{line}");
+            assert!(line.ends_with('}'), "{line}");
+        }
+    }
+
+    #[rstest]
+    #[case("--python", "#!/usr/bin/env python3\n# foo\n")]
+    #[case("--python", "#!/usr/bin/pypy3 -u\n# foo\n")]
+    #[case("--javascript", "#!/usr/bin/env node\n// foo\n")]
+    #[case("--typescript", "#!/usr/bin/env -S deno run\n// foo\n")]
+    #[case("--rust", "#!/usr/bin/env rust-script\n// foo\n")]
+    fn test_cli_shebang_discovery(
+        #[case] lang: &str,
+        #[case] contents: &str,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("bin"))?;
+        std::fs::write(dir.path().join("bin/deploy"), contents)?;
+        // Not a script of any kind
+        std::fs::write(dir.path().join("bin/data"), "foo\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            lang,
+            "comments",
+            "foo",
+            "bar",
+        ]);
+        cmd.assert().success();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("bin/deploy"))?,
+            contents.replace("foo", "bar")
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("bin/data"))?,
+            "foo\n"
+        );
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(&["--python", "comments"], &["a.py", "defs.inc"])]
+    #[case(&["--auto", "comments"], &["a.py", "defs.inc"])]
+    #[case(
+        &["--python", "comments", "--no-gitattributes"],
+        &["a.py", "gen.py", "vendor/v.py"]
     )]
-    #[case(
-        "fail-none-explicitly-in-search-mode-multithreaded",
-        None,
-        &[
-            "--fail-none",
+    fn test_cli_gitattributes(
+        #[case] args: &[&str],
+        #[case] changed: &[&str],
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("vendor"))?;
+        std::fs::write(
+            dir.path().join(".gitattributes"),
+            "gen.py linguist-generated\nvendor/** linguist-vendored\n*.inc linguist-language=Python\n",
+        )?;
+        let files = ["a.py", "gen.py", "vendor/v.py", "defs.inc"];
+        for file in files {
+            std::fs::write(dir.path().join(file), "# foo\n")?;
+        }
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args(["--stdin-override-to", "false"]);
+        cmd.args(args);
+        cmd.args(["foo", "bar"]);
+        cmd.assert().success();
+
+        for file in files {
+            let expected = if changed.contains(&file) {
+                "# bar\n"
+            } else {
+                "# foo\n"
+            };
+            assert_eq!(
+                std::fs::read_to_string(dir.path().join(file))?,
+                expected,
+                "{file}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_auto() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for (path, contents) in [
+            ("a.py", "# TODO\nx = 'TODO'\n"),
+            ("b.rs", "// TODO\nconst X: &str = \"TODO\";\n"),
+            ("c.txt", "TODO\n"),
+            ("script", "#!/usr/bin/env python3\n# TODO\n"),
+        ] {
+            std::fs::write(dir.path().join(path), contents)?;
+        }
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--auto",
+            "comments",
+            "TODO",
+            "FIXME",
+        ]);
+        cmd.assert().success();
+
+        for (path, expected) in [
+            ("a.py", "# FIXME\nx = 'TODO'\n"),
+            ("b.rs", "// FIXME\nconst X: &str = \"TODO\";\n"),
+            ("c.txt", "TODO\n"),
+            ("script", "#!/usr/bin/env python3\n# FIXME\n"),
+        ] {
+            assert_eq!(
+                std::fs::read_to_string(dir.path().join(path))?,
+                expected,
+                "{path}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("imports", "import foo\n", "use foo;\n")]
+    #[case("function-names", "def foo(): pass\n", "fn foo() {}\n")]
+    fn test_cli_auto_generic_queries(
+        #[case] query: &str,
+        #[case] python: &str,
+        #[case] rust: &str,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.py"), python)?;
+        std::fs::write(dir.path().join("b.rs"), rust)?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--auto",
+            query,
+            "foo",
+            "bar",
+        ]);
+        cmd.assert().success();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.py"))?,
+            python.replace("foo", "bar")
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("b.rs"))?,
+            rust.replace("foo", "bar")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_format_sarif() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("sub"))?;
+        std::fs::write(dir.path().join("sub/a.py"), "x = 1  # TODO: ünïcode TODO\n")?;
+        std::fs::write(dir.path().join("b.py"), "'''\nTODO\n'''\n# TODO\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
             "--python",
-            "strings",
-            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
-        ],
-        None,
-    )]
-    #[case(
-        "fail-none-explicitly-outside-search-mode-multithreaded",
-        None,
-        &[
-            "--fail-none",
+            "comments",
+            "--format",
+            "sarif",
+            "TODO",
+            "DONE",
+        ]);
+
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let output = String::from_utf8(output)?;
+
+        assert!(output.starts_with(r#"{"version":"2.1.0","#));
+        assert!(output.contains(r#""rules":[{"id":"python/comments:TODO"}]"#));
+        // Sorted by file, then position; columns count characters
+        let regions = output
+            .match_indices(r#""uri":"#)
+            .map(|(i, _)| &output[i..output[i..].find("\"snippet\"").unwrap() + i])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            regions,
+            [
+                r#""uri":"b.py"},"region":{"startLine":4,"startColumn":3,"endLine":4,"endColumn":7,"#,
+                r#""uri":"sub/a.py"},"region":{"startLine":1,"startColumn":10,"endLine":1,"endColumn":14,"#,
+                r#""uri":"sub/a.py"},"region":{"startLine":1,"startColumn":24,"endLine":1,"endColumn":28,"#,
+            ]
+        );
+
+        // Reporting only
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("b.py"))?,
+            "'''\nTODO\n'''\n# TODO\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_format_patch() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("sub"))?;
+        std::fs::write(dir.path().join("a.txt"), "foo\nbar\n")?;
+        std::fs::write(dir.path().join("sub/b.txt"), "bar\nfoo")?;
+        std::fs::write(dir.path().join("unchanged.txt"), "bar\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--sorted",
             "--glob",
-            "**/*.py",
-            "unfindable-string-dheuihiuhiulerfiuehrilufhiusdho438ryh9vuoih",
-        ],
-        None,
-    )]
-    #[case(
-        "fail-any-in-search-mode-multithreaded",
-        None,
-        &[
-            "--fail-any",
+            "**/*.txt",
+            "--format",
+            "patch",
+            "foo",
+            "baz",
+        ]);
+
+        cmd.assert().success().stdout(concat!(
+            "--- a/a.txt\n",
+            "+++ b/a.txt\n",
+            "@@ -1,2 +1,2 @@\n",
+            "-foo\n",
+            "+baz\n",
+            " bar\n",
+            "--- a/sub/b.txt\n",
+            "+++ b/sub/b.txt\n",
+            "@@ -1,2 +1,2 @@\n",
+            " bar\n",
+            "-foo\n",
+            "\\ No newline at end of file\n",
+            "+baz\n",
+            "\\ No newline at end of file\n",
+        ));
+
+        // Nothing is written
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.txt"))?,
+            "foo\nbar\n"
+        );
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("foo", false, "a.txt\n")]
+    #[case("qux", true, "")]
+    fn test_cli_check(
+        #[case] scope: &str,
+        #[case] success: bool,
+        #[case] expected: &str,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.txt"), "foo\n")?;
+        std::fs::write(dir.path().join("b.txt"), "bar\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--glob",
+            "*.txt",
+            "--check",
+            scope,
+            "baz",
+        ]);
+
+        let assert = cmd.assert().stdout(expected.to_owned());
+        if success {
+            assert.success();
+        } else {
+            assert
+                .failure()
+                .stderr(predicates::str::contains("1 file(s) would change"));
+        }
+        assert_eq!(std::fs::read_to_string(dir.path().join("a.txt"))?, "foo\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_fail_any_report() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.py"), "# TODO: one\nx = 1\n")?;
+        std::fs::write(dir.path().join("b.py"), "y = 2\n")?;
+        std::fs::write(dir.path().join("c.py"), "z = 3  # TODO: two\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
+            "--sorted",
             "--python",
-            "strings",
-            r".",
-        ],
-        None,
-    )]
+            "comments",
+            "--fail-any-report",
+            "TODO",
+        ]);
+
+        let output = cmd.output()?;
+        assert!(!output.status.success());
+
+        // All offending files are reported, not just the first one
+        let stdout = String::from_utf8(output.stdout)?;
+        assert!(stdout.contains("a.py"), "{stdout}");
+        assert!(stdout.contains("1:# TODO: one"), "{stdout}");
+        assert!(!stdout.contains("b.py"), "{stdout}");
+        assert!(stdout.contains("c.py"), "{stdout}");
+        assert!(stdout.contains("1:z = 3  # TODO: two"), "{stdout}");
+        assert!(String::from_utf8(output.stderr)?.contains("Some input was in scope"));
+
+        // Nothing is written
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.py"))?,
+            "# TODO: one\nx = 1\n"
+        );
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("TODO", "--fail-if-more-than", "3", None)]
     #[case(
-        "fail-any-outside-search-mode-multithreaded",
-        None,
-        &[
-            "--fail-any",
-            "--glob",
-            "**/*.py",
-            r".",
-        ],
-        None,
+        "TODO",
+        "--fail-if-more-than",
+        "2",
+        Some("Found 3 match(es), more than the allowed 2")
     )]
+    #[case("FIXME", "--fail-if-more-than", "0", None)]
+    #[case("TODO", "--fail-if-fewer-than", "3", None)]
     #[case(
-        "fail-no-files-in-search-mode-multithreaded",
-        None,
-        &[
-            "--fail-no-files",
-            "--python",
-            "strings",
-            r".",
-        ],
-        Some(Path::new("tests/langs/go")), // No Python files here...
+        "TODO",
+        "--fail-if-fewer-than",
+        "4",
+        Some("Found 3 match(es), fewer than the required 4")
     )]
     #[case(
-        "fail-no-files-outside-search-mode-multithreaded",
-        None,
-        &[
-            "--fail-no-files",
-            "--glob",
-            "**/*.there-is-no-such-suffix",
-            r".",
-        ],
-        None,
+        "FIXME",
+        "--fail-if-fewer-than",
+        "1",
+        Some("Found 0 match(es), fewer than the required 1")
     )]
-    //
-    //
-    #[case(
-        "fail-multiple-languages",
-        None,
-        &[
-            // This should be stopped very early on, in CLI entry
+    fn test_cli_fail_thresholds(
+        #[case] pattern: &str,
+        #[case] option: &str,
+        #[case] n: &str,
+        #[case] error: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("a.py"), "# TODO: one\n# TODO: two\n")?;
+        std::fs::write(dir.path().join("b.py"), "x = 1  # TODO: three\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.current_dir(&dir);
+        cmd.args([
+            "--stdin-override-to",
+            "false",
             "--python",
-            "strings",
-            "--go",
-            "strings",
-        ],
-        None,
-    )]
-    #[case(
-        "go-ignores-vendor-directory",
-        None,
-        &[
-            "--go",
             "comments",
-        ],
-        Some(Path::new("tests/langs/go/vendor-dir-test/")), // Contains vendor dir
-    )]
-    fn test_cli_failure_modes(
-        #[case] snapshot_name: String,
-        #[case] stdin: Option<&str>,
-        #[case] args: &[&str],
-        #[case] cwd: Option<&Path>,
-    ) {
-        let args = args.iter().map(ToString::to_string).collect_vec();
+            option,
+            n,
+            pattern,
+        ]);
+
+        match error {
+            None => {
+                cmd.assert().success();
+            }
+            Some(error) => {
+                cmd.assert()
+                    .failure()
+                    .stderr(predicates::str::contains(error));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_german_words() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let words = dir.path().join("words.txt");
+        std::fs::write(&words, "\n  Schrökedäk  \n\n")?;
 
-        // Arrange
         let mut cmd = get_cmd();
+        cmd.args(["--stdin-override-to", "true", "--german-words"]);
+        cmd.arg(&words);
+        cmd.write_stdin("Frau Schroekedaek gruesst\n");
 
-        if let Some(stdin) = stdin {
-            cmd.write_stdin(stdin);
-        } else {
-            cmd.args(
-                // Override; `Command` is detected as providing stdin but we're working on
-                // files here.
-                ["--stdin-override-to", "false"],
-            );
-        }
-        cmd.args(&args);
+        cmd.assert().success().stdout("Frau Schrökedäk grüßt\n");
 
-        if let Some(cwd) = cwd {
-            cmd.current_dir(cwd);
-        }
+        Ok(())
+    }
 
-        // Act
-        let output = cmd.output().expect("failed to execute binary under test");
+    #[test]
+    fn test_cli_german_exceptions() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let exceptions = dir.path().join("exceptions.txt");
+        std::fs::write(&exceptions, "Muesli\n")?;
 
-        // Assert
-        let exit_code = output
-            .status
-            .code()
-            .expect("Process unexpectedly terminated via signal, not `exit`.");
-        let stdout = String::from_utf8(output.stdout).unwrap();
-        let stderr = String::from_utf8(output.stderr).unwrap();
+        let mut cmd = get_cmd();
+        cmd.args(["--stdin-override-to", "true", "--german-exceptions"]);
+        cmd.arg(&exceptions);
+        cmd.write_stdin("Die Muesli AG verkauft Muesliriegel\n");
 
-        let info = CommandInfo::new(&stderr);
+        cmd.assert()
+            .success()
+            .stdout("Die Muesli AG verkauft Müsliriegel\n");
 
-        with_settings!({
-            info => &info,
-        }, {
-            insta::assert_yaml_snapshot!(
-                snapshot_name,
-                CommandSnap {
-                    args,
-                    stdin: None,
-                    stdout: stdout.split_inclusive('\n').map(ToOwned::to_owned).collect_vec(),
-                    exit_code,
-                }
-            );
-        });
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_symbols_map() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let map = dir.path().join("map.toml");
+        std::fs::write(&map, "\"<=>\" = \"⇔\"\n\"->\" = \"⇾\"\n")?;
+
+        let mut cmd = get_cmd();
+        cmd.args(["--stdin-override-to", "true", "--symbols-map"]);
+        cmd.arg(&map);
+        cmd.write_stdin("a <=> b -> c <= d\n");
+
+        cmd.assert().success().stdout("a ⇔ b ⇾ c ≤ d\n");
+
+        Ok(())
     }
 
     #[test]
@@ -826,6 +3443,19 @@ Heizoelrueckstossabdaempfung.
         cmd.assert().failure();
     }
 
+    #[rstest]
+    fn test_cli_on_invalid_utf8_lossy(#[values(true, false)] stream: bool) {
+        let mut cmd = get_cmd();
+
+        cmd.args(["--binary-mode", "lossy", "utf8", "UTF8"]);
+        if stream {
+            cmd.arg("--stream");
+        }
+        cmd.write_stdin(*b"invalid utf8 \xFF\n");
+
+        cmd.assert().success().stdout("invalid UTF8 \u{FFFD}\n");
+    }
+
     /// Tests the helper function itself.
     #[test]
     fn test_directory_comparison() -> anyhow::Result<()> {
@@ -933,10 +3563,8 @@ Heizoelrueckstossabdaempfung.
                     .with_context(|| format!("Failure reading right file: {candidate:?}"))?;
 
                 if left_contents != right_contents {
-                    return Err(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        format!(
-                            r"File contents differ:
+                    return Err(std::io::Error::other(format!(
+                        r"File contents differ:
 left path: {:?}
 right path: {:?}
 ---------
@@ -947,12 +3575,11 @@ right contents:
 {}
 ---------
 ",
-                            left.path(),
-                            candidate,
-                            String::from_utf8_lossy(&left_contents).escape_debug(),
-                            String::from_utf8_lossy(&right_contents).escape_debug()
-                        ),
-                    )
+                        left.path(),
+                        candidate,
+                        String::from_utf8_lossy(&left_contents).escape_debug(),
+                        String::from_utf8_lossy(&right_contents).escape_debug()
+                    ))
                     .into());
                 }
             } else if metadata.is_dir() {
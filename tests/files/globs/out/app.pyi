@@ -0,0 +1 @@
+def baz() -> None: ...
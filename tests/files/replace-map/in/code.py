@@ -0,0 +1,2 @@
+def get_user(old_name):
+    return get_account(old_name) or old_names
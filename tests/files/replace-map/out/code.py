@@ -0,0 +1,2 @@
+def fetch_user(new_name):
+    return fetch_user(new_name) or old_names
@@ -0,0 +1,12 @@
+//go:build linux && (amd64 || arm64)
+// +build linux
+// +build amd64 arm64
+
+// Package buildtags is only built on some platforms.
+package buildtags
+
+// This is not a build constraint: //go:build windows
+func Platform() string {
+	//go:build is only meaningful at the top of the file
+	return "linux"
+}
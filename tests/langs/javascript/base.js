@@ -0,0 +1,35 @@
+// A small, legacy-flavored module.
+import React from "react";
+import { helper } from './helper.js';
+const fs = require("fs");
+const lazy = import("./lazy.js");
+
+/* Block comment */
+var greeting = `Hello, ${"world"}!`;
+
+function add(a, b) {
+  return a + b; // Trailing comment
+}
+
+function* ids() {
+  yield 1;
+}
+
+const multiply = (a, b) => a * b;
+const named = function inner() {};
+
+class Greeter {
+  constructor(name) {
+    this.name = name;
+  }
+
+  greet() {
+    return "Hi " + this.name;
+  }
+}
+
+export default function App() {
+  return <div className="app">{greeting}</div>;
+}
+
+module.exports = { add, multiply };
@@ -2,7 +2,9 @@ use std::ops::Range;
 
 use rstest::rstest;
 use serde::{Deserialize, Serialize};
-use srgn::scoping::langs::{c, csharp, go, hcl, python, rust, typescript, LanguageScoper};
+use srgn::scoping::langs::{
+    c, csharp, go, hcl, javascript, python, rust, toml, tsx, typescript, yaml, LanguageScoper,
+};
 use srgn::scoping::scope::Scope;
 use srgn::scoping::view::ScopedViewBuilder;
 
@@ -93,6 +95,21 @@ impl InScopeLinePart {
     include_str!("python/base.py"),
     python::CompiledQuery::from(python::PreparedQuery::FunctionCalls),
 )]
+#[case(
+    "base.py_class-names",
+    include_str!("python/base.py"),
+    python::CompiledQuery::from(python::PreparedQuery::ClassNames),
+)]
+#[case(
+    "base.py_method-names",
+    include_str!("python/base.py"),
+    python::CompiledQuery::from(python::PreparedQuery::MethodNames),
+)]
+#[case(
+    "base.py_decorators",
+    include_str!("python/base.py"),
+    python::CompiledQuery::from(python::PreparedQuery::Decorators),
+)]
 #[case(
     "base.py_class",
     include_str!("python/base.py"),
@@ -168,6 +185,11 @@ impl InScopeLinePart {
     include_str!("typescript/base.ts"),
     typescript::CompiledQuery::from(typescript::PreparedQuery::Strings),
 )]
+#[case(
+    "base.ts_template-strings",
+    include_str!("typescript/base.ts"),
+    typescript::CompiledQuery::from(typescript::PreparedQuery::TemplateStrings),
+)]
 #[case(
     "base.ts_comments",
     include_str!("typescript/base.ts"),
@@ -218,6 +240,11 @@ impl InScopeLinePart {
     include_str!("typescript/base.ts"),
     typescript::CompiledQuery::from(typescript::PreparedQuery::Interface),
 )]
+#[case(
+    "base.ts_interface-names",
+    include_str!("typescript/base.ts"),
+    typescript::CompiledQuery::from(typescript::PreparedQuery::InterfaceNames),
+)]
 #[case(
     "base.ts_try-block",
     include_str!("typescript/base.ts"),
@@ -253,6 +280,11 @@ impl InScopeLinePart {
     include_str!("typescript/base.ts"),
     typescript::CompiledQuery::from(typescript::PreparedQuery::TypeAlias),
 )]
+#[case(
+    "base.ts_type-alias-names",
+    include_str!("typescript/base.ts"),
+    typescript::CompiledQuery::from(typescript::PreparedQuery::TypeAliasNames),
+)]
 #[case(
     "base.ts_namespace",
     include_str!("typescript/base.ts"),
@@ -293,6 +325,11 @@ impl InScopeLinePart {
     include_str!("rust/base.rs"),
     rust::CompiledQuery::from(rust::PreparedQuery::Struct),
 )]
+#[case(
+    "base.rs_struct-name",
+    include_str!("rust/base.rs"),
+    rust::CompiledQuery::from(rust::PreparedQuery::StructName),
+)]
 #[case(
     "base.rs_pub-struct",
     include_str!("rust/base.rs"),
@@ -323,6 +360,11 @@ impl InScopeLinePart {
     include_str!("rust/base.rs"),
     rust::CompiledQuery::from(rust::PreparedQuery::Enum),
 )]
+#[case(
+    "base.rs_enum-name",
+    include_str!("rust/base.rs"),
+    rust::CompiledQuery::from(rust::PreparedQuery::EnumName),
+)]
 #[case(
     "base.rs_pub-priv-enum",
     include_str!("rust/base.rs"),
@@ -358,6 +400,11 @@ impl InScopeLinePart {
     include_str!("rust/base.rs"),
     rust::CompiledQuery::from(rust::PreparedQuery::Fn),
 )]
+#[case(
+    "base.rs_fn-name",
+    include_str!("rust/base.rs"),
+    rust::CompiledQuery::from(rust::PreparedQuery::FnName),
+)]
 #[case(
     "base.rs_impl-fn",
     include_str!("rust/base.rs"),
@@ -418,6 +465,11 @@ impl InScopeLinePart {
     include_str!("rust/base.rs"),
     rust::CompiledQuery::from(rust::PreparedQuery::Trait),
 )]
+#[case(
+    "base.rs_trait-name",
+    include_str!("rust/base.rs"),
+    rust::CompiledQuery::from(rust::PreparedQuery::TraitName),
+)]
 #[case(
     "base.rs_impl",
     include_str!("rust/base.rs"),
@@ -533,6 +585,31 @@ impl InScopeLinePart {
     include_str!("hcl/base.tf"),
     hcl::CompiledQuery::from(hcl::PreparedQuery::DataSources),
 )]
+#[case(
+    "base.tf_module-names",
+    include_str!("hcl/base.tf"),
+    hcl::CompiledQuery::from(hcl::PreparedQuery::ModuleNames),
+)]
+#[case(
+    "base.tf_local-names",
+    include_str!("hcl/base.tf"),
+    hcl::CompiledQuery::from(hcl::PreparedQuery::LocalNames),
+)]
+#[case(
+    "base.tf_provider-names",
+    include_str!("hcl/base.tf"),
+    hcl::CompiledQuery::from(hcl::PreparedQuery::ProviderNames),
+)]
+#[case(
+    "base.tf_output-names",
+    include_str!("hcl/base.tf"),
+    hcl::CompiledQuery::from(hcl::PreparedQuery::OutputNames),
+)]
+#[case(
+    "base.tf_attributes",
+    include_str!("hcl/base.tf"),
+    hcl::CompiledQuery::from(hcl::PreparedQuery::Attributes),
+)]
 #[case(
     "base.tf_comments",
     include_str!("hcl/base.tf"),
@@ -648,16 +725,46 @@ impl InScopeLinePart {
     include_str!("go/base.go"),
     go::CompiledQuery::from(go::PreparedQuery::StructTags),
 )]
+#[case(
+    "base.go_func-names",
+    include_str!("go/base.go"),
+    go::CompiledQuery::from(go::PreparedQuery::FuncNames),
+)]
+#[case(
+    "base.go_type-names",
+    include_str!("go/base.go"),
+    go::CompiledQuery::from(go::PreparedQuery::TypeNames),
+)]
+#[case(
+    "base.go_struct-fields",
+    include_str!("go/base.go"),
+    go::CompiledQuery::from(go::PreparedQuery::StructFields),
+)]
+#[case(
+    "build-tags.go_build-tags",
+    include_str!("go/build-tags.go"),
+    go::CompiledQuery::from(go::PreparedQuery::BuildTags),
+)]
 #[case(
     "base.cs_strings",
     include_str!("csharp/base.cs"),
     csharp::CompiledQuery::from(csharp::PreparedQuery::Strings),
 )]
+#[case(
+    "base.cs_interpolated-strings",
+    include_str!("csharp/base.cs"),
+    csharp::CompiledQuery::from(csharp::PreparedQuery::InterpolatedStrings),
+)]
 #[case(
     "base.cs_usings",
     include_str!("csharp/base.cs"),
     csharp::CompiledQuery::from(csharp::PreparedQuery::Usings),
 )]
+#[case(
+    "base.cs_namespaces",
+    include_str!("csharp/base.cs"),
+    csharp::CompiledQuery::from(csharp::PreparedQuery::Namespaces),
+)]
 #[case(
     "base.cs_comments",
     include_str!("csharp/base.cs"),
@@ -693,6 +800,11 @@ impl InScopeLinePart {
     include_str!("csharp/base.cs"),
     csharp::CompiledQuery::from(csharp::PreparedQuery::Class),
 )]
+#[case(
+    "base.cs_class-names",
+    include_str!("csharp/base.cs"),
+    csharp::CompiledQuery::from(csharp::PreparedQuery::ClassNames),
+)]
 #[case(
     "base.cs_variable_declaration",
     include_str!("csharp/base.cs"),
@@ -718,6 +830,11 @@ impl InScopeLinePart {
     include_str!("csharp/base.cs"),
     csharp::CompiledQuery::from(csharp::PreparedQuery::Method),
 )]
+#[case(
+    "base.cs_method-names",
+    include_str!("csharp/base.cs"),
+    csharp::CompiledQuery::from(csharp::PreparedQuery::MethodNames),
+)]
 #[case(
     "base.cs_identifier",
     include_str!("csharp/base.cs"),
@@ -818,6 +935,111 @@ impl InScopeLinePart {
     include_str!("c/base.c"),
    c::CompiledQuery::from (c::PreparedQuery::CallExpression),
 )]
+#[case(
+    "base.yaml_comments",
+    include_str!("yaml/base.yaml"),
+    yaml::CompiledQuery::from(yaml::PreparedQuery::Comments),
+)]
+#[case(
+    "base.yaml_keys",
+    include_str!("yaml/base.yaml"),
+    yaml::CompiledQuery::from(yaml::PreparedQuery::Keys),
+)]
+#[case(
+    "base.yaml_values",
+    include_str!("yaml/base.yaml"),
+    yaml::CompiledQuery::from(yaml::PreparedQuery::Values),
+)]
+#[case(
+    "base.yaml_anchors",
+    include_str!("yaml/base.yaml"),
+    yaml::CompiledQuery::from(yaml::PreparedQuery::Anchors),
+)]
+#[case(
+    "base.js_comments",
+    include_str!("javascript/base.js"),
+    javascript::CompiledQuery::from(javascript::PreparedQuery::Comments),
+)]
+#[case(
+    "base.js_strings",
+    include_str!("javascript/base.js"),
+    javascript::CompiledQuery::from(javascript::PreparedQuery::Strings),
+)]
+#[case(
+    "base.js_template-strings",
+    include_str!("javascript/base.js"),
+    javascript::CompiledQuery::from(javascript::PreparedQuery::TemplateStrings),
+)]
+#[case(
+    "base.js_imports",
+    include_str!("javascript/base.js"),
+    javascript::CompiledQuery::from(javascript::PreparedQuery::Imports),
+)]
+#[case(
+    "base.js_function-names",
+    include_str!("javascript/base.js"),
+    javascript::CompiledQuery::from(javascript::PreparedQuery::FunctionNames),
+)]
+#[case(
+    "base.toml_comments",
+    include_str!("toml/base.toml"),
+    toml::CompiledQuery::from(toml::PreparedQuery::Comments),
+)]
+#[case(
+    "base.toml_keys",
+    include_str!("toml/base.toml"),
+    toml::CompiledQuery::from(toml::PreparedQuery::Keys),
+)]
+#[case(
+    "base.toml_strings",
+    include_str!("toml/base.toml"),
+    toml::CompiledQuery::from(toml::PreparedQuery::Strings),
+)]
+#[case(
+    "base.toml_table-headers",
+    include_str!("toml/base.toml"),
+    toml::CompiledQuery::from(toml::PreparedQuery::TableHeaders),
+)]
+#[case(
+    "base.tsx_comments",
+    include_str!("tsx/base.tsx"),
+    tsx::CompiledQuery::from(tsx::PreparedQuery::Comments),
+)]
+#[case(
+    "base.tsx_strings",
+    include_str!("tsx/base.tsx"),
+    tsx::CompiledQuery::from(tsx::PreparedQuery::Strings),
+)]
+#[case(
+    "base.tsx_template-strings",
+    include_str!("tsx/base.tsx"),
+    tsx::CompiledQuery::from(tsx::PreparedQuery::TemplateStrings),
+)]
+#[case(
+    "base.tsx_imports",
+    include_str!("tsx/base.tsx"),
+    tsx::CompiledQuery::from(tsx::PreparedQuery::Imports),
+)]
+#[case(
+    "base.tsx_interface-names",
+    include_str!("tsx/base.tsx"),
+    tsx::CompiledQuery::from(tsx::PreparedQuery::InterfaceNames),
+)]
+#[case(
+    "base.tsx_type-alias-names",
+    include_str!("tsx/base.tsx"),
+    tsx::CompiledQuery::from(tsx::PreparedQuery::TypeAliasNames),
+)]
+#[case(
+    "base.tsx_jsx-element-names",
+    include_str!("tsx/base.tsx"),
+    tsx::CompiledQuery::from(tsx::PreparedQuery::JsxElementNames),
+)]
+#[case(
+    "base.tsx_jsx-attribute-names",
+    include_str!("tsx/base.tsx"),
+    tsx::CompiledQuery::from(tsx::PreparedQuery::JsxAttributeNames),
+)]
 fn test_language_scopers(
     #[case] snapshot_name: &str,
     #[case] contents: &str,
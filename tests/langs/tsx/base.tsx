@@ -0,0 +1,28 @@
+// A small React component.
+import React, { useState } from "react";
+import * as Icons from "./icons";
+
+interface ButtonProps {
+  label: string;
+  onClick?: () => void;
+}
+
+type Variant = "primary" | "secondary";
+
+const greeting = `Hello, ${"world"}!`;
+
+export function Button({ label, onClick }: ButtonProps) {
+  const [count, setCount] = useState<number>(0);
+  const value = count as number; // `as` assertions work; `<number>count` would not
+
+  return (
+    <div className="button-wrapper" data-count={value}>
+      {/* Clicking increments */}
+      <button type="button" onClick={() => setCount(count + 1)} disabled>
+        {label}: {count}
+      </button>
+      <Icons.Star size={16} />
+      <svg:rect xlink:href="#shape" />
+    </div>
+  );
+}
@@ -31,10 +31,10 @@ mod tests {
         alpha1 as ascii_alpha1, alphanumeric1 as ascii_alphanumeric1, anychar, char, line_ending,
         none_of, space0, space1,
     };
-    use nom::combinator::{cut, eof, map, opt, recognize};
+    use nom::combinator::{cut, eof, map, opt, peek, recognize};
     use nom::error::ParseError;
     use nom::multi::{many0, many1, many_till, separated_list1};
-    use nom::sequence::{delimited, preceded, tuple};
+    use nom::sequence::{delimited, preceded, terminated, tuple};
     use nom::{Finish, IResult};
     use pretty_assertions::assert_eq;
     use tempfile::NamedTempFile;
@@ -367,7 +367,7 @@ mod tests {
                                 stdout.expect("Stdout should be given for standalone program"),
                             );
                         }
-                    };
+                    }
 
                     assert!(standalone, "Should have been set before.");
 
@@ -493,60 +493,67 @@ mod tests {
                             // registered here, `--some-option` will be understood as a
                             // *flag*, and `some_value` as a *positional argument*.
                             tag("--"),
-                            alt((
-                                // ⚠️ Careful: all `--<lang>-query` options need to come
-                                // first; otherwise, the shorter `--<lang>` options eat
-                                // them and results turn bad (complaining that `-query`
-                                // is not a valid value). Generally, parsing is greedy,
-                                // so shorter values will short-circuit, potentially
-                                // incorrectly. That's why lines are generally sorted
-                                // longest to shortest, to avoid confusion.
+                            terminated(
                                 alt((
-                                    tag("typescript-query-file"),
-                                    tag("csharp-query-file"),
-                                    tag("python-query-file"),
-                                    tag("rust-query-file"),
-                                    tag("hcl-query-file"),
-                                    tag("go-query-file"),
-                                    tag("c-query-file"),
+                                    // ⚠️ Careful: all `--<lang>-query` options need to come
+                                    // first; otherwise, the shorter `--<lang>` options eat
+                                    // them and results turn bad (complaining that `-query`
+                                    // is not a valid value). Generally, parsing is greedy,
+                                    // so shorter values will short-circuit, potentially
+                                    // incorrectly. That's why lines are generally sorted
+                                    // longest to shortest, to avoid confusion.
+                                    alt((
+                                        tag("typescript-query-file"),
+                                        tag("csharp-query-file"),
+                                        tag("python-query-file"),
+                                        tag("rust-query-file"),
+                                        tag("hcl-query-file"),
+                                        tag("go-query-file"),
+                                        tag("c-query-file"),
+                                    )),
+                                    //
+                                    // Parsing is brittle here :-(
+                                    alt((
+                                        tag("typescript-query"),
+                                        tag("csharp-query"),
+                                        tag("python-query"),
+                                        tag("rust-query"),
+                                        tag("hcl-query"),
+                                        tag("go-query"),
+                                        tag("c-query"),
+                                    )),
+                                    // Before `--c`, which would eat it otherwise
+                                    tag("capture"),
+                                    //
+                                    alt((
+                                        tag("typescript"),
+                                        tag("csharp"),
+                                        tag("python"),
+                                        tag("rust"),
+                                        tag("hcl"),
+                                        tag("go"),
+                                        tag("c"),
+                                    )),
+                                    // Misc. flags used in the docs
+                                    alt((tag("glob"), tag("stdin-override-to"), tag("threads"))),
+                                    // Shorthands
+                                    alt((
+                                        tag("tsx"),
+                                        tag("hcl"),
+                                        tag("cs"),
+                                        tag("py"),
+                                        tag("rs"),
+                                        tag("ts"),
+                                        tag("go"),
+                                        tag("tf"),
+                                        tag("c"),
+                                        // tag("h"), // Breaks `--help` and isn't used
+                                    )),
                                 )),
-                                //
-                                // Parsing is brittle here :-(
-                                alt((
-                                    tag("typescript-query"),
-                                    tag("csharp-query"),
-                                    tag("python-query"),
-                                    tag("rust-query"),
-                                    tag("hcl-query"),
-                                    tag("go-query"),
-                                    tag("c-query"),
-                                )),
-                                //
-                                alt((
-                                    tag("typescript"),
-                                    tag("csharp"),
-                                    tag("python"),
-                                    tag("rust"),
-                                    tag("hcl"),
-                                    tag("go"),
-                                    tag("c"),
-                                )),
-                                // Misc. flags used in the docs
-                                alt((tag("glob"), tag("stdin-override-to"), tag("threads"))),
-                                // Shorthands
-                                alt((
-                                    tag("tsx"),
-                                    tag("hcl"),
-                                    tag("cs"),
-                                    tag("py"),
-                                    tag("rs"),
-                                    tag("ts"),
-                                    tag("go"),
-                                    tag("tf"),
-                                    tag("c"),
-                                    // tag("h"), // Breaks `--help` and isn't used
-                                )),
-                            )),
+                                // Only entire option names, not prefixes of flags such as
+                                // `--comment-out`
+                                peek(space1),
+                            ),
                         ),
                         cut(
                             // `cut`: should we get here, and not succeed, parsing has
@@ -568,10 +575,13 @@ mod tests {
                 map(
                     tuple((
                         preceded(
-                            // Long flags, like `--long-flag`. No values. Can contain
-                            // hyphens itself.
+                            // Long flags, like `--long-flag`. No separate values, but
+                            // can carry one attached, like `--long-flag=value`. Can
+                            // contain hyphens itself.
                             tag("--"),
-                            take_while1(|c: char| c == '-' || c.is_ascii_alphanumeric()),
+                            take_while1(|c: char| {
+                                c == '-' || c == '=' || c.is_ascii_alphanumeric()
+                            }),
                         ),
                         space0,
                     )),
@@ -981,10 +991,10 @@ mod tests {
         fn drop(&mut self) {
             if std::thread::panicking() {
                 println!(
-                    r#"
+                    r"
 💡 README test failed!
 Run with `{FIX_ENV_VAR}=1` env var to fix the README automatically (BEST EFFORT!).
-"#
+"
                 );
             }
         }